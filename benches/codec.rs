@@ -0,0 +1,61 @@
+// benches/codec.rs
+//! Decoding the transactions and encoding the receipts of the blocks bundled
+//! for `self-test`, the steps before hashing the leaves of the roots.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fuel_core_client::client::schema::tx::TransactionStatus;
+use fuel_root_validation::self_test;
+use fuel_tx::{Receipt, Transaction};
+use fuel_types::canonical::{Deserialize, Serialize};
+
+fn transactions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_transactions");
+    for fixture in self_test::fixtures() {
+        let payloads: Vec<&[u8]> = fixture
+            .block
+            .transactions
+            .iter()
+            .map(|tx| tx.raw_payload.0 .0.as_slice())
+            .collect();
+        group.throughput(Throughput::Elements(payloads.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(fixture.name),
+            &payloads,
+            |b, payloads| {
+                b.iter(|| {
+                    for payload in payloads {
+                        Transaction::from_bytes(payload).expect("the fixtures decode");
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn receipts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode_receipts");
+    for fixture in self_test::fixtures() {
+        let receipts: Vec<Receipt> = fixture
+            .block
+            .transactions
+            .iter()
+            .flat_map(|tx| match &tx.status {
+                Some(TransactionStatus::SuccessStatus(status)) => status.receipts.clone(),
+                Some(TransactionStatus::FailureStatus(status)) => status.receipts.clone(),
+                _ => vec![],
+            })
+            .map(|receipt| receipt.try_into().expect("the fixtures decode"))
+            .collect();
+        group.throughput(Throughput::Elements(receipts.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(fixture.name),
+            &receipts,
+            |b, receipts| b.iter(|| receipts.iter().map(Receipt::to_bytes).collect::<Vec<_>>()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, transactions, receipts);
+criterion_main!(benches);
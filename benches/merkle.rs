@@ -0,0 +1,92 @@
+// benches/merkle.rs
+//! Recomputing the transactions and receipts roots of blocks of growing
+//! size, and of the blocks bundled for `self-test`, and hashing leaves with
+//! every hash backend. Compare against `cargo bench --features parallel`
+//! and `cargo bench --features ring`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fuel_root_validation::{checks, hash, merkle, self_test, FullBlock};
+
+const TRANSACTIONS: [u32; 3] = [10, 100, 1000];
+
+/// The block of 64 scripts bundled for `self-test`, with its scripts
+/// repeated or dropped to `count`, followed by its mint. Its roots no longer
+/// match its header, which the benches don't check.
+fn block_with_transactions(count: u32) -> FullBlock {
+    let mut block = self_test::fixtures()
+        .into_iter()
+        .max_by_key(|fixture| fixture.block.transactions.len())
+        .expect("self-test bundles blocks")
+        .block;
+    let mint = block
+        .transactions
+        .pop()
+        .expect("blocks end with their mint");
+    let scripts = std::mem::take(&mut block.transactions);
+    block.transactions = scripts.into_iter().cycle().take(count as usize).collect();
+    block.transactions.push(mint);
+    block
+}
+
+fn roots(c: &mut Criterion) {
+    let mut group = c.benchmark_group("roots");
+    for count in TRANSACTIONS {
+        let block = block_with_transactions(count);
+        group.throughput(Throughput::Elements(u64::from(count)));
+        group.bench_with_input(BenchmarkId::new("tx_root", count), &block, |b, block| {
+            b.iter(|| checks::tx_root(block))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("receipt_roots", count),
+            &block,
+            |b, block| b.iter(|| checks::receipt_roots(block)),
+        );
+    }
+    group.finish();
+}
+
+fn fixture_roots(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fixture_roots");
+    for fixture in self_test::fixtures() {
+        let block = fixture.block;
+        group.throughput(Throughput::Elements(block.transactions.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("tx_root", fixture.name),
+            &block,
+            |b, block| b.iter(|| checks::tx_root(block)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("receipt_roots", fixture.name),
+            &block,
+            |b, block| b.iter(|| checks::receipt_roots(block)),
+        );
+    }
+    group.finish();
+}
+
+fn hash_backends(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash_backends");
+    // About the size of a script transaction and of a receipt.
+    for size in [512, 64] {
+        let leaves = vec![vec![7u8; size]; 1000];
+        group.throughput(Throughput::Bytes((size * leaves.len()) as u64));
+        for backend in hash::backends() {
+            group.bench_with_input(
+                BenchmarkId::new(backend.name(), size),
+                &leaves,
+                |b, leaves| {
+                    b.iter(|| {
+                        let hashes = leaves
+                            .iter()
+                            .map(|leaf| merkle::leaf_hash_with(backend, leaf));
+                        merkle::root_with(backend, hashes)
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, roots, fixture_roots, hash_backends);
+criterion_main!(benches);
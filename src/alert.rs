@@ -0,0 +1,140 @@
+// src/alert.rs
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::error::ErrorKind;
+use crate::report::CheckResult;
+use crate::validator::BlockOutcome;
+
+/// Timeout of a single webhook request.
+pub const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Discord rejects messages longer than this.
+const DISCORD_MAX_CONTENT: usize = 2000;
+
+/// Shape of the JSON body posted to a webhook.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WebhookFormat {
+    /// The [`Alert`] itself.
+    #[default]
+    Json,
+    /// A Slack incoming webhook message.
+    Slack,
+    /// A Discord webhook message.
+    Discord,
+}
+
+impl FromStr for WebhookFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(WebhookFormat::Json),
+            "slack" => Ok(WebhookFormat::Slack),
+            "discord" => Ok(WebhookFormat::Discord),
+            _ => Err(format!(
+                "unknown webhook format `{s}`, expected one of json, slack, discord"
+            )),
+        }
+    }
+}
+
+/// A block whose commitments don't match, or that endpoints disagree on.
+#[derive(Clone, Debug, Serialize)]
+pub struct Alert {
+    pub height: u32,
+    pub block_id: String,
+    pub failures: Vec<CheckResult>,
+}
+
+impl Alert {
+    /// The alert for `outcome`, if any of its failures must page someone.
+    /// Query and parse errors are left to the logs and metrics.
+    pub fn for_outcome(outcome: &BlockOutcome) -> Option<Self> {
+        let report = outcome.result.as_ref().ok()?;
+        let failures: Vec<_> = report
+            .failures()
+            .filter(|failure| {
+                failure.to_error(report.height).is_some_and(|e| {
+                    matches!(e.kind(), ErrorKind::Mismatch | ErrorKind::Disagreement)
+                })
+            })
+            .cloned()
+            .collect();
+
+        (!failures.is_empty()).then(|| Self {
+            height: report.height,
+            block_id: report.block_id.clone(),
+            failures,
+        })
+    }
+
+    /// Human readable summary, as posted to chat webhooks.
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Block #{} ({}) failed validation:",
+            self.height, self.block_id
+        );
+        for failure in &self.failures {
+            let _ = write!(summary, "\n- {failure}");
+        }
+        summary
+    }
+
+    pub fn payload(&self, format: WebhookFormat) -> serde_json::Value {
+        match format {
+            WebhookFormat::Json => json!(self),
+            WebhookFormat::Slack => json!({ "text": self.summary() }),
+            WebhookFormat::Discord => {
+                let mut content = self.summary();
+                if content.len() > DISCORD_MAX_CONTENT {
+                    let mut end = DISCORD_MAX_CONTENT - 3;
+                    while !content.is_char_boundary(end) {
+                        end -= 1;
+                    }
+                    content.truncate(end);
+                    content.push_str("...");
+                }
+                json!({ "content": content })
+            }
+        }
+    }
+}
+
+/// Posts alerts to a webhook.
+#[derive(Clone, Debug)]
+pub struct Webhook {
+    url: String,
+    format: WebhookFormat,
+    client: reqwest::Client,
+}
+
+impl Webhook {
+    pub fn new(url: impl Into<String>, format: WebhookFormat) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            url: url.into(),
+            format,
+            client,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub async fn send(&self, alert: &Alert) -> reqwest::Result<()> {
+        self.client
+            .post(&self.url)
+            .json(&alert.payload(self.format))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
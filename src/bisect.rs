@@ -0,0 +1,100 @@
+// src/bisect.rs
+//! The first block two endpoints disagree on, found by binary search.
+//!
+//! Once two nodes diverge, every later block differs as well, as block ids
+//! commit to their parent. Whether two endpoints agree on a height therefore
+//! tells on which side of it they first diverged, so `log2(n)` blocks are
+//! enough to find the first divergent block of `n`.
+
+use serde::Serialize;
+
+use crate::checks;
+use crate::error::Result;
+use crate::report::CheckResult;
+use crate::source::BlockSource;
+
+/// The first block two endpoints diverge on.
+#[derive(Clone, Debug, Serialize)]
+pub struct Divergence {
+    pub height: u32,
+    /// How the block of the other endpoint differs, see
+    /// [`checks::endpoint_agreement`].
+    pub differences: Vec<CheckResult>,
+}
+
+/// Result of a [`bisect`] of `from..=to`.
+#[derive(Clone, Debug, Serialize)]
+pub struct Bisection {
+    pub from: u32,
+    pub to: u32,
+    /// `None` when the endpoints agree on the block at `to`, and thus on
+    /// every block before it.
+    pub divergence: Option<Divergence>,
+    /// Heights compared to find it, in the order they were compared.
+    pub probes: Vec<u32>,
+}
+
+/// Finds the first block of `from..=to` on which `other`, served by
+/// `endpoint`, differs from `source`.
+///
+/// Blocks are compared with [`checks::endpoint_agreement`]. The endpoints
+/// are assumed to agree on every block before the first one they diverge
+/// on, so a divergence that both started and ended within the range isn't
+/// found.
+pub async fn bisect(
+    source: &dyn BlockSource,
+    other: &dyn BlockSource,
+    endpoint: &str,
+    from: u32,
+    to: u32,
+) -> Result<Bisection> {
+    let mut bisection = Bisection {
+        from,
+        to,
+        divergence: None,
+        probes: vec![],
+    };
+    if from > to {
+        return Ok(bisection);
+    }
+
+    let mut compare = |height| {
+        bisection.probes.push(height);
+        differences(source, other, endpoint, height)
+    };
+    let Some(mut divergent) = compare(to).await? else {
+        return Ok(bisection);
+    };
+
+    // Every height below `low` agrees, and `high` diverges with `divergent`.
+    let (mut low, mut high) = (from, to);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match compare(mid).await? {
+            Some(differences) => (high, divergent) = (mid, differences),
+            None => low = mid + 1,
+        }
+    }
+
+    bisection.divergence = Some(Divergence {
+        height: high,
+        differences: divergent,
+    });
+    Ok(bisection)
+}
+
+/// How the block at `height` of `other` differs from the one of `source`,
+/// `None` if they agree.
+async fn differences(
+    source: &dyn BlockSource,
+    other: &dyn BlockSource,
+    endpoint: &str,
+    height: u32,
+) -> Result<Option<Vec<CheckResult>>> {
+    let (block, other_block) =
+        futures::try_join!(source.fetch_block(height), other.fetch_block(height))?;
+    let results = checks::endpoint_agreement(&block, endpoint, &other_block);
+    let agree = results.iter().all(|result| result.passed);
+    tracing::info!(block_height = height, agree, "compared block");
+    Ok((!agree).then_some(results))
+}
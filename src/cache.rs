@@ -0,0 +1,315 @@
+// src/cache.rs
+//! Blocks fetched from nodes, kept on disk so repeated runs, the `prev_root`
+//! check and cross-checks don't fetch them again.
+//!
+//! Blocks are stored by block id, as CBOR dumps in `blocks/`. Which block an
+//! endpoint served at each height is recorded in an index per endpoint in
+//! `index/`, together with the block ids fetched for the `prev_root` check.
+//! A block is only served back to the endpoints that served it, so
+//! cross-checked endpoints are still compared with each other: an endpoint
+//! serving different contents under the id of a cached block isn't cached.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use fuel_crypto::Hasher;
+use fuel_types::Bytes32;
+
+use crate::dump::{self, BlockDump, DumpFormat};
+use crate::error::{Result, ValidationError};
+use crate::query::FullBlock;
+
+/// Size the cached blocks may take on disk unless set otherwise.
+pub const DEFAULT_MAX_SIZE: u64 = 1 << 30;
+
+/// On-disk cache of the blocks served by nodes, see the module docs.
+///
+/// Once the cached blocks take more than [`Self::max_size`] bytes, the least
+/// recently used ones are evicted. Failing to read or write the cache is
+/// logged and treated as a miss.
+#[derive(Debug)]
+pub struct BlockCache {
+    dir: PathBuf,
+    max_size: u64,
+    /// Bytes taken by the files of `blocks/`.
+    size: Mutex<u64>,
+    indexes: Mutex<HashMap<String, Index>>,
+}
+
+/// The block ids an endpoint served, by height.
+#[derive(Debug)]
+struct Index {
+    ids: BTreeMap<u32, Bytes32>,
+    file: Option<File>,
+}
+
+impl BlockCache {
+    /// Opens the cache in `dir`, creating it if it doesn't exist.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        let io_error = |source| ValidationError::CacheIo {
+            path: dir.clone(),
+            source,
+        };
+        fs::create_dir_all(dir.join("blocks")).map_err(io_error)?;
+        fs::create_dir_all(dir.join("index")).map_err(io_error)?;
+
+        let size = block_files(&dir)
+            .map_err(io_error)?
+            .iter()
+            .map(|file| file.size)
+            .sum();
+        Ok(Self {
+            dir,
+            max_size: DEFAULT_MAX_SIZE,
+            size: Mutex::new(size),
+            indexes: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// `fuel-block-validator` in the user's cache directory, if it is known.
+    pub fn default_dir() -> Option<PathBuf> {
+        let cache_home = std::env::var_os("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))?;
+        Some(cache_home.join("fuel-block-validator"))
+    }
+
+    /// Sets how many bytes the cached blocks may take, [`DEFAULT_MAX_SIZE`]
+    /// by default. Blocks already cached beyond it are evicted.
+    pub fn with_max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes;
+        self.evict();
+        self
+    }
+
+    pub fn max_size(&self) -> u64 {
+        self.max_size
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Bytes taken by the cached blocks.
+    pub fn size(&self) -> u64 {
+        *self.size.lock().unwrap()
+    }
+
+    /// Id of the block `endpoint` served at `height`, if it is known.
+    pub fn block_id(&self, endpoint: &str, height: u32) -> Option<Bytes32> {
+        self.with_index(endpoint, |index| index.ids.get(&height).copied())
+    }
+
+    /// The block `endpoint` served at `height`, if it is cached.
+    pub fn block(&self, endpoint: &str, height: u32) -> Option<FullBlock> {
+        let id = self.block_id(endpoint, height)?;
+        let path = self.block_path(&id);
+        if !path.exists() {
+            return None;
+        }
+        match dump::load(&path) {
+            Ok(blocks) => {
+                // Marks the block as recently used for eviction.
+                let _ = File::options()
+                    .write(true)
+                    .open(&path)
+                    .and_then(|file| file.set_modified(SystemTime::now()));
+                blocks.into_iter().next()
+            }
+            Err(e) => {
+                tracing::warn!("dropping unreadable cached block {}: {e}", path.display());
+                self.remove(&path);
+                None
+            }
+        }
+    }
+
+    /// Caches `block` as served by `endpoint`.
+    pub fn insert_block(&self, endpoint: &str, block: &FullBlock) {
+        let id: Bytes32 = block.id.clone().into();
+        match self.write_block(&id, block) {
+            Ok(true) => self.insert_block_id(endpoint, block.height(), id),
+            Ok(false) => tracing::warn!(
+                block_height = block.height(),
+                "{endpoint} served different contents for cached block {id}, not caching it"
+            ),
+            Err(e) => tracing::warn!("failed to cache block {id}: {e}"),
+        }
+    }
+
+    /// Records that `endpoint` served the block `id` at `height`.
+    pub fn insert_block_id(&self, endpoint: &str, height: u32, id: Bytes32) {
+        let result = self.with_index(endpoint, |index| {
+            if index.ids.insert(height, id) == Some(id) {
+                return Ok(());
+            }
+            match &mut index.file {
+                Some(file) => writeln!(file, "{height} {id}"),
+                None => Ok(()),
+            }
+        });
+        if let Err(e) = result {
+            tracing::warn!("failed to index block {id} in the cache: {e}");
+        }
+    }
+
+    /// Writes `block` to its file unless it is already cached. `false` if a
+    /// block with different contents is cached under the same id.
+    fn write_block(&self, id: &Bytes32, block: &FullBlock) -> Result<bool> {
+        let path = self.block_path(id);
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(format!(".tmp-{}", std::process::id()));
+        let tmp = PathBuf::from(tmp);
+        BlockDump::from(block).save(&tmp, DumpFormat::Cbor)?;
+
+        let io_error = |source| ValidationError::CacheIo {
+            path: path.clone(),
+            source,
+        };
+        let cached = match fs::read(&path) {
+            Ok(cached) => Some(cached),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(io_error(e)),
+        };
+        if let Some(cached) = cached {
+            let same = fs::read(&tmp).map_err(io_error)? == cached;
+            let _ = fs::remove_file(&tmp);
+            return Ok(same);
+        }
+
+        let size = fs::metadata(&tmp).map_err(io_error)?.len();
+        fs::rename(&tmp, &path).map_err(io_error)?;
+        *self.size.lock().unwrap() += size;
+        self.evict();
+        Ok(true)
+    }
+
+    /// Removes the least recently used blocks until the cached blocks fit in
+    /// [`Self::max_size`].
+    fn evict(&self) {
+        if self.size() <= self.max_size {
+            return;
+        }
+        let mut files = match block_files(&self.dir) {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::warn!("failed to list the cached blocks: {e}");
+                return;
+            }
+        };
+        files.sort_by_key(|file| file.used);
+        // The size is recounted from the files, which other processes may
+        // have added to.
+        let mut size: u64 = files.iter().map(|file| file.size).sum();
+        for file in files {
+            if size <= self.max_size {
+                break;
+            }
+            if fs::remove_file(&file.path).is_ok() {
+                size -= file.size;
+            }
+        }
+        *self.size.lock().unwrap() = size;
+    }
+
+    fn remove(&self, path: &Path) {
+        if let Ok(metadata) = fs::metadata(path) {
+            if fs::remove_file(path).is_ok() {
+                let mut size = self.size.lock().unwrap();
+                *size = size.saturating_sub(metadata.len());
+            }
+        }
+    }
+
+    fn block_path(&self, id: &Bytes32) -> PathBuf {
+        self.dir.join("blocks").join(format!("{id}.cbor"))
+    }
+
+    /// Runs `f` on the index of `endpoint`, loading it on first use.
+    fn with_index<T>(&self, endpoint: &str, f: impl FnOnce(&mut Index) -> T) -> T {
+        let mut indexes = self.indexes.lock().unwrap();
+        let index = indexes
+            .entry(endpoint.to_string())
+            .or_insert_with(|| self.load_index(endpoint));
+        f(index)
+    }
+
+    /// Reads the index of `endpoint`. Its file is named after a hash of the
+    /// endpoint, and starts with the endpoint for whoever looks into it.
+    fn load_index(&self, endpoint: &str) -> Index {
+        let hash = Hasher::hash(endpoint.as_bytes());
+        let path = self
+            .dir
+            .join("index")
+            .join(format!("{}.txt", hex::encode(&hash[..8])));
+
+        let mut ids = BTreeMap::new();
+        if let Ok(file) = File::open(&path) {
+            for line in BufReader::new(file).lines().skip(1) {
+                let Ok(line) = line else { break };
+                let parsed = line
+                    .split_once(' ')
+                    .and_then(|(height, id)| Some((height.parse().ok()?, id.parse().ok()?)));
+                match parsed {
+                    Some((height, id)) => {
+                        ids.insert(height, id);
+                    }
+                    None => tracing::warn!("skipping invalid line `{line}` of {}", path.display()),
+                }
+            }
+        }
+
+        let file = File::options()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| {
+                if file.metadata()?.len() == 0 {
+                    writeln!(file, "{endpoint}")?;
+                }
+                Ok(file)
+            });
+        let file = match file {
+            Ok(file) => Some(file),
+            Err(e) => {
+                tracing::warn!(
+                    "failed to open {}, blocks won't be indexed: {e}",
+                    path.display()
+                );
+                None
+            }
+        };
+        Index { ids, file }
+    }
+}
+
+/// A file of `blocks/`.
+struct BlockFile {
+    path: PathBuf,
+    size: u64,
+    used: SystemTime,
+}
+
+fn block_files(dir: &Path) -> io::Result<Vec<BlockFile>> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir.join("blocks"))? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            files.push(BlockFile {
+                path: entry.path(),
+                size: metadata.len(),
+                used: metadata.modified()?,
+            });
+        }
+    }
+    Ok(files)
+}
@@ -0,0 +1,136 @@
+// src/cassette.rs
+//! Recordings of the GraphQL responses of nodes, to validate blocks again
+//! without them: `--record` keeps every response of a run in a cassette, and
+//! `--replay` answers the queries of a later run from it, so a failing block
+//! can be reproduced from a bug report.
+//!
+//! Queries are matched on the endpoint they are sent to and their body. A
+//! query sent several times is answered with its recorded responses in turn,
+//! then with the last one.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Result, ValidationError};
+
+/// One query and the response of the node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub endpoint: String,
+    /// Body of the GraphQL request: the query and its variables.
+    pub request: Value,
+    /// HTTP status of the response.
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Recording {
+    interactions: Vec<Interaction>,
+}
+
+/// The interactions of a run, being recorded or replayed.
+#[derive(Debug)]
+pub struct Cassette {
+    replaying: bool,
+    interactions: Mutex<Vec<Interaction>>,
+    /// Interactions already replayed, by index.
+    replayed: Mutex<Vec<bool>>,
+}
+
+impl Cassette {
+    /// An empty cassette recording the responses given to [`Self::record`].
+    pub fn recording() -> Self {
+        Self {
+            replaying: false,
+            interactions: Mutex::default(),
+            replayed: Mutex::default(),
+        }
+    }
+
+    /// Reads a cassette written by [`Self::save`], to replay it.
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read(path).map_err(|source| ValidationError::CassetteIo {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let recording: Recording =
+            serde_json::from_slice(&json).map_err(|source| ValidationError::ParseCassette {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        let replayed = vec![false; recording.interactions.len()];
+        Ok(Self {
+            replaying: true,
+            interactions: Mutex::new(recording.interactions),
+            replayed: Mutex::new(replayed),
+        })
+    }
+
+    /// Writes the recorded interactions to `path` as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let recording = Recording {
+            interactions: self.interactions().clone(),
+        };
+        let json = serde_json::to_string_pretty(&recording).expect("cassettes serialize to JSON");
+        fs::write(path, json).map_err(|source| ValidationError::CassetteIo {
+            path: PathBuf::from(path),
+            source,
+        })
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replaying
+    }
+
+    pub fn len(&self) -> usize {
+        self.interactions().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn interactions(&self) -> std::sync::MutexGuard<'_, Vec<Interaction>> {
+        self.interactions
+            .lock()
+            .expect("the cassette lock isn't poisoned")
+    }
+
+    /// Records the response of the node to a query.
+    pub fn record(&self, interaction: Interaction) {
+        self.interactions().push(interaction);
+    }
+
+    /// The status and body of the recorded response to `request` sent to
+    /// `endpoint`, if it was recorded.
+    pub fn replay(&self, endpoint: &str, request: &Value) -> Option<(u16, String)> {
+        let interactions = self.interactions();
+        let mut replayed = self
+            .replayed
+            .lock()
+            .expect("the cassette lock isn't poisoned");
+        let matching: Vec<_> = interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| {
+                interaction.endpoint == endpoint && interaction.request == *request
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let index = matching
+            .iter()
+            .copied()
+            .find(|index| !replayed[*index])
+            .or_else(|| matching.last().copied())?;
+        replayed[index] = true;
+        let interaction = &interactions[index];
+        Some((interaction.status, interaction.body.clone()))
+    }
+}
@@ -0,0 +1,127 @@
+// src/checkpoint.rs
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ErrorKind, Result, ValidationError};
+use crate::validator::BlockOutcome;
+
+/// A block that didn't pass validation, as recorded in a [`Checkpoint`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedFailure {
+    pub height: u32,
+    pub reason: String,
+    /// Classes of the errors the block failed with.
+    pub kinds: Vec<ErrorKind>,
+}
+
+impl RecordedFailure {
+    /// The failure `outcome` records, unless the block passed.
+    pub fn of(outcome: &BlockOutcome) -> Option<Self> {
+        if outcome.passed() {
+            return None;
+        }
+
+        let (reason, mut kinds) = match &outcome.result {
+            Ok(report) => (
+                report
+                    .failures()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                report.errors().iter().map(ValidationError::kind).collect(),
+            ),
+            Err(e) => (e.to_string(), vec![e.kind()]),
+        };
+        kinds.dedup();
+        Some(Self {
+            height: outcome.height,
+            reason,
+            kinds,
+        })
+    }
+}
+
+/// Progress of a range scan, persisted so an interrupted scan can be resumed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub from: u32,
+    pub to: u32,
+    /// Height of the last block validated, whether it passed or not.
+    pub last_validated: Option<u32>,
+    pub passed: usize,
+    /// Blocks among the passed ones whose failures are known anomalies.
+    #[serde(default)]
+    pub acknowledged: usize,
+    pub failures: Vec<RecordedFailure>,
+}
+
+impl Checkpoint {
+    pub fn new(from: u32, to: u32) -> Self {
+        Self {
+            from,
+            to,
+            last_validated: None,
+            passed: 0,
+            acknowledged: 0,
+            failures: vec![],
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).map_err(|source| ValidationError::CheckpointIo {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        serde_json::from_str(&json).map_err(|source| ValidationError::ParseCheckpoint {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Writes the checkpoint to `path`. The file is replaced atomically so a
+    /// crash while saving leaves the previous checkpoint intact.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let io_error = |source| ValidationError::CheckpointIo {
+            path: path.to_path_buf(),
+            source,
+        };
+
+        let json = serde_json::to_string_pretty(self).expect("checkpoints serialize to JSON");
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        fs::write(&tmp, json).map_err(io_error)?;
+        fs::rename(&tmp, path).map_err(io_error)
+    }
+
+    /// First height that still has to be validated.
+    pub fn next_height(&self) -> u32 {
+        self.last_validated.map_or(self.from, |height| height + 1)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.last_validated.is_some_and(|height| height >= self.to)
+    }
+
+    pub fn validated(&self) -> usize {
+        self.passed + self.failures.len()
+    }
+
+    /// Records the outcome of the next block of the range.
+    pub fn record(&mut self, outcome: &BlockOutcome) {
+        self.last_validated = Some(outcome.height);
+        if outcome.passed() {
+            self.passed += 1;
+            self.acknowledged += usize::from(outcome.acknowledged());
+            return;
+        }
+
+        self.failures.extend(RecordedFailure::of(outcome));
+    }
+}
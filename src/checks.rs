@@ -0,0 +1,827 @@
+// src/checks.rs
+//! Checks that only need the block itself, each recomputing one commitment
+//! and comparing it with what the node reported.
+
+use fuel_core_client::client::schema::{
+    block::Consensus, tx::transparent_receipt, tx::TransactionStatus, ConversionError,
+};
+use fuel_crypto::Hasher;
+use fuel_tx::{
+    field::{
+        BytecodeWitnessIndex, InputContract, MintAmount, MintAssetId, MintGasPrice, Outputs,
+        ReceiptsRoot, Salt, StorageSlots, Witnesses,
+    },
+    policies::PolicyType,
+    Chargeable, ConsensusParameters, Contract, Create, Mint, Output, Receipt,
+    ScriptExecutionResult, Transaction, UniqueIdentifier,
+};
+use fuel_types::{
+    canonical::{Deserialize, Serialize},
+    AssetId, Bytes32, ChainId, ContractId,
+};
+
+use crate::consensus::PoaAuthority;
+use crate::error::{Result, ValidationError};
+use crate::event_inbox::EventInbox;
+use crate::header;
+use crate::merkle;
+use crate::proof::MerkleProof;
+use crate::query::{FullBlock, Header, LightBlock, OpaqueTransactionWithStatusAndId};
+use crate::receipt_diff::ReceiptDiff;
+use crate::registry::{CheckContext, CheckRegistry};
+use crate::report::{BlockReport, Check, CheckResult};
+
+/// Runs every check of the default [`CheckRegistry`], which only need the
+/// block itself. Transaction ids are only checked with a `chain_id`, and the
+/// block signature with an `authority`.
+pub fn validate_block(
+    block: &FullBlock,
+    chain_id: Option<&ChainId>,
+    authority: Option<&PoaAuthority>,
+) -> BlockReport {
+    CheckRegistry::default().run(&CheckContext {
+        block,
+        chain_id,
+        authority,
+        params: None,
+    })
+}
+
+/// Recomputes the application hash and the block id from the header fields
+/// and compares them with `header.application_hash` and `block.id`.
+///
+/// V2 headers commit to the transaction ids reported by the node, which are
+/// checked against the payloads by [`tx_ids`]. Headers of unknown versions
+/// can't be recomputed and fail both checks with a parse error.
+pub fn block_id(block: &FullBlock) -> Vec<CheckResult> {
+    let tx_ids: Vec<Bytes32> = block
+        .transactions
+        .iter()
+        .map(|tx| tx.id.clone().into())
+        .collect();
+    header_ids(&block.header, block.id.clone().into(), &tx_ids)
+}
+
+/// [`block_id`] of a block fetched without its transactions, over the
+/// transaction ids reported by the node.
+pub fn light_block_id(block: &LightBlock) -> Vec<CheckResult> {
+    let tx_ids: Vec<Bytes32> = block
+        .transaction_ids
+        .iter()
+        .map(|id| id.clone().into())
+        .collect();
+    header_ids(&block.header, block.id.clone().into(), &tx_ids)
+}
+
+fn header_ids(header: &Header, block_id: Bytes32, tx_ids: &[Bytes32]) -> Vec<CheckResult> {
+    let application_hash: Bytes32 = header.application_hash.clone().into();
+
+    let (Some(calculated_application_hash), Some(calculated_block_id)) = (
+        header::application_hash(header, tx_ids),
+        header::block_id(header, tx_ids),
+    ) else {
+        let e = ValidationError::UnsupportedHeaderVersion {
+            height: header.height.0,
+        };
+        return vec![
+            CheckResult::from_error(Check::ApplicationHash, &e),
+            CheckResult::from_error(Check::BlockId, &e),
+        ];
+    };
+
+    vec![
+        CheckResult::compare(
+            Check::ApplicationHash,
+            application_hash,
+            calculated_application_hash,
+        ),
+        CheckResult::compare(Check::BlockId, block_id, calculated_block_id),
+    ]
+}
+
+/// Recomputes the id of every transaction from its raw payload and compares
+/// it with the id reported by the node.
+pub fn tx_ids(block: &FullBlock, chain_id: &ChainId) -> Vec<CheckResult> {
+    block
+        .transactions
+        .iter()
+        .map(|tx| {
+            let reported_id: Bytes32 = tx.id.clone().into();
+            match parse_transaction(tx) {
+                Ok(tx_body) => CheckResult::compare(Check::TxId, reported_id, tx_body.id(chain_id)),
+                Err(e) => CheckResult::from_error(Check::TxId, &e),
+            }
+            .with_tx_id(&tx.id)
+        })
+        .collect()
+}
+
+/// Proves that the transaction `tx_id` is a leaf of the transactions root of
+/// `block`: the root its [`MerkleProof`] leads to must be
+/// `header.transactions_root`.
+pub fn tx_inclusion(block: &FullBlock, tx_id: &Bytes32) -> CheckResult {
+    let committed: Bytes32 = block.header.transactions_root.clone().into();
+    match MerkleProof::build(block, tx_id) {
+        Ok(proof) => CheckResult::compare(Check::TxInclusion, committed, proof.root),
+        Err(ValidationError::TxRootMismatch {
+            expected, actual, ..
+        }) => CheckResult::compare(Check::TxInclusion, expected, actual),
+        Err(e) => CheckResult::from_error(Check::TxInclusion, &e),
+    }
+    .with_tx_id(format_args!("{tx_id:#x}"))
+}
+
+/// Recomputes the transactions root from the raw payloads and compares it
+/// with `header.transactions_root`.
+///
+/// The root commits to every transaction of the block, whatever the status
+/// the node reports for it, see [`tx_statuses`].
+pub fn tx_root(block: &FullBlock) -> CheckResult {
+    let tx_root: Bytes32 = block.header.transactions_root.clone().into();
+    let leaves = merkle::map(&block.transactions, |tx| {
+        parse_transaction(tx).map(|tx_body| merkle::leaf_hash(&tx_body.to_bytes()))
+    });
+
+    let mut leaf_hashes = Vec::with_capacity(leaves.len());
+    for (tx, leaf) in block.transactions.iter().zip(leaves) {
+        match leaf {
+            Ok(hash) => leaf_hashes.push(hash),
+            Err(e) => return CheckResult::from_error(Check::TxRoot, &e).with_tx_id(&tx.id),
+        }
+    }
+
+    CheckResult::compare(Check::TxRoot, tx_root, merkle::root(leaf_hashes))
+}
+
+/// Checks that every transaction of the block was executed. A block the
+/// node produced only holds transactions that succeeded or failed, so a
+/// `Submitted`, `SqueezedOut`, unknown or missing status is the node
+/// misreporting it. The other checks skip the receipts of such transactions,
+/// which would hide mismatches of the roots committing to them, so each of
+/// them is a warning.
+pub fn tx_statuses(block: &FullBlock) -> Vec<CheckResult> {
+    let unexpected: Vec<_> = block
+        .transactions
+        .iter()
+        .filter_map(|tx| {
+            let (status, message) = match &tx.status {
+                Some(TransactionStatus::SuccessStatus(_) | TransactionStatus::FailureStatus(_)) => {
+                    return None
+                }
+                Some(TransactionStatus::SubmittedStatus(_)) => ("submitted", None),
+                Some(TransactionStatus::SqueezedOutStatus(status)) => {
+                    ("squeezed out", Some(status.reason.clone()))
+                }
+                Some(TransactionStatus::Unknown) => ("unknown", None),
+                None => ("missing", None),
+            };
+            let result = CheckResult::compare(Check::TxStatus, "success or failure", status)
+                .with_tx_id(&tx.id);
+            Some(match message {
+                Some(message) => result.with_message(message),
+                None => result,
+            })
+        })
+        .collect();
+
+    if unexpected.is_empty() {
+        return vec![CheckResult::compare(
+            Check::TxStatus,
+            block.transactions.len(),
+            block.transactions.len(),
+        )
+        .with_message("executed transactions")];
+    }
+    tracing::warn!(
+        unexpected = unexpected.len(),
+        transactions = block.transactions.len(),
+        "transactions of a finalized block weren't executed"
+    );
+    unexpected
+}
+
+/// Checks that the details of the status of every executed transaction are
+/// consistent with its receipts and its block: the status is at the height
+/// of the block, a script succeeded exactly when its `ScriptResult` receipt
+/// says so and used no more gas than the `total_gas` of the status, and a
+/// failure has the reason the node derives from the first `Revert` or
+/// `Panic` receipt. No root commits to these details, so a node mangling
+/// them while serving the right receipts would pass the other checks.
+pub fn status_receipts(block: &FullBlock) -> Vec<CheckResult> {
+    let height = block.height();
+    let mut results = vec![];
+    for (tx, receipts) in executed_transactions(block) {
+        let (block_height, total_gas, reason) = match &tx.status {
+            Some(TransactionStatus::SuccessStatus(status)) => {
+                (status.block_height.0, status.total_gas.0, None)
+            }
+            Some(TransactionStatus::FailureStatus(status)) => (
+                status.block_height.0,
+                status.total_gas.0,
+                Some(status.reason.as_str()),
+            ),
+            _ => continue,
+        };
+        results.push(
+            CheckResult::compare(Check::StatusHeight, height, block_height).with_tx_id(&tx.id),
+        );
+
+        // Receipts that don't decode fail the receipts root.
+        let receipts: Vec<_> = receipts
+            .iter()
+            .filter_map(|receipt| parse_receipt(tx, receipt).ok())
+            .collect();
+        let script_result = receipts.iter().find_map(|receipt| match receipt {
+            Receipt::ScriptResult { result, gas_used } => Some((result, *gas_used)),
+            _ => None,
+        });
+        if let Some((result, gas_used)) = script_result {
+            let expected = match result {
+                ScriptExecutionResult::Success => "success",
+                _ => "failure",
+            };
+            let actual = if reason.is_some() {
+                "failure"
+            } else {
+                "success"
+            };
+            results.push(
+                CheckResult::compare(Check::StatusResult, expected, actual).with_tx_id(&tx.id),
+            );
+            results.push(
+                CheckResult::at_most(Check::StatusGas, total_gas, gas_used).with_tx_id(&tx.id),
+            );
+        }
+        if let (Some(reason), Some(expected)) = (reason, failure_reason(&receipts)) {
+            results.push(
+                CheckResult::compare(Check::StatusReason, expected.as_str(), reason)
+                    .with_tx_id(&tx.id),
+            );
+        }
+    }
+    results
+}
+
+/// The reason fuel-core gives for the failure of a transaction with
+/// `receipts`: `Revert($ra)` for its first `Revert` receipt, or the name of
+/// the reason of its first `Panic`. `None` when it has neither, as the node
+/// then describes the program state instead.
+fn failure_reason(receipts: &[Receipt]) -> Option<String> {
+    receipts.iter().find_map(|receipt| match receipt {
+        Receipt::Revert { ra, .. } => Some(format!("Revert({ra})")),
+        Receipt::Panic { reason, .. } => Some(reason.reason().to_string()),
+        _ => None,
+    })
+}
+
+/// Recomputes the message outbox root from the ids of the `MessageOut`
+/// receipts of every transaction, in block order, and compares it and the
+/// number of messages with the header.
+pub fn message_outbox(block: &FullBlock) -> Vec<CheckResult> {
+    let message_outbox_root: Bytes32 = block.header.message_outbox_root.clone().into();
+    let mut message_hashes = vec![];
+    let mut message_count = 0u32;
+
+    for (tx, receipts) in executed_transactions(block) {
+        for receipt in receipts {
+            let receipt = match parse_receipt(tx, receipt) {
+                Ok(receipt) => receipt,
+                Err(e) => {
+                    return vec![
+                        CheckResult::from_error(Check::MessageOutboxRoot, &e).with_tx_id(&tx.id)
+                    ]
+                }
+            };
+            if let Some(message_id) = receipt.message_id() {
+                message_hashes.push(merkle::leaf_hash(message_id.as_ref()));
+                message_count += 1;
+            }
+        }
+    }
+
+    let calculated_outbox_root = merkle::root(message_hashes);
+    vec![
+        CheckResult::compare(
+            Check::MessageReceiptCount,
+            block.header.message_receipt_count.0,
+            message_count,
+        ),
+        CheckResult::compare(
+            Check::MessageOutboxRoot,
+            message_outbox_root,
+            calculated_outbox_root,
+        ),
+    ]
+}
+
+/// Recomputes the receipts root of every transaction.
+///
+/// Only `Script` transactions commit to their receipts with a
+/// `receipts_root`. The receipts of `Create`, `Upgrade`, `Upload` and `Blob`
+/// transactions are still decoded so malformed receipts are caught, while
+/// `Mint` transactions produce no receipts and are skipped.
+pub fn receipt_roots(block: &FullBlock) -> Vec<CheckResult> {
+    let transactions: Vec<_> = executed_transactions(block).collect();
+    merkle::map(&transactions, |&(tx, receipts)| receipt_root(tx, receipts))
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Checks the receipts root of one transaction, `None` if it doesn't commit
+/// to one.
+fn receipt_root(
+    tx: &OpaqueTransactionWithStatusAndId,
+    receipts: &[transparent_receipt::Receipt],
+) -> Option<CheckResult> {
+    let _span = tracing::debug_span!("tx", tx_id = %tx.id).entered();
+    if let Some(TransactionStatus::FailureStatus(status)) = &tx.status {
+        tracing::info!(reason = %status.reason, "found failed transaction");
+    }
+
+    let receipt_root = match parse_transaction(tx) {
+        Ok(Transaction::Script(tx_body)) => Some(*tx_body.receipts_root()),
+        Ok(
+            Transaction::Create(_)
+            | Transaction::Upgrade(_)
+            | Transaction::Upload(_)
+            | Transaction::Blob(_),
+        ) => None,
+        Ok(Transaction::Mint(_)) => {
+            tracing::debug!("skipping receipts of mint transaction: mints don't produce receipts");
+            return None;
+        }
+        Err(e) => return Some(CheckResult::from_error(Check::ReceiptRoot, &e).with_tx_id(&tx.id)),
+    };
+
+    let calculated_receipt_root = match calculate_receipts_root(tx, receipts) {
+        Ok(root) => root,
+        Err(e) => return Some(CheckResult::from_error(Check::ReceiptRoot, &e).with_tx_id(&tx.id)),
+    };
+
+    match receipt_root {
+        Some(receipt_root) => {
+            let result =
+                CheckResult::compare(Check::ReceiptRoot, receipt_root, calculated_receipt_root)
+                    .with_tx_id(&tx.id);
+            if result.passed {
+                return Some(result);
+            }
+            // The receipts parsed to compute the root.
+            let parsed: Vec<_> = receipts
+                .iter()
+                .filter_map(|receipt| parse_receipt(tx, receipt).ok())
+                .collect();
+            Some(result.with_receipt_diff(ReceiptDiff::new(receipt_root, &parsed)))
+        }
+        None => {
+            tracing::debug!(
+                receipts = receipts.len(),
+                "transaction has receipts but doesn't commit to a receipts root"
+            );
+            None
+        }
+    }
+}
+
+/// Checks the `Mint` transaction paying the fees of the block to its
+/// producer: there must be exactly one, the last of the block, minting the
+/// fees paid by the other transactions. The node mints nothing when the
+/// coinbase recipient is the zero contract. The genesis block has no
+/// transactions and is skipped.
+pub fn mint(block: &FullBlock) -> Vec<CheckResult> {
+    if matches!(block.consensus, Consensus::Genesis(_)) {
+        return vec![];
+    }
+    let mints = match mints(block) {
+        Ok(mints) => mints,
+        Err((tx, e)) => {
+            return vec![CheckResult::from_error(Check::MintPosition, &e).with_tx_id(&tx.id)]
+        }
+    };
+
+    let last = block.transactions.len().max(1) - 1;
+    let positions: Vec<_> = mints.iter().map(|(index, _)| *index).collect();
+    let mut results = vec![CheckResult::compare(
+        Check::MintPosition,
+        format!("{:?}", [last]),
+        format!("{positions:?}"),
+    )];
+
+    if let Some((index, mint)) = mints.last() {
+        let tx = &block.transactions[*index];
+        let fees = block
+            .transactions
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| other != index)
+            .filter_map(|(_, tx)| match &tx.status {
+                Some(TransactionStatus::SuccessStatus(status)) => Some(status.total_fee.0),
+                Some(TransactionStatus::FailureStatus(status)) => Some(status.total_fee.0),
+                _ => None,
+            })
+            .fold(0u64, u64::saturating_add);
+        let minted = if mint.input_contract().contract_id == ContractId::zeroed() {
+            0
+        } else {
+            fees
+        };
+        results.push(
+            CheckResult::compare(Check::MintAmount, *mint.mint_amount(), minted).with_tx_id(&tx.id),
+        );
+    }
+    results
+}
+
+/// Checks that the `Mint` transaction of the block mints `base_asset_id`,
+/// the asset fees are paid in. `None` if the block has no mint.
+pub fn mint_asset_id(block: &FullBlock, base_asset_id: &AssetId) -> Option<CheckResult> {
+    let (index, mint) = mints(block).ok()?.pop()?;
+    Some(
+        CheckResult::compare(Check::MintAssetId, *base_asset_id, *mint.mint_asset_id())
+            .with_tx_id(&block.transactions[index].id),
+    )
+}
+
+/// Recomputes the gas and the fee charged for every transaction from its
+/// payload, the gas its script used and the gas price of the block, carried
+/// by its mint, and compares them with the `total_gas` and `total_fee` of its
+/// status. Mints aren't charged, and transactions without an executed
+/// status are skipped.
+pub fn fees(block: &FullBlock, params: &ConsensusParameters) -> Vec<CheckResult> {
+    let gas_price = match mints(block) {
+        Ok(mints) => match mints.last() {
+            Some((_, mint)) => *mint.gas_price(),
+            None => {
+                return vec![CheckResult::error(
+                    Check::TxFee,
+                    "the block has no mint carrying its gas price",
+                )]
+            }
+        },
+        Err((tx, e)) => return vec![CheckResult::from_error(Check::TxFee, &e).with_tx_id(&tx.id)],
+    };
+
+    let mut results = vec![];
+    for (tx, receipts) in executed_transactions(block) {
+        let (total_gas, total_fee) = match &tx.status {
+            Some(TransactionStatus::SuccessStatus(status)) => {
+                (status.total_gas.0, status.total_fee.0)
+            }
+            Some(TransactionStatus::FailureStatus(status)) => {
+                (status.total_gas.0, status.total_fee.0)
+            }
+            _ => continue,
+        };
+        let gas_used = receipts
+            .iter()
+            .find_map(|receipt| match parse_receipt(tx, receipt) {
+                Ok(Receipt::ScriptResult { gas_used, .. }) => Some(Ok(gas_used)),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            });
+        let charged = match (parse_transaction(tx), gas_used.transpose()) {
+            (Ok(Transaction::Script(tx)), Ok(gas_used)) => {
+                charged(&tx, params, gas_used.unwrap_or_default(), gas_price)
+            }
+            (Ok(Transaction::Create(tx)), Ok(_)) => charged(&tx, params, 0, gas_price),
+            (Ok(Transaction::Upgrade(tx)), Ok(_)) => charged(&tx, params, 0, gas_price),
+            (Ok(Transaction::Upload(tx)), Ok(_)) => charged(&tx, params, 0, gas_price),
+            (Ok(Transaction::Blob(tx)), Ok(_)) => charged(&tx, params, 0, gas_price),
+            (Ok(Transaction::Mint(_)), _) => continue,
+            (Err(e), _) | (_, Err(e)) => {
+                results.push(CheckResult::from_error(Check::TxFee, &e).with_tx_id(&tx.id));
+                continue;
+            }
+        };
+        let (calculated_gas, calculated_fee) = charged;
+        results
+            .push(CheckResult::compare(Check::TxGas, total_gas, calculated_gas).with_tx_id(&tx.id));
+        results
+            .push(CheckResult::compare(Check::TxFee, total_fee, calculated_fee).with_tx_id(&tx.id));
+    }
+    results
+}
+
+/// The gas and the fee charged for `tx`, as the node's executor computes
+/// them: the gas needed to start it plus `gas_used`, at `gas_price`, plus
+/// its tip.
+fn charged<Tx: Chargeable>(
+    tx: &Tx,
+    params: &ConsensusParameters,
+    gas_used: u64,
+    gas_price: u64,
+) -> (u64, u64) {
+    let fee_params = params.fee_params();
+    let gas = tx
+        .min_gas(params.gas_costs(), fee_params)
+        .saturating_add(gas_used);
+    let fee = (u128::from(gas) * u128::from(gas_price))
+        .div_ceil(u128::from(fee_params.gas_price_factor()))
+        .saturating_add(u128::from(tx.policies().get(PolicyType::Tip).unwrap_or(0)));
+    (gas, u64::try_from(fee).unwrap_or(u64::MAX))
+}
+
+/// The `Mint` transactions of the block, with their index, or the first
+/// transaction that doesn't decode.
+fn mints(
+    block: &FullBlock,
+) -> std::result::Result<Vec<(usize, Mint)>, (&OpaqueTransactionWithStatusAndId, ValidationError)> {
+    let mut mints = vec![];
+    for (index, tx) in block.transactions.iter().enumerate() {
+        match parse_transaction(tx) {
+            Ok(Transaction::Mint(mint)) => mints.push((index, mint)),
+            Ok(_) => {}
+            Err(e) => return Err((tx, e)),
+        }
+    }
+    Ok(mints)
+}
+
+/// Recomputes the state root of every contract the block creates, the root
+/// of the sparse Merkle tree over its initial storage slots, and compares it
+/// with the `state_root` of the `ContractCreated` output. The contract id,
+/// which commits to the bytecode, the salt and the state root, is derived
+/// again as well.
+///
+/// Nodes don't serve the storage and balances of contracts at past heights,
+/// so the roots in the contract outputs of other transactions can't be
+/// recomputed.
+pub fn contract_state_roots(block: &FullBlock) -> Vec<CheckResult> {
+    block
+        .transactions
+        .iter()
+        .flat_map(|tx| match parse_transaction(tx) {
+            Ok(Transaction::Create(create)) => contract_state_root(&create)
+                .into_iter()
+                .map(|result| result.with_tx_id(&tx.id))
+                .collect(),
+            Ok(_) => vec![],
+            Err(e) => {
+                vec![CheckResult::from_error(Check::ContractStateRoot, &e).with_tx_id(&tx.id)]
+            }
+        })
+        .collect()
+}
+
+fn contract_state_root(create: &Create) -> Vec<CheckResult> {
+    let created = create.outputs().iter().find_map(|output| match output {
+        Output::ContractCreated {
+            contract_id,
+            state_root,
+        } => Some((*contract_id, *state_root)),
+        _ => None,
+    });
+    let Some((contract_id, state_root)) = created else {
+        return vec![CheckResult::error(
+            Check::ContractStateRoot,
+            "the transaction has no contract created output",
+        )];
+    };
+
+    let calculated = merkle::contract_state_root(create.storage_slots());
+    let mut results = vec![CheckResult::compare(
+        Check::ContractStateRoot,
+        state_root,
+        calculated,
+    )];
+    let index = usize::from(*create.bytecode_witness_index());
+    results.push(match create.witnesses().get(index) {
+        Some(bytecode) => {
+            let contract = Contract::from(bytecode.as_vec().as_slice());
+            let calculated_id = contract.id(create.salt(), &contract.root(), &calculated);
+            CheckResult::compare(Check::ContractId, contract_id, calculated_id)
+        }
+        None => CheckResult::error(
+            Check::ContractId,
+            format!("the bytecode witness #{index} is missing"),
+        ),
+    });
+    results
+}
+
+/// Compares `header.prev_root` with the root of the tree of previous block
+/// ids. The genesis block has no previous blocks and is skipped.
+pub fn prev_root(block: &FullBlock, calculated: Option<Bytes32>) -> Option<CheckResult> {
+    header_prev_root(&block.consensus, &block.header, calculated)
+}
+
+/// [`prev_root`] of a block fetched without its transactions.
+pub fn light_prev_root(block: &LightBlock, calculated: Option<Bytes32>) -> Option<CheckResult> {
+    header_prev_root(&block.consensus, &block.header, calculated)
+}
+
+fn header_prev_root(
+    consensus: &Consensus,
+    header: &Header,
+    calculated: Option<Bytes32>,
+) -> Option<CheckResult> {
+    if matches!(consensus, Consensus::Genesis(_)) {
+        return None;
+    }
+
+    let prev_root: Bytes32 = header.prev_root.clone().into();
+    Some(match calculated {
+        Some(calculated) => CheckResult::compare(Check::PrevRoot, prev_root, calculated),
+        None => CheckResult::error(
+            Check::PrevRoot,
+            format!(
+                "ids of the blocks before #{} are not available",
+                header.height.0
+            ),
+        ),
+    })
+}
+
+/// Recomputes the event inbox root from the L1 events included since the
+/// parent block's DA height and compares it with the header.
+///
+/// Without `events`, only blocks that didn't advance the DA height (and must
+/// therefore have an empty inbox) can be checked; others are skipped.
+pub fn event_inbox_root(
+    block: &FullBlock,
+    parent_da_height: u64,
+    events: Option<&EventInbox>,
+) -> Option<CheckResult> {
+    let da_height = block.header.da_height.0;
+    if da_height < parent_da_height {
+        return Some(CheckResult::error(
+            Check::EventInboxRoot,
+            format!("DA height decreased from {parent_da_height} to {da_height}"),
+        ));
+    }
+
+    let calculated_inbox_root = match events {
+        _ if da_height == parent_da_height => merkle::root([]),
+        Some(events) => events.root_between(parent_da_height, da_height),
+        None => {
+            tracing::warn!(
+                block_height = block.height(),
+                "skipping event inbox root: no L1 events for DA heights {}..={}",
+                parent_da_height + 1,
+                da_height
+            );
+            return None;
+        }
+    };
+
+    let event_inbox_root: Bytes32 = block.header.event_inbox_root.clone().into();
+    Some(CheckResult::compare(
+        Check::EventInboxRoot,
+        event_inbox_root,
+        calculated_inbox_root,
+    ))
+}
+
+/// Compares a block with the same block as served by another endpoint.
+///
+/// The block id, header, consensus data, transaction ids, raw payloads and
+/// statuses must all be identical. Returns a single passed result when they
+/// are, and one failed result per difference otherwise. Values that aren't
+/// short enough to report are compared and reported by their hash.
+pub fn endpoint_agreement(
+    block: &FullBlock,
+    endpoint: &str,
+    other: &FullBlock,
+) -> Vec<CheckResult> {
+    let mut results = vec![];
+    let mut compare = |what: String, expected: String, actual: String| {
+        if expected != actual {
+            results.push(
+                CheckResult::compare(Check::EndpointAgreement, expected, actual)
+                    .with_message(format!("{what} differs on {endpoint}"))
+                    .with_endpoint(endpoint),
+            );
+        }
+    };
+
+    compare(
+        "block id".into(),
+        block.id.to_string(),
+        other.id.to_string(),
+    );
+    compare(
+        "header".into(),
+        fingerprint(&block.header).to_string(),
+        fingerprint(&other.header).to_string(),
+    );
+    compare(
+        "consensus".into(),
+        fingerprint(&block.consensus).to_string(),
+        fingerprint(&other.consensus).to_string(),
+    );
+    compare(
+        "transaction count".into(),
+        block.transactions.len().to_string(),
+        other.transactions.len().to_string(),
+    );
+
+    for (index, (tx, other_tx)) in block
+        .transactions
+        .iter()
+        .zip(&other.transactions)
+        .enumerate()
+    {
+        compare(
+            format!("id of transaction #{index}"),
+            tx.id.to_string(),
+            other_tx.id.to_string(),
+        );
+        compare(
+            format!("payload hash of transaction {}", tx.id),
+            Hasher::hash(&tx.raw_payload.0 .0).to_string(),
+            Hasher::hash(&other_tx.raw_payload.0 .0).to_string(),
+        );
+        compare(
+            format!("status of transaction {}", tx.id),
+            fingerprint(&tx.status).to_string(),
+            fingerprint(&other_tx.status).to_string(),
+        );
+    }
+
+    if results.is_empty() {
+        results.push(
+            CheckResult::compare(Check::EndpointAgreement, true, true)
+                .with_message(format!("{endpoint} serves the same block"))
+                .with_endpoint(endpoint),
+        );
+    }
+    results
+}
+
+/// Hash of the debug representation of a GraphQL value, which contains every
+/// field returned by the node.
+fn fingerprint(value: &impl std::fmt::Debug) -> Bytes32 {
+    Hasher::hash(format!("{value:?}"))
+}
+
+/// Decodes every transaction and receipt of the block like the checks do,
+/// and drops them. Decoding errors are left to the checks to report; this is
+/// only used to time decoding on its own.
+#[cfg(feature = "native")]
+pub(crate) fn decode(block: &FullBlock) {
+    for tx in &block.transactions {
+        std::hint::black_box(parse_transaction(tx).ok());
+    }
+    for (tx, receipts) in executed_transactions(block) {
+        for receipt in receipts {
+            std::hint::black_box(parse_receipt(tx, receipt).ok());
+        }
+    }
+}
+
+/// Transactions of the block that were executed, paired with their receipts.
+///
+/// Transactions without a success or failure status are skipped, and
+/// reported by [`tx_statuses`].
+pub(crate) fn executed_transactions(
+    block: &FullBlock,
+) -> impl Iterator<
+    Item = (
+        &OpaqueTransactionWithStatusAndId,
+        &[transparent_receipt::Receipt],
+    ),
+> {
+    block.transactions.iter().filter_map(|tx| {
+        let receipts = match &tx.status {
+            Some(TransactionStatus::SuccessStatus(status)) => &status.receipts,
+            Some(TransactionStatus::FailureStatus(status)) => &status.receipts,
+            _ => return None,
+        };
+        Some((tx, receipts.as_slice()))
+    })
+}
+
+/// Merkle root over the canonical encoding of `receipts`, in order.
+pub(crate) fn calculate_receipts_root(
+    tx: &OpaqueTransactionWithStatusAndId,
+    receipts: &[transparent_receipt::Receipt],
+) -> Result<Bytes32> {
+    let leaf_hashes = receipts
+        .iter()
+        .map(|receipt| Ok(merkle::leaf_hash(&parse_receipt(tx, receipt)?.to_bytes())))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(merkle::root(leaf_hashes))
+}
+
+pub(crate) fn parse_receipt(
+    tx: &OpaqueTransactionWithStatusAndId,
+    receipt: &transparent_receipt::Receipt,
+) -> Result<Receipt> {
+    receipt
+        .clone()
+        .try_into()
+        .map_err(|e: ConversionError| ValidationError::ParseError {
+            tx_id: tx.id.to_string(),
+            reason: format!("invalid receipt: {e}"),
+        })
+}
+
+pub(crate) fn parse_transaction(tx: &OpaqueTransactionWithStatusAndId) -> Result<Transaction> {
+    Transaction::from_bytes(tx.raw_payload.0 .0.as_slice()).map_err(|e| {
+        ValidationError::ParseError {
+            tx_id: tx.id.to_string(),
+            reason: e.to_string(),
+        }
+    })
+}
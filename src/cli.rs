@@ -0,0 +1,816 @@
+// src/cli.rs
+use std::{collections::BTreeMap, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
+
+use anyhow::{bail, Result};
+use clap::{builder::PossibleValuesParser, Args, Parser, Subcommand, ValueEnum};
+use fuel_core_client::client::schema::Bytes;
+use fuel_root_validation::{
+    corrupt::Corruption, dump::DumpFormat, events::DEFAULT_EVENTS_TOPIC,
+    follow::DEFAULT_MAX_REORG_DEPTH, hash, health::DEFAULT_MAX_LAG, registry,
+    retry::DEFAULT_MAX_ATTEMPTS, validator::BLOCKS_PAGE_SIZE, BlockTarget, Cassette, EthAddress,
+    Network, NetworkProfile, Severity, WebhookFormat,
+};
+use fuel_types::{Address, Bytes32};
+use tokio_util::sync::CancellationToken;
+
+use crate::{config::MonitorConfig, logging::LogFormat, output::OutputFormat};
+
+/// Independently recompute and check the Merkle roots of Fuel blocks.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// TOML file the settings below are read from. Flags given on the command
+    /// line override the values of the file.
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    /// GraphQL endpoint of the node to validate against. Overrides `--network`.
+    /// May be repeated: every block is then also fetched from the other
+    /// endpoints and must be identical on all of them.
+    #[arg(long = "endpoint", global = true)]
+    pub endpoints: Vec<String>,
+
+    /// Network whose profile is used: `mainnet`, `testnet`, `devnet` or one
+    /// of the `[networks.<name>]` of the config file. Its endpoints are
+    /// queried when `--endpoint` isn't given, and its chain id and PoA
+    /// authority used when `--chain-id` and `--poa-authority` aren't.
+    #[arg(long, default_value_t = Network::Testnet.to_string(), global = true)]
+    pub network: String,
+
+    /// The profile of `--network`, resolved once the config file is read.
+    #[arg(skip)]
+    pub profile: NetworkProfile,
+
+    /// The `[[monitors]]` of the config file, followed instead of
+    /// `--network` in follow mode.
+    #[arg(skip)]
+    pub monitors: Vec<MonitorConfig>,
+
+    /// Chain id used to recompute transaction ids. Queried from the node when not given.
+    #[arg(long)]
+    pub chain_id: Option<u64>,
+
+    /// JSON file with the consensus parameters of the chain by version, as
+    /// written by the `consensus-parameters` command. Pins them instead of
+    /// querying the node at startup, e.g. to check `--input` blocks offline.
+    #[arg(long)]
+    pub consensus_parameters: Option<PathBuf>,
+
+    /// Check to run besides the default ones, by name, may be repeated:
+    /// `fees` recomputes the gas and the fee charged for every transaction,
+    /// `contract-state` the state roots of the contracts created, `prev-root` and `event-inbox` are `--check-prev-root` and
+    /// `--check-event-inbox`. An unknown name lists the known ones.
+    #[arg(long = "check", value_name = "NAME")]
+    pub checks: Vec<String>,
+
+    /// Check not to run, by name, may be repeated, e.g. `receipt-root`.
+    /// Overrides `--check`.
+    #[arg(long = "skip-check", value_name = "NAME")]
+    pub skip_checks: Vec<String>,
+
+    /// Check whose failures are only warnings, by name, may be repeated.
+    /// The `tx-gas` and `tx-fee` results of `fees` always default to
+    /// warnings.
+    #[arg(long = "warn-check", value_name = "NAME")]
+    pub warn_checks: Vec<String>,
+
+    /// Least severity of a failed check that fails its block: `warning` or
+    /// `error`. Failures below it are printed as warnings.
+    #[arg(long, default_value = "error")]
+    pub fail_on: Severity,
+
+    /// Also validate `header.prev_root` against the ids of all previous blocks.
+    /// Requires fetching the id of every block since genesis.
+    #[arg(long)]
+    pub check_prev_root: bool,
+
+    /// Also validate `header.event_inbox_root`. Blocks that advance the DA
+    /// height are only checked when `--l1-events` is given.
+    #[arg(long)]
+    pub check_event_inbox: bool,
+
+    /// JSON file with the relayed L1 events (messages and forced transactions)
+    /// used to recompute the event inbox root. Implies `--check-event-inbox`.
+    #[arg(long)]
+    pub l1_events: Option<PathBuf>,
+
+    /// JSON file of known anomalies: failures of a block height or a
+    /// transaction id, optionally of a single check, with a note explaining
+    /// them. They are reported as acknowledged and don't fail the run.
+    #[arg(long)]
+    pub known_anomalies: Option<PathBuf>,
+
+    /// Address of the PoA authority expected to have signed every block. Block
+    /// signatures are not verified without it.
+    #[arg(long)]
+    pub poa_authority: Option<Address>,
+
+    /// Signing keys replacing `--poa-authority` from the given heights on,
+    /// from the profile the authority comes from.
+    #[arg(skip)]
+    pub signing_key_overrides: BTreeMap<u32, Address>,
+
+    /// Ethereum JSON-RPC endpoint used to cross-check blocks with the chain
+    /// state committed on L1. Requires `--l1-chain-state`.
+    #[arg(long)]
+    pub l1_rpc_url: Option<String>,
+
+    /// Address of the `FuelChainState` contract on L1. Every block's DA
+    /// height is checked against the latest L1 block, and the block committed
+    /// for its commit interval against the blocks served by the node.
+    #[arg(long)]
+    pub l1_chain_state: Option<EthAddress>,
+
+    /// Validate the blocks dumped in this file, or directory of files,
+    /// instead of fetching them. Every block of the input is validated unless
+    /// `--block-height` or `--from-height` is given. No node is contacted;
+    /// transaction ids are only checked when `--chain-id` is given.
+    #[arg(long, conflicts_with = "follow")]
+    pub input: Option<PathBuf>,
+
+    /// Validate the blocks of a node's RocksDB database, the `--db-path` of
+    /// fuel-core, instead of fetching them through its API. The database is
+    /// opened read-only. Requires building with the `rocksdb` feature.
+    #[arg(long, conflicts_with_all = ["input", "follow"])]
+    pub rocksdb: Option<PathBuf>,
+
+    /// Block to validate: a height, `latest`, or `latest-N` for the block N
+    /// below the latest one. May be repeated. Defaults to the bundled list of
+    /// testnet blocks with known receipt root issues.
+    #[arg(
+        long = "block-height",
+        visible_alias = "block",
+        value_name = "BLOCK",
+        conflicts_with = "from_height",
+        global = true
+    )]
+    pub block_heights: Vec<BlockTarget>,
+
+    /// First height of a range of blocks to validate. In follow mode, the
+    /// height to start following from.
+    #[arg(long, global = true)]
+    pub from_height: Option<u32>,
+
+    /// Last height (inclusive) of a range of blocks to validate.
+    #[arg(long, global = true)]
+    pub to_height: Option<u32>,
+
+    /// Range of blocks to validate, `A..B` or `A..=B`, both inclusive. The
+    /// same as `--from-height A --to-height B`.
+    #[arg(long, value_parser = parse_range, conflicts_with_all = ["block_heights", "from_height", "to_height"], global = true)]
+    pub range: Option<(u32, u32)>,
+
+    /// Validate this many blocks picked at random in the range instead of
+    /// all of them, as a quick spot check of a node's history.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..), conflicts_with_all = ["follow", "checkpoint"])]
+    pub sample: Option<u64>,
+
+    /// Seed of the `--sample` heights, to spot check the same blocks again.
+    /// A random seed is picked and logged otherwise.
+    #[arg(long, requires = "sample")]
+    pub seed: Option<u64>,
+
+    /// Number of block pages fetched and validated concurrently in range mode.
+    #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..), global = true)]
+    pub jobs: u32,
+
+    /// Number of blocks fetched with a single query in range mode. Nodes
+    /// reject queries above their complexity limit.
+    #[arg(long, default_value_t = BLOCKS_PAGE_SIZE, value_parser = clap::value_parser!(u32).range(1..), global = true)]
+    pub page_size: u32,
+
+    /// Memory the blocks being validated may hold, e.g. `256MiB`. Pages
+    /// hold fewer blocks when blocks are large, down to one. A hint rather
+    /// than a limit: the process itself and larger blocks take more.
+    #[arg(long, value_parser = parse_size, global = true)]
+    pub max_memory: Option<u64>,
+
+    /// File the progress of a range scan is saved to while it runs.
+    #[arg(long, conflicts_with_all = ["block_heights", "follow"])]
+    pub checkpoint: Option<PathBuf>,
+
+    /// Continue the range scan saved in `--checkpoint` instead of starting
+    /// over. The range is read from the checkpoint when not given.
+    #[arg(long)]
+    pub resume: bool,
+
+    /// Keep running and validate every new block as it is produced. SIGHUP
+    /// reloads the settings and the config file, SIGUSR1 logs statistics.
+    #[arg(long, conflicts_with_all = ["block_heights", "to_height"])]
+    pub follow: bool,
+
+    /// Number of validated blocks remembered in follow mode to trace a reorg
+    /// back to its fork point.
+    #[arg(long, default_value_t = DEFAULT_MAX_REORG_DEPTH, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_reorg_depth: u32,
+
+    /// Only validate the blocks of follow mode once this many blocks are on
+    /// top of them, leaving alone the fresh blocks most likely to be reorged.
+    #[arg(long, default_value_t = 0)]
+    pub confirmations: u32,
+
+    /// Address Prometheus metrics are served on at `/metrics` in follow mode,
+    /// e.g. `0.0.0.0:9100`, along with the `/healthz` and `/readyz` probes.
+    #[arg(long)]
+    pub metrics_addr: Option<SocketAddr>,
+
+    /// Number of blocks validation may lag behind the head in follow mode
+    /// before `/readyz` reports the validator as not ready. The blocks left
+    /// alone by `--confirmations` count as well.
+    #[arg(long, default_value_t = DEFAULT_MAX_LAG)]
+    pub max_lag: u32,
+
+    /// Delay between two polls of the latest block in follow mode, e.g. `500ms` or `2s`.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "1s")]
+    pub poll_interval: Duration,
+
+    /// Show a terminal dashboard in follow mode instead of printing results:
+    /// the head and validation lag, the pass rate of every check, the latest
+    /// failures and the health of the endpoints. Logs are dropped unless
+    /// stderr is redirected. Requires building with the `tui` feature.
+    #[arg(long, conflicts_with_all = ["output", "out_file"])]
+    pub tui: bool,
+
+    /// Poll the latest block in follow mode even if the node pushes new
+    /// blocks. Nodes that don't are polled anyway.
+    #[arg(long)]
+    pub no_subscribe: bool,
+
+    /// Number of attempts made for every query before giving up. Only
+    /// timeouts, connection errors, rate limiting and server errors are retried.
+    #[arg(long, default_value_t = DEFAULT_MAX_ATTEMPTS, value_parser = clap::value_parser!(u32).range(1..), global = true)]
+    pub max_attempts: u32,
+
+    /// Base delay of the exponential backoff between two attempts, e.g. `250ms`.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "250ms", global = true)]
+    pub retry_backoff: Duration,
+
+    /// How long every attempt of a query waits for the node to answer, e.g.
+    /// `30s`, before it is retried as a timeout. Unlimited by default.
+    #[arg(long, value_parser = humantime::parse_duration, global = true)]
+    pub timeout: Option<Duration>,
+
+    /// Fail blocks whose query fails, or whose statuses lack their receipts,
+    /// instead of querying them without the statuses and fetching the
+    /// missing receipts per transaction.
+    #[arg(long, global = true)]
+    pub no_receipt_fallback: bool,
+
+    /// SHA-256 implementation the Merkle roots are hashed with: `sha2`, or
+    /// `ring` in builds with the `ring` feature.
+    #[arg(long, default_value = "sha2", value_parser = PossibleValuesParser::new(hash::BACKENDS), global = true)]
+    pub hash_backend: String,
+
+    /// Also hash every root with `sha2`, logging where `--hash-backend`
+    /// disagrees with it and failing the run with the mismatch exit code.
+    /// The roots of `sha2` are used.
+    #[arg(long, global = true)]
+    pub cross_check_hash: bool,
+
+    /// Stop the run after this long, e.g. `1h`: queries in flight are
+    /// abandoned, a range scan saves its `--checkpoint` and fails, and
+    /// follow mode shuts down like on Ctrl-C.
+    #[arg(long, value_parser = humantime::parse_duration, global = true)]
+    pub run_deadline: Option<Duration>,
+
+    /// Cancelled on Ctrl-C or once `--run-deadline` is reached.
+    #[arg(skip)]
+    pub cancel: CancellationToken,
+
+    /// Most queries sent to every endpoint per second, e.g. `5` or `0.5`,
+    /// to stay under the rate limits of public endpoints. Unlimited by
+    /// default. Endpoints answering `429` with `Retry-After` are always
+    /// left alone for as long as they ask.
+    #[arg(long, value_parser = parse_rps, global = true)]
+    pub max_rps: Option<f64>,
+
+    /// Most queries waiting for a response from every endpoint at once,
+    /// whatever `--jobs`. Unlimited by default.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..), global = true)]
+    pub max_in_flight: Option<u32>,
+
+    /// Directory the blocks fetched from the node are cached in, so later
+    /// runs don't fetch them again. Defaults to `fuel-block-validator` in the
+    /// user's cache directory. Not used in follow mode, which has to see
+    /// reorged blocks.
+    #[arg(long, global = true)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Fetch every block from the node, without reading or filling the cache.
+    #[arg(long, conflicts_with = "cache_dir", global = true)]
+    pub no_cache: bool,
+
+    /// Space the cached blocks may take on disk, e.g. `500MiB` or `2GB`. The
+    /// least recently used blocks are evicted beyond it.
+    #[arg(long, value_parser = parse_size, default_value = "1GiB", global = true)]
+    pub cache_max_size: u64,
+
+    /// Record every GraphQL response of the run in this JSON file, to replay
+    /// it later with `--replay`. The block cache isn't used.
+    #[arg(long, global = true)]
+    pub record: Option<PathBuf>,
+
+    /// Answer the GraphQL queries of the run from a file written by
+    /// `--record` instead of querying the nodes, to reproduce a run. Queries
+    /// missing from the recording fail. The block cache isn't used.
+    #[arg(long, conflicts_with = "record", global = true)]
+    pub replay: Option<PathBuf>,
+
+    /// The cassette of `--record` or `--replay`, opened by `configure`.
+    #[arg(skip)]
+    pub cassette: Option<Arc<Cassette>>,
+
+    /// SQLite database every validated block and check is recorded in. Blocks
+    /// already validated by a previous run are skipped, except in follow mode.
+    /// A `postgres://` URL records them in a PostgreSQL database instead,
+    /// which validators of the same `--network` can share. Requires building
+    /// with the `postgres` feature.
+    #[arg(long)]
+    pub db: Option<PathBuf>,
+
+    /// URL an alert is posted to whenever a block doesn't match its
+    /// commitments or endpoints disagree on it.
+    #[arg(long)]
+    pub webhook_url: Option<String>,
+
+    /// Body of the alerts posted to `--webhook-url`: `json`, `slack` or `discord`.
+    #[arg(long, default_value = "json")]
+    pub webhook_format: WebhookFormat,
+
+    /// Message bus the report of every validated block is published to as
+    /// JSON: `nats://host:4222` or `kafka://host:9092[,host:9092...]`.
+    /// Requires building with the `nats` or `kafka` feature.
+    #[arg(long)]
+    pub events_url: Option<String>,
+
+    /// NATS subject or Kafka topic of `--events-url`.
+    #[arg(long, default_value = DEFAULT_EVENTS_TOPIC)]
+    pub events_topic: String,
+
+    /// Format of the validation results written to stdout.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    /// File the rows of `--output csv` are written to instead of stdout.
+    #[arg(long)]
+    pub out_file: Option<PathBuf>,
+
+    /// Record the time spent fetching, decoding and checking every block,
+    /// and print the percentiles of each phase once the blocks are
+    /// validated. JSON output carries the timing of every block.
+    #[arg(long)]
+    pub timing: bool,
+
+    /// Keep validating when blocks can't be fetched, reporting them as
+    /// failed, and end with the list of every failed block and why. The exit
+    /// code is still nonzero if any block failed.
+    #[arg(long)]
+    pub keep_going: bool,
+
+    /// Only fetch the headers of the blocks, without their transactions and
+    /// receipts, and validate the header chain: the block ids, the PoA
+    /// signatures and the `prev_root` of every block. An order of magnitude
+    /// cheaper than full validation, to pre-screen large ranges.
+    #[arg(long)]
+    pub headers_only: bool,
+
+    /// Don't show the progress bar of range scans or announce every block,
+    /// e.g. in CI. Failures and the summary are still printed.
+    #[arg(long, short, global = true)]
+    pub quiet: bool,
+
+    /// Format of the logs written to stderr. `json` logs carry the height of
+    /// the block, the transaction id and the check they are about as fields.
+    /// The level is set with `RUST_LOG`.
+    #[arg(long, value_enum, default_value_t = LogFormat::Text, global = true)]
+    pub log_format: LogFormat,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Verify a Merkle proof that a transaction is part of a block, without
+    /// contacting a node.
+    VerifyProof(VerifyProofArgs),
+    /// Fetch blocks and write them to disk, to be validated later with
+    /// `--input`. Exports the blocks selected with `--block-height` or
+    /// `--from-height`/`--to-height`.
+    Export(ExportArgs),
+    /// Serve an HTTP API validating blocks on request: `GET /validate/{height}`
+    /// validates a block of the node or `--input`, `POST /validate` the
+    /// exported block in the body. The optional checks given are applied.
+    Serve(ServeArgs),
+    /// Fetch every version of the consensus parameters of the node and write
+    /// them to a JSON file, to be pinned with `--consensus-parameters`.
+    ConsensusParameters(ConsensusParametersArgs),
+    /// Binary search the first block `--endpoint` and another endpoint
+    /// disagree on, in `--from-height`..=`--to-height` or up to the latest
+    /// block both serve.
+    Bisect(BisectArgs),
+    /// Validate a single transaction by id, with the block it is part of:
+    /// recompute its id and receipts root, and prove that it is included in
+    /// the transactions root of the block. The block is looked up on the
+    /// node unless `--block-height` is given, e.g. with `--input`.
+    ValidateTx(ValidateTxArgs),
+    /// Write a self-contained proof of a transaction and its receipts: the
+    /// header and signature of its block, the inclusion proof of the
+    /// transaction and the proof of every receipt against the receipts root
+    /// of the transaction, for a light client or a contract on another chain
+    /// to verify. The block is looked up on the node unless `--block-height`
+    /// is given.
+    ExportProof(ExportProofArgs),
+    /// Decode the raw payload of every transaction of a block and print its
+    /// fields (inputs, outputs, witnesses, script) and its receipts, to
+    /// investigate why the block failed validation.
+    Inspect(InspectArgs),
+    /// Validate again the blocks that failed in a report of `--output json`
+    /// or `--output ndjson`, or a `--checkpoint`, with only the checks and
+    /// the cross-checked endpoints they failed. Blocks the report doesn't
+    /// say the failures of are validated with every check enabled.
+    Revalidate(RevalidateArgs),
+    /// Compare every field of the blocks of a range, e.g. `100..200`, as
+    /// served by `--endpoint` with the blocks of `--rocksdb` or `--input`:
+    /// headers, consensus data, transaction payloads, statuses and receipts.
+    /// Checks that the API of a node serves what the node stored.
+    Diff(DiffArgs),
+    /// Apply targeted mutations to an exported block and write the corrupted
+    /// fixture, to show with `--input` that each check fires. For testing
+    /// validators; no node is contacted.
+    Corrupt(CorruptArgs),
+    /// Validate the blocks bundled with the binary, whose roots are known,
+    /// to check that this build computes them right. No node is contacted.
+    SelfTest(SelfTestArgs),
+    /// Compare a fuel-core state snapshot with the blocks up to its height:
+    /// its last block, its blocks root, recomputed from the ids of every
+    /// block from genesis, its chain id, consensus parameters and block
+    /// producer, and the state created after its height.
+    CheckSnapshot(CheckSnapshotArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CheckSnapshotArgs {
+    /// Directory of the snapshot, with its `metadata.json`.
+    #[arg(long)]
+    pub snapshot: PathBuf,
+
+    /// Height of the block the snapshot was taken at. Defaults to the last
+    /// block of the snapshot.
+    #[arg(long)]
+    pub height: Option<u32>,
+}
+
+#[derive(Args, Debug)]
+pub struct SelfTestArgs {
+    /// Also time the transactions and receipts roots of every bundled block.
+    #[arg(long)]
+    pub bench_merkle: bool,
+
+    /// Times the roots are computed with `--bench-merkle`.
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+    pub iterations: u32,
+}
+
+#[derive(Args, Debug)]
+pub struct RevalidateArgs {
+    /// The report of the previous run.
+    #[arg(long)]
+    pub from_report: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct InspectArgs {
+    /// Height of the block.
+    pub height: u32,
+}
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Heights to compare, `A..B` or `A..=B`, both inclusive.
+    #[arg(value_parser = parse_range, value_name = "HEIGHT_RANGE")]
+    pub range: (u32, u32),
+}
+
+#[derive(Args, Debug)]
+pub struct CorruptArgs {
+    /// File with the block, as written by `export`.
+    pub block: PathBuf,
+
+    /// Mutation to apply, may be repeated, applied in order:
+    /// `flip-payload-byte:TX:OFFSET` flips a byte of a transaction payload,
+    /// `drop-receipt:TX:RECEIPT` removes a receipt, and
+    /// `swap-transactions:A:B` and `swap-receipts:TX:A:B` reorder the leaves
+    /// of the transactions and receipts roots. Transactions and receipts are
+    /// given by their index, from 0.
+    #[arg(long = "mutation", value_name = "MUTATION", required = true)]
+    pub mutations: Vec<Corruption>,
+
+    /// File the corrupted block is written to.
+    #[arg(long)]
+    pub out: PathBuf,
+
+    /// Encoding of the file: `json`, or `cbor`.
+    #[arg(long, default_value = "json")]
+    pub format: DumpFormat,
+}
+
+#[derive(Args, Debug)]
+pub struct ValidateTxArgs {
+    /// Id of the transaction.
+    pub tx_id: Bytes32,
+}
+
+#[derive(Args, Debug)]
+pub struct ExportProofArgs {
+    /// Id of the transaction.
+    #[arg(long)]
+    pub tx: Bytes32,
+
+    /// Encoding of the bundle, written to stdout.
+    #[arg(long, default_value = "json")]
+    pub format: ProofFormat,
+}
+
+/// How `export-proof` encodes the bundle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ProofFormat {
+    /// The fields of the bundle, hashes and payloads as hex strings.
+    Json,
+    /// The binary encoding of `ProofBundle::to_bytes`, hex encoded, for
+    /// verifiers that only hash bytes.
+    Hex,
+}
+
+#[derive(Args, Debug)]
+pub struct BisectArgs {
+    /// GraphQL endpoint compared with `--endpoint`.
+    #[arg(long)]
+    pub against: String,
+}
+
+#[derive(Args, Debug)]
+pub struct ConsensusParametersArgs {
+    /// File the consensus parameters are written to.
+    #[arg(long)]
+    pub out: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    /// Address the API is served on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub addr: SocketAddr,
+}
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Directory the blocks are written to, one file per block. Created if
+    /// it doesn't exist.
+    #[arg(long)]
+    pub out: PathBuf,
+
+    /// Encoding of the files: `json`, or `cbor` for a compact binary encoding.
+    #[arg(long, default_value = "json")]
+    pub format: DumpFormat,
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyProofArgs {
+    /// JSON file with the proof, as serialized from a `MerkleProof`. `-` reads
+    /// it from stdin.
+    pub proof: PathBuf,
+
+    /// Trusted transactions root of the block, e.g. from a verified header.
+    #[arg(long)]
+    pub transactions_root: Bytes32,
+
+    /// Raw transaction, hex encoded, that must be the leaf of the proof.
+    /// Defaults to the transaction carried by the proof.
+    #[arg(long, conflicts_with = "tx_id")]
+    pub tx: Option<Bytes>,
+
+    /// Id of the transaction that must be the leaf of the proof. The id is
+    /// recomputed from the transaction carried by the proof.
+    #[arg(long, requires = "chain_id")]
+    pub tx_id: Option<Bytes32>,
+
+    /// Chain id the transaction id is recomputed for.
+    #[arg(long)]
+    pub chain_id: Option<u64>,
+}
+
+impl Cli {
+    /// Checks the settings that depend on each other. These can come from
+    /// the config file as well as the command line, so clap can't check them.
+    pub fn check(&self) -> Result<()> {
+        if self.to_height.is_some() && self.from_height.is_none() {
+            bail!("--to-height requires --from-height");
+        }
+        if self.resume && self.checkpoint.is_none() {
+            bail!("--resume requires --checkpoint");
+        }
+        if self.checkpoint.is_some() && self.follow {
+            bail!("--checkpoint can't be used with --follow");
+        }
+        if self.sample.is_some() && (self.follow || self.checkpoint.is_some()) {
+            bail!("--sample can't be used with --follow or --checkpoint");
+        }
+        if self.headers_only && (self.reads_local_blocks() || self.follow) {
+            bail!("--headers-only queries a node, it can't be used with --input, --rocksdb or --follow");
+        }
+        if self.headers_only && self.command.is_some() {
+            bail!("--headers-only is only available when validating blocks");
+        }
+        if self.reads_local_blocks()
+            && (self.checks_prev_root() || self.checks_event_inbox() || self.endpoints.len() > 1)
+        {
+            bail!(
+                "--input and --rocksdb can't be used with --check-prev-root, \
+                 --check-event-inbox or several --endpoint, which need a node"
+            );
+        }
+        if self.reads_local_blocks()
+            && self.enables(registry::FEES)
+            && self.consensus_parameters.is_none()
+        {
+            bail!("--check fees with --input or --rocksdb requires --consensus-parameters");
+        }
+        if self.l1_rpc_url.is_some() != self.l1_chain_state.is_some() {
+            bail!("--l1-rpc-url and --l1-chain-state must be given together");
+        }
+        if self.out_file.is_some() && self.output != OutputFormat::Csv {
+            bail!("--out-file requires --output csv");
+        }
+        if self.output == OutputFormat::Csv
+            && matches!(
+                self.command,
+                Some(
+                    Command::Bisect(_)
+                        | Command::ValidateTx(_)
+                        | Command::ExportProof(_)
+                        | Command::Inspect(_)
+                        | Command::Diff(_)
+                        | Command::CheckSnapshot(_)
+                )
+            )
+        {
+            bail!("--output csv is only available when validating blocks");
+        }
+        if (self.record.is_some() || self.replay.is_some())
+            && (self.reads_local_blocks() || self.follow)
+        {
+            bail!("--record and --replay can't be used with --input, --rocksdb or --follow");
+        }
+        if self.metrics_addr.is_some() && !self.follow {
+            bail!("--metrics-addr requires --follow");
+        }
+        if self.confirmations > 0 && !self.follow {
+            bail!("--confirmations requires --follow");
+        }
+        if self.tui && !self.follow {
+            bail!("--tui requires --follow");
+        }
+        if self.tui && !cfg!(feature = "tui") {
+            bail!("--tui requires building with `--features tui`");
+        }
+        if self.monitors_networks() && (self.tui || self.output == OutputFormat::Csv) {
+            bail!(
+                "--tui and --output csv can't be used with the `[[monitors]]` of the config file"
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether the check `name` is enabled with `--check` and not skipped.
+    pub fn enables(&self, name: &str) -> bool {
+        self.checks.iter().any(|check| check == name)
+            && !self.skip_checks.iter().any(|check| check == name)
+    }
+
+    pub fn checks_prev_root(&self) -> bool {
+        (self.check_prev_root || self.headers_only || self.enables(registry::PREV_ROOT))
+            && !self
+                .skip_checks
+                .iter()
+                .any(|check| check == registry::PREV_ROOT)
+    }
+
+    pub fn checks_event_inbox(&self) -> bool {
+        (self.check_event_inbox || self.l1_events.is_some() || self.enables(registry::EVENT_INBOX))
+            && !self
+                .skip_checks
+                .iter()
+                .any(|check| check == registry::EVENT_INBOX)
+    }
+
+    /// Whether following the chain follows the `[[monitors]]` of the config
+    /// file rather than `--network`.
+    pub fn monitors_networks(&self) -> bool {
+        self.follow && self.command.is_none() && !self.monitors.is_empty()
+    }
+
+    /// Whether blocks are read from `--input` or `--rocksdb` rather than
+    /// fetched from a node.
+    pub fn reads_local_blocks(&self) -> bool {
+        self.input.is_some() || self.rocksdb.is_some()
+    }
+
+    pub fn range(&self) -> Option<(u32, u32)> {
+        self.from_height.zip(self.to_height)
+    }
+
+    /// The `--endpoint`s, or those of the profile of `--network` when blocks
+    /// come from a node.
+    fn endpoints(&self) -> &[String] {
+        if self.queries_profile() {
+            &self.profile.endpoints
+        } else {
+            &self.endpoints
+        }
+    }
+
+    /// Whether blocks are fetched from the endpoints of the profile.
+    fn queries_profile(&self) -> bool {
+        self.endpoints.is_empty() && !self.reads_local_blocks()
+    }
+
+    pub fn endpoint(&self) -> &str {
+        self.endpoints()
+            .first()
+            .or(self.profile.endpoints.first())
+            .map_or("", String::as_str)
+    }
+
+    /// Endpoints the blocks served by [`Self::endpoint`] are compared with.
+    pub fn cross_check_endpoints(&self) -> &[String] {
+        self.endpoints().get(1..).unwrap_or_default()
+    }
+
+    /// Uses `profile` for `--network`. Its chain id and PoA authority fill in
+    /// `--chain-id` and `--poa-authority` when the network was `chosen` or
+    /// blocks are fetched from its endpoints, not when they are only its
+    /// default. The signing key overrides of the profile only come along
+    /// with its authority.
+    pub fn use_profile(&mut self, profile: NetworkProfile, chosen: bool) {
+        self.profile = profile;
+        if chosen || self.queries_profile() {
+            self.chain_id = self.chain_id.or(self.profile.chain_id);
+            if self.poa_authority.is_none() {
+                self.poa_authority = self.profile.poa_authority;
+                self.signing_key_overrides = self.profile.signing_key_overrides.clone();
+            }
+        }
+    }
+}
+
+/// Parses a positive number of requests per second.
+pub fn parse_rps(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(rps) if rps > 0.0 && rps.is_finite() => Ok(rps),
+        _ => Err(format!(
+            "invalid rate `{s}`, expected a positive number of requests per second"
+        )),
+    }
+}
+
+/// Parses a range of heights, `A..B` or `A..=B`, both inclusive.
+pub fn parse_range(s: &str) -> Result<(u32, u32), String> {
+    let invalid = || format!("invalid range `{s}`, expected e.g. `1000..2000`");
+    let (from, to) = s.split_once("..").ok_or_else(invalid)?;
+    let to = to.strip_prefix('=').unwrap_or(to);
+    let from: u32 = from.trim().parse().map_err(|_| invalid())?;
+    let to: u32 = to.trim().parse().map_err(|_| invalid())?;
+    if from > to {
+        return Err(format!("range `{s}` starts after it ends"));
+    }
+    Ok((from, to))
+}
+
+/// Parses a size in bytes, with an optional unit: `B`, `KB`, `MB`, `GB` in
+/// powers of 1000 or `KiB`, `MiB`, `GiB` in powers of 1024.
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid size `{s}`, expected e.g. `500MiB`"))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1000,
+        "mb" => 1000 * 1000,
+        "gb" => 1000 * 1000 * 1000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        unit => {
+            return Err(format!(
+                "unknown unit `{unit}`, expected one of B, KB, MB, GB, KiB, MiB, GiB"
+            ))
+        }
+    };
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("size `{s}` is too large"))
+}
@@ -0,0 +1,447 @@
+// src/config.rs
+use std::{collections::BTreeMap, fs, net::SocketAddr, path::Path, path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{parser::ValueSource, ArgMatches, ValueEnum};
+use serde::Deserialize;
+
+use fuel_root_validation::{hash, NetworkProfile};
+
+use crate::{
+    cli::{parse_size, Cli},
+    logging::LogFormat,
+    output::OutputFormat,
+};
+
+/// Settings read from `--config`. Every value is optional and only used when
+/// the matching flag isn't given on the command line.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub network: Option<String>,
+    pub endpoints: Option<Vec<String>>,
+    pub chain_id: Option<u64>,
+    pub consensus_parameters: Option<PathBuf>,
+    pub jobs: Option<u32>,
+    pub page_size: Option<u32>,
+    pub max_memory: Option<String>,
+    pub follow: Option<bool>,
+    pub tui: Option<bool>,
+    pub confirmations: Option<u32>,
+    pub from_height: Option<u32>,
+    pub to_height: Option<u32>,
+    pub sample: Option<u64>,
+    pub seed: Option<u64>,
+    pub poll_interval: Option<String>,
+    pub timeout: Option<String>,
+    pub no_receipt_fallback: Option<bool>,
+    pub hash_backend: Option<String>,
+    pub cross_check_hash: Option<bool>,
+    pub run_deadline: Option<String>,
+    pub no_subscribe: Option<bool>,
+    pub output: Option<String>,
+    pub out_file: Option<PathBuf>,
+    pub timing: Option<bool>,
+    pub keep_going: Option<bool>,
+    pub headers_only: Option<bool>,
+    pub fail_on: Option<String>,
+    pub quiet: Option<bool>,
+    pub log_format: Option<String>,
+    #[serde(default)]
+    pub checks: ChecksConfig,
+    #[serde(default)]
+    pub retry: RetryConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub alerting: AlertingConfig,
+    #[serde(default)]
+    pub events: EventsConfig,
+    #[serde(default)]
+    pub persistence: PersistenceConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// Custom network profiles by name, or changes to the built-in ones.
+    #[serde(default)]
+    pub networks: BTreeMap<String, NetworkConfig>,
+    /// Networks followed at once by the same process.
+    #[serde(default)]
+    pub monitors: Vec<MonitorConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChecksConfig {
+    pub prev_root: Option<bool>,
+    /// Names of the checks `--check` enables.
+    pub enable: Option<Vec<String>>,
+    /// Names of the checks `--skip-check` disables.
+    pub skip: Option<Vec<String>>,
+    /// Names of the checks `--warn-check` only warns about.
+    pub warn: Option<Vec<String>>,
+    pub event_inbox: Option<bool>,
+    pub l1_events: Option<PathBuf>,
+    pub known_anomalies: Option<PathBuf>,
+    pub poa_authority: Option<String>,
+    pub l1_rpc_url: Option<String>,
+    pub l1_chain_state: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkConfig {
+    pub endpoints: Option<Vec<String>>,
+    pub chain_id: Option<u64>,
+    pub poa_authority: Option<String>,
+    /// Addresses signing the blocks from the heights they are keyed by.
+    #[serde(default)]
+    pub signing_key_overrides: BTreeMap<String, String>,
+    #[serde(default)]
+    pub upgrades: Vec<UpgradeConfig>,
+}
+
+/// A network followed along with the other `[[monitors]]`, with its own
+/// validator, checkpoint and metrics. The settings it doesn't give are
+/// shared by every network.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MonitorConfig {
+    pub network: String,
+    pub endpoints: Option<Vec<String>>,
+    pub chain_id: Option<u64>,
+    pub consensus_parameters: Option<PathBuf>,
+    pub from_height: Option<u32>,
+    /// File the last block validated is saved to, to resume from it.
+    pub checkpoint: Option<PathBuf>,
+    /// The profile of `network`, resolved once the config file is read.
+    #[serde(skip)]
+    pub profile: NetworkProfile,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpgradeConfig {
+    pub height: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetryConfig {
+    pub max_attempts: Option<u32>,
+    pub backoff: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    pub max_rps: Option<f64>,
+    pub max_in_flight: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertingConfig {
+    pub webhook_url: Option<String>,
+    pub webhook_format: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventsConfig {
+    pub url: Option<String>,
+    pub topic: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PersistenceConfig {
+    pub db: Option<PathBuf>,
+    pub checkpoint: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    pub addr: Option<SocketAddr>,
+    pub max_lag: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CacheConfig {
+    pub enabled: Option<bool>,
+    pub dir: Option<PathBuf>,
+    pub max_size: Option<String>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let toml = fs::read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        toml::from_str(&toml).with_context(|| format!("failed to parse config {}", path.display()))
+    }
+
+    /// The `[networks]` of the file. Those named like a built-in network
+    /// change its profile rather than replacing it.
+    pub fn profiles(&self) -> Result<BTreeMap<String, NetworkProfile>> {
+        self.networks
+            .iter()
+            .map(|(name, network)| {
+                let key = |field: &str| format!("networks.{name}.{field}");
+                let mut profile =
+                    NetworkProfile::builtin(name).unwrap_or_else(|| NetworkProfile::new(name));
+                if let Some(endpoints) = network.endpoints.clone() {
+                    profile = profile.with_endpoints(endpoints);
+                }
+                if profile.endpoints.is_empty() {
+                    bail!(
+                        "`{}` in config needs at least one endpoint",
+                        key("endpoints")
+                    );
+                }
+                if let Some(chain_id) = network.chain_id {
+                    profile = profile.with_chain_id(chain_id);
+                }
+                if let Some(address) = &network.poa_authority {
+                    profile = profile.with_poa_authority(parse(&key("poa_authority"), address)?);
+                }
+                for (height, address) in &network.signing_key_overrides {
+                    let key = key("signing_key_overrides");
+                    profile = profile
+                        .with_signing_key_override(parse(&key, height)?, parse(&key, address)?);
+                }
+                for upgrade in &network.upgrades {
+                    profile = profile.with_upgrade(upgrade.height, &upgrade.name);
+                }
+                Ok((name.clone(), profile))
+            })
+            .collect()
+    }
+
+    /// Fills the settings of `cli` that weren't given on the command line.
+    pub fn apply(self, cli: &mut Cli, matches: &ArgMatches) -> Result<()> {
+        let unset = |id: &str| matches.value_source(id) != Some(ValueSource::CommandLine);
+
+        if let Some(network) = self.network.filter(|_| unset("network")) {
+            cli.network = network;
+        }
+        if let Some(endpoints) = self.endpoints.filter(|_| unset("endpoints")) {
+            cli.endpoints = endpoints;
+        }
+        // A network chosen on the command line is followed on its own.
+        if unset("network") && unset("endpoints") {
+            cli.monitors = self.monitors;
+        }
+        if let Some(chain_id) = self.chain_id.filter(|_| unset("chain_id")) {
+            cli.chain_id = Some(chain_id);
+        }
+        if let Some(path) = self
+            .consensus_parameters
+            .filter(|_| unset("consensus_parameters"))
+        {
+            cli.consensus_parameters = Some(path);
+        }
+        if let Some(jobs) = self.jobs.filter(|_| unset("jobs")) {
+            cli.jobs = jobs.max(1);
+        }
+        if let Some(size) = self.page_size.filter(|_| unset("page_size")) {
+            cli.page_size = size.max(1);
+        }
+        if let Some(size) = self.max_memory.filter(|_| unset("max_memory")) {
+            cli.max_memory = Some(
+                parse_size(&size).map_err(|e| anyhow!("invalid `max_memory` in config: {e}"))?,
+            );
+        }
+        if let Some(follow) = self.follow.filter(|_| unset("follow")) {
+            cli.follow = follow;
+        }
+        if let Some(confirmations) = self.confirmations.filter(|_| unset("confirmations")) {
+            cli.confirmations = confirmations;
+        }
+        if let Some(tui) = self.tui.filter(|_| unset("tui")) {
+            cli.tui = tui;
+        }
+        if let Some(height) = self.from_height.filter(|_| unset("from_height")) {
+            cli.from_height = Some(height);
+        }
+        if let Some(height) = self.to_height.filter(|_| unset("to_height")) {
+            cli.to_height = Some(height);
+        }
+        if let Some(count) = self.sample.filter(|_| unset("sample")) {
+            cli.sample = Some(count);
+        }
+        if let Some(seed) = self.seed.filter(|_| unset("seed")) {
+            cli.seed = Some(seed);
+        }
+        if let Some(interval) = self.poll_interval.filter(|_| unset("poll_interval")) {
+            cli.poll_interval = parse_duration("poll_interval", &interval)?;
+        }
+        if let Some(timeout) = self.timeout.filter(|_| unset("timeout")) {
+            cli.timeout = Some(parse_duration("timeout", &timeout)?);
+        }
+        if let Some(no_fallback) = self
+            .no_receipt_fallback
+            .filter(|_| unset("no_receipt_fallback"))
+        {
+            cli.no_receipt_fallback = no_fallback;
+        }
+        if let Some(backend) = self.hash_backend.filter(|_| unset("hash_backend")) {
+            if hash::named(&backend).is_none() {
+                bail!(
+                    "invalid `hash_backend` in config: `{backend}`, this build has {}",
+                    hash::BACKENDS.join(", ")
+                );
+            }
+            cli.hash_backend = backend;
+        }
+        if let Some(cross_check) = self.cross_check_hash.filter(|_| unset("cross_check_hash")) {
+            cli.cross_check_hash = cross_check;
+        }
+        if let Some(deadline) = self.run_deadline.filter(|_| unset("run_deadline")) {
+            cli.run_deadline = Some(parse_duration("run_deadline", &deadline)?);
+        }
+        if let Some(no_subscribe) = self.no_subscribe.filter(|_| unset("no_subscribe")) {
+            cli.no_subscribe = no_subscribe;
+        }
+        if let Some(output) = self.output.filter(|_| unset("output")) {
+            cli.output = OutputFormat::from_str(&output, true)
+                .map_err(|e| anyhow!("invalid `output` in config: {e}"))?;
+        }
+        if let Some(path) = self.out_file.filter(|_| unset("out_file")) {
+            cli.out_file = Some(path);
+        }
+        if let Some(timing) = self.timing.filter(|_| unset("timing")) {
+            cli.timing = timing;
+        }
+        if let Some(keep_going) = self.keep_going.filter(|_| unset("keep_going")) {
+            cli.keep_going = keep_going;
+        }
+        if let Some(headers_only) = self.headers_only.filter(|_| unset("headers_only")) {
+            cli.headers_only = headers_only;
+        }
+        if let Some(severity) = self.fail_on.filter(|_| unset("fail_on")) {
+            cli.fail_on = parse("fail_on", &severity)?;
+        }
+        if let Some(quiet) = self.quiet.filter(|_| unset("quiet")) {
+            cli.quiet = quiet;
+        }
+        if let Some(format) = self.log_format.filter(|_| unset("log_format")) {
+            cli.log_format = LogFormat::from_str(&format, true)
+                .map_err(|e| anyhow!("invalid `log_format` in config: {e}"))?;
+        }
+
+        let checks = self.checks;
+        if let Some(enabled) = checks.prev_root.filter(|_| unset("check_prev_root")) {
+            cli.check_prev_root = enabled;
+        }
+        if let Some(names) = checks.enable.filter(|_| unset("checks")) {
+            cli.checks = names;
+        }
+        if let Some(names) = checks.skip.filter(|_| unset("skip_checks")) {
+            cli.skip_checks = names;
+        }
+        if let Some(names) = checks.warn.filter(|_| unset("warn_checks")) {
+            cli.warn_checks = names;
+        }
+        if let Some(enabled) = checks.event_inbox.filter(|_| unset("check_event_inbox")) {
+            cli.check_event_inbox = enabled;
+        }
+        if let Some(path) = checks.l1_events.filter(|_| unset("l1_events")) {
+            cli.l1_events = Some(path);
+        }
+        if let Some(path) = checks.known_anomalies.filter(|_| unset("known_anomalies")) {
+            cli.known_anomalies = Some(path);
+        }
+        if let Some(address) = checks.poa_authority.filter(|_| unset("poa_authority")) {
+            cli.poa_authority = Some(parse("checks.poa_authority", &address)?);
+        }
+        if let Some(url) = checks.l1_rpc_url.filter(|_| unset("l1_rpc_url")) {
+            cli.l1_rpc_url = Some(url);
+        }
+        if let Some(address) = checks.l1_chain_state.filter(|_| unset("l1_chain_state")) {
+            cli.l1_chain_state = Some(parse("checks.l1_chain_state", &address)?);
+        }
+
+        let retry = self.retry;
+        if let Some(attempts) = retry.max_attempts.filter(|_| unset("max_attempts")) {
+            cli.max_attempts = attempts.max(1);
+        }
+        if let Some(backoff) = retry.backoff.filter(|_| unset("retry_backoff")) {
+            cli.retry_backoff = parse_duration("retry.backoff", &backoff)?;
+        }
+
+        let rate_limit = self.rate_limit;
+        if let Some(rps) = rate_limit.max_rps.filter(|_| unset("max_rps")) {
+            if !(rps > 0.0 && rps.is_finite()) {
+                bail!("invalid `rate_limit.max_rps` in config: expected a positive number");
+            }
+            cli.max_rps = Some(rps);
+        }
+        if let Some(max) = rate_limit.max_in_flight.filter(|_| unset("max_in_flight")) {
+            cli.max_in_flight = Some(max.max(1));
+        }
+
+        let alerting = self.alerting;
+        if let Some(url) = alerting.webhook_url.filter(|_| unset("webhook_url")) {
+            cli.webhook_url = Some(url);
+        }
+        if let Some(format) = alerting.webhook_format.filter(|_| unset("webhook_format")) {
+            cli.webhook_format = parse("alerting.webhook_format", &format)?;
+        }
+
+        let events = self.events;
+        if let Some(url) = events.url.filter(|_| unset("events_url")) {
+            cli.events_url = Some(url);
+        }
+        if let Some(topic) = events.topic.filter(|_| unset("events_topic")) {
+            cli.events_topic = topic;
+        }
+
+        let persistence = self.persistence;
+        if let Some(path) = persistence.db.filter(|_| unset("db")) {
+            cli.db = Some(path);
+        }
+        if let Some(path) = persistence.checkpoint.filter(|_| unset("checkpoint")) {
+            cli.checkpoint = Some(path);
+        }
+
+        let metrics = self.metrics;
+        if let Some(addr) = metrics.addr.filter(|_| unset("metrics_addr")) {
+            cli.metrics_addr = Some(addr);
+        }
+        if let Some(blocks) = metrics.max_lag.filter(|_| unset("max_lag")) {
+            cli.max_lag = blocks;
+        }
+
+        let cache = self.cache;
+        if let Some(enabled) = cache.enabled.filter(|_| unset("no_cache")) {
+            cli.no_cache = !enabled;
+        }
+        if let Some(dir) = cache.dir.filter(|_| unset("cache_dir")) {
+            cli.cache_dir = Some(dir);
+        }
+        if let Some(size) = cache.max_size.filter(|_| unset("cache_max_size")) {
+            cli.cache_max_size = parse_size(&size)
+                .map_err(|e| anyhow!("invalid `cache.max_size` in config: {e}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse<T>(key: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e| anyhow!("invalid `{key}` in config: {e}"))
+}
+
+fn parse_duration(key: &str, value: &str) -> Result<std::time::Duration> {
+    humantime::parse_duration(value).with_context(|| format!("invalid `{key}` in config"))
+}
@@ -0,0 +1,91 @@
+// src/consensus.rs
+use std::collections::BTreeMap;
+
+use fuel_core_client::client::schema::block::Consensus;
+use fuel_crypto::Message;
+use fuel_tx::Input;
+use fuel_types::{Address, Bytes32};
+
+use crate::query::{FullBlock, LightBlock};
+use crate::report::{Check, CheckResult};
+
+/// Addresses allowed to sign PoA blocks, mirroring the `PoAV2` consensus
+/// config of fuel-core: a genesis signing key that can be overridden starting
+/// at given heights.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoaAuthority {
+    pub genesis_signing_key: Address,
+    pub signing_key_overrides: BTreeMap<u32, Address>,
+}
+
+impl PoaAuthority {
+    pub fn new(genesis_signing_key: Address) -> Self {
+        Self {
+            genesis_signing_key,
+            signing_key_overrides: BTreeMap::new(),
+        }
+    }
+
+    /// Address expected to have signed the block at `height`.
+    pub fn signing_key_at(&self, height: u32) -> Address {
+        self.signing_key_overrides
+            .range(..=height)
+            .next_back()
+            .map(|(_, key)| *key)
+            .unwrap_or(self.genesis_signing_key)
+    }
+}
+
+/// Recovers the producer of a PoA block from its signature over the block id
+/// and checks it is the authority for that height. Genesis blocks carry no
+/// signature and are skipped.
+pub fn poa_signature(block: &FullBlock, authority: &PoaAuthority) -> Option<CheckResult> {
+    signature(
+        &block.consensus,
+        block.height(),
+        block.id.clone().into(),
+        authority,
+    )
+}
+
+/// [`poa_signature`] of a block fetched without its transactions.
+pub fn light_poa_signature(block: &LightBlock, authority: &PoaAuthority) -> Option<CheckResult> {
+    signature(
+        &block.consensus,
+        block.height(),
+        block.id.clone().into(),
+        authority,
+    )
+}
+
+/// Checks that `consensus` signs `block_id` with the key of `authority` for
+/// `height`.
+pub(crate) fn signature(
+    consensus: &Consensus,
+    height: u32,
+    block_id: Bytes32,
+    authority: &PoaAuthority,
+) -> Option<CheckResult> {
+    let poa = match consensus {
+        Consensus::Genesis(_) => return None,
+        Consensus::PoAConsensus(poa) => poa,
+        Consensus::Unknown => {
+            return Some(CheckResult::error(
+                Check::PoaSignature,
+                "unknown consensus type",
+            ))
+        }
+    };
+
+    let expected = authority.signing_key_at(height);
+    let message = Message::from_bytes(*block_id);
+
+    Some(
+        match poa.signature.clone().into_signature().recover(&message) {
+            Ok(producer) => {
+                CheckResult::compare(Check::PoaSignature, expected, Input::owner(&producer))
+            }
+            Err(e) => CheckResult::error(Check::PoaSignature, format!("invalid signature: {e:?}")),
+        },
+    )
+}
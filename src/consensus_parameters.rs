@@ -0,0 +1,88 @@
+// src/consensus_parameters.rs
+//! The consensus parameters of a chain, by version, as fetched from a node at
+//! startup or pinned from a file for offline runs.
+//!
+//! Every block header names the version of the consensus parameters it was
+//! produced with. The checks that depend on them, starting with the chain id
+//! transaction ids are computed for, use the version of the block.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::BufWriter,
+    path::Path,
+};
+
+use fuel_tx::ConsensusParameters;
+use fuel_types::ChainId;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, ValidationError};
+
+/// Consensus parameters of a chain, by version.
+///
+/// Serialized as a JSON object from the version to the parameters, which is
+/// the format of the files `--consensus-parameters` pins.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ChainParameters {
+    versions: BTreeMap<u32, ConsensusParameters>,
+}
+
+impl ChainParameters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the parameters of `version`.
+    pub fn insert(&mut self, version: u32, params: ConsensusParameters) {
+        self.versions.insert(version, params);
+    }
+
+    /// The parameters of `version`, if they are known.
+    pub fn get(&self, version: u32) -> Option<&ConsensusParameters> {
+        self.versions.get(&version)
+    }
+
+    /// The known version with the highest number, and its parameters.
+    pub fn latest(&self) -> Option<(u32, &ConsensusParameters)> {
+        self.versions
+            .last_key_value()
+            .map(|(version, params)| (*version, params))
+    }
+
+    /// Chain id of the latest known version.
+    pub fn chain_id(&self) -> Option<ChainId> {
+        self.latest().map(|(_, params)| params.chain_id())
+    }
+
+    pub fn versions(&self) -> impl Iterator<Item = u32> + '_ {
+        self.versions.keys().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.versions.is_empty()
+    }
+
+    /// Reads parameters written by [`Self::save`].
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read(path).map_err(|source| ValidationError::ReadConsensusParameters {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        serde_json::from_slice(&json).map_err(|source| ValidationError::ParseConsensusParameters {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Writes the parameters to `path` as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let write_error = |source| ValidationError::WriteConsensusParameters {
+            path: path.to_path_buf(),
+            source,
+        };
+        let file = File::create(path).map_err(write_error)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self).map_err(|e| write_error(e.into()))
+    }
+}
@@ -0,0 +1,150 @@
+// src/corrupt.rs
+//! Targeted mutations of exported blocks, to build fixtures showing that
+//! each check fires on the data it is meant to catch.
+//!
+//! Mutations are deterministic: the same block and mutations always give the
+//! same fixture, so corrupted fixtures can be regenerated rather than
+//! committed.
+
+use std::{fmt, str::FromStr};
+
+use crate::dump::{BlockDump, ReceiptDump, StatusDump};
+use crate::error::{Result, ValidationError};
+
+/// A mutation of a block. Transactions and receipts are given by their index
+/// in the block and in the receipts of their transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Corruption {
+    /// Flips every bit of the byte at `offset` of the raw payload of a
+    /// transaction, which changes its id.
+    FlipPayloadByte { tx: usize, offset: usize },
+    /// Removes a receipt from the status of a transaction, which changes
+    /// its receipts root.
+    DropReceipt { tx: usize, receipt: usize },
+    /// Swaps two transactions, reordering the leaves of the transactions
+    /// root.
+    SwapTransactions { a: usize, b: usize },
+    /// Swaps two receipts of a transaction, reordering the leaves of its
+    /// receipts root.
+    SwapReceipts { tx: usize, a: usize, b: usize },
+}
+
+impl Corruption {
+    /// Applies the mutation to `block`, failing if it has no such
+    /// transaction, byte or receipt.
+    pub fn apply(&self, block: &mut BlockDump) -> Result<()> {
+        let count = block.transactions.len();
+        let out_of_range = |what: &str, index: usize, len: usize| {
+            self.invalid(format!("{what} {index} is out of range, there are {len}"))
+        };
+        let check_tx = |tx: usize| {
+            if tx < count {
+                Ok(())
+            } else {
+                Err(out_of_range("transaction", tx, count))
+            }
+        };
+        match *self {
+            Corruption::FlipPayloadByte { tx, offset } => {
+                check_tx(tx)?;
+                let payload = &mut block.transactions[tx].raw_payload.0;
+                let len = payload.len();
+                let byte = payload
+                    .get_mut(offset)
+                    .ok_or_else(|| out_of_range("byte", offset, len))?;
+                *byte = !*byte;
+            }
+            Corruption::DropReceipt { tx, receipt } => {
+                check_tx(tx)?;
+                let receipts = self.receipts(block, tx)?;
+                if receipt >= receipts.len() {
+                    return Err(out_of_range("receipt", receipt, receipts.len()));
+                }
+                receipts.remove(receipt);
+            }
+            Corruption::SwapTransactions { a, b } => {
+                check_tx(a)?;
+                check_tx(b)?;
+                block.transactions.swap(a, b);
+            }
+            Corruption::SwapReceipts { tx, a, b } => {
+                check_tx(tx)?;
+                let receipts = self.receipts(block, tx)?;
+                let len = receipts.len();
+                for index in [a, b] {
+                    if index >= len {
+                        return Err(out_of_range("receipt", index, len));
+                    }
+                }
+                receipts.swap(a, b);
+            }
+        }
+        Ok(())
+    }
+
+    fn receipts<'a>(
+        &self,
+        block: &'a mut BlockDump,
+        tx: usize,
+    ) -> Result<&'a mut Vec<ReceiptDump>> {
+        match &mut block.transactions[tx].status {
+            Some(StatusDump::Success { receipts, .. } | StatusDump::Failure { receipts, .. }) => {
+                Ok(receipts)
+            }
+            _ => Err(self.invalid(format!(
+                "transaction {tx} wasn't executed, it has no receipts"
+            ))),
+        }
+    }
+
+    fn invalid(&self, reason: String) -> ValidationError {
+        ValidationError::InvalidCorruption {
+            corruption: self.to_string(),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for Corruption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Corruption::FlipPayloadByte { tx, offset } => {
+                write!(f, "flip-payload-byte:{tx}:{offset}")
+            }
+            Corruption::DropReceipt { tx, receipt } => write!(f, "drop-receipt:{tx}:{receipt}"),
+            Corruption::SwapTransactions { a, b } => write!(f, "swap-transactions:{a}:{b}"),
+            Corruption::SwapReceipts { tx, a, b } => write!(f, "swap-receipts:{tx}:{a}:{b}"),
+        }
+    }
+}
+
+impl FromStr for Corruption {
+    type Err = ValidationError;
+
+    /// Parses `flip-payload-byte:TX:OFFSET`, `drop-receipt:TX:RECEIPT`,
+    /// `swap-transactions:A:B` or `swap-receipts:TX:A:B`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| ValidationError::InvalidCorruption {
+            corruption: s.to_string(),
+            reason: reason.to_string(),
+        };
+        let (name, indices) = s.split_once(':').unwrap_or((s, ""));
+        let indices: Option<Vec<usize>> =
+            indices.split(':').map(|index| index.parse().ok()).collect();
+        match (name, indices.as_deref()) {
+            ("flip-payload-byte", Some(&[tx, offset])) => {
+                Ok(Corruption::FlipPayloadByte { tx, offset })
+            }
+            ("drop-receipt", Some(&[tx, receipt])) => Ok(Corruption::DropReceipt { tx, receipt }),
+            ("swap-transactions", Some(&[a, b])) => Ok(Corruption::SwapTransactions { a, b }),
+            ("swap-receipts", Some(&[tx, a, b])) => Ok(Corruption::SwapReceipts { tx, a, b }),
+            ("flip-payload-byte" | "drop-receipt" | "swap-transactions" | "swap-receipts", _) => {
+                Err(invalid("expected its indices separated by `:`"))
+            }
+            _ => Err(invalid(
+                "expected one of flip-payload-byte:TX:OFFSET, drop-receipt:TX:RECEIPT, \
+                 swap-transactions:A:B, swap-receipts:TX:A:B",
+            )),
+        }
+    }
+}
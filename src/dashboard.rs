@@ -0,0 +1,253 @@
+// src/dashboard.rs
+//! What the terminal dashboard of follow mode shows: the head of the chain
+//! and how far validation lags behind it, the pass rate of every check, the
+//! latest failures and the health of the endpoints. Drawing it needs the
+//! `tui` feature.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Duration,
+};
+
+use crate::follow::Reorg;
+use crate::validator::{BlockOutcome, HeadProbe};
+
+/// Number of failed blocks the dashboard lists.
+pub const RECENT_FAILURES: usize = 10;
+
+/// The state of the dashboard, updated as blocks are validated and
+/// endpoints are probed.
+#[derive(Clone, Debug, Default)]
+pub struct Dashboard {
+    /// What blocks are validated from, e.g. the URL of the node.
+    pub source: String,
+    /// Height of the latest block of the source.
+    pub head: Option<u32>,
+    /// Height of the latest block validated.
+    pub validated: Option<u32>,
+    pub passed: u64,
+    pub failed: u64,
+    pub reorgs: u64,
+    /// The latest failed blocks, most recent first.
+    pub recent_failures: VecDeque<RecentFailure>,
+    /// Results of every check, by name.
+    pub checks: BTreeMap<&'static str, CheckStats>,
+    /// The source first, then the cross-check endpoints.
+    pub endpoints: Vec<EndpointHealth>,
+}
+
+/// A block that failed validation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecentFailure {
+    pub height: u32,
+    /// Its first failure, or why it couldn't be validated.
+    pub reason: String,
+}
+
+/// How often a check passed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CheckStats {
+    pub passed: u64,
+    pub failed: u64,
+}
+
+impl CheckStats {
+    /// Share of the results that passed, between 0 and 1.
+    pub fn pass_rate(&self) -> f64 {
+        match self.passed + self.failed {
+            0 => 1.0,
+            total => self.passed as f64 / total as f64,
+        }
+    }
+}
+
+/// The last probe of an endpoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EndpointHealth {
+    pub endpoint: String,
+    pub head: Option<u32>,
+    pub latency: Duration,
+    /// Why the probe failed, if it did.
+    pub error: Option<String>,
+}
+
+impl Dashboard {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Blocks between the head and the latest block validated.
+    pub fn lag(&self) -> Option<u32> {
+        Some(self.head?.saturating_sub(self.validated?))
+    }
+
+    pub fn observe(&mut self, outcome: &BlockOutcome) {
+        self.validated = self.validated.max(Some(outcome.height));
+        self.head = self.head.max(Some(outcome.height));
+        if let Ok(report) = &outcome.result {
+            for result in &report.checks {
+                let stats = self.checks.entry(result.check.name()).or_default();
+                if result.passed {
+                    stats.passed += 1;
+                } else {
+                    stats.failed += 1;
+                }
+            }
+        }
+        if outcome.passed() {
+            self.passed += 1;
+            return;
+        }
+        self.failed += 1;
+        let reason = match &outcome.result {
+            Ok(report) => report
+                .failures()
+                .next()
+                .map_or_else(|| "failed".to_string(), ToString::to_string),
+            Err(e) => format!("{e:#}"),
+        };
+        self.recent_failures.push_front(RecentFailure {
+            height: outcome.height,
+            reason,
+        });
+        self.recent_failures.truncate(RECENT_FAILURES);
+    }
+
+    pub fn observe_reorg(&mut self, _reorg: &Reorg) {
+        self.reorgs += 1;
+    }
+
+    /// Records the `probes` of [`BlockValidator::probe_heads`].
+    ///
+    /// [`BlockValidator::probe_heads`]: crate::BlockValidator::probe_heads
+    pub fn observe_probes(&mut self, probes: &[HeadProbe]) {
+        self.endpoints = probes
+            .iter()
+            .map(|probe| EndpointHealth {
+                endpoint: probe
+                    .endpoint
+                    .as_deref()
+                    .unwrap_or(&self.source)
+                    .to_string(),
+                head: probe.height.as_ref().ok().copied(),
+                latency: probe.latency,
+                error: probe.height.as_ref().err().map(|e| e.to_string()),
+            })
+            .collect();
+        if let Some(head) = self.endpoints.first().and_then(|source| source.head) {
+            self.head = self.head.max(Some(head));
+        }
+    }
+
+    /// Draws the dashboard over the whole `frame`.
+    #[cfg(feature = "tui")]
+    pub fn render(&self, frame: &mut ratatui::Frame) {
+        use ratatui::{
+            layout::{Constraint, Layout},
+            style::{Color, Style, Stylize},
+            text::Line,
+            widgets::{Block, List, Paragraph, Row, Table},
+        };
+
+        let height =
+            |height: Option<u32>| height.map_or_else(|| "-".to_string(), |h| format!("#{h}"));
+        let [summary, tables, failures, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Length(RECENT_FAILURES as u16 + 2),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [checks, endpoints] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(tables);
+
+        let lag = self
+            .lag()
+            .map_or_else(|| "-".to_string(), |lag| format!("{lag} blocks"));
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                format!("Head {}", height(self.head)).bold(),
+                format!("   Validated {}", height(self.validated)).into(),
+                format!("   Lag {lag}").into(),
+                format!("   Passed {}", self.passed).green(),
+                format!("   Failed {}", self.failed).red(),
+                format!("   Reorgs {}", self.reorgs).into(),
+            ]))
+            .block(Block::bordered().title("fuel-block-validator")),
+            summary,
+        );
+
+        let rows = self.checks.iter().map(|(name, stats)| {
+            let style = if stats.failed == 0 {
+                Style::default()
+            } else {
+                Style::default().fg(Color::Red)
+            };
+            Row::new([
+                name.to_string(),
+                stats.passed.to_string(),
+                stats.failed.to_string(),
+                format!("{:.1}%", stats.pass_rate() * 100.0),
+            ])
+            .style(style)
+        });
+        frame.render_widget(
+            Table::new(
+                rows,
+                [
+                    Constraint::Fill(1),
+                    Constraint::Length(8),
+                    Constraint::Length(8),
+                    Constraint::Length(8),
+                ],
+            )
+            .header(Row::new(["check", "passed", "failed", "rate"]).bold())
+            .block(Block::bordered().title("Checks")),
+            checks,
+        );
+
+        let rows = self.endpoints.iter().map(|endpoint| {
+            let (status, style) = match &endpoint.error {
+                Some(error) => (error.clone(), Style::default().fg(Color::Red)),
+                None => ("ok".to_string(), Style::default().fg(Color::Green)),
+            };
+            Row::new([
+                endpoint.endpoint.clone(),
+                height(endpoint.head),
+                format!("{}ms", endpoint.latency.as_millis()),
+                status,
+            ])
+            .style(style)
+        });
+        frame.render_widget(
+            Table::new(
+                rows,
+                [
+                    Constraint::Fill(2),
+                    Constraint::Length(10),
+                    Constraint::Length(8),
+                    Constraint::Fill(1),
+                ],
+            )
+            .header(Row::new(["endpoint", "head", "latency", "status"]).bold())
+            .block(Block::bordered().title("Endpoints")),
+            endpoints,
+        );
+
+        let items = self
+            .recent_failures
+            .iter()
+            .map(|failure| format!("#{}: {}", failure.height, failure.reason));
+        frame.render_widget(
+            List::new(items)
+                .style(Style::default().fg(Color::Red))
+                .block(Block::bordered().title("Recent failures")),
+            failures,
+        );
+        frame.render_widget(Line::from("q: quit").dim(), help);
+    }
+}
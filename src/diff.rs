@@ -0,0 +1,178 @@
+// src/diff.rs
+//! Field by field comparison of the blocks of two sources, e.g. the GraphQL
+//! API of a node and its own database, to check the API serves what the
+//! node stored.
+//!
+//! Blocks are compared as the [`BlockDump`] they are exported as, so every
+//! field of the header, consensus data, transactions, statuses and receipts
+//! is compared, and differences are named by their path in the dump, e.g.
+//! `transactions[2].status.receipts[1].gas_used`.
+
+use std::collections::BTreeMap;
+
+use fuel_crypto::Hasher;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::dump::BlockDump;
+use crate::error::Result;
+use crate::query::FullBlock;
+use crate::source::BlockSource;
+
+/// Number of blocks fetched from each source at once.
+pub const DIFF_PAGE_SIZE: u32 = 100;
+
+/// Longest value reported as is; longer ones, like raw payloads, are
+/// reported by their length and hash.
+const MAX_VALUE_LEN: usize = 66;
+
+/// How the block at `height` of one source differs from the other's.
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockDiff {
+    pub height: u32,
+    /// Empty when both sources serve the same block.
+    pub differences: Vec<FieldDifference>,
+}
+
+impl BlockDiff {
+    pub fn is_identical(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// A field whose value differs. A side is `None` when the field, or the
+/// whole block at path `block`, is missing from it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FieldDifference {
+    pub path: String,
+    /// The value of the source compared against.
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+/// The fields of `actual` differing from those of `expected`.
+pub fn diff_blocks(expected: &FullBlock, actual: &FullBlock) -> Vec<FieldDifference> {
+    let mut differences = vec![];
+    diff_values(
+        "",
+        Some(&dump_value(expected)),
+        Some(&dump_value(actual)),
+        &mut differences,
+    );
+    differences
+}
+
+/// Compares the blocks of `from..=to` of `actual` with those of `expected`,
+/// calling `on_block` with the diff of every height either source has, in
+/// height order.
+pub async fn diff_range(
+    expected: &dyn BlockSource,
+    actual: &dyn BlockSource,
+    from: u32,
+    to: u32,
+    mut on_block: impl FnMut(BlockDiff),
+) -> Result<()> {
+    let mut page_from = from;
+    while page_from <= to {
+        let page_to = page_from.saturating_add(DIFF_PAGE_SIZE - 1).min(to);
+        let (expected_blocks, actual_blocks) = futures::try_join!(
+            expected.fetch_range(page_from, page_to),
+            actual.fetch_range(page_from, page_to),
+        )?;
+        let by_height = |blocks: Vec<FullBlock>| -> BTreeMap<u32, FullBlock> {
+            blocks
+                .into_iter()
+                .map(|block| (block.height(), block))
+                .collect()
+        };
+        let (mut expected_blocks, mut actual_blocks) =
+            (by_height(expected_blocks), by_height(actual_blocks));
+
+        for height in page_from..=page_to {
+            let differences = match (
+                expected_blocks.remove(&height),
+                actual_blocks.remove(&height),
+            ) {
+                (Some(expected), Some(actual)) => diff_blocks(&expected, &actual),
+                (None, None) => continue,
+                (expected, actual) => vec![FieldDifference {
+                    path: "block".to_string(),
+                    expected: expected.map(|block| block.id.to_string()),
+                    actual: actual.map(|block| block.id.to_string()),
+                }],
+            };
+            tracing::debug!(
+                block_height = height,
+                differences = differences.len(),
+                "compared block"
+            );
+            on_block(BlockDiff {
+                height,
+                differences,
+            });
+        }
+        match page_to.checked_add(1) {
+            Some(next) => page_from = next,
+            None => break,
+        }
+    }
+    Ok(())
+}
+
+fn dump_value(block: &FullBlock) -> Value {
+    // Dumps only hold strings, numbers and collections of them.
+    serde_json::to_value(BlockDump::from(block)).unwrap_or(Value::Null)
+}
+
+pub(crate) fn diff_values(
+    path: &str,
+    expected: Option<&Value>,
+    actual: Option<&Value>,
+    differences: &mut Vec<FieldDifference>,
+) {
+    match (expected, actual) {
+        (Some(Value::Object(expected)), Some(Value::Object(actual))) => {
+            let mut keys: Vec<_> = expected.keys().chain(actual.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let path = match path {
+                    "" => key.clone(),
+                    _ => format!("{path}.{key}"),
+                };
+                diff_values(&path, expected.get(key), actual.get(key), differences);
+            }
+        }
+        (Some(Value::Array(expected)), Some(Value::Array(actual))) => {
+            for index in 0..expected.len().max(actual.len()) {
+                diff_values(
+                    &format!("{path}[{index}]"),
+                    expected.get(index),
+                    actual.get(index),
+                    differences,
+                );
+            }
+        }
+        (expected, actual) if expected != actual => differences.push(FieldDifference {
+            path: path.to_string(),
+            expected: expected.map(render),
+            actual: actual.map(render),
+        }),
+        _ => {}
+    }
+}
+
+fn render(value: &Value) -> String {
+    let rendered = match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    };
+    if rendered.len() <= MAX_VALUE_LEN {
+        return rendered;
+    }
+    format!(
+        "{} chars with hash {:#x}",
+        rendered.len(),
+        Hasher::hash(rendered.as_bytes())
+    )
+}
@@ -0,0 +1,932 @@
+// src/dump.rs
+//! Serde model of archived blocks, so blocks can be validated without a node.
+//!
+//! [`BlockDump`] has the shape of a [`FullBlock`] but doesn't depend on the
+//! GraphQL query types, whose encoding follows the API rather than a stable
+//! file format. Hashes and payloads are hex strings, numbers are numbers and
+//! times are Unix timestamps.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use fuel_core_client::client::schema::{
+    block::{Consensus, Genesis, PoAConsensus},
+    tx::{
+        transparent_receipt::{Receipt, ReceiptType},
+        FailureStatus, ProgramState, ReturnType, SqueezedOutStatus, SubmittedStatus, SuccessStatus,
+        TransactionStatus,
+    },
+    Bytes, HexString, Tai64Timestamp, U16, U32, U64,
+};
+use fuel_types::{Address, AssetId, Bytes32, Bytes64, ContractId, Nonce};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, ValidationError};
+use crate::query::{FullBlock, Header, HeaderVersion, OpaqueTransactionWithStatusAndId};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlockDump {
+    pub id: Bytes32,
+    pub header: HeaderDump,
+    pub consensus: ConsensusDump,
+    pub transactions: Vec<TransactionDump>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HeaderDump {
+    pub version: HeaderVersionDump,
+    pub id: Bytes32,
+    pub da_height: u64,
+    pub consensus_parameters_version: u32,
+    pub state_transition_bytecode_version: u32,
+    pub transactions_count: u16,
+    pub message_receipt_count: u32,
+    pub transactions_root: Bytes32,
+    pub message_outbox_root: Bytes32,
+    pub event_inbox_root: Bytes32,
+    pub height: u32,
+    pub prev_root: Bytes32,
+    pub time: i64,
+    pub application_hash: Bytes32,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum HeaderVersionDump {
+    V1,
+    V2,
+    /// A version the validator doesn't know, whose block can't be validated.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConsensusDump {
+    Genesis {
+        chain_config_hash: Bytes32,
+        coins_root: Bytes32,
+        contracts_root: Bytes32,
+        messages_root: Bytes32,
+        transactions_root: Bytes32,
+    },
+    Poa {
+        signature: Bytes64,
+    },
+    Unknown,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionDump {
+    pub id: Bytes32,
+    pub raw_payload: Bytes,
+    pub status: Option<StatusDump>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StatusDump {
+    Submitted {
+        time: i64,
+    },
+    Success {
+        block_height: u32,
+        time: i64,
+        program_state: Option<ProgramStateDump>,
+        receipts: Vec<ReceiptDump>,
+        total_gas: u64,
+        total_fee: u64,
+    },
+    SqueezedOut {
+        reason: String,
+    },
+    Failure {
+        block_height: u32,
+        time: i64,
+        reason: String,
+        program_state: Option<ProgramStateDump>,
+        receipts: Vec<ReceiptDump>,
+        total_gas: u64,
+        total_fee: u64,
+    },
+    Unknown,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProgramStateDump {
+    pub return_type: ReturnTypeDump,
+    pub data: Bytes,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnTypeDump {
+    Return,
+    ReturnData,
+    Revert,
+}
+
+/// A receipt with the fields of every receipt type, as served by the API.
+/// Fields the receipt type doesn't have are left out.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReceiptDump {
+    pub receipt_type: ReceiptTypeDump,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub param1: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub param2: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<AssetId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<Bytes32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<ContractId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pc: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ptr: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ra: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rb: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rc: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rd: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<ContractId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_address: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub val: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub len: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Nonce>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_id: Option<ContractId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub_id: Option<Bytes32>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiptTypeDump {
+    Call,
+    Return,
+    ReturnData,
+    Panic,
+    Revert,
+    Log,
+    LogData,
+    Transfer,
+    TransferOut,
+    ScriptResult,
+    MessageOut,
+    Mint,
+    Burn,
+}
+
+/// Encoding of block dump files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// Pretty printed JSON, `.json` files.
+    #[default]
+    Json,
+    /// CBOR, a compact binary encoding of the same model, `.cbor` files.
+    Cbor,
+}
+
+impl DumpFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            DumpFormat::Json => "json",
+            DumpFormat::Cbor => "cbor",
+        }
+    }
+
+    fn of_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(DumpFormat::Json),
+            "cbor" => Some(DumpFormat::Cbor),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for DumpFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(DumpFormat::Json),
+            "cbor" => Ok(DumpFormat::Cbor),
+            _ => Err(format!(
+                "unknown dump format `{s}`, expected one of json, cbor"
+            )),
+        }
+    }
+}
+
+impl BlockDump {
+    /// Name of the file the block is written to in a dump directory.
+    pub fn file_name(&self, format: DumpFormat) -> String {
+        format!("block-{}.{}", self.header.height, format.extension())
+    }
+
+    /// Writes the block to `path` in `format`.
+    pub fn save(&self, path: impl AsRef<Path>, format: DumpFormat) -> Result<()> {
+        let path = path.as_ref();
+        let write_error = |source| ValidationError::WriteBlocks {
+            path: path.to_path_buf(),
+            source,
+        };
+
+        let mut file = BufWriter::new(File::create(path).map_err(write_error)?);
+        match format {
+            DumpFormat::Json => {
+                serde_json::to_writer_pretty(&mut file, self).map_err(|e| write_error(e.into()))?
+            }
+            // Goes through JSON values, see `load_file`.
+            DumpFormat::Cbor => ciborium::into_writer(
+                &serde_json::to_value(self).map_err(|e| write_error(e.into()))?,
+                &mut file,
+            )
+            .map_err(|e| {
+                write_error(match e {
+                    ciborium::ser::Error::Io(e) => e,
+                    ciborium::ser::Error::Value(e) => io::Error::other(e),
+                })
+            })?,
+        }
+        file.flush().map_err(write_error)
+    }
+}
+
+/// Reads the blocks dumped at `path`: a JSON file with one block or an array
+/// of blocks, a CBOR file with one block, or a directory of such files.
+/// Blocks are returned in height order.
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<FullBlock>> {
+    let path = path.as_ref();
+    let read_error = |source| ValidationError::ReadBlocks {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    let files: Vec<PathBuf> = if path.is_dir() {
+        let mut files = fs::read_dir(path)
+            .map_err(read_error)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()
+            .map_err(read_error)?;
+        files.retain(|file| DumpFormat::of_path(file).is_some());
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut blocks = vec![];
+    for file in files {
+        blocks.extend(load_file(&file)?.into_iter().map(FullBlock::from));
+    }
+    blocks.sort_by_key(FullBlock::height);
+    Ok(blocks)
+}
+
+fn load_file(path: &Path) -> Result<Vec<BlockDump>> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Dumps {
+        One(Box<BlockDump>),
+        Many(Vec<BlockDump>),
+    }
+
+    let file = File::open(path).map_err(|source| ValidationError::ReadBlocks {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let file = BufReader::new(file);
+
+    // CBOR isn't human readable, so the fuel types would be encoded as byte
+    // sequences, but serde decodes the content of the tagged enums as if it
+    // were and expects hex strings. CBOR dumps hold the JSON model instead.
+    if DumpFormat::of_path(path) == Some(DumpFormat::Cbor) {
+        let decode_error = |source| ValidationError::DecodeBlocks {
+            path: path.to_path_buf(),
+            source,
+        };
+        let value: serde_json::Value = ciborium::from_reader(file).map_err(decode_error)?;
+        let block = serde_json::from_value(value)
+            .map_err(|e| decode_error(ciborium::de::Error::Semantic(None, e.to_string())))?;
+        return Ok(vec![block]);
+    }
+
+    let dumps = serde_json::from_reader(file).map_err(|source| ValidationError::ParseBlocks {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(match dumps {
+        Dumps::One(block) => vec![*block],
+        Dumps::Many(blocks) => blocks,
+    })
+}
+
+impl From<&FullBlock> for BlockDump {
+    fn from(block: &FullBlock) -> Self {
+        Self {
+            id: block.id.clone().into(),
+            header: (&block.header).into(),
+            consensus: (&block.consensus).into(),
+            transactions: block.transactions.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<BlockDump> for FullBlock {
+    fn from(block: BlockDump) -> Self {
+        Self {
+            id: block.id.into(),
+            header: block.header.into(),
+            consensus: block.consensus.into(),
+            transactions: block.transactions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&Consensus> for ConsensusDump {
+    fn from(consensus: &Consensus) -> Self {
+        match consensus {
+            Consensus::Genesis(genesis) => ConsensusDump::Genesis {
+                chain_config_hash: genesis.chain_config_hash.clone().into(),
+                coins_root: genesis.coins_root.clone().into(),
+                contracts_root: genesis.contracts_root.clone().into(),
+                messages_root: genesis.messages_root.clone().into(),
+                transactions_root: genesis.transactions_root.clone().into(),
+            },
+            Consensus::PoAConsensus(poa) => ConsensusDump::Poa {
+                signature: poa.signature.clone().into(),
+            },
+            Consensus::Unknown => ConsensusDump::Unknown,
+        }
+    }
+}
+
+impl From<ConsensusDump> for Consensus {
+    fn from(consensus: ConsensusDump) -> Self {
+        match consensus {
+            ConsensusDump::Genesis {
+                chain_config_hash,
+                coins_root,
+                contracts_root,
+                messages_root,
+                transactions_root,
+            } => Consensus::Genesis(Genesis {
+                chain_config_hash: chain_config_hash.into(),
+                coins_root: coins_root.into(),
+                contracts_root: contracts_root.into(),
+                messages_root: messages_root.into(),
+                transactions_root: transactions_root.into(),
+            }),
+            ConsensusDump::Poa { signature } => Consensus::PoAConsensus(PoAConsensus {
+                signature: signature.into(),
+            }),
+            ConsensusDump::Unknown => Consensus::Unknown,
+        }
+    }
+}
+
+impl From<&Header> for HeaderDump {
+    fn from(header: &Header) -> Self {
+        Self {
+            version: match header.version {
+                HeaderVersion::V1 => HeaderVersionDump::V1,
+                HeaderVersion::V2 => HeaderVersionDump::V2,
+                HeaderVersion::Unknown => HeaderVersionDump::Unknown,
+            },
+            id: header.id.clone().into(),
+            da_height: header.da_height.0,
+            consensus_parameters_version: header.consensus_parameters_version.0,
+            state_transition_bytecode_version: header.state_transition_bytecode_version.0,
+            transactions_count: header.transactions_count.0,
+            message_receipt_count: header.message_receipt_count.0,
+            transactions_root: header.transactions_root.clone().into(),
+            message_outbox_root: header.message_outbox_root.clone().into(),
+            event_inbox_root: header.event_inbox_root.clone().into(),
+            height: header.height.0,
+            prev_root: header.prev_root.clone().into(),
+            time: header.time.clone().to_unix(),
+            application_hash: header.application_hash.clone().into(),
+        }
+    }
+}
+
+impl From<HeaderDump> for Header {
+    fn from(header: HeaderDump) -> Self {
+        Self {
+            version: match header.version {
+                HeaderVersionDump::V1 => HeaderVersion::V1,
+                HeaderVersionDump::V2 => HeaderVersion::V2,
+                HeaderVersionDump::Unknown => HeaderVersion::Unknown,
+            },
+            id: header.id.into(),
+            da_height: U64(header.da_height),
+            consensus_parameters_version: U32(header.consensus_parameters_version),
+            state_transition_bytecode_version: U32(header.state_transition_bytecode_version),
+            transactions_count: U16(header.transactions_count),
+            message_receipt_count: U32(header.message_receipt_count),
+            transactions_root: header.transactions_root.into(),
+            message_outbox_root: header.message_outbox_root.into(),
+            event_inbox_root: header.event_inbox_root.into(),
+            height: U32(header.height),
+            prev_root: header.prev_root.into(),
+            time: Tai64Timestamp::from_unix(header.time),
+            application_hash: header.application_hash.into(),
+        }
+    }
+}
+
+impl From<&OpaqueTransactionWithStatusAndId> for TransactionDump {
+    fn from(tx: &OpaqueTransactionWithStatusAndId) -> Self {
+        Self {
+            id: tx.id.clone().into(),
+            raw_payload: tx.raw_payload.0.clone(),
+            status: tx.status.as_ref().map(|status| match status {
+                TransactionStatus::SubmittedStatus(status) => StatusDump::Submitted {
+                    time: status.time.clone().to_unix(),
+                },
+                TransactionStatus::SuccessStatus(status) => StatusDump::Success {
+                    block_height: status.block_height.0,
+                    time: status.time.clone().to_unix(),
+                    program_state: status.program_state.as_ref().map(Into::into),
+                    receipts: status.receipts.iter().map(Into::into).collect(),
+                    total_gas: status.total_gas.0,
+                    total_fee: status.total_fee.0,
+                },
+                TransactionStatus::SqueezedOutStatus(status) => StatusDump::SqueezedOut {
+                    reason: status.reason.clone(),
+                },
+                TransactionStatus::FailureStatus(status) => StatusDump::Failure {
+                    block_height: status.block_height.0,
+                    time: status.time.clone().to_unix(),
+                    reason: status.reason.clone(),
+                    program_state: status.program_state.as_ref().map(Into::into),
+                    receipts: status.receipts.iter().map(Into::into).collect(),
+                    total_gas: status.total_gas.0,
+                    total_fee: status.total_fee.0,
+                },
+                TransactionStatus::Unknown => StatusDump::Unknown,
+            }),
+        }
+    }
+}
+
+impl From<TransactionDump> for OpaqueTransactionWithStatusAndId {
+    fn from(tx: TransactionDump) -> Self {
+        Self {
+            id: tx.id.into(),
+            raw_payload: HexString(tx.raw_payload),
+            status: tx.status.map(|status| match status {
+                StatusDump::Submitted { time } => {
+                    TransactionStatus::SubmittedStatus(SubmittedStatus {
+                        time: Tai64Timestamp::from_unix(time),
+                    })
+                }
+                StatusDump::Success {
+                    block_height,
+                    time,
+                    program_state,
+                    receipts,
+                    total_gas,
+                    total_fee,
+                } => TransactionStatus::SuccessStatus(SuccessStatus {
+                    block_height: U32(block_height),
+                    time: Tai64Timestamp::from_unix(time),
+                    program_state: program_state.map(Into::into),
+                    receipts: receipts.into_iter().map(Into::into).collect(),
+                    total_gas: U64(total_gas),
+                    total_fee: U64(total_fee),
+                }),
+                StatusDump::SqueezedOut { reason } => {
+                    TransactionStatus::SqueezedOutStatus(SqueezedOutStatus { reason })
+                }
+                StatusDump::Failure {
+                    block_height,
+                    time,
+                    reason,
+                    program_state,
+                    receipts,
+                    total_gas,
+                    total_fee,
+                } => TransactionStatus::FailureStatus(FailureStatus {
+                    block_height: U32(block_height),
+                    time: Tai64Timestamp::from_unix(time),
+                    reason,
+                    program_state: program_state.map(Into::into),
+                    receipts: receipts.into_iter().map(Into::into).collect(),
+                    total_gas: U64(total_gas),
+                    total_fee: U64(total_fee),
+                }),
+                StatusDump::Unknown => TransactionStatus::Unknown,
+            }),
+        }
+    }
+}
+
+impl From<&ProgramState> for ProgramStateDump {
+    fn from(state: &ProgramState) -> Self {
+        Self {
+            return_type: match state.return_type {
+                ReturnType::Return => ReturnTypeDump::Return,
+                ReturnType::ReturnData => ReturnTypeDump::ReturnData,
+                ReturnType::Revert => ReturnTypeDump::Revert,
+            },
+            data: state.data.0.clone(),
+        }
+    }
+}
+
+impl From<ProgramStateDump> for ProgramState {
+    fn from(state: ProgramStateDump) -> Self {
+        Self {
+            return_type: match state.return_type {
+                ReturnTypeDump::Return => ReturnType::Return,
+                ReturnTypeDump::ReturnData => ReturnType::ReturnData,
+                ReturnTypeDump::Revert => ReturnType::Revert,
+            },
+            data: HexString(state.data),
+        }
+    }
+}
+
+impl From<&Receipt> for ReceiptDump {
+    fn from(receipt: &Receipt) -> Self {
+        let number = |value: &Option<U64>| value.as_ref().map(|value| value.0);
+        Self {
+            receipt_type: receipt.receipt_type.into(),
+            param1: number(&receipt.param1),
+            param2: number(&receipt.param2),
+            amount: number(&receipt.amount),
+            asset_id: receipt.asset_id.clone().map(Into::into),
+            gas: number(&receipt.gas),
+            digest: receipt.digest.clone().map(Into::into),
+            id: receipt.id.clone().map(Into::into),
+            is: number(&receipt.is),
+            pc: number(&receipt.pc),
+            ptr: number(&receipt.ptr),
+            ra: number(&receipt.ra),
+            rb: number(&receipt.rb),
+            rc: number(&receipt.rc),
+            rd: number(&receipt.rd),
+            reason: number(&receipt.reason),
+            to: receipt.to.clone().map(Into::into),
+            to_address: receipt.to_address.clone().map(Into::into),
+            val: number(&receipt.val),
+            len: number(&receipt.len),
+            result: number(&receipt.result),
+            gas_used: number(&receipt.gas_used),
+            data: receipt.data.clone().map(|data| data.0),
+            sender: receipt.sender.clone().map(Into::into),
+            recipient: receipt.recipient.clone().map(Into::into),
+            nonce: receipt.nonce.clone().map(Into::into),
+            contract_id: receipt.contract_id.clone().map(Into::into),
+            sub_id: receipt.sub_id.clone().map(Into::into),
+        }
+    }
+}
+
+impl ReceiptDump {
+    fn new(receipt_type: ReceiptTypeDump) -> Self {
+        Self {
+            receipt_type,
+            param1: None,
+            param2: None,
+            amount: None,
+            asset_id: None,
+            gas: None,
+            digest: None,
+            id: None,
+            is: None,
+            pc: None,
+            ptr: None,
+            ra: None,
+            rb: None,
+            rc: None,
+            rd: None,
+            reason: None,
+            to: None,
+            to_address: None,
+            val: None,
+            len: None,
+            result: None,
+            gas_used: None,
+            data: None,
+            sender: None,
+            recipient: None,
+            nonce: None,
+            contract_id: None,
+            sub_id: None,
+        }
+    }
+}
+
+/// The receipt as the API would serve it, for blocks that weren't read
+/// through the API.
+impl From<&fuel_tx::Receipt> for ReceiptDump {
+    fn from(receipt: &fuel_tx::Receipt) -> Self {
+        use fuel_tx::Receipt as R;
+
+        let data = |data: &Option<Vec<u8>>| Some(Bytes(data.clone().unwrap_or_default()));
+        match receipt {
+            &R::Call {
+                id,
+                to,
+                amount,
+                asset_id,
+                gas,
+                param1,
+                param2,
+                pc,
+                is,
+            } => Self {
+                id: Some(id),
+                to: Some(to),
+                amount: Some(amount),
+                asset_id: Some(asset_id),
+                gas: Some(gas),
+                param1: Some(param1),
+                param2: Some(param2),
+                pc: Some(pc),
+                is: Some(is),
+                ..Self::new(ReceiptTypeDump::Call)
+            },
+            &R::Return { id, val, pc, is } => Self {
+                id: Some(id),
+                val: Some(val),
+                pc: Some(pc),
+                is: Some(is),
+                ..Self::new(ReceiptTypeDump::Return)
+            },
+            R::ReturnData {
+                id,
+                ptr,
+                len,
+                digest,
+                pc,
+                is,
+                data: bytes,
+            } => Self {
+                id: Some(*id),
+                ptr: Some(*ptr),
+                len: Some(*len),
+                digest: Some(*digest),
+                pc: Some(*pc),
+                is: Some(*is),
+                data: data(bytes),
+                ..Self::new(ReceiptTypeDump::ReturnData)
+            },
+            &R::Panic {
+                id,
+                reason,
+                pc,
+                is,
+                contract_id,
+            } => Self {
+                id: Some(id),
+                reason: Some(reason.into()),
+                pc: Some(pc),
+                is: Some(is),
+                contract_id,
+                ..Self::new(ReceiptTypeDump::Panic)
+            },
+            &R::Revert { id, ra, pc, is } => Self {
+                id: Some(id),
+                ra: Some(ra),
+                pc: Some(pc),
+                is: Some(is),
+                ..Self::new(ReceiptTypeDump::Revert)
+            },
+            &R::Log {
+                id,
+                ra,
+                rb,
+                rc,
+                rd,
+                pc,
+                is,
+            } => Self {
+                id: Some(id),
+                ra: Some(ra),
+                rb: Some(rb),
+                rc: Some(rc),
+                rd: Some(rd),
+                pc: Some(pc),
+                is: Some(is),
+                ..Self::new(ReceiptTypeDump::Log)
+            },
+            R::LogData {
+                id,
+                ra,
+                rb,
+                ptr,
+                len,
+                digest,
+                pc,
+                is,
+                data: bytes,
+            } => Self {
+                id: Some(*id),
+                ra: Some(*ra),
+                rb: Some(*rb),
+                ptr: Some(*ptr),
+                len: Some(*len),
+                digest: Some(*digest),
+                pc: Some(*pc),
+                is: Some(*is),
+                data: data(bytes),
+                ..Self::new(ReceiptTypeDump::LogData)
+            },
+            &R::Transfer {
+                id,
+                to,
+                amount,
+                asset_id,
+                pc,
+                is,
+            } => Self {
+                id: Some(id),
+                to: Some(to),
+                amount: Some(amount),
+                asset_id: Some(asset_id),
+                pc: Some(pc),
+                is: Some(is),
+                ..Self::new(ReceiptTypeDump::Transfer)
+            },
+            &R::TransferOut {
+                id,
+                to,
+                amount,
+                asset_id,
+                pc,
+                is,
+            } => Self {
+                id: Some(id),
+                to_address: Some(to),
+                amount: Some(amount),
+                asset_id: Some(asset_id),
+                pc: Some(pc),
+                is: Some(is),
+                ..Self::new(ReceiptTypeDump::TransferOut)
+            },
+            &R::ScriptResult { result, gas_used } => Self {
+                result: Some(result.into()),
+                gas_used: Some(gas_used),
+                ..Self::new(ReceiptTypeDump::ScriptResult)
+            },
+            R::MessageOut {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                len,
+                digest,
+                data: bytes,
+            } => Self {
+                sender: Some(*sender),
+                recipient: Some(*recipient),
+                amount: Some(*amount),
+                nonce: Some(*nonce),
+                len: Some(*len),
+                digest: Some(*digest),
+                data: data(bytes),
+                ..Self::new(ReceiptTypeDump::MessageOut)
+            },
+            &R::Mint {
+                sub_id,
+                contract_id,
+                val,
+                pc,
+                is,
+            } => Self {
+                sub_id: Some(sub_id),
+                // The API serves the minting contract as `id`.
+                id: Some(contract_id),
+                contract_id: Some(contract_id),
+                val: Some(val),
+                pc: Some(pc),
+                is: Some(is),
+                ..Self::new(ReceiptTypeDump::Mint)
+            },
+            &R::Burn {
+                sub_id,
+                contract_id,
+                val,
+                pc,
+                is,
+            } => Self {
+                sub_id: Some(sub_id),
+                id: Some(contract_id),
+                contract_id: Some(contract_id),
+                val: Some(val),
+                pc: Some(pc),
+                is: Some(is),
+                ..Self::new(ReceiptTypeDump::Burn)
+            },
+        }
+    }
+}
+
+impl From<ReceiptDump> for Receipt {
+    fn from(receipt: ReceiptDump) -> Self {
+        Self {
+            receipt_type: receipt.receipt_type.into(),
+            param1: receipt.param1.map(U64),
+            param2: receipt.param2.map(U64),
+            amount: receipt.amount.map(U64),
+            asset_id: receipt.asset_id.map(Into::into),
+            gas: receipt.gas.map(U64),
+            digest: receipt.digest.map(Into::into),
+            id: receipt.id.map(Into::into),
+            is: receipt.is.map(U64),
+            pc: receipt.pc.map(U64),
+            ptr: receipt.ptr.map(U64),
+            ra: receipt.ra.map(U64),
+            rb: receipt.rb.map(U64),
+            rc: receipt.rc.map(U64),
+            rd: receipt.rd.map(U64),
+            reason: receipt.reason.map(U64),
+            to: receipt.to.map(Into::into),
+            to_address: receipt.to_address.map(Into::into),
+            val: receipt.val.map(U64),
+            len: receipt.len.map(U64),
+            result: receipt.result.map(U64),
+            gas_used: receipt.gas_used.map(U64),
+            data: receipt.data.map(HexString),
+            sender: receipt.sender.map(Into::into),
+            recipient: receipt.recipient.map(Into::into),
+            nonce: receipt.nonce.map(Into::into),
+            contract_id: receipt.contract_id.map(Into::into),
+            sub_id: receipt.sub_id.map(Into::into),
+        }
+    }
+}
+
+impl From<ReceiptType> for ReceiptTypeDump {
+    fn from(receipt_type: ReceiptType) -> Self {
+        match receipt_type {
+            ReceiptType::Call => ReceiptTypeDump::Call,
+            ReceiptType::Return => ReceiptTypeDump::Return,
+            ReceiptType::ReturnData => ReceiptTypeDump::ReturnData,
+            ReceiptType::Panic => ReceiptTypeDump::Panic,
+            ReceiptType::Revert => ReceiptTypeDump::Revert,
+            ReceiptType::Log => ReceiptTypeDump::Log,
+            ReceiptType::LogData => ReceiptTypeDump::LogData,
+            ReceiptType::Transfer => ReceiptTypeDump::Transfer,
+            ReceiptType::TransferOut => ReceiptTypeDump::TransferOut,
+            ReceiptType::ScriptResult => ReceiptTypeDump::ScriptResult,
+            ReceiptType::MessageOut => ReceiptTypeDump::MessageOut,
+            ReceiptType::Mint => ReceiptTypeDump::Mint,
+            ReceiptType::Burn => ReceiptTypeDump::Burn,
+        }
+    }
+}
+
+impl From<ReceiptTypeDump> for ReceiptType {
+    fn from(receipt_type: ReceiptTypeDump) -> Self {
+        match receipt_type {
+            ReceiptTypeDump::Call => ReceiptType::Call,
+            ReceiptTypeDump::Return => ReceiptType::Return,
+            ReceiptTypeDump::ReturnData => ReceiptType::ReturnData,
+            ReceiptTypeDump::Panic => ReceiptType::Panic,
+            ReceiptTypeDump::Revert => ReceiptType::Revert,
+            ReceiptTypeDump::Log => ReceiptType::Log,
+            ReceiptTypeDump::LogData => ReceiptType::LogData,
+            ReceiptTypeDump::Transfer => ReceiptType::Transfer,
+            ReceiptTypeDump::TransferOut => ReceiptType::TransferOut,
+            ReceiptTypeDump::ScriptResult => ReceiptType::ScriptResult,
+            ReceiptTypeDump::MessageOut => ReceiptType::MessageOut,
+            ReceiptTypeDump::Mint => ReceiptType::Mint,
+            ReceiptTypeDump::Burn => ReceiptType::Burn,
+        }
+    }
+}
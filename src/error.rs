@@ -0,0 +1,534 @@
+// src/error.rs
+use std::{io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::report::Check;
+#[cfg(feature = "native")]
+use crate::retry;
+
+pub type Result<T, E = ValidationError> = std::result::Result<T, E>;
+
+/// Everything that can go wrong while validating blocks.
+///
+/// The `*Mismatch` variants describe a failed check of a [`BlockReport`]
+/// (see [`BlockReport::errors`]). `expected` is the value committed to by the
+/// node and `actual` the value recomputed by the validator.
+///
+/// [`BlockReport`]: crate::report::BlockReport
+/// [`BlockReport::errors`]: crate::report::BlockReport::errors
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationError {
+    #[error("failed to query {query} after {attempts} attempt(s): {source}")]
+    Query {
+        query: &'static str,
+        attempts: u32,
+        #[source]
+        source: io::Error,
+    },
+    #[error("validation was cancelled")]
+    Cancelled,
+    #[error("querying the {query} needs a GraphQL node")]
+    NodeRequired { query: &'static str },
+    #[error("L1 call {call} failed: {reason}")]
+    L1Query { call: &'static str, reason: String },
+    #[error("block subscription failed: {reason}")]
+    Subscription { reason: String },
+    #[error("failed to publish events to {url}: {reason}")]
+    Publish { url: String, reason: String },
+    #[error("invalid endpoint `{url}`: {reason}")]
+    InvalidEndpoint { url: String, reason: String },
+    #[error("unknown check `{name}`, expected one of: {known}")]
+    UnknownCheck { name: String, known: String },
+    #[error("block #{height} not found")]
+    BlockNotFound { height: u32 },
+    #[error("block #{height} has a header version this validator doesn't know")]
+    UnsupportedHeaderVersion { height: u32 },
+    #[error("consensus parameters version {version} not found")]
+    ConsensusParametersNotFound { version: u32 },
+    #[error("invalid consensus parameters version {version}: {reason}")]
+    InvalidConsensusParameters { version: u32, reason: String },
+    #[error("transaction {tx_id} not found in block #{height}")]
+    TransactionNotFound { height: u32, tx_id: String },
+    #[error("transaction {tx_id} isn't part of any block of the node")]
+    TransactionNotIncluded { tx_id: String },
+    #[error("failed to parse transaction {tx_id}: {reason}")]
+    ParseError { tx_id: String, reason: String },
+    #[error("non-contiguous block ids: expected height {expected}, got {actual}")]
+    NonContiguousHeight { expected: u32, actual: u32 },
+    #[error("failed to read L1 events from {}: {source}", path.display())]
+    ReadEvents {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to parse L1 events from {}: {source}", path.display())]
+    ParseEvents {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("failed to read known anomalies from {}: {source}", path.display())]
+    ReadKnownAnomalies {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to parse known anomalies from {}: {source}", path.display())]
+    ParseKnownAnomalies {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("invalid known anomaly {index}: {reason}")]
+    InvalidKnownAnomaly { index: usize, reason: String },
+    #[error("failed to read snapshot file {}: {source}", path.display())]
+    ReadSnapshot {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to parse snapshot file {}: {source}", path.display())]
+    ParseSnapshot {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("unsupported snapshot {}: {reason}", path.display())]
+    UnsupportedSnapshot { path: PathBuf, reason: String },
+    #[error("failed to read consensus parameters from {}: {source}", path.display())]
+    ReadConsensusParameters {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to parse consensus parameters from {}: {source}", path.display())]
+    ParseConsensusParameters {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("failed to write consensus parameters to {}: {source}", path.display())]
+    WriteConsensusParameters {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to read blocks from {}: {source}", path.display())]
+    ReadBlocks {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to parse blocks from {}: {source}", path.display())]
+    ParseBlocks {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("failed to decode blocks from {}: {source}", path.display())]
+    DecodeBlocks {
+        path: PathBuf,
+        #[source]
+        source: ciborium::de::Error<io::Error>,
+    },
+    #[error("failed to write blocks to {}: {source}", path.display())]
+    WriteBlocks {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to read the node database at {}: {reason}", path.display())]
+    Database { path: PathBuf, reason: String },
+    #[error("failed to access checkpoint {}: {source}", path.display())]
+    CheckpointIo {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to access the block cache at {}: {source}", path.display())]
+    CacheIo {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to parse checkpoint {}: {source}", path.display())]
+    ParseCheckpoint {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("failed to access cassette {}: {source}", path.display())]
+    CassetteIo {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to parse cassette {}: {source}", path.display())]
+    ParseCassette {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("failed to read report {}: {source}", path.display())]
+    ReportIo {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to parse report {}: {source}", path.display())]
+    ParseReport {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[cfg(feature = "native")]
+    #[error("results store error: {0}")]
+    Store(#[from] rusqlite::Error),
+    #[cfg(feature = "postgres")]
+    #[error("results store error: {0}")]
+    Postgres(#[from] postgres::Error),
+    #[error("unknown network `{0}`, expected one of mainnet, testnet, devnet")]
+    UnknownNetwork(String),
+    #[error("invalid block `{target}`: {reason}")]
+    InvalidBlockTarget { target: String, reason: String },
+    #[error("invalid corruption `{corruption}`: {reason}")]
+    InvalidCorruption { corruption: String, reason: String },
+
+    #[error("application hash mismatch in block #{height}: expected {expected}, got {actual}")]
+    ApplicationHashMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error("block id mismatch in block #{height}: expected {expected}, got {actual}")]
+    BlockIdMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error("block #{height} was signed by {actual} instead of {expected}")]
+    PoaSignatureMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error(
+        "id mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}"
+    )]
+    TxIdMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("transactions root mismatch in block #{height}: expected {expected}, got {actual}")]
+    TxRootMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error(
+        "status mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}"
+    )]
+    TxStatusMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error(
+        "status block height mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}"
+    )]
+    StatusHeightMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error(
+        "status result mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}"
+    )]
+    StatusResultMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error(
+        "failure reason mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}"
+    )]
+    StatusReasonMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error(
+        "status gas mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}"
+    )]
+    StatusGasMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error(
+        "message receipt count mismatch in block #{height}: expected {expected}, got {actual}"
+    )]
+    MessageReceiptCountMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error("message outbox root mismatch in block #{height}: expected {expected}, got {actual}")]
+    MessageOutboxRootMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error("receipt root mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}")]
+    ReceiptRootMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error(
+        "mint transaction of block #{height} is misplaced: expected at {expected}, got {actual}"
+    )]
+    MintPositionMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error("mint amount mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}")]
+    MintAmountMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("mint asset id mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}")]
+    MintAssetIdMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("gas mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}")]
+    TxGasMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("fee mismatch for transaction {tx_id} in block #{height}: expected {expected}, got {actual}")]
+    TxFeeMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("state root mismatch for the contract created by transaction {tx_id} in block #{height}: expected {expected}, got {actual}")]
+    ContractStateRootMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("contract id mismatch for the contract created by transaction {tx_id} in block #{height}: expected {expected}, got {actual}")]
+    ContractIdMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("transaction {tx_id} isn't included in the transactions root of block #{height}: expected {expected}, got {actual}")]
+    TxInclusionMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("prev root mismatch in block #{height}: expected {expected}, got {actual}")]
+    PrevRootMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error("event inbox root mismatch in block #{height}: expected {expected}, got {actual}")]
+    EventInboxRootMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error("relayed transaction {tx_id} is included by block #{expected} but failed in block #{actual}")]
+    RelayedTxMismatch {
+        height: u32,
+        tx_id: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("DA height of block #{height} is ahead of L1: expected {expected}, got {actual}")]
+    L1DaHeightMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error("L1 commitment mismatch in block #{height}: expected {expected}, got {actual}")]
+    L1CommitMismatch {
+        height: u32,
+        expected: String,
+        actual: String,
+    },
+    #[error("endpoints disagree on block #{height}: {detail} (expected {expected}, got {actual})")]
+    EndpointDisagreement {
+        height: u32,
+        detail: String,
+        expected: String,
+        actual: String,
+    },
+    /// A check whose value couldn't be recomputed, e.g. because the data it
+    /// needs doesn't decode.
+    #[error("{check} of block #{height} could not be computed: {message}")]
+    CheckFailed {
+        check: Check,
+        height: u32,
+        tx_id: Option<String>,
+        message: String,
+        kind: ErrorKind,
+    },
+}
+
+/// Broad class of a [`ValidationError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorKind {
+    /// The node couldn't be queried or didn't serve the requested data.
+    Query,
+    /// Data couldn't be decoded, whether it was served by the node or given
+    /// by the user, or local files couldn't be read or written.
+    Parse,
+    /// The block doesn't match what it commits to.
+    Mismatch,
+    /// Several endpoints serve different data for the same block.
+    Disagreement,
+}
+
+impl ErrorKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorKind::Query => "query",
+            ErrorKind::Parse => "parse",
+            ErrorKind::Mismatch => "mismatch",
+            ErrorKind::Disagreement => "disagreement",
+        }
+    }
+}
+
+impl ValidationError {
+    /// Whether retrying the failed operation could succeed.
+    #[cfg(feature = "native")]
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Query { source, .. } if retry::is_retryable(source))
+    }
+
+    /// Whether the operation was stopped by its cancellation token rather
+    /// than failing.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled)
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Query { .. }
+            | Self::Cancelled
+            | Self::BlockNotFound { .. }
+            | Self::TransactionNotFound { .. }
+            | Self::TransactionNotIncluded { .. }
+            | Self::L1Query { .. }
+            | Self::Subscription { .. }
+            | Self::Publish { .. }
+            | Self::ConsensusParametersNotFound { .. }
+            | Self::NonContiguousHeight { .. } => ErrorKind::Query,
+            Self::InvalidEndpoint { .. }
+            | Self::NodeRequired { .. }
+            | Self::ParseError { .. }
+            | Self::UnsupportedHeaderVersion { .. }
+            | Self::InvalidConsensusParameters { .. }
+            | Self::UnknownCheck { .. }
+            | Self::ReadConsensusParameters { .. }
+            | Self::ParseConsensusParameters { .. }
+            | Self::WriteConsensusParameters { .. }
+            | Self::ReadEvents { .. }
+            | Self::ParseEvents { .. }
+            | Self::ReadKnownAnomalies { .. }
+            | Self::ParseKnownAnomalies { .. }
+            | Self::InvalidKnownAnomaly { .. }
+            | Self::ReadSnapshot { .. }
+            | Self::ParseSnapshot { .. }
+            | Self::UnsupportedSnapshot { .. }
+            | Self::ReadBlocks { .. }
+            | Self::ParseBlocks { .. }
+            | Self::DecodeBlocks { .. }
+            | Self::WriteBlocks { .. }
+            | Self::Database { .. }
+            | Self::CheckpointIo { .. }
+            | Self::CacheIo { .. }
+            | Self::ParseCheckpoint { .. }
+            | Self::CassetteIo { .. }
+            | Self::ParseCassette { .. }
+            | Self::ReportIo { .. }
+            | Self::ParseReport { .. }
+            | Self::UnknownNetwork(_)
+            | Self::InvalidBlockTarget { .. }
+            | Self::InvalidCorruption { .. } => ErrorKind::Parse,
+            #[cfg(feature = "native")]
+            Self::Store(_) => ErrorKind::Parse,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(_) => ErrorKind::Parse,
+            Self::EndpointDisagreement { .. } => ErrorKind::Disagreement,
+            Self::CheckFailed { kind, .. } => *kind,
+            _ => ErrorKind::Mismatch,
+        }
+    }
+
+    /// Height of the block the error is about, if any.
+    pub fn height(&self) -> Option<u32> {
+        match self {
+            Self::BlockNotFound { height }
+            | Self::UnsupportedHeaderVersion { height }
+            | Self::TransactionNotFound { height, .. }
+            | Self::ApplicationHashMismatch { height, .. }
+            | Self::BlockIdMismatch { height, .. }
+            | Self::PoaSignatureMismatch { height, .. }
+            | Self::TxIdMismatch { height, .. }
+            | Self::TxRootMismatch { height, .. }
+            | Self::TxStatusMismatch { height, .. }
+            | Self::StatusHeightMismatch { height, .. }
+            | Self::StatusResultMismatch { height, .. }
+            | Self::StatusReasonMismatch { height, .. }
+            | Self::StatusGasMismatch { height, .. }
+            | Self::MessageReceiptCountMismatch { height, .. }
+            | Self::MessageOutboxRootMismatch { height, .. }
+            | Self::ReceiptRootMismatch { height, .. }
+            | Self::MintPositionMismatch { height, .. }
+            | Self::MintAmountMismatch { height, .. }
+            | Self::MintAssetIdMismatch { height, .. }
+            | Self::TxGasMismatch { height, .. }
+            | Self::TxFeeMismatch { height, .. }
+            | Self::ContractStateRootMismatch { height, .. }
+            | Self::ContractIdMismatch { height, .. }
+            | Self::TxInclusionMismatch { height, .. }
+            | Self::PrevRootMismatch { height, .. }
+            | Self::EventInboxRootMismatch { height, .. }
+            | Self::RelayedTxMismatch { height, .. }
+            | Self::L1DaHeightMismatch { height, .. }
+            | Self::L1CommitMismatch { height, .. }
+            | Self::EndpointDisagreement { height, .. }
+            | Self::CheckFailed { height, .. } => Some(*height),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// src/event_inbox.rs
+use std::{fs, path::Path};
+
+use fuel_core_client::client::schema::Bytes;
+use fuel_crypto::Hasher;
+use fuel_merkle::binary::root_calculator::MerkleRootCalculator;
+use fuel_tx::input::message::compute_message_id;
+use fuel_types::{Address, Bytes32, Nonce};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, ValidationError};
+
+/// An event emitted on L1 and relayed to Fuel, as found in the bridge
+/// contract logs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RelayedEvent {
+    /// A message sent to Fuel through the message portal.
+    Message {
+        da_height: u64,
+        sender: Address,
+        recipient: Address,
+        nonce: Nonce,
+        amount: u64,
+        data: Bytes,
+    },
+    /// A transaction forced into Fuel from L1.
+    Transaction {
+        da_height: u64,
+        nonce: Nonce,
+        max_gas: u64,
+        serialized_transaction: Bytes,
+    },
+}
+
+impl RelayedEvent {
+    pub fn da_height(&self) -> u64 {
+        match self {
+            RelayedEvent::Message { da_height, .. } => *da_height,
+            RelayedEvent::Transaction { da_height, .. } => *da_height,
+        }
+    }
+
+    /// Leaf of the event inbox tree: the message id for messages and the
+    /// relayed transaction id for forced transactions.
+    pub fn hash(&self) -> Bytes32 {
+        match self {
+            RelayedEvent::Message {
+                sender,
+                recipient,
+                nonce,
+                amount,
+                data,
+                ..
+            } => (*compute_message_id(sender, recipient, nonce, *amount, data)).into(),
+            RelayedEvent::Transaction {
+                nonce,
+                max_gas,
+                serialized_transaction,
+                ..
+            } => Hasher::default()
+                .chain(nonce.as_slice())
+                .chain(max_gas.to_be_bytes())
+                .chain(serialized_transaction.as_ref())
+                .finalize(),
+        }
+    }
+}
+
+/// L1 events known to the validator, ordered by DA height. Events of the same
+/// DA height keep the order in which they were emitted.
+#[derive(Clone, Debug, Default)]
+pub struct EventInbox {
+    events: Vec<RelayedEvent>,
+}
+
+impl EventInbox {
+    pub fn new(mut events: Vec<RelayedEvent>) -> Self {
+        events.sort_by_key(RelayedEvent::da_height);
+        Self { events }
+    }
+
+    /// Loads events from a JSON array of [`RelayedEvent`]s.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).map_err(|source| ValidationError::ReadEvents {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let events =
+            serde_json::from_str(&json).map_err(|source| ValidationError::ParseEvents {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        Ok(Self::new(events))
+    }
+
+    /// Events with a DA height in `after + 1..=up_to`, i.e. the events included
+    /// by a block whose parent is at DA height `after`.
+    pub fn events_between(&self, after: u64, up_to: u64) -> impl Iterator<Item = &RelayedEvent> {
+        self.events
+            .iter()
+            .filter(move |event| event.da_height() > after && event.da_height() <= up_to)
+    }
+
+    pub fn root_between(&self, after: u64, up_to: u64) -> Bytes32 {
+        let mut calculator = MerkleRootCalculator::new();
+        for event in self.events_between(after, up_to) {
+            calculator.push(event.hash().as_ref());
+        }
+        calculator.root().into()
+    }
+}
@@ -0,0 +1,174 @@
+// src/events.rs
+//! Publishing the report of every validated block to a message bus, for
+//! pipelines consuming validation results as they are produced.
+//!
+//! The bus is picked by the scheme of its URL: `nats://host:4222` publishes
+//! to a NATS subject and needs the `nats` feature, `kafka://host:9092` (with
+//! more brokers separated by commas) appends to partition 0 of a Kafka topic
+//! and needs the `kafka` feature.
+
+use std::fmt;
+
+use crate::error::{Result, ValidationError};
+
+/// Subject or topic events are published to when none is given.
+pub const DEFAULT_EVENTS_TOPIC: &str = "fuel.block-validation";
+
+/// Publishes one JSON message per validated block.
+#[derive(Clone)]
+pub struct EventPublisher {
+    url: String,
+    topic: String,
+    bus: Bus,
+}
+
+#[derive(Clone)]
+enum Bus {
+    #[cfg(feature = "nats")]
+    Nats(async_nats::Client),
+    #[cfg(feature = "kafka")]
+    Kafka(std::sync::Arc<rskafka::client::partition::PartitionClient>),
+}
+
+impl EventPublisher {
+    /// Connects to the bus at `url`, publishing to `topic`.
+    pub async fn connect(url: &str, topic: impl Into<String>) -> Result<Self> {
+        let topic = topic.into();
+        let bus = Bus::connect(url, &topic).await?;
+        Ok(Self {
+            url: url.to_string(),
+            topic,
+            bus,
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Publishes the JSON `payload` describing the block at `height`, which
+    /// is the key of Kafka records and the `Fuel-Block-Height` header of NATS
+    /// messages.
+    pub async fn publish(&self, height: u32, payload: Vec<u8>) -> Result<()> {
+        match &self.bus {
+            #[cfg(feature = "nats")]
+            Bus::Nats(client) => {
+                let mut headers = async_nats::HeaderMap::new();
+                headers.insert("Fuel-Block-Height", height.to_string());
+                client
+                    .publish_with_headers(self.topic.clone(), headers, payload.into())
+                    .await
+                    .map_err(|e| publish_error(&self.url, e))
+            }
+            #[cfg(feature = "kafka")]
+            Bus::Kafka(partition) => {
+                use rskafka::{chrono::DateTime, client::partition::Compression, record::Record};
+
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default();
+                let record = Record {
+                    key: Some(height.to_string().into_bytes()),
+                    value: Some(payload),
+                    headers: Default::default(),
+                    timestamp: DateTime::from_timestamp_millis(now.as_millis() as i64)
+                        .unwrap_or_default(),
+                };
+                partition
+                    .produce(vec![record], Compression::NoCompression)
+                    .await
+                    .map(drop)
+                    .map_err(|e| publish_error(&self.url, e))
+            }
+            #[cfg(not(any(feature = "nats", feature = "kafka")))]
+            _ => {
+                let _ = (height, payload);
+                unreachable!(
+                    "no event bus can be connected to without the `nats` or `kafka` feature"
+                )
+            }
+        }
+    }
+
+    /// Waits for the events published so far to reach the bus.
+    pub async fn flush(&self) -> Result<()> {
+        match &self.bus {
+            #[cfg(feature = "nats")]
+            Bus::Nats(client) => client
+                .flush()
+                .await
+                .map_err(|e| publish_error(&self.url, e)),
+            // Every record is acknowledged by `publish`.
+            #[cfg(feature = "kafka")]
+            Bus::Kafka(_) => Ok(()),
+            #[cfg(not(any(feature = "nats", feature = "kafka")))]
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Bus {
+    // Only Kafka clients are bound to their topic.
+    #[cfg_attr(not(feature = "kafka"), allow(unused_variables))]
+    async fn connect(url: &str, topic: &str) -> Result<Self> {
+        let invalid = |reason: &str| ValidationError::InvalidEndpoint {
+            url: url.to_string(),
+            reason: reason.to_string(),
+        };
+        let Some((scheme, addresses)) = url.split_once("://") else {
+            return Err(invalid("expected a nats:// or kafka:// URL"));
+        };
+        if addresses.is_empty() {
+            return Err(invalid("no broker address"));
+        }
+        match scheme {
+            #[cfg(feature = "nats")]
+            "nats" => Ok(Bus::Nats(
+                async_nats::connect(url)
+                    .await
+                    .map_err(|e| publish_error(url, e))?,
+            )),
+            #[cfg(not(feature = "nats"))]
+            "nats" => Err(invalid("publishing to NATS requires the `nats` feature")),
+            #[cfg(feature = "kafka")]
+            "kafka" => {
+                use rskafka::client::{partition::UnknownTopicHandling, ClientBuilder};
+
+                let brokers = addresses.split(',').map(str::to_string).collect();
+                let client = ClientBuilder::new(brokers)
+                    .build()
+                    .await
+                    .map_err(|e| publish_error(url, e))?;
+                let partition = client
+                    .partition_client(topic, 0, UnknownTopicHandling::Retry)
+                    .await
+                    .map_err(|e| publish_error(url, e))?;
+                Ok(Bus::Kafka(std::sync::Arc::new(partition)))
+            }
+            #[cfg(not(feature = "kafka"))]
+            "kafka" => Err(invalid("publishing to Kafka requires the `kafka` feature")),
+            _ => Err(invalid("expected a nats:// or kafka:// URL")),
+        }
+    }
+}
+
+impl fmt::Debug for EventPublisher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventPublisher")
+            .field("url", &self.url)
+            .field("topic", &self.topic)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(any(feature = "nats", feature = "kafka"))]
+fn publish_error(url: &str, e: impl fmt::Display) -> ValidationError {
+    ValidationError::Publish {
+        url: url.to_string(),
+        reason: e.to_string(),
+    }
+}
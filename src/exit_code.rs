@@ -0,0 +1,144 @@
+// src/exit_code.rs
+use std::{io, process::ExitCode};
+
+use fuel_root_validation::{checkpoint::RecordedFailure, BlockOutcome, ErrorKind, ValidationError};
+
+/// Overall result of a run, reported through the process exit code so that
+/// wrapping scripts can tell failure classes apart.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Status {
+    /// Every block passed every check.
+    #[default]
+    Valid,
+    /// At least one block doesn't match what it commits to.
+    Mismatch,
+    /// The node couldn't be queried.
+    Network,
+    /// Blocks or inputs couldn't be decoded.
+    Parse,
+    /// Endpoints served different data for the same block.
+    Disagreement,
+    /// A local file or database, the config file included, couldn't be read
+    /// or written.
+    Io,
+}
+
+impl Status {
+    pub fn code(self) -> u8 {
+        match self {
+            Status::Valid => 0,
+            Status::Mismatch => 1,
+            Status::Network => 2,
+            Status::Parse => 3,
+            Status::Disagreement => 4,
+            Status::Io => 5,
+        }
+    }
+
+    /// The status of a run that hit both `self` and `other`. A mismatch wins
+    /// over a disagreement between endpoints, then a parse error, then a
+    /// local IO error, then a network error.
+    pub fn worst(self, other: Status) -> Status {
+        let rank = |status| match status {
+            Status::Valid => 0,
+            Status::Network => 1,
+            Status::Io => 2,
+            Status::Parse => 3,
+            Status::Disagreement => 4,
+            Status::Mismatch => 5,
+        };
+        if rank(other) > rank(self) {
+            other
+        } else {
+            self
+        }
+    }
+
+    pub fn of_outcome(outcome: &BlockOutcome) -> Status {
+        match &outcome.result {
+            Ok(report) => report
+                .errors()
+                .iter()
+                .map(Status::of_error)
+                .fold(Status::Valid, Status::worst),
+            Err(e) => Status::of_error(e),
+        }
+    }
+
+    pub fn of_outcomes<'a>(outcomes: impl IntoIterator<Item = &'a BlockOutcome>) -> Status {
+        outcomes
+            .into_iter()
+            .map(Status::of_outcome)
+            .fold(Status::Valid, Status::worst)
+    }
+
+    /// Status of the failures recorded in a checkpoint, including the ones of
+    /// previous runs.
+    pub fn of_recorded(failures: &[RecordedFailure]) -> Status {
+        failures
+            .iter()
+            .flat_map(|failure| &failure.kinds)
+            .map(|&kind| Status::of_kind(kind))
+            .fold(Status::Valid, Status::worst)
+    }
+
+    pub fn of_error(error: &ValidationError) -> Status {
+        Status::of_kind(error.kind())
+    }
+
+    fn of_kind(kind: ErrorKind) -> Status {
+        match kind {
+            ErrorKind::Query => Status::Network,
+            ErrorKind::Parse => Status::Parse,
+            ErrorKind::Mismatch => Status::Mismatch,
+            ErrorKind::Disagreement => Status::Disagreement,
+        }
+    }
+
+    /// Status of a run aborted by `error`. Errors that don't come from the
+    /// validator are local IO errors when caused by one, and invalid
+    /// arguments otherwise.
+    pub fn of_fatal(error: &anyhow::Error) -> Status {
+        if let Some(error) = error.downcast_ref::<ValidationError>() {
+            return if is_local_io(error) {
+                Status::Io
+            } else {
+                Status::of_error(error)
+            };
+        }
+        if error.chain().any(|cause| cause.is::<io::Error>()) {
+            Status::Io
+        } else {
+            Status::Parse
+        }
+    }
+}
+
+/// Whether `error` is about reading or writing a local file or database,
+/// rather than about what was read.
+fn is_local_io(error: &ValidationError) -> bool {
+    match error {
+        ValidationError::ReadEvents { .. }
+        | ValidationError::ReadKnownAnomalies { .. }
+        | ValidationError::ReadSnapshot { .. }
+        | ValidationError::ReadConsensusParameters { .. }
+        | ValidationError::WriteConsensusParameters { .. }
+        | ValidationError::ReadBlocks { .. }
+        | ValidationError::WriteBlocks { .. }
+        | ValidationError::Database { .. }
+        | ValidationError::CheckpointIo { .. }
+        | ValidationError::CacheIo { .. }
+        | ValidationError::CassetteIo { .. }
+        | ValidationError::ReportIo { .. }
+        | ValidationError::Store(_) => true,
+        #[cfg(feature = "postgres")]
+        ValidationError::Postgres(_) => true,
+        _ => false,
+    }
+}
+
+impl From<Status> for ExitCode {
+    fn from(status: Status) -> Self {
+        ExitCode::from(status.code())
+    }
+}
@@ -0,0 +1,156 @@
+// src/failure_report.rs
+//! The blocks a previous run failed, read back from its report so they can
+//! be validated again without scanning the whole range: the document of
+//! `--output json`, the lines of `--output ndjson`, or a checkpoint.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use serde::{de::IgnoredAny, Deserialize};
+
+use crate::error::{Result, ValidationError};
+use crate::registry;
+
+/// A block that failed, with what failed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FailedBlock {
+    pub height: u32,
+    /// Names of the registered checks that failed, empty when the block
+    /// couldn't be validated or the report doesn't say why it failed.
+    pub checks: BTreeSet<String>,
+    /// The cross-checked endpoints that disagreed with the main one, or
+    /// couldn't be queried.
+    pub endpoints: BTreeSet<String>,
+}
+
+/// The failed blocks of a report, in height order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FailureReport {
+    pub blocks: Vec<FailedBlock>,
+}
+
+/// What a report is made of: a whole range, a checkpoint or a single block.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Document {
+    Range {
+        outcomes: Vec<Outcome>,
+    },
+    Checkpoint {
+        failures: Vec<Failure>,
+    },
+    Outcome(Outcome),
+    /// Other lines of `--output ndjson`, e.g. reorgs.
+    Other(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct Outcome {
+    height: u32,
+    passed: bool,
+    report: Option<Report>,
+}
+
+#[derive(Deserialize)]
+struct Report {
+    checks: Vec<ResultLine>,
+}
+
+#[derive(Deserialize)]
+struct ResultLine {
+    check: String,
+    passed: bool,
+    endpoint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Failure {
+    height: u32,
+}
+
+impl Document {
+    fn failures(self) -> Vec<FailedBlock> {
+        let outcomes = match self {
+            Document::Range { outcomes } => outcomes,
+            Document::Outcome(outcome) => vec![outcome],
+            Document::Checkpoint { failures } => {
+                return failures
+                    .into_iter()
+                    .map(|failure| FailedBlock {
+                        height: failure.height,
+                        ..FailedBlock::default()
+                    })
+                    .collect()
+            }
+            Document::Other(_) => vec![],
+        };
+        outcomes
+            .into_iter()
+            .filter(|outcome| !outcome.passed)
+            .map(|outcome| {
+                let mut block = FailedBlock {
+                    height: outcome.height,
+                    ..FailedBlock::default()
+                };
+                let results = outcome.report.map(|report| report.checks);
+                for result in results.iter().flatten().filter(|result| !result.passed) {
+                    block
+                        .checks
+                        .insert(registry::reported_by(&result.check).to_string());
+                    block.endpoints.extend(result.endpoint.clone());
+                }
+                block
+            })
+            .collect()
+    }
+}
+
+impl FailureReport {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).map_err(|source| ValidationError::ReportIo {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&text).map_err(|source| ValidationError::ParseReport {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Reads `text` as a JSON document, or as one JSON document per line.
+    /// Blocks reported more than once are merged.
+    pub fn parse(text: &str) -> serde_json::Result<Self> {
+        let documents = match serde_json::from_str::<Document>(text) {
+            Ok(document) => vec![document],
+            Err(_) => text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect::<serde_json::Result<_>>()?,
+        };
+
+        let mut blocks = BTreeMap::<u32, FailedBlock>::new();
+        for failed in documents.into_iter().flat_map(Document::failures) {
+            let block = blocks.entry(failed.height).or_insert_with(|| FailedBlock {
+                height: failed.height,
+                ..FailedBlock::default()
+            });
+            block.checks.extend(failed.checks);
+            block.endpoints.extend(failed.endpoints);
+        }
+        Ok(Self {
+            blocks: blocks.into_values().collect(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn heights(&self) -> impl Iterator<Item = u32> + '_ {
+        self.blocks.iter().map(|block| block.height)
+    }
+}
@@ -0,0 +1,306 @@
+// src/follow.rs
+use std::{
+    collections::VecDeque,
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use fuel_types::Bytes32;
+use futures::{FutureExt, StreamExt};
+use serde::Serialize;
+
+use crate::{
+    error::{Result, ValidationError},
+    prev_root::PrevRoots,
+    subscription::NewBlocks,
+    validator::{BlockOutcome, BlockValidator},
+};
+
+/// Default delay between two polls of the chain tip.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Delay between two polls of the chain tip while the node pushes new
+/// blocks, in case a notification went missing.
+pub const SUBSCRIBED_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Default number of validated blocks whose ids are remembered to find the
+/// fork point of a reorg.
+pub const DEFAULT_MAX_REORG_DEPTH: u32 = 64;
+
+#[derive(Clone, Debug)]
+pub struct FollowConfig {
+    /// Delay between two polls of the chain tip once the follower caught up.
+    pub poll_interval: Duration,
+    /// Wait for the blocks pushed by the source instead of polling, if it
+    /// can push them.
+    pub subscribe: bool,
+    /// First height to validate. Defaults to the latest block at startup.
+    pub start_height: Option<u32>,
+    /// Number of validated blocks a reorg can replace and still be traced
+    /// back to its fork point.
+    pub max_reorg_depth: u32,
+    /// Number of blocks a block must have on top of it to be validated, so
+    /// fresh blocks that may still be reorged are left alone. 0 validates
+    /// the latest block.
+    pub confirmations: u32,
+}
+
+impl Default for FollowConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            subscribe: true,
+            start_height: None,
+            max_reorg_depth: DEFAULT_MAX_REORG_DEPTH,
+            confirmations: 0,
+        }
+    }
+}
+
+/// What happened while following the chain.
+#[derive(Debug)]
+pub enum FollowEvent {
+    /// A block was validated.
+    Block(BlockOutcome),
+    /// Blocks already validated were replaced by another branch, which is
+    /// validated from the fork point on.
+    Reorg(Reorg),
+}
+
+/// Blocks of the followed chain that the node replaced.
+#[derive(Clone, Debug, Serialize)]
+pub struct Reorg {
+    /// Height of the last block both branches share, `None` if the fork is
+    /// deeper than the remembered blocks.
+    pub fork_height: Option<u32>,
+    /// Heights and ids of the validated blocks that are no longer part of
+    /// the chain, from the lowest up.
+    pub abandoned: Vec<(u32, Bytes32)>,
+}
+
+impl Reorg {
+    /// Number of validated blocks that were replaced.
+    pub fn depth(&self) -> usize {
+        self.abandoned.len()
+    }
+}
+
+/// Validates every new block as it is produced until `shutdown` resolves,
+/// or the validator is [cancelled](BlockValidator::with_cancellation).
+///
+/// The chain tip is polled every `poll_interval`, or whenever the source
+/// pushes a new block when `subscribe` is set and the source supports it.
+/// A subscription that fails falls back to polling. Blocks between the last
+/// validated height and the tip are validated one by one and handed to
+/// `on_event`. Query errors are logged and retried on the next poll so a
+/// flaky endpoint doesn't stop the daemon.
+///
+/// Before validating new blocks, every poll checks that the last validated
+/// block is still part of the chain. If the node replaced it, the follower
+/// reports a [`Reorg`], goes back to the fork point and validates the new
+/// branch.
+pub async fn follow<S, F>(
+    validator: &BlockValidator,
+    config: FollowConfig,
+    shutdown: S,
+    on_event: F,
+) -> Result<()>
+where
+    S: Future<Output = ()>,
+    F: FnMut(FollowEvent),
+{
+    match follow_until(validator, config, shutdown, on_event).await {
+        Err(e) if e.is_cancelled() => Ok(()),
+        result => result,
+    }
+}
+
+/// [`follow`], failing with [`ValidationError::Cancelled`] when the
+/// validator is cancelled.
+async fn follow_until<S, F>(
+    validator: &BlockValidator,
+    config: FollowConfig,
+    shutdown: S,
+    mut on_event: F,
+) -> Result<()>
+where
+    S: Future<Output = ()>,
+    F: FnMut(FollowEvent),
+{
+    tokio::pin!(shutdown);
+
+    let mut next = match config.start_height {
+        Some(height) => height,
+        None => validator
+            .latest_height()
+            .await?
+            .saturating_sub(config.confirmations),
+    };
+
+    let mut prev_roots = PrevRoots::new();
+    if validator.checks_prev_root() {
+        validator
+            .extend_prev_roots(&mut prev_roots, next, |_, _| {})
+            .await?;
+    }
+    let mut validated = VecDeque::new();
+    let mut new_blocks = None;
+    if config.subscribe {
+        new_blocks = tokio::select! {
+            _ = &mut shutdown => return Ok(()),
+            new_blocks = subscribe(validator) => new_blocks,
+        };
+    }
+
+    loop {
+        let polled = tokio::select! {
+            _ = &mut shutdown => return Ok(()),
+            latest = validator.latest_height() => latest,
+        };
+        let polled = match polled {
+            Ok(latest) => tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                reorg = find_reorg(validator, &mut validated) => reorg.map(|reorg| (latest, reorg)),
+            },
+            Err(e) => Err(e),
+        };
+
+        match polled {
+            Ok((latest, reorg)) => {
+                if let Some(reorg) = reorg {
+                    tracing::warn!(
+                        fork_height = reorg.fork_height,
+                        depth = reorg.depth(),
+                        "reorg detected, validating the new branch"
+                    );
+                    let (first, _) = reorg.abandoned[0];
+                    next = first;
+                    if validator.checks_prev_root() {
+                        prev_roots = PrevRoots::new();
+                        validator
+                            .extend_prev_roots(&mut prev_roots, next, |_, _| {})
+                            .await?;
+                    }
+                    on_event(FollowEvent::Reorg(reorg));
+                }
+
+                // Nothing is deep enough until the chain is `confirmations` long.
+                let confirmed = latest.checked_sub(config.confirmations);
+                while confirmed.is_some_and(|confirmed| next <= confirmed) {
+                    let started = Instant::now();
+                    let fetched = tokio::select! {
+                        _ = &mut shutdown => return Ok(()),
+                        block = validator.fetch_block(next) => block,
+                    };
+                    let block = match fetched {
+                        Ok(block) => block,
+                        Err(e) if e.is_cancelled() => return Err(e),
+                        Err(e) => {
+                            tracing::warn!(block_height = next, "failed to fetch block: {e:#}");
+                            break;
+                        }
+                    };
+                    let outcome = validator
+                        .validate_fetched_outcome(
+                            &block,
+                            prev_roots.expected_prev_root(next),
+                            started.elapsed(),
+                        )
+                        .await;
+                    // A block cut short by the shutdown isn't a failure.
+                    if let Err(e @ ValidationError::Cancelled) = outcome.result {
+                        return Err(e);
+                    }
+                    if validator.checks_prev_root() {
+                        prev_roots.push(next, &block.id.clone().into())?;
+                    }
+                    validated.push_back((next, block.id.clone().into()));
+                    if validated.len() > config.max_reorg_depth.max(1) as usize {
+                        validated.pop_front();
+                    }
+                    on_event(FollowEvent::Block(outcome));
+                    next += 1;
+                }
+            }
+            Err(e) if e.is_cancelled() => return Err(e),
+            Err(e) => tracing::warn!("failed to poll the chain: {e:#}"),
+        }
+
+        tokio::select! {
+            _ = &mut shutdown => return Ok(()),
+            _ = validator.cancellation_token().cancelled() => return Ok(()),
+            _ = next_poll(&mut new_blocks, config.poll_interval) => {}
+        }
+    }
+}
+
+/// Subscribes to the new blocks of the validator's source, `None` if they
+/// have to be polled.
+async fn subscribe(validator: &BlockValidator) -> Option<NewBlocks> {
+    match validator.subscribe_new_blocks().await {
+        Ok(Some(new_blocks)) => {
+            tracing::info!("waiting for the blocks pushed by the node");
+            Some(new_blocks)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            tracing::info!("polling the chain tip, the node doesn't push new blocks: {e:#}");
+            None
+        }
+    }
+}
+
+/// Waits until the tip is to be polled again: the next pushed block, or
+/// `poll_interval` without a subscription.
+async fn next_poll(new_blocks: &mut Option<NewBlocks>, poll_interval: Duration) {
+    let Some(stream) = new_blocks else {
+        return tokio::time::sleep(poll_interval).await;
+    };
+    let Ok(pushed) = tokio::time::timeout(SUBSCRIBED_POLL_INTERVAL, stream.next()).await else {
+        return;
+    };
+    match pushed {
+        // Blocks pushed while the previous ones were validated are fetched
+        // by the same poll.
+        Some(Ok(())) => while let Some(Some(Ok(()))) = stream.next().now_or_never() {},
+        Some(Err(e)) => {
+            tracing::warn!("block subscription failed, polling the chain tip instead: {e:#}");
+            *new_blocks = None;
+        }
+        None => {
+            tracing::warn!("block subscription ended, polling the chain tip instead");
+            *new_blocks = None;
+        }
+    }
+}
+
+/// Compares the remembered blocks with the ones the node serves now, from
+/// the last validated block down, and forgets the ones it replaced.
+async fn find_reorg(
+    validator: &BlockValidator,
+    validated: &mut VecDeque<(u32, Bytes32)>,
+) -> Result<Option<Reorg>> {
+    let mut abandoned = vec![];
+    while let Some(&(height, id)) = validated.back() {
+        // A block the node no longer has was replaced by a shorter branch.
+        let current = match validator.fetch_header(height).await {
+            Ok(header) => Some(Bytes32::from(header.id)),
+            Err(ValidationError::BlockNotFound { .. }) => None,
+            Err(e) => return Err(e),
+        };
+        if current == Some(id) {
+            break;
+        }
+        abandoned.push((height, id));
+        validated.pop_back();
+    }
+
+    if abandoned.is_empty() {
+        return Ok(None);
+    }
+    abandoned.reverse();
+    Ok(Some(Reorg {
+        fork_height: validated.back().map(|&(height, _)| height),
+        abandoned,
+    }))
+}
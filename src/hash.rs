@@ -0,0 +1,150 @@
+// src/hash.rs
+//! The SHA-256 implementation the Merkle roots are hashed with, the main
+//! CPU cost of range scans on fast networks.
+//!
+//! [`Sha2`], the hasher of fuel-crypto, is the default. It already uses the
+//! SHA extensions of x86 and ARM CPUs when it detects them at runtime. With
+//! the `ring` feature, [`Ring`] hashes with the assembly of ring instead,
+//! tuned for each CPU generation. The backend is chosen once per process
+//! with [`set_backend`], before the first root is computed; [`CrossCheck`]
+//! hashes with both a backend and [`Sha2`] and reports where they differ.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+};
+
+use fuel_crypto::Hasher;
+use fuel_types::Bytes32;
+
+/// A SHA-256 implementation.
+pub trait HashBackend: fmt::Debug + Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// SHA-256 of the concatenation of `parts`.
+    fn sha256(&self, parts: &[&[u8]]) -> Bytes32;
+}
+
+/// The SHA-256 of fuel-crypto, which the roots committed to are computed
+/// with by nodes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sha2;
+
+impl HashBackend for Sha2 {
+    fn name(&self) -> &'static str {
+        "sha2"
+    }
+
+    fn sha256(&self, parts: &[&[u8]]) -> Bytes32 {
+        parts
+            .iter()
+            .fold(Hasher::default(), |hasher, part| hasher.chain(part))
+            .finalize()
+    }
+}
+
+/// The SHA-256 of ring.
+#[cfg(feature = "ring")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ring;
+
+#[cfg(feature = "ring")]
+impl HashBackend for Ring {
+    fn name(&self) -> &'static str {
+        "ring"
+    }
+
+    fn sha256(&self, parts: &[&[u8]]) -> Bytes32 {
+        let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+        for part in parts {
+            context.update(part);
+        }
+        let digest = context.finish();
+        Bytes32::try_from(digest.as_ref()).expect("SHA-256 digests are 32 bytes")
+    }
+}
+
+/// Names of the backends of this build, the default first.
+#[cfg(not(feature = "ring"))]
+pub const BACKENDS: &[&str] = &["sha2"];
+#[cfg(feature = "ring")]
+pub const BACKENDS: &[&str] = &["sha2", "ring"];
+
+/// The backend called `name`, if this build has it.
+pub fn named(name: &str) -> Option<&'static dyn HashBackend> {
+    match name {
+        "sha2" => Some(&Sha2),
+        #[cfg(feature = "ring")]
+        "ring" => Some(&Ring),
+        _ => None,
+    }
+}
+
+/// Every backend of this build.
+pub fn backends() -> impl Iterator<Item = &'static dyn HashBackend> {
+    BACKENDS.iter().filter_map(|name| named(name))
+}
+
+/// Hashes with a backend and with [`Sha2`], to check the backend before
+/// trusting it. Where they differ, the error is logged and the hash of
+/// [`Sha2`] is used, so roots are never reported wrong because of the
+/// backend.
+#[derive(Debug)]
+pub struct CrossCheck {
+    backend: &'static dyn HashBackend,
+    mismatches: AtomicU64,
+}
+
+impl CrossCheck {
+    pub fn new(backend: &'static dyn HashBackend) -> Self {
+        Self {
+            backend,
+            mismatches: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of hashes the backend got wrong so far.
+    pub fn mismatches(&self) -> u64 {
+        self.mismatches.load(Ordering::Relaxed)
+    }
+}
+
+impl HashBackend for CrossCheck {
+    fn name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    fn sha256(&self, parts: &[&[u8]]) -> Bytes32 {
+        let expected = Sha2.sha256(parts);
+        let actual = self.backend.sha256(parts);
+        if actual != expected {
+            // Only the first few, a broken backend gets every hash wrong.
+            if self.mismatches.fetch_add(1, Ordering::Relaxed) < 10 {
+                tracing::error!(
+                    backend = self.backend.name(),
+                    len = parts.iter().map(|part| part.len()).sum::<usize>(),
+                    %expected,
+                    %actual,
+                    "hash backend disagrees with sha2"
+                );
+            }
+        }
+        expected
+    }
+}
+
+static BACKEND: OnceLock<&'static dyn HashBackend> = OnceLock::new();
+
+/// The backend of the process, [`Sha2`] unless another one was set.
+pub fn backend() -> &'static dyn HashBackend {
+    *BACKEND.get_or_init(|| &Sha2)
+}
+
+/// Hashes the roots of the process with `backend`. Returns false, keeping
+/// the current backend, once a root has been computed or a backend set.
+pub fn set_backend(backend: &'static dyn HashBackend) -> bool {
+    BACKEND.set(backend).is_ok()
+}
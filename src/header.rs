@@ -0,0 +1,71 @@
+// src/header.rs
+use fuel_crypto::Hasher;
+use fuel_types::Bytes32;
+
+use crate::query::{Header, HeaderVersion};
+
+/// Hash of the application header fields, in the order defined by the spec
+/// for the version of the header. V2 headers also commit to `tx_ids`, the
+/// ids of the transactions of the block in order. `None` for versions the
+/// validator doesn't know.
+pub fn application_hash(header: &Header, tx_ids: &[Bytes32]) -> Option<Bytes32> {
+    application_header_bytes(header, tx_ids).map(Hasher::hash)
+}
+
+/// The bytes [`application_hash`] hashes: the application header fields,
+/// big-endian.
+pub fn application_header_bytes(header: &Header, tx_ids: &[Bytes32]) -> Option<Vec<u8>> {
+    let mut bytes = vec![];
+
+    bytes.extend(header.da_height.0.to_be_bytes());
+    bytes.extend(header.consensus_parameters_version.0.to_be_bytes());
+    bytes.extend(header.state_transition_bytecode_version.0.to_be_bytes());
+
+    bytes.extend(header.transactions_count.0.to_be_bytes());
+    bytes.extend(header.message_receipt_count.0.to_be_bytes());
+    bytes.extend(*Bytes32::from(header.transactions_root.clone()));
+    bytes.extend(*Bytes32::from(header.message_outbox_root.clone()));
+    bytes.extend(*Bytes32::from(header.event_inbox_root.clone()));
+
+    match header.version {
+        HeaderVersion::V1 => {}
+        HeaderVersion::V2 => bytes.extend(*tx_id_commitment(tx_ids)),
+        HeaderVersion::Unknown => return None,
+    }
+
+    Some(bytes)
+}
+
+/// Commitment of V2 headers to the ids of the transactions of the block: the
+/// hash of the ids in order.
+pub fn tx_id_commitment(tx_ids: &[Bytes32]) -> Bytes32 {
+    let mut hasher = Hasher::default();
+    for tx_id in tx_ids {
+        hasher.input(tx_id.as_ref());
+    }
+    hasher.digest()
+}
+
+/// Block id, the hash of the consensus header fields over the recomputed
+/// application hash. The consensus header is the same in every version.
+pub fn block_id(header: &Header, tx_ids: &[Bytes32]) -> Option<Bytes32> {
+    let application_hash = application_hash(header, tx_ids)?;
+    Some(Hasher::hash(consensus_header_bytes(
+        header,
+        &application_hash,
+    )))
+}
+
+/// The bytes [`block_id`] hashes: the consensus header fields, big-endian,
+/// followed by `application_hash`.
+pub fn consensus_header_bytes(header: &Header, application_hash: &Bytes32) -> Vec<u8> {
+    let mut bytes = vec![];
+
+    bytes.extend(*Bytes32::from(header.prev_root.clone()));
+    bytes.extend(header.height.0.to_be_bytes());
+    bytes.extend(header.time.0 .0.to_be_bytes());
+
+    bytes.extend(**application_hash);
+
+    bytes
+}
@@ -0,0 +1,140 @@
+// src/health.rs
+//! Liveness and readiness of the validator, for the probes of container
+//! orchestrators like Kubernetes.
+//!
+//! The process is alive as long as it answers `/healthz`. It is ready once
+//! the node answered the latest query of its head and, when a maximum lag
+//! is set, validation is at most that many blocks behind the head.
+//! `/readyz` answers with the [`Readiness`] of the validator as JSON.
+
+use std::sync::{Mutex, MutexGuard};
+
+use hyper::StatusCode;
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::validator::{BlockOutcome, BlockValidator};
+
+/// Default number of blocks validation may lag behind the head and still be
+/// ready.
+pub const DEFAULT_MAX_LAG: u32 = 10;
+
+/// What the readiness of a validator is decided from, updated as blocks are
+/// validated and the head of the node is queried.
+#[derive(Debug, Default)]
+pub struct Health {
+    max_lag: Option<u32>,
+    state: Mutex<HealthState>,
+}
+
+#[derive(Debug, Default)]
+struct HealthState {
+    head: Option<u32>,
+    validated: Option<u32>,
+    /// Why the latest query of the head failed, if it did.
+    node_error: Option<String>,
+}
+
+/// Whether the validator is ready, as `/readyz` reports it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Readiness {
+    pub ready: bool,
+    /// Height of the latest block validated.
+    pub validated_height: Option<u32>,
+    /// Height of the latest block of the node.
+    pub head_height: Option<u32>,
+    /// Blocks between the head and the latest block validated.
+    pub lag: Option<u32>,
+    pub max_lag: Option<u32>,
+    /// Why the validator isn't ready.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl Health {
+    /// Health that is ready whenever the node answers, however far behind
+    /// validation is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of blocks validation may lag behind the head and
+    /// still be ready. Until a block is validated, the validator isn't.
+    pub fn with_max_lag(mut self, blocks: u32) -> Self {
+        self.max_lag = Some(blocks);
+        self
+    }
+
+    pub fn max_lag(&self) -> Option<u32> {
+        self.max_lag
+    }
+
+    /// Accounts for a validated block. After a reorg, the validated height
+    /// goes back to the fork point.
+    pub fn observe(&self, outcome: &BlockOutcome) {
+        let mut state = self.lock();
+        state.validated = Some(outcome.height);
+        state.head = state.head.max(Some(outcome.height));
+    }
+
+    /// Accounts for a query of the head of the node.
+    pub fn observe_head(&self, head: &Result<u32>) {
+        let mut state = self.lock();
+        match head {
+            Ok(head) => {
+                state.head = Some(*head);
+                state.node_error = None;
+            }
+            Err(e) => state.node_error = Some(format!("{e:#}")),
+        }
+    }
+
+    /// Queries the head of the source of `validator`.
+    pub async fn probe(&self, validator: &BlockValidator) {
+        self.observe_head(&validator.latest_height().await);
+    }
+
+    pub fn readiness(&self) -> Readiness {
+        let state = self.lock();
+        let lag = state
+            .head
+            .zip(state.validated)
+            .map(|(head, validated)| head.saturating_sub(validated));
+        let reason = if let Some(e) = &state.node_error {
+            Some(format!("the node can't be reached: {e}"))
+        } else if state.head.is_none() {
+            Some("the node hasn't been reached yet".to_string())
+        } else {
+            match (self.max_lag, lag) {
+                (Some(_), None) => Some("no block has been validated yet".to_string()),
+                (Some(max_lag), Some(lag)) if lag > max_lag => Some(format!(
+                    "validation lags {lag} blocks behind the head, more than {max_lag}"
+                )),
+                _ => None,
+            }
+        };
+        Readiness {
+            ready: reason.is_none(),
+            validated_height: state.validated,
+            head_height: state.head,
+            lag,
+            max_lag: self.max_lag,
+            reason,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HealthState> {
+        self.state.lock().expect("the lock isn't poisoned")
+    }
+}
+
+impl Readiness {
+    /// `200 OK` when ready, `503 Service Unavailable` otherwise.
+    pub fn status(&self) -> StatusCode {
+        if self.ready {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
+}
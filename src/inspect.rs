@@ -0,0 +1,154 @@
+// src/inspect.rs
+//! The transactions of a block decoded from their raw payloads, along with
+//! their receipts, for investigating why the block failed validation.
+
+use std::fmt;
+
+use fuel_core_client::client::schema::tx::TransactionStatus;
+use fuel_tx::{
+    field::{Inputs, Outputs, Script, ScriptData, Witnesses},
+    Receipt, Transaction,
+};
+use fuel_types::Bytes32;
+use serde::Serialize;
+
+use crate::checks::{parse_receipt, parse_transaction};
+use crate::query::FullBlock;
+
+/// One transaction of a block, decoded.
+#[derive(Clone, Debug, Serialize)]
+pub struct InspectedTransaction {
+    pub index: usize,
+    pub id: Bytes32,
+    /// The transaction, `None` if its payload doesn't decode.
+    pub transaction: Option<Transaction>,
+    /// Type of the status of the transaction, `None` without a status.
+    pub status: Option<&'static str>,
+    /// Why the transaction failed, for failed transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The receipts that decode, in order.
+    pub receipts: Vec<Receipt>,
+    /// Why the payload or receipts don't decode.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// Decodes every transaction of `block` and its receipts, the way the
+/// checks do. Transactions and receipts that don't decode are reported with
+/// the error instead of failing the whole block.
+pub fn inspect(block: &FullBlock) -> Vec<InspectedTransaction> {
+    block
+        .transactions
+        .iter()
+        .enumerate()
+        .map(|(index, tx)| {
+            let mut errors = vec![];
+            let transaction = parse_transaction(tx)
+                .map_err(|e| errors.push(e.to_string()))
+                .ok();
+            let (status, reason, served) = match &tx.status {
+                Some(TransactionStatus::SubmittedStatus(_)) => (Some("submitted"), None, &[][..]),
+                Some(TransactionStatus::SuccessStatus(status)) => {
+                    (Some("success"), None, status.receipts.as_slice())
+                }
+                Some(TransactionStatus::SqueezedOutStatus(status)) => {
+                    (Some("squeezed_out"), Some(status.reason.clone()), &[][..])
+                }
+                Some(TransactionStatus::FailureStatus(status)) => (
+                    Some("failure"),
+                    Some(status.reason.clone()),
+                    status.receipts.as_slice(),
+                ),
+                Some(TransactionStatus::Unknown) => (Some("unknown"), None, &[][..]),
+                None => (None, None, &[][..]),
+            };
+            let receipts = served
+                .iter()
+                .enumerate()
+                .filter_map(|(i, receipt)| {
+                    parse_receipt(tx, receipt)
+                        .map_err(|e| errors.push(format!("receipt #{i}: {e}")))
+                        .ok()
+                })
+                .collect();
+            InspectedTransaction {
+                index,
+                id: Bytes32::from(tx.id.clone()),
+                transaction,
+                status,
+                reason,
+                receipts,
+                errors,
+            }
+        })
+        .collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn write_list<T: fmt::Debug>(f: &mut fmt::Formatter<'_>, name: &str, items: &[T]) -> fmt::Result {
+    writeln!(f, "  {name}: {}", items.len())?;
+    for (index, item) in items.iter().enumerate() {
+        writeln!(f, "    #{index} {item:?}")?;
+    }
+    Ok(())
+}
+
+fn write_chargeable<T: Inputs + Outputs + Witnesses>(
+    f: &mut fmt::Formatter<'_>,
+    tx: &T,
+) -> fmt::Result {
+    write_list(f, "inputs", tx.inputs())?;
+    write_list(f, "outputs", tx.outputs())?;
+    writeln!(f, "  witnesses: {}", tx.witnesses().len())?;
+    for (index, witness) in tx.witnesses().iter().enumerate() {
+        writeln!(f, "    #{index} {}", hex(witness.as_vec()))?;
+    }
+    Ok(())
+}
+
+/// The type and status of the transaction, then its fields and receipts, one
+/// per line.
+impl fmt::Display for InspectedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match &self.transaction {
+            Some(Transaction::Script(_)) => "script",
+            Some(Transaction::Create(_)) => "create",
+            Some(Transaction::Mint(_)) => "mint",
+            Some(Transaction::Upgrade(_)) => "upgrade",
+            Some(Transaction::Upload(_)) => "upload",
+            Some(Transaction::Blob(_)) => "blob",
+            None => "undecodable",
+        };
+        write!(f, "#{} {:#x} {kind}", self.index, self.id)?;
+        if let Some(status) = self.status {
+            write!(f, ", {status}")?;
+        }
+        if let Some(reason) = &self.reason {
+            write!(f, ": {reason}")?;
+        }
+        writeln!(f)?;
+
+        match &self.transaction {
+            Some(Transaction::Script(tx)) => {
+                write_chargeable(f, tx)?;
+                writeln!(f, "  script: {}", hex(tx.script()))?;
+                writeln!(f, "  script data: {}", hex(tx.script_data()))?;
+            }
+            Some(Transaction::Create(tx)) => write_chargeable(f, tx)?,
+            Some(Transaction::Upgrade(tx)) => write_chargeable(f, tx)?,
+            Some(Transaction::Upload(tx)) => write_chargeable(f, tx)?,
+            Some(Transaction::Blob(tx)) => write_chargeable(f, tx)?,
+            Some(Transaction::Mint(tx)) => writeln!(f, "  {tx:?}")?,
+            None => {}
+        }
+        write_list(f, "receipts", &self.receipts)?;
+        for error in &self.errors {
+            writeln!(f, "  error: {error}")?;
+        }
+        Ok(())
+    }
+}
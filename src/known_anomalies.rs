@@ -0,0 +1,130 @@
+// src/known_anomalies.rs
+//! Failures known to be historical quirks of a chain rather than new
+//! problems, e.g. blocks produced by a node version with a bug since fixed.
+//!
+//! Failed checks matching a [`KnownAnomaly`] are
+//! [acknowledged](crate::CheckResult::acknowledged): they are still reported,
+//! apart from the other failures, but don't fail their block.
+
+use std::{collections::HashMap, fs, path::Path, str::FromStr};
+
+use fuel_types::Bytes32;
+use serde::Deserialize;
+
+use crate::error::{Result, ValidationError};
+use crate::report::{BlockReport, CheckResult};
+
+/// A failure known in advance, by the block it is in, the transaction it is
+/// about, or both.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KnownAnomaly {
+    pub height: Option<u32>,
+    pub tx_id: Option<Bytes32>,
+    /// Name of the check whose failures are acknowledged. Every check is
+    /// when `None`.
+    pub check: Option<String>,
+    /// Why the failure is known, reported along with it.
+    pub note: String,
+}
+
+impl KnownAnomaly {
+    /// Whether `result`, a failed check of the block at `height`, is this
+    /// anomaly.
+    pub fn matches(&self, height: u32, result: &CheckResult) -> bool {
+        self.height.is_none_or(|known| known == height)
+            && self.tx_id.is_none_or(|known| {
+                result
+                    .tx_id
+                    .as_deref()
+                    .and_then(|id| Bytes32::from_str(id).ok())
+                    == Some(known)
+            })
+            && self
+                .check
+                .as_deref()
+                .is_none_or(|check| check == result.check.name())
+    }
+}
+
+/// The known anomalies of a chain, looked up by height and transaction id.
+#[derive(Clone, Debug, Default)]
+pub struct KnownAnomalies {
+    anomalies: Vec<KnownAnomaly>,
+    /// Indices of the anomalies with a height but no transaction id.
+    by_height: HashMap<u32, Vec<usize>>,
+    /// Indices of the anomalies with a transaction id.
+    by_tx_id: HashMap<Bytes32, Vec<usize>>,
+}
+
+impl KnownAnomalies {
+    /// Fails if an anomaly has neither a height nor a transaction id, which
+    /// would acknowledge every failure.
+    pub fn new(anomalies: Vec<KnownAnomaly>) -> Result<Self> {
+        let mut known = Self::default();
+        for (index, anomaly) in anomalies.iter().enumerate() {
+            match (anomaly.tx_id, anomaly.height) {
+                (Some(tx_id), _) => known.by_tx_id.entry(tx_id).or_default().push(index),
+                (None, Some(height)) => known.by_height.entry(height).or_default().push(index),
+                (None, None) => {
+                    return Err(ValidationError::InvalidKnownAnomaly {
+                        index,
+                        reason: "expected a `height`, a `tx_id` or both".to_string(),
+                    })
+                }
+            }
+        }
+        known.anomalies = anomalies;
+        Ok(known)
+    }
+
+    /// Loads anomalies from a JSON array of [`KnownAnomaly`]s.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json =
+            fs::read_to_string(path).map_err(|source| ValidationError::ReadKnownAnomalies {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        let anomalies =
+            serde_json::from_str(&json).map_err(|source| ValidationError::ParseKnownAnomalies {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        Self::new(anomalies)
+    }
+
+    pub fn len(&self) -> usize {
+        self.anomalies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+
+    /// The anomaly `result`, a failed check of the block at `height`, is.
+    pub fn find(&self, height: u32, result: &CheckResult) -> Option<&KnownAnomaly> {
+        let tx_id = result
+            .tx_id
+            .as_deref()
+            .and_then(|id| Bytes32::from_str(id).ok());
+        let of_tx = tx_id.and_then(|tx_id| self.by_tx_id.get(&tx_id));
+        let of_height = self.by_height.get(&height);
+        of_tx
+            .into_iter()
+            .chain(of_height)
+            .flatten()
+            .map(|&index| &self.anomalies[index])
+            .find(|anomaly| anomaly.matches(height, result))
+    }
+
+    /// Acknowledges the failed checks of `report` that are known anomalies.
+    pub fn acknowledge(&self, report: &mut BlockReport) {
+        let height = report.height;
+        for result in report.checks.iter_mut().filter(|result| !result.passed) {
+            if let Some(anomaly) = self.find(height, result) {
+                result.acknowledged = Some(anomaly.note.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,217 @@
+// src/l1.rs
+//! Cross-checks against the Fuel chain state committed on Ethereum by the
+//! `FuelChainState` contract of the bridge.
+//!
+//! The block committer posts the id of one Fuel block per commit interval of
+//! `BLOCKS_PER_COMMIT_INTERVAL` blocks. A node serving a chain different from
+//! the one committed on L1, or blocks whose DA height is ahead of L1, can't
+//! be trusted by the bridge.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use fuel_types::Bytes32;
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+use tokio::sync::OnceCell;
+
+use crate::error::{Result, ValidationError};
+
+/// Timeout of a single L1 RPC request.
+pub const L1_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Address of an Ethereum contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EthAddress(pub [u8; 20]);
+
+impl FromStr for EthAddress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        let bytes = hex::decode(digits).map_err(|e| format!("invalid address `{s}`: {e}"))?;
+        let address = bytes
+            .try_into()
+            .map_err(|_| format!("invalid address `{s}`: expected 20 bytes"))?;
+        Ok(Self(address))
+    }
+}
+
+impl fmt::Display for EthAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+/// The `FuelChainState` contract, read over an Ethereum JSON-RPC endpoint.
+///
+/// The commit interval and the hashes of past commits are cached, so
+/// validating many blocks of the same interval costs a single call.
+#[derive(Clone, Debug)]
+pub struct L1ChainState {
+    url: String,
+    contract: EthAddress,
+    client: reqwest::Client,
+    commit_interval: Arc<OnceCell<u64>>,
+    commits: Arc<Mutex<HashMap<u64, Bytes32>>>,
+    latest_block: Arc<Mutex<u64>>,
+    /// Committed blocks the node was found to serve.
+    served: Arc<Mutex<HashSet<Bytes32>>>,
+}
+
+impl L1ChainState {
+    pub fn new(url: impl Into<String>, contract: EthAddress) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(L1_RPC_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            url: url.into(),
+            contract,
+            client,
+            commit_interval: Arc::default(),
+            commits: Arc::default(),
+            latest_block: Arc::default(),
+            served: Arc::default(),
+        }
+    }
+
+    pub fn contract(&self) -> EthAddress {
+        self.contract
+    }
+
+    /// Number of the latest L1 block.
+    pub async fn latest_block_number(&self) -> Result<u64> {
+        let result = self.request("eth_blockNumber", json!([])).await?;
+        let number = result
+            .as_str()
+            .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+            .ok_or_else(|| l1_error("eth_blockNumber", format!("invalid result {result}")))?;
+
+        *self.latest_block.lock().expect("the lock isn't poisoned") = number;
+        Ok(number)
+    }
+
+    /// Number of the latest L1 block, only queried again when the last known
+    /// one is below `da_height`.
+    pub async fn latest_block_at_least(&self, da_height: u64) -> Result<u64> {
+        let known = *self.latest_block.lock().expect("the lock isn't poisoned");
+        if known >= da_height {
+            return Ok(known);
+        }
+        self.latest_block_number().await
+    }
+
+    /// Number of Fuel blocks per commit interval.
+    pub async fn commit_interval(&self) -> Result<u64> {
+        self.commit_interval
+            .get_or_try_init(|| async {
+                let word = self.call("BLOCKS_PER_COMMIT_INTERVAL()", &[]).await?;
+                let interval = u64::from_be_bytes(word[24..].try_into().expect("8 bytes"));
+                if interval == 0 || word[..24].iter().any(|&byte| byte != 0) {
+                    return Err(l1_error(
+                        "BLOCKS_PER_COMMIT_INTERVAL()",
+                        format!("unexpected interval 0x{}", hex::encode(word)),
+                    ));
+                }
+                Ok(interval)
+            })
+            .await
+            .copied()
+    }
+
+    /// Id of the Fuel block committed for the commit interval `commit_height`,
+    /// zero if nothing was committed for it yet.
+    ///
+    /// The contract keeps a ring buffer of commits, so the commit of an old
+    /// interval may have been overwritten by a more recent one.
+    pub async fn block_hash_at_commit(&self, commit_height: u64) -> Result<Bytes32> {
+        if let Some(hash) = self.cached_commit(commit_height) {
+            return Ok(hash);
+        }
+
+        let mut argument = [0u8; 32];
+        argument[24..].copy_from_slice(&commit_height.to_be_bytes());
+        let hash = Bytes32::new(self.call("blockHashAtCommit(uint256)", &argument).await?);
+        // Intervals without a commit yet will get one.
+        if hash != Bytes32::zeroed() {
+            self.commits
+                .lock()
+                .expect("the lock isn't poisoned")
+                .insert(commit_height, hash);
+        }
+        Ok(hash)
+    }
+
+    pub(crate) fn is_served(&self, block_id: &Bytes32) -> bool {
+        self.served
+            .lock()
+            .expect("the lock isn't poisoned")
+            .contains(block_id)
+    }
+
+    pub(crate) fn mark_served(&self, block_id: Bytes32) {
+        self.served
+            .lock()
+            .expect("the lock isn't poisoned")
+            .insert(block_id);
+    }
+
+    fn cached_commit(&self, commit_height: u64) -> Option<Bytes32> {
+        self.commits
+            .lock()
+            .expect("the lock isn't poisoned")
+            .get(&commit_height)
+            .copied()
+    }
+
+    /// Calls the view function `signature` of the contract with the
+    /// ABI-encoded `arguments` and returns the first word of the result.
+    async fn call(&self, signature: &'static str, arguments: &[u8]) -> Result<[u8; 32]> {
+        let selector = &Keccak256::digest(signature.as_bytes())[..4];
+        let data = format!("0x{}{}", hex::encode(selector), hex::encode(arguments));
+        let params = json!([{ "to": self.contract.to_string(), "data": data }, "latest"]);
+
+        let result = self.request("eth_call", params).await?;
+        let bytes = result
+            .as_str()
+            .and_then(|hex| hex::decode(hex.trim_start_matches("0x")).ok())
+            .ok_or_else(|| l1_error(signature, format!("invalid result {result}")))?;
+        bytes
+            .get(..32)
+            .and_then(|word| word.try_into().ok())
+            .ok_or_else(|| l1_error(signature, format!("short result {result}")))
+    }
+
+    async fn request(&self, method: &'static str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response: Value = self
+            .client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| l1_error(method, e))?
+            .json()
+            .await
+            .map_err(|e| l1_error(method, e))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(l1_error(method, error));
+        }
+        Ok(response["result"].take())
+    }
+}
+
+fn l1_error(call: &'static str, reason: impl fmt::Display) -> ValidationError {
+    ValidationError::L1Query {
+        call,
+        reason: reason.to_string(),
+    }
+}
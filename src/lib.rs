@@ -0,0 +1,144 @@
+//! Independent validation of the Merkle roots committed to in Fuel block headers.
+//!
+//! [`BlockValidator`] fetches blocks over GraphQL and recomputes their block
+//! id, transactions root, message outbox root and per-transaction receipts
+//! roots, so the node serving them doesn't have to be trusted. Given the PoA
+//! authority of the network, it also checks who produced each block.
+//! [`MerkleProof`] proves that a transaction is part of a block, for light
+//! clients that need more than pass/fail.
+//!
+//! Every check ends up as a [`CheckResult`] in the block's [`BlockReport`],
+//! which can be serialized with serde.
+
+#[cfg(feature = "native")]
+pub mod alert;
+#[cfg(feature = "native")]
+pub mod bisect;
+#[cfg(feature = "native")]
+pub mod cache;
+#[cfg(feature = "native")]
+pub mod cassette;
+#[cfg(feature = "native")]
+pub mod checkpoint;
+pub mod checks;
+pub mod consensus;
+pub mod consensus_parameters;
+pub mod corrupt;
+#[cfg(feature = "native")]
+pub mod dashboard;
+#[cfg(feature = "native")]
+pub mod diff;
+pub mod dump;
+pub mod error;
+pub mod event_inbox;
+#[cfg(feature = "native")]
+pub mod events;
+#[cfg(feature = "native")]
+pub mod failure_report;
+#[cfg(feature = "native")]
+pub mod follow;
+pub mod hash;
+pub mod header;
+#[cfg(feature = "native")]
+pub mod health;
+pub mod inspect;
+pub mod known_anomalies;
+#[cfg(feature = "native")]
+pub mod l1;
+#[cfg(feature = "native")]
+pub mod memory;
+pub mod merkle;
+#[cfg(feature = "native")]
+pub mod metrics;
+pub mod network;
+pub mod prev_root;
+pub mod proof;
+pub mod proof_bundle;
+pub mod query;
+#[cfg(feature = "native")]
+pub mod rate_limit;
+pub mod receipt_diff;
+pub mod receipt_fallback;
+pub mod registry;
+pub mod report;
+#[cfg(feature = "native")]
+pub mod retry;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
+#[cfg(feature = "native")]
+pub mod sample;
+pub mod self_test;
+#[cfg(feature = "native")]
+pub mod server;
+#[cfg(feature = "native")]
+pub mod snapshot;
+#[cfg(feature = "native")]
+pub mod source;
+#[cfg(feature = "native")]
+pub mod store;
+#[cfg(feature = "native")]
+pub mod subscription;
+pub mod target;
+pub mod timing;
+#[cfg(feature = "native")]
+pub mod validator;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "native")]
+pub use alert::{Alert, Webhook, WebhookFormat};
+#[cfg(feature = "native")]
+pub use cache::BlockCache;
+#[cfg(feature = "native")]
+pub use cassette::Cassette;
+#[cfg(feature = "native")]
+pub use checkpoint::Checkpoint;
+pub use consensus::PoaAuthority;
+pub use consensus_parameters::ChainParameters;
+pub use corrupt::Corruption;
+#[cfg(feature = "native")]
+pub use dashboard::Dashboard;
+#[cfg(feature = "native")]
+pub use diff::{BlockDiff, FieldDifference};
+pub use dump::BlockDump;
+pub use error::{ErrorKind, ValidationError};
+pub use event_inbox::{EventInbox, RelayedEvent};
+#[cfg(feature = "native")]
+pub use events::EventPublisher;
+#[cfg(feature = "native")]
+pub use failure_report::{FailedBlock, FailureReport};
+#[cfg(feature = "native")]
+pub use follow::{follow, FollowConfig, FollowEvent, Reorg};
+#[cfg(feature = "native")]
+pub use health::{Health, Readiness};
+pub use known_anomalies::{KnownAnomalies, KnownAnomaly};
+#[cfg(feature = "native")]
+pub use l1::{EthAddress, L1ChainState};
+#[cfg(feature = "native")]
+pub use memory::MemoryBudget;
+#[cfg(feature = "native")]
+pub use metrics::Metrics;
+pub use network::{Network, NetworkProfile, NetworkUpgrade};
+pub use prev_root::PrevRoots;
+pub use proof::MerkleProof;
+pub use proof_bundle::{ProofBundle, ReceiptProof};
+pub use query::{FullBlock, LightBlock};
+#[cfg(feature = "native")]
+pub use rate_limit::RateLimit;
+pub use receipt_diff::ReceiptDiff;
+pub use registry::{BlockCheck, CheckContext, CheckRegistry};
+pub use report::{BlockReport, Check, CheckResult, Severity};
+#[cfg(feature = "native")]
+pub use retry::RetryPolicy;
+#[cfg(feature = "rocksdb")]
+pub use rocksdb::RocksDbSource;
+#[cfg(feature = "native")]
+pub use snapshot::{Snapshot, SnapshotComparison};
+#[cfg(feature = "native")]
+pub use source::{BlockSource, FileSource, GraphqlSource, MemorySource};
+#[cfg(feature = "native")]
+pub use store::ResultStore;
+pub use target::BlockTarget;
+pub use timing::{BlockTiming, TimingSummary};
+#[cfg(feature = "native")]
+pub use validator::{BlockOutcome, BlockValidator, HeadProbe, RangeValidation};
@@ -0,0 +1,36 @@
+// src/logging.rs
+use std::io::{self, IsTerminal};
+
+use clap::ValueEnum;
+use tracing_subscriber::{fmt::writer::BoxMakeWriter, EnvFilter};
+
+/// How logs are written to stderr.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human readable lines.
+    #[default]
+    Text,
+    /// One JSON object per event, with the fields of the event and of the
+    /// block and transaction spans it happened in.
+    Json,
+}
+
+/// Installs the global subscriber. The level is read from `RUST_LOG`, `info`
+/// by default. Logs are dropped when the `dashboard` is drawn on the
+/// terminal stderr would write to.
+pub fn init(format: LogFormat, dashboard: bool) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let terminal = io::stderr().is_terminal();
+    let logs = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(if dashboard && terminal {
+            BoxMakeWriter::new(io::sink)
+        } else {
+            BoxMakeWriter::new(io::stderr)
+        })
+        .with_ansi(terminal);
+    match format {
+        LogFormat::Text => logs.init(),
+        LogFormat::Json => logs.json().flatten_event(true).init(),
+    }
+}
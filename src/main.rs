@@ -1,908 +1,1690 @@
 // src/main.rs
-use anyhow::{anyhow, Context, Result};
-use cynic::QueryBuilder;
-use fuel_core_client::client::{
-    schema::{
-        block::{Consensus, Header},
-        schema,
-        tx::TransactionStatus,
-        BlockId, HexString, TransactionId, U32,
-    },
-    FuelClient,
+mod cli;
+mod config;
+mod exit_code;
+mod logging;
+mod output;
+mod repro_heights;
+mod signals;
+#[cfg(feature = "tui")]
+mod tui;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, future, io,
+    net::SocketAddr,
+    path::Path,
+    process::ExitCode,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
-use fuel_merkle::binary::root_calculator::MerkleRootCalculator;
-use fuel_tx::{field::ReceiptsRoot, Receipt, Transaction};
-use fuel_types::{
-    canonical::{Deserialize, Serialize},
-    Bytes32,
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{parser::ValueSource, ArgMatches, CommandFactory, FromArgMatches};
+use fuel_root_validation::{
+    bisect,
+    checkpoint::RecordedFailure,
+    checks, diff, dump, follow,
+    hash::{self, CrossCheck, HashBackend},
+    inspect, merkle, metrics,
+    metrics::Monitored,
+    sample, self_test, server, snapshot, store, BlockCache, BlockDump, BlockOutcome, BlockSource,
+    BlockValidator, Cassette, ChainParameters, Checkpoint, Dashboard, EventInbox, EventPublisher,
+    FailedBlock, FailureReport, FileSource, FollowConfig, FollowEvent, FullBlock, Health,
+    KnownAnomalies, L1ChainState, MerkleProof, Metrics, NetworkProfile, PoaAuthority,
+    RangeValidation, RateLimit, ResultStore, RetryPolicy, Severity, Snapshot, ValidationError,
+    Webhook,
+};
+use fuel_types::ChainId;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+
+use crate::{
+    cli::{
+        BisectArgs, CheckSnapshotArgs, Cli, Command, ConsensusParametersArgs, CorruptArgs,
+        DiffArgs, ExportArgs, ExportProofArgs, InspectArgs, ProofFormat, RevalidateArgs,
+        SelfTestArgs, ServeArgs, ValidateTxArgs, VerifyProofArgs,
+    },
+    config::{Config, MonitorConfig},
+    exit_code::Status,
+    output::{CsvWriter, EventQueue, OutputFormat, Reporter},
+    repro_heights::REPRO_BLOCK_HEIGHTS,
+    signals::SignalListener,
 };
-use std::sync::Arc;
 
-// Simple block query
-#[derive(cynic::QueryFragment, Debug)]
-#[cynic(
-    schema_path = "./src/schema/schema.sdl",
-    graphql_type = "Query",
-    variables = "BlockQueryVariables"
-)]
-pub struct BlockQuery {
-    #[arguments(height: $height)]
-    pub block: Option<FullBlock>,
+/// Minimum delay between two saves of the checkpoint of a range scan.
+const CHECKPOINT_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let parsed = Cli::command()
+        .try_get_matches()
+        .and_then(|matches| Ok((Cli::from_arg_matches(&matches)?, matches)));
+    let (cli, matches) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let _ = e.print();
+            // Clap exits with 2 on usage errors, which is the network error code here.
+            return if e.use_stderr() {
+                Status::Parse.into()
+            } else {
+                ExitCode::SUCCESS
+            };
+        }
+    };
+
+    let mut cli = match configure(cli, &matches) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            return Status::of_fatal(&e).into();
+        }
+    };
+    logging::init(cli.log_format, cli.tui);
+    let hash_check = use_hash_backend(&cli);
+    tokio::spawn(cancel_on_sigint(cli.cancel.clone(), cli.run_deadline));
+    let recording = cli.record.clone().zip(cli.cassette.clone());
+
+    let status = match cli.command.take() {
+        Some(Command::VerifyProof(args)) => verify_proof(&args),
+        Some(Command::Export(args)) => export(&cli, &args).await,
+        Some(Command::Serve(args)) => serve(cli, &args).await,
+        Some(Command::ConsensusParameters(args)) => save_consensus_parameters(&cli, &args).await,
+        Some(Command::Bisect(args)) => bisect(&cli, &args).await,
+        Some(Command::ValidateTx(args)) => validate_tx(cli, &args).await,
+        Some(Command::ExportProof(args)) => export_proof(cli, &args).await,
+        Some(Command::Inspect(args)) => inspect(cli, &args).await,
+        Some(Command::Diff(args)) => diff(&cli, &args).await,
+        Some(Command::Corrupt(args)) => corrupt(&args),
+        Some(Command::SelfTest(args)) => self_test(&args),
+        Some(Command::CheckSnapshot(args)) => check_snapshot(&cli, &args).await,
+        Some(Command::Revalidate(args)) => revalidate(cli, &args).await,
+        None if cli.monitors_networks() => monitor(cli, &matches).await,
+        None => run(cli, &matches).await,
+    };
+    // Failed runs are recorded too, they are the ones worth reproducing.
+    if let Some((path, cassette)) = recording {
+        match cassette.save(&path) {
+            Ok(()) => tracing::info!(
+                "recorded {} responses to {}",
+                cassette.len(),
+                path.display()
+            ),
+            Err(e) => eprintln!("Error: {e}"),
+        }
+    }
+    let status = status.map(|status| match hash_check {
+        Some(check) if check.mismatches() > 0 => {
+            tracing::error!(
+                "--hash-backend {} got {} hashes wrong, the roots of sha2 were used",
+                check.name(),
+                check.mismatches()
+            );
+            status.worst(Status::Mismatch)
+        }
+        _ => status,
+    });
+    match status {
+        Ok(status) => status.into(),
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            Status::of_fatal(&e).into()
+        }
+    }
 }
 
-#[derive(cynic::QueryVariables, Debug)]
-pub struct BlockQueryVariables {
-    pub height: Option<U32>,
+/// Hashes the roots with `--hash-backend`, checked against sha2 with
+/// `--cross-check-hash`. Returns the check.
+fn use_hash_backend(cli: &Cli) -> Option<&'static CrossCheck> {
+    let backend = hash::named(&cli.hash_backend).expect("clap and the config check the name");
+    if !cli.cross_check_hash {
+        hash::set_backend(backend);
+        return None;
+    }
+    let check: &'static CrossCheck = Box::leak(Box::new(CrossCheck::new(backend)));
+    hash::set_backend(check);
+    Some(check)
 }
 
-#[derive(cynic::QueryFragment, Clone, Debug)]
-#[cynic(graphql_type = "Transaction", schema_path = "./src/schema/schema.sdl")]
-pub struct OpaqueTransactionWithStatusAndId {
-    pub id: TransactionId,
-    pub raw_payload: HexString,
-    pub status: Option<TransactionStatus>,
+/// Fills in the settings of the `--config` file, if any, and checks the result.
+fn configure(mut cli: Cli, matches: &ArgMatches) -> Result<Cli> {
+    let mut chosen = matches.value_source("network") == Some(ValueSource::CommandLine);
+    let mut profiles = BTreeMap::new();
+    if let Some(path) = cli.config.clone() {
+        let config = Config::load(&path)?;
+        chosen |= config.network.is_some();
+        profiles = config.profiles()?;
+        config.apply(&mut cli, matches)?;
+    }
+    for monitor in &mut cli.monitors {
+        monitor.profile = network_profile(&profiles, &monitor.network)?;
+    }
+    let profile = network_profile(&profiles, &cli.network)?;
+    cli.use_profile(profile, chosen);
+    if let Some((from, to)) = cli.range.take() {
+        (cli.from_height, cli.to_height) = (Some(from), Some(to));
+    }
+    cli.check()?;
+    cli.cassette = match (&cli.record, &cli.replay) {
+        (Some(_), _) => Some(Arc::new(Cassette::recording())),
+        (None, Some(path)) => Some(Arc::new(Cassette::load(path)?)),
+        (None, None) => None,
+    };
+    Ok(cli)
 }
 
-#[derive(cynic::QueryFragment, Debug)]
-#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Block")]
-pub struct FullBlock {
-    pub id: BlockId,
-    pub header: Header,
-    pub consensus: Consensus,
-    pub transactions: Vec<OpaqueTransactionWithStatusAndId>,
+/// The profile of the network `name`: one of the `[networks]` of the config
+/// file, or a built-in one.
+fn network_profile(
+    profiles: &BTreeMap<String, NetworkProfile>,
+    name: &str,
+) -> Result<NetworkProfile> {
+    match profiles.get(name) {
+        Some(profile) => Ok(profile.clone()),
+        None => NetworkProfile::builtin(name).ok_or_else(|| {
+            anyhow!(
+                "unknown network `{name}`, expected mainnet, testnet, devnet or one of the \
+                 `[networks]` of the config file"
+            )
+        }),
+    }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize logging
-    env_logger::init();
-
-    // Create fuel client
-    let fuel_client = Arc::new(FuelClient::new("https://testnet.fuel.network/v1/graphql")?);
-
-    let block_heights = vec![
-        3674967, 3674968, 3675223, 3675278, 3675630, 3675868, 3675903, 3676312, 3676343, 3676344,
-        3676369, 3676372, 3676410, 3676471, 3676516, 3676535, 3676835, 3677126, 3677425, 3677522,
-        3677619, 3677677, 3677834, 3678004, 3678092, 3678940, 3679518, 3679603, 3679867, 3679913,
-        3681140, 3681537, 3682125, 3682337, 3682442, 3683615, 3683872, 3683903, 3684563, 3685107,
-        3685189, 3685353, 3685593, 3685647, 3685777, 3685899, 3686090, 3686104, 3687066, 3687479,
-        3688242, 3688379, 3688438, 3688668, 3688710, 3689336, 3689999, 3690152, 3690248, 3690264,
-        3692247, 3692921, 3692931, 3693489, 3693783, 3694127, 3694578, 3694581, 3695199, 3695645,
-        3695646, 3697298, 3698245, 3698473, 3699361, 3699796, 3700178, 3700914, 3701383, 3701742,
-        3701749, 3701885, 3703364, 3703411, 3703793, 3704133, 3704822, 3706325, 3706501, 3706664,
-        3707999, 3708179, 3708481, 3709481, 3709804, 3710152, 3710942, 3711627, 3712140, 3712298,
-        3712499, 3712735, 3713029, 3713144, 3713324, 3713954, 3714557, 3714777, 3715420, 3715469,
-        3715588, 3715591, 3716430, 3716518, 3716828, 3717202, 3717668, 3717787, 3718015, 3718086,
-        3718279, 3718702, 3718795, 3719530, 3719592, 3719839, 3720590, 3721507, 3721675, 3722017,
-        3722192, 3722704, 3722823, 3723024, 3723028, 3723106, 3724109, 3724147, 3724227, 3724559,
-        3725079, 3726007, 3726199, 3726413, 3727012, 3727443, 3727906, 3727915, 3728075, 3728093,
-        3728389, 3728401, 3728403, 3728692, 3728705, 3729041, 3729236, 3729927, 3730332, 3730398,
-        3730786, 3730787, 3731138, 3731173, 3731252, 3731294, 3732020, 3732602, 3732664, 3732863,
-        3733021, 3733237, 3733418, 3733786, 3733866, 3733875, 3733906, 3734251, 3734283, 3734390,
-        3735132, 3735257, 3735414, 3735473, 3736264, 3736352, 3736675, 3737105, 3737776, 3738123,
-        3738636, 3739312, 3739789, 3739836, 3739942, 3740224, 3740428, 3740647, 3740757, 3741445,
-        3741472, 3741725, 3741755, 3741761, 3741785, 3742540, 3743177, 3743422, 3743493, 3743497,
-        3743502, 3743636, 3743814, 3744086, 3744193, 3744342, 3744496, 3744544, 3744694, 3744785,
-        3744900, 3745063, 3745180, 3745209, 3745441, 3745504, 3745569, 3745802, 3745861, 3746018,
-        3746315, 3746342, 3746367, 3746400, 3746522, 3746529, 3747245, 3747277, 3747418, 3747860,
-        3747916, 3747945, 3748020, 3748041, 3748091, 3748179, 3748337, 3748377, 3748487, 3748489,
-        3748686, 3748773, 3748839, 3749006, 3749116, 3749125, 3749136, 3749141, 3749160, 3749173,
-        3749227, 3749230, 3749236, 3749252, 3749275, 3749305, 3749320, 3749323, 3749331, 3749335,
-        3749335, 3749350, 3749360, 3749364, 3749373, 3749378, 3749391, 3749401, 3749408, 3749428,
-        3749435, 3749444, 3749452, 3749485, 3749491, 3749500, 3749518, 3749526, 3749527, 3749529,
-        3749532, 3749533, 3749549, 3749550, 3749555, 3749556, 3749557, 3749562, 3749564, 3749565,
-        3749569, 3749596, 3749606, 3749611, 3749613, 3749622, 3749652, 3749654, 3749676, 3749677,
-        3749708, 3749757, 3749758, 3749769, 3749785, 3749813, 3749816, 3749819, 3749826, 3749840,
-        3749841, 3749849, 3749864, 3749873, 3749882, 3749894, 3749935, 3749941, 3749945, 3749962,
-        3749995, 3750007, 3750010, 3750021, 3750063, 3750073, 3750093, 3750093, 3750093, 3750093,
-        3750123, 3750129, 3750141, 3750166, 3750191, 3750212, 3750223, 3750280, 3750285, 3750286,
-        3750313, 3750322, 3750329, 3750330, 3750384, 3750405, 3750409, 3750413, 3750413, 3750413,
-        3750413, 3750414, 3750416, 3750428, 3750429, 3750442, 3750463, 3750475, 3750554, 3750554,
-        3750560, 3750582, 3750582, 3750586, 3750622, 3750622, 3750622, 3750622, 3750623, 3750624,
-        3750631, 3750668, 3750686, 3750690, 3750720, 3750745, 3750749, 3750759, 3750759, 3750759,
-        3750759, 3750760, 3750763, 3750769, 3750779, 3750779, 3750779, 3750779, 3750790, 3750816,
-        3750818, 3750818, 3750818, 3750825, 3750835, 3750836, 3750836, 3750880, 3750884, 3750923,
-        3750932, 3750933, 3750935, 3750963, 3750968, 3750979, 3750982, 3751002, 3751005, 3751012,
-        3751040, 3751049, 3751056, 3751067, 3751093, 3751105, 3751108, 3751142, 3751145, 3751151,
-        3751152, 3751156, 3751162, 3751165, 3751206, 3751221, 3751221, 3751221, 3751221, 3751238,
-        3751239, 3751251, 3751255, 3751255, 3751255, 3751255, 3751257, 3751257, 3751281, 3751295,
-        3751303, 3751322, 3751330, 3751344, 3751348, 3751352, 3751354, 3751359, 3751361, 3751393,
-        3751394, 3751443, 3751444, 3751444, 3751444, 3751444, 3751462, 3751475, 3751491, 3751491,
-        3751526, 3751545, 3751554, 3751562, 3751580, 3751599, 3751616, 3751620, 3751627, 3751637,
-        3751646, 3751660, 3751788, 3751804, 3751816, 3751832, 3751836, 3751841, 3751847, 3751847,
-        3751864, 3752200, 3752204, 3752209, 3752222, 3752232, 3752240, 3752247, 3752251, 3752255,
-        3752257, 3752272, 3752275, 3752289, 3752310, 3752321, 3752342, 3752345, 3752345, 3752380,
-        3752393, 3752410, 3752417, 3752480, 3752483, 3752485, 3752490, 3752504, 3752517, 3752521,
-        3752535, 3752536, 3752537, 3752540, 3752542, 3752547, 3752560, 3752580, 3752634, 3752634,
-        3752652, 3752679, 3752680, 3752684, 3752689, 3752702, 3752703, 3752716, 3752728, 3752731,
-        3752742, 3752743, 3752760, 3752760, 3752768, 3752775, 3752777, 3752793, 3752809, 3752835,
-        3752843, 3752850, 3752855, 3752855, 3752899, 3752927, 3752929, 3752935, 3752953, 3752953,
-        3752953, 3752953, 3752964, 3752987, 3752998, 3753004, 3753026, 3753029, 3753040, 3753050,
-        3753057, 3753096, 3753109, 3753112, 3753114, 3753141, 3753148, 3753148, 3753148, 3753148,
-        3753149, 3753172, 3753172, 3753179, 3753184, 3753210, 3753226, 3753262, 3753268, 3753289,
-        3753298, 3753300, 3753301, 3753305, 3753305, 3753305, 3753305, 3753312, 3753313, 3753316,
-        3753330, 3753344, 3753350, 3753437, 3753438, 3753438, 3753441, 3753457, 3753466, 3753480,
-        3753483, 3753488, 3753524, 3753524, 3753547, 3753549, 3753564, 3753582, 3753603, 3753607,
-        3753608, 3753611, 3753620, 3753639, 3753640, 3753647, 3753657, 3753665, 3753667, 3753669,
-        3753681, 3753686, 3753695, 3753702, 3753715, 3753719, 3753723, 3753730, 3753730, 3753784,
-        3753792, 3753818, 3753849, 3753869, 3753872, 3753910, 3754176, 3754183, 3754183, 3754183,
-        3754183, 3754200, 3754201, 3754213, 3754234, 3754257, 3754257, 3754257, 3754257, 3754269,
-        3754269, 3754276, 3754276, 3754276, 3754276, 3754276, 3754276, 3754280, 3754295, 3754334,
-        3754341, 3754353, 3754361, 3754375, 3754378, 3754378, 3754380, 3754380, 3754392, 3754397,
-        3754404, 3754418, 3754429, 3754442, 3754443, 3754444, 3754450, 3754450, 3754450, 3754450,
-        3754459, 3754478, 3754481, 3754498, 3754503, 3754505, 3754506, 3754531, 3754539, 3754543,
-        3754548, 3754553, 3754570, 3754602, 3754611, 3754623, 3754625, 3754643, 3754651, 3754668,
-        3754672, 3754688, 3754699, 3754715, 3754722, 3754727, 3754729, 3754744, 3754745, 3754745,
-        3754750, 3754768, 3754772, 3754783, 3754810, 3754814, 3754837, 3754840, 3754842, 3754853,
-        3754862, 3754870, 3754872, 3754882, 3754904, 3754912, 3754932, 3754943, 3754943, 3754943,
-        3754943, 3754957, 3754960, 3754984, 3754986, 3754995, 3755006, 3755027, 3755029, 3755030,
-        3755030, 3755030, 3755030, 3755038, 3755043, 3755064, 3755071, 3755082, 3755083, 3755084,
-        3755086, 3755087, 3755105, 3755120, 3755123, 3755130, 3755134, 3755136, 3755137, 3755138,
-        3755139, 3755163, 3755164, 3755167, 3755194, 3755194, 3755199, 3755209, 3755219, 3755233,
-        3755233, 3755233, 3755233, 3755247, 3755272, 3755273, 3755292, 3755329, 3755331, 3755352,
-        3755354, 3755368, 3755376, 3755380, 3755396, 3755397, 3755408, 3755414, 3755445, 3755455,
-        3755464, 3755468, 3755488, 3755489, 3755511, 3755524, 3755549, 3755579, 3755579, 3755579,
-        3755579, 3755609, 3755609, 3755620, 3755637, 3755651, 3755691, 3755695, 3755711, 3755719,
-        3755721, 3755732, 3755733, 3755759, 3755767, 3755773, 3755785, 3755816, 3755816, 3755816,
-        3755816, 3755820, 3755838, 3755844, 3755846, 3755851, 3755858, 3755888, 3755902, 3755924,
-        3755954, 3755954, 3755954, 3755954, 3755959, 3755962, 3755995, 3756000, 3756006, 3756038,
-        3756057, 3756059, 3756064, 3756065, 3756080, 3756107, 3756138, 3756138, 3756138, 3756138,
-        3756206, 3756206, 3756206, 3756206, 3756227, 3756232, 3756264, 3756265, 3756267, 3756349,
-        3756356, 3756358, 3756368, 3756372, 3756383, 3756391, 3756414, 3756414, 3756437, 3756443,
-        3756444, 3756446, 3756484, 3756485, 3756489, 3756525, 3756527, 3756549, 3756552, 3756569,
-        3756587, 3756588, 3756607, 3756610, 3756643, 3756671, 3756672, 3756686, 3756703, 3756703,
-        3756709, 3756710, 3756713, 3756718, 3756751, 3756757, 3756766, 3756791, 3756803, 3756824,
-        3756847, 3756865, 3756871, 3756912, 3756913, 3756922, 3756970, 3756973, 3756983, 3756995,
-        3756996, 3757010, 3757014, 3757060, 3757064, 3757067, 3757088, 3757090, 3757091, 3757098,
-        3757104, 3757117, 3757123, 3757129, 3757133, 3757151, 3757162, 3757174, 3757183, 3757188,
-        3757199, 3757205, 3757211, 3757258, 3757260, 3757260, 3757260, 3757260, 3757261, 3757272,
-        3757274, 3757277, 3757297, 3757299, 3757317, 3757321, 3757360, 3757377, 3757377, 3757377,
-        3757377, 3757405, 3757415, 3757462, 3757521, 3757529, 3757533, 3757535, 3757536, 3757547,
-        3757555, 3757574, 3757576, 3757578, 3757606, 3757610, 3757616, 3757646, 3757674, 3757675,
-        3757680, 3757695, 3757704, 3757731, 3757734, 3757743, 3757747, 3757773, 3757778, 3757778,
-        3757778, 3757778, 3757793, 3757797, 3757809, 3757810, 3757829, 3757838, 3757846, 3757872,
-        3757873, 3757879, 3757887, 3757900, 3757904, 3757913, 3757921, 3757924, 3757926, 3757940,
-        3757946, 3757950, 3757974, 3757976, 3757979, 3757984, 3757984, 3757984, 3757984, 3757993,
-        3757995, 3757995, 3758006, 3758011, 3758028, 3758036, 3758043, 3758046, 3758051, 3758065,
-        3758066, 3758085, 3758091, 3758093, 3758103, 3758106, 3758113, 3758122, 3758123, 3758130,
-        3758151, 3758151, 3758153, 3758153, 3758154, 3758156, 3758196, 3758199, 3758209, 3758211,
-        3758219, 3758225, 3758232, 3758235, 3758254, 3758307, 3758309, 3758311, 3758314, 3758326,
-        3758366, 3758366, 3758366, 3758366, 3758379, 3758381, 3758394, 3758402, 3758412, 3758420,
-        3758427, 3758431, 3758442, 3758445, 3758445, 3758480, 3758520, 3758531, 3758535, 3758538,
-        3758538, 3758538, 3758538, 3758551, 3758554, 3758560, 3758563, 3758595, 3758595, 3758595,
-        3758595, 3758614, 3758628, 3758635, 3758643, 3758643, 3758643, 3758643, 3758661, 3758670,
-        3758670, 3758670, 3758670, 3758673, 3758696, 3758698, 3758724, 3758724, 3758747, 3758755,
-        3758795, 3758813, 3758831, 3758876, 3758878, 3758894, 3758896, 3758896, 3758898, 3758900,
-        3758901, 3758917, 3758940, 3758948, 3758967, 3758969, 3758971, 3758980, 3758980, 3758980,
-        3758980, 3759013, 3759016, 3759065, 3759072, 3759079, 3759092, 3759093, 3759111, 3759125,
-        3759132, 3759132, 3759132, 3759132, 3759141, 3759145, 3759153, 3759186, 3759196, 3759226,
-        3759237, 3759289, 3759329, 3759348, 3759348, 3759348, 3759348, 3759375, 3759395, 3759397,
-        3759416, 3759416, 3759416, 3759416, 3759428, 3759429, 3759435, 3759439, 3759446, 3759459,
-        3759498, 3759504, 3759517, 3759525, 3759551, 3759553, 3759570, 3759582, 3759589, 3759635,
-        3759652, 3759668, 3759680, 3759693, 3759698, 3759710, 3759711, 3759738, 3759739, 3759750,
-        3759751, 3759751, 3759766, 3759774, 3759799, 3759808, 3759826, 3759829, 3759834, 3759852,
-        3759866, 3759881, 3759884, 3759887, 3759892, 3759903, 3759914, 3759935, 3759950, 3759976,
-        3759980, 3759998, 3760011, 3760018, 3760018, 3760025, 3760035, 3760042, 3760044, 3760052,
-        3760052, 3760052, 3760052, 3760060, 3760062, 3760062, 3760062, 3760062, 3760071, 3760095,
-        3760104, 3760132, 3760137, 3760137, 3760138, 3760141, 3760149, 3760156, 3760156, 3760172,
-        3760185, 3760203, 3760204, 3760235, 3760244, 3760246, 3760247, 3760255, 3760258, 3760286,
-        3760303, 3760304, 3760317, 3760326, 3760327, 3760334, 3760356, 3760374, 3760374, 3760376,
-        3760385, 3760400, 3760411, 3760442, 3760451, 3760458, 3760458, 3760458, 3760458, 3760461,
-        3760467, 3760491, 3760504, 3760517, 3760537, 3760545, 3760556, 3760559, 3760570, 3760574,
-        3760593, 3760594, 3760595, 3760621, 3760662, 3760681, 3760693, 3760706, 3760756, 3760757,
-        3760757, 3760757, 3760757, 3760758, 3760766, 3760792, 3760817, 3760850, 3760871, 3760892,
-        3760901, 3760913, 3760920, 3760935, 3760938, 3760948, 3760965, 3760982, 3760998, 3761010,
-        3761027, 3761040, 3761041, 3761055, 3761068, 3761073, 3761079, 3761084, 3761086, 3761100,
-        3761102, 3761106, 3761144, 3761145, 3761148, 3761174, 3761182, 3761186, 3761203, 3761209,
-        3761221, 3761226, 3761232, 3761242, 3761256, 3761262, 3761294, 3761322, 3761325, 3761351,
-        3761357, 3761385, 3761409, 3761420, 3761429, 3761431, 3761440, 3761470, 3761473, 3761488,
-        3761497, 3761520, 3761521, 3761527, 3761532, 3761536, 3761545, 3761551, 3761551, 3761551,
-        3761551, 3761554, 3761555, 3761556, 3761567, 3761585, 3761593, 3761600, 3761632, 3761679,
-        3761684, 3761701, 3761708, 3761728, 3761743, 3761750, 3761750, 3761753, 3761753, 3761773,
-        3761775, 3761788, 3761789, 3761789, 3761790, 3761794, 3761805, 3761810, 3761844, 3761899,
-        3761938, 3761965, 3761970, 3761976, 3761989, 3762048, 3762051, 3762054, 3762055, 3762074,
-        3762111, 3762128, 3762131, 3762141, 3762152, 3762186, 3762196, 3762200, 3762222, 3762227,
-        3762231, 3762256, 3762256, 3762256, 3762256, 3762275, 3762315, 3762320, 3762337, 3762349,
-        3762358, 3762360, 3762363, 3762369, 3762370, 3762384, 3762393, 3762396, 3762397, 3762400,
-        3762404, 3762408, 3762423, 3762447, 3762458, 3762468, 3762494, 3762494, 3762494, 3762494,
-        3762494, 3762494, 3762517, 3762548, 3762548, 3762611, 3762611, 3762612, 3762613, 3762625,
-        3762625, 3762641, 3762694, 3762701, 3762701, 3762704, 3762705, 3762709, 3762709, 3762718,
-        3762729, 3762731, 3762752, 3762771, 3762786, 3762788, 3762800, 3762835, 3762839, 3762839,
-        3762839, 3762839, 3762846, 3762872, 3762890, 3762891, 3762939, 3762964, 3762992, 3763019,
-        3763043, 3763053, 3763053, 3763053, 3763053, 3763075, 3763085, 3763100, 3763105, 3763106,
-        3763123, 3763133, 3763133, 3763133, 3763133, 3763139, 3763145, 3763150, 3763182, 3763209,
-        3763218, 3763229, 3763238, 3763269, 3763315, 3763319, 3763332, 3763369, 3763369, 3763372,
-        3763373, 3763398, 3763399, 3763420, 3763444, 3763471, 3763474, 3763488, 3763491, 3763491,
-        3763491, 3763491, 3763524, 3763539, 3763588, 3763595, 3763621, 3763640, 3763686, 3763713,
-        3763719, 3763747, 3763790, 3763799, 3763805, 3763817, 3763818, 3763833, 3763836, 3763837,
-        3763840, 3763846, 3763847, 3763861, 3763905, 3763920, 3763923, 3763930, 3763933, 3763939,
-        3763943, 3763945, 3763945, 3763945, 3763945, 3764002, 3764009, 3764022, 3764041, 3764050,
-        3764069, 3764084, 3764106, 3764162, 3764172, 3764188, 3764232, 3764233, 3764261, 3764262,
-        3764263, 3764265, 3764310, 3764322, 3764336, 3764346, 3764357, 3764362, 3764400, 3764406,
-        3764423, 3764449, 3764450, 3764473, 3764485, 3764494, 3764502, 3764506, 3764527, 3764530,
-        3764532, 3764547, 3764562, 3764576, 3764583, 3764589, 3764596, 3764617, 3764654, 3764656,
-        3764663, 3764675, 3764707, 3764739, 3764759, 3764762, 3764764, 3764765, 3764767, 3764771,
-        3764791, 3764793, 3764817, 3764832, 3764859, 3764865, 3764897, 3764908, 3764914, 3764935,
-        3764950, 3764957, 3764962, 3764967, 3764978, 3764978, 3764978, 3764978, 3765013, 3765037,
-        3765043, 3765044, 3765074, 3765082, 3765096, 3765152, 3765155, 3765166, 3765176, 3765176,
-        3765194, 3765199, 3765199, 3765206, 3765206, 3765229, 3765242, 3765242, 3765242, 3765242,
-        3765244, 3765244, 3765244, 3765258, 3765264, 3765271, 3765278, 3765301, 3765322, 3765344,
-        3765345, 3765350, 3765391, 3765399, 3765403, 3765446, 3765447, 3765498, 3765505, 3765525,
-        3765525, 3765525, 3765525, 3765525, 3765525, 3765525, 3765525, 3765525, 3765525, 3765525,
-        3765525, 3765525, 3765525, 3765525, 3765525, 3765525, 3765525, 3765525, 3765525, 3765526,
-        3765526, 3765546, 3765559, 3765578, 3765582, 3765618, 3765620, 3765621, 3765625, 3765625,
-        3765625, 3765625, 3765636, 3765642, 3765657, 3765657, 3765706, 3765709, 3765709, 3765720,
-        3765724, 3765738, 3765779, 3765790, 3765790, 3765805, 3765813, 3765817, 3765820, 3765858,
-        3765858, 3765858, 3765858, 3765860, 3765862, 3765869, 3765876, 3765877, 3765886, 3765887,
-        3765887, 3765887, 3765887, 3765887, 3765887, 3765912, 3765912, 3765936, 3765945, 3765945,
-        3765945, 3765945, 3765949, 3765963, 3765963, 3765984, 3765985, 3765991, 3765991, 3766003,
-        3766010, 3766014, 3766043, 3766046, 3766051, 3766051, 3766053, 3766057, 3766075, 3766075,
-        3766107, 3766113, 3766114, 3766114, 3766117, 3766144, 3766156, 3766165, 3766179, 3766181,
-        3766194, 3766201, 3766205, 3766213, 3766220, 3766245, 3766245, 3766245, 3766245, 3766248,
-        3766276, 3766326, 3766327, 3766327, 3766327, 3766327, 3766373, 3766380, 3766380, 3766393,
-        3766393, 3766397, 3766397, 3766398, 3766398, 3766398, 3766398, 3766419, 3766423, 3766427,
-        3766434, 3766451, 3766452, 3766475, 3766506, 3766507, 3766507, 3766509, 3766513, 3766516,
-        3766516, 3766521, 3766787, 3766788, 3766806, 3766811, 3766823, 3766853, 3766856, 3766865,
-        3766865, 3766865, 3766865, 3766874, 3766874, 3766889, 3766893, 3766893, 3766895, 3766911,
-        3766923, 3766932, 3766943, 3766950, 3766960, 3766961, 3766970, 3766976, 3766990, 3766997,
-        3767004, 3767004, 3767004, 3767004, 3767038, 3767039, 3767043, 3767060, 3767075, 3767076,
-        3767086, 3767086, 3767086, 3767086, 3767086, 3767086, 3767086, 3767086, 3767086, 3767090,
-        3767105, 3767106, 3767107, 3767107, 3767107, 3767107, 3767107, 3767107, 3767107, 3767107,
-        3767107, 3767107, 3767107, 3767107, 3767107, 3767107, 3767107, 3767107, 3767107, 3767107,
-        3767107, 3767107, 3767107, 3767107, 3767107, 3767107, 3767107, 3767112, 3767120, 3767121,
-        3767126, 3767133, 3767177, 3767179, 3767179, 3767179, 3767179, 3767215, 3767215, 3767234,
-        3767239, 3767243, 3767254, 3767273, 3767276, 3767423, 3767479, 3767484, 3767485, 3767485,
-        3767488, 3767490, 3767494, 3767498, 3767498, 3767532, 3767535, 3767550, 3767553, 3767562,
-        3767575, 3767575, 3767587, 3767589, 3767598, 3767607, 3767621, 3767628, 3767640, 3767659,
-        3767663, 3767666, 3767692, 3767722, 3767731, 3767740, 3767773, 3767776, 3767783, 3767796,
-        3767796, 3767809, 3767822, 3767840, 3767848, 3767855, 3767861, 3767867, 3767868, 3767901,
-        3767902, 3767932, 3767948, 3767948, 3767964, 3767964, 3767964, 3767964, 3767980, 3767999,
-        3768049, 3768051, 3768055, 3768064, 3768090, 3768118, 3768120, 3768128, 3768134, 3768152,
-        3768171, 3768181, 3768183, 3768194, 3768219, 3768224, 3768264, 3768276, 3768282, 3768295,
-        3768296, 3768306, 3768334, 3768335, 3768343, 3768344, 3768382, 3768384, 3768407, 3768413,
-        3768422, 3768423, 3768479, 3768486, 3768527, 3768550, 3768561, 3768561, 3768580, 3768589,
-        3768589, 3768593, 3768611, 3768678, 3768681, 3768689, 3768691, 3768696, 3768701, 3768708,
-        3768739, 3768746, 3768747, 3768747, 3768747, 3768747, 3768767, 3768783, 3768786, 3768801,
-        3768806, 3768857, 3768858, 3768865, 3768872, 3768901, 3768901, 3768901, 3768901, 3768904,
-        3768905, 3768961, 3768991, 3769014, 3769037, 3769055, 3769058, 3769060, 3769062, 3769062,
-        3769062, 3769062, 3769070, 3769099, 3769103, 3769156, 3769177, 3769181, 3769195, 3769205,
-        3769231, 3769242, 3769243, 3769244, 3769262, 3769267, 3769267, 3769275, 3769287, 3769293,
-        3769300, 3769302, 3769308, 3769313, 3769328, 3769330, 3769335, 3769358, 3769358, 3769358,
-        3769358, 3769382, 3769382, 3769412, 3769415, 3769415, 3769415, 3769415, 3769418, 3769456,
-        3769477, 3769500, 3769506, 3769528, 3769541, 3769544, 3769570, 3769606, 3769654, 3769662,
-        3769663, 3769669, 3769680, 3769691, 3769698, 3769779, 3769784, 3769808, 3769816, 3769828,
-        3769830, 3769851, 3769874, 3769878, 3769879, 3769907, 3769913, 3769924, 3769929, 3769947,
-        3769974, 3769984, 3770043, 3770049, 3770057, 3770074, 3770076, 3770080, 3770088, 3770106,
-        3770142, 3770145, 3770156, 3770165, 3770173, 3770174, 3770182, 3770191, 3770202, 3770208,
-        3770220, 3770224, 3770227, 3770240, 3770242, 3770244, 3770274, 3770274, 3770303, 3770335,
-        3770350, 3770407, 3770409, 3770411, 3770422, 3770441, 3770449, 3770484, 3770497, 3770555,
-        3770560, 3770563, 3770566, 3770567, 3770587, 3770620, 3770623, 3770653, 3770656, 3770661,
-        3770671, 3770671, 3770686, 3770729, 3770750, 3770765, 3770777, 3770794, 3770802, 3770830,
-        3770840, 3770843, 3770847, 3770849, 3770882, 3770920, 3770938, 3770940, 3770945, 3770964,
-        3770968, 3771019, 3771019, 3771025, 3771028, 3771063, 3771096, 3771105, 3771130, 3771131,
-        3771138, 3771157, 3771159, 3771193, 3771224, 3771241, 3771294, 3771299, 3771302, 3771302,
-        3771308, 3771314, 3771348, 3771355, 3771402, 3771402, 3771402, 3771402, 3771440, 3771446,
-        3771447, 3771448, 3771472, 3771482, 3771489, 3771497, 3771514, 3771514, 3771514, 3771514,
-        3771543, 3771545, 3771558, 3771562, 3771564, 3771588, 3771597, 3771608, 3771620, 3771635,
-        3771642, 3771675, 3771696, 3771724, 3771727, 3771732, 3771746, 3771746, 3771747, 3771751,
-        3771754, 3771763, 3771774, 3771777, 3771780, 3771788, 3771789, 3771813, 3771818, 3771826,
-        3771843, 3771844, 3771859, 3771870, 3771875, 3771879, 3771885, 3771886, 3771916, 3771927,
-        3771927, 3771927, 3771927, 3771928, 3771953, 3771970, 3771980, 3772013, 3772015, 3772017,
-        3772048, 3772056, 3772081, 3772096, 3772097, 3772108, 3772132, 3772134, 3772174, 3772188,
-        3772230, 3772257, 3772262, 3772269, 3772272, 3772276, 3772288, 3772296, 3772311, 3772319,
-        3772331, 3772331, 3772337, 3772353, 3772369, 3772369, 3772377, 3772378, 3772388, 3772394,
-        3772395, 3772395, 3772395, 3772395, 3772396, 3772424, 3772451, 3772456, 3772457, 3772462,
-        3772462, 3772472, 3772473, 3772481, 3772531, 3772546, 3772570, 3772570, 3772602, 3772604,
-        3772614, 3772625, 3772635, 3772651, 3772669, 3772674, 3772684, 3772731, 3772738, 3772739,
-        3772747, 3772780, 3772806, 3772812, 3772824, 3772828, 3772831, 3772845, 3772860, 3772894,
-        3772894, 3772894, 3772894, 3772898, 3772940, 3772941, 3772952, 3772969, 3772985, 3773006,
-        3773032, 3773032, 3773039, 3773039, 3773046, 3773053, 3773059, 3773081, 3773085, 3773108,
-        3773110, 3773153, 3773179, 3773190, 3773192, 3773219, 3773222, 3773224, 3773225, 3773236,
-        3773236, 3773259, 3773273, 3773274, 3773287, 3773306, 3773332, 3773343, 3773344, 3773352,
-        3773362, 3773391, 3773429, 3773435, 3773440, 3773485, 3773488, 3773503, 3773514, 3773520,
-        3773527, 3773532, 3773532, 3773551, 3773564, 3773564, 3773564, 3773564, 3773581, 3773589,
-        3773612, 3773623, 3773623, 3773624, 3773699, 3773712, 3773722, 3773746, 3773746, 3773746,
-        3773746, 3773747, 3773750, 3773775, 3773783, 3773796, 3773796, 3773796, 3773796, 3773822,
-        3773849, 3773851, 3773866, 3773900, 3773900, 3773908, 3773937, 3773939, 3773983, 3773983,
-        3773983, 3774000, 3774016, 3774040, 3774054, 3774070, 3774090, 3774106, 3774116, 3774121,
-        3774134, 3774136, 3774142, 3774147, 3774156, 3774159, 3774159, 3774159, 3774159, 3774162,
-        3774196, 3774199, 3774239, 3774269, 3774288, 3774301, 3774306, 3774312, 3774341, 3774349,
-        3774395, 3774400, 3774453, 3774453, 3774453, 3774453, 3774461, 3774472, 3774506, 3774528,
-        3774542, 3774543, 3774605, 3774624, 3774638, 3774661, 3774663, 3774666, 3774702, 3774712,
-        3774731, 3774781, 3774795, 3774795, 3774833, 3774833, 3774833, 3774833, 3774858, 3774873,
-        3774894, 3774975, 3774988, 3775017, 3775064, 3775085, 3775088, 3775110, 3775110, 3775110,
-        3775110, 3775127, 3775141, 3775155, 3775160, 3775163, 3775164, 3775169, 3775202, 3775221,
-        3775227, 3775246, 3775305, 3775308, 3775331, 3775345, 3775397, 3775455, 3775517, 3775634,
-        3775677, 3775679, 3775718, 3775731, 3775824, 3775836, 3775841, 3775867, 3775905, 3775927,
-        3775930, 3775930, 3775930, 3775930, 3775945, 3775958, 3775958, 3776038, 3776044, 3776058,
-        3776093, 3776098, 3776104, 3776111, 3776151, 3776154, 3776158, 3776170, 3776175, 3776200,
-        3776225, 3776244, 3776258, 3776277, 3776309, 3776346, 3776346, 3776372, 3776400, 3776411,
-        3776460, 3776478, 3776485, 3776487, 3776511, 3776511, 3776511, 3776511, 3776532, 3776551,
-        3776556, 3776559, 3776619, 3776624, 3776649, 3776663, 3776671, 3776701, 3776729, 3776798,
-        3776858, 3776866, 3776935, 3776959, 3776970, 3776978, 3776991, 3777031, 3777033, 3777033,
-        3777033, 3777033, 3777036, 3777045, 3777048, 3777050, 3777086, 3777106, 3777116, 3777194,
-        3777217, 3777254, 3777255, 3777256, 3777263, 3777322, 3777325, 3777447, 3777454, 3777479,
-        3777522, 3777583, 3777603, 3777613, 3777640, 3777661, 3777689, 3777703, 3777789, 3777791,
-        3777800, 3777812, 3777813, 3777863, 3777878, 3777911, 3777912, 3777922, 3777931, 3777958,
-        3777965, 3777978, 3777989, 3777991, 3778013, 3778028, 3778034, 3778036, 3778112, 3778115,
-        3778130, 3778149, 3778154, 3778175, 3778213, 3778253, 3778262, 3778264, 3778279, 3778350,
-        3778352, 3778353, 3778360, 3778454, 3778469, 3778476, 3778477, 3778517, 3778517, 3778533,
-        3778533, 3778581, 3778612, 3778632, 3778632, 3778681, 3778709, 3778724, 3778750, 3778789,
-        3778796, 3778814, 3778817, 3778828, 3778830, 3778838, 3778902, 3778907, 3778918, 3778923,
-        3778982, 3779012, 3779051, 3779051, 3779055, 3779084, 3779100, 3779106, 3779107, 3779122,
-        3779127, 3779129, 3779154, 3779200, 3779218, 3779268, 3779270, 3779281, 3779283, 3779351,
-        3779355, 3779389, 3779420, 3779420, 3779421, 3779464, 3779473, 3779489, 3779530, 3779532,
-        3779532, 3779561, 3779583, 3779632, 3779642, 3779644, 3779662, 3779672, 3779710, 3779736,
-        3779737, 3779824, 3779898, 3779923, 3779939, 3779944, 3779944, 3779944, 3779944, 3779953,
-        3779971, 3780021, 3780079, 3780113, 3780124, 3780131, 3780148, 3780175, 3780225, 3780225,
-        3780225, 3780225, 3780232, 3780241, 3780308, 3780350, 3780375, 3780412, 3780436, 3780436,
-        3780452, 3780452, 3780460, 3780460, 3780467, 3780502, 3780515, 3780539, 3780540, 3780542,
-        3780555, 3780575, 3780595, 3780614, 3780628, 3780659, 3780680, 3780682, 3780686, 3780698,
-        3780710, 3780711, 3780714, 3780714, 3780717, 3780816, 3780817, 3780834, 3780858, 3780879,
-        3780894, 3780904, 3780941, 3780953, 3780959, 3781000, 3781015, 3781024, 3781042, 3781080,
-        3781083, 3781084, 3781091, 3781159, 3781164, 3781167, 3781236, 3781287, 3781287, 3781287,
-        3781287, 3781299, 3781326, 3781340, 3781356, 3781372, 3781392, 3781412, 3781471, 3781520,
-        3781520, 3781557, 3781566, 3781608, 3781617, 3781631, 3781665, 3781684, 3781694, 3781694,
-        3781723, 3781734, 3781793, 3781836, 3781844, 3781942, 3781968, 3781987, 3782023, 3782088,
-        3782112, 3782150, 3782162, 3782187, 3782188, 3782188, 3782189, 3782199, 3782199, 3782199,
-        3782199, 3782199, 3782199, 3782199, 3782199, 3782199, 3782255, 3782266, 3782319, 3782332,
-        3782373, 3782441, 3782461, 3782486, 3782500, 3782501, 3782519, 3782563, 3782576, 3782577,
-        3782579, 3782583, 3782600, 3782605, 3782623, 3782662, 3782685, 3782708, 3782764, 3782777,
-        3782781, 3782810, 3782831, 3782839, 3782863, 3782878, 3782929, 3782980, 3782985, 3783007,
-        3783028, 3783037, 3783041, 3783042, 3783042, 3783042, 3783042, 3783042, 3783042, 3783052,
-        3783058, 3783101, 3783105, 3783113, 3783123, 3783125, 3783161, 3783172, 3783265, 3783282,
-        3783313, 3783318, 3783366, 3783375, 3783379, 3783379, 3783394, 3783464, 3783506, 3783509,
-        3783519, 3783526, 3783625, 3783644, 3783708, 3783716, 3783730, 3783741, 3783754, 3783770,
-        3783832, 3783833, 3783839, 3783881, 3783884, 3783886, 3783907, 3783971, 3783987, 3783988,
-        3783997, 3784036, 3784075, 3784080, 3784127, 3784150, 3784210, 3784212, 3784332, 3784349,
-        3784354, 3784362, 3784391, 3784399, 3784412, 3784419, 3784442, 3784459, 3784463, 3784474,
-        3784474, 3784518, 3784544, 3784557, 3784573, 3784597, 3784603, 3784603, 3784604, 3784623,
-        3784686, 3784687, 3784703, 3784711, 3784732, 3784783, 3784838, 3784845, 3784879, 3784911,
-        3784953, 3785019, 3785080, 3785113, 3785121, 3785128, 3785142, 3785153, 3785165, 3785179,
-        3785247, 3785296, 3785306, 3785331, 3785340, 3785367, 3785371, 3785380, 3785386, 3785405,
-        3785476, 3785482, 3785485, 3785504, 3785539, 3785561, 3785584, 3785605, 3785647, 3785654,
-        3785677, 3785730, 3785744, 3785751, 3785768, 3785795, 3785835, 3785889, 3785900, 3785934,
-        3785957, 3786046, 3786050, 3786083, 3786097, 3786115, 3786163, 3786188, 3786190, 3786194,
-        3786227, 3786274, 3786275, 3786275, 3786275, 3786275, 3786284, 3786287, 3786358, 3786400,
-        3786426, 3786441, 3786454, 3786465, 3786494, 3786531, 3786549, 3786578, 3786579, 3786601,
-        3786637, 3786670, 3786728, 3786730, 3786753, 3786884, 3786891, 3786940, 3786942, 3786988,
-        3786999, 3787011, 3787021, 3787026, 3787040, 3787106, 3787106, 3787118, 3787155, 3787174,
-        3787200, 3787207, 3787220, 3787272, 3787285, 3787306, 3787354, 3787364, 3787407, 3787411,
-        3787423, 3787450, 3787450, 3787455, 3787456, 3787521, 3787522, 3787553, 3787557, 3787574,
-        3787650, 3787652, 3787678, 3787701, 3787732, 3787735, 3787756, 3787768, 3787810, 3787824,
-        3787852, 3787858, 3787858, 3787858, 3787858, 3787911, 3787951, 3787962, 3788014, 3788041,
-        3788115, 3788162, 3788183, 3788198, 3788198, 3788198, 3788198, 3788209, 3788228, 3788240,
-        3788246, 3788285, 3788303, 3788314, 3788379, 3788386, 3788418, 3788461, 3788485, 3788509,
-        3788527, 3788551, 3788560, 3788572, 3788591, 3788600, 3788607, 3788665, 3788755, 3788773,
-        3788786, 3788819, 3788819, 3788819, 3788819, 3788837, 3788848, 3788853, 3788873, 3788893,
-        3788893, 3788896, 3788900, 3788905, 3788930, 3788936, 3789011, 3789021, 3789033, 3789042,
-        3789051, 3789056, 3789061, 3789068, 3789079, 3789106, 3789117, 3789118, 3789122, 3789132,
-        3789147, 3789159, 3789180, 3789194, 3789201, 3789237, 3789242, 3789264, 3789271, 3789273,
-        3789296, 3789299, 3789319, 3789379, 3789403, 3789420, 3789431, 3789453, 3789468, 3789472,
-        3789480, 3789508, 3789532, 3789533, 3789552, 3789569, 3789583, 3789593, 3789624, 3789654,
-        3789667, 3789714, 3789726, 3789748, 3789756, 3789787, 3789813, 3789815, 3789837, 3789837,
-        3789849, 3789859, 3789873, 3789919, 3789920, 3789928, 3789950, 3789954, 3789967, 3789972,
-        3789977, 3789992, 3789992, 3790008, 3790015, 3790021, 3790036, 3790065, 3790103, 3790124,
-        3790131, 3790132, 3790158, 3790211, 3790227, 3790234, 3790270, 3790272, 3790298, 3790308,
-        3790427, 3790428, 3790462, 3790488, 3790569, 3790592, 3790618, 3790628, 3790642, 3790642,
-        3790646, 3790689, 3790696, 3790697, 3790705, 3790714, 3790744, 3790748, 3790773, 3790780,
-        3790805, 3790809, 3790813, 3790829, 3790849, 3790875, 3790887, 3790887, 3790904, 3790940,
-        3790971, 3790989, 3791000, 3791003, 3791028, 3791031, 3791032, 3791032, 3791037, 3791070,
-        3791071, 3791081, 3791093, 3791105, 3791109, 3791117, 3791121, 3791133, 3791139, 3791140,
-        3791166, 3791167, 3791175, 3791179, 3791195, 3791228, 3791250, 3791250, 3791274, 3791284,
-        3791296, 3791310, 3791355, 3791365, 3791368, 3791376, 3791403, 3791462, 3791492, 3791492,
-        3791492, 3791492, 3791492, 3791492, 3791501, 3791551, 3791573, 3791588, 3791599, 3791607,
-        3791635, 3791643, 3791648, 3791650, 3791664, 3791700, 3791704, 3791719, 3791742, 3791767,
-        3791775, 3791779, 3791808, 3791816, 3791824, 3791920, 3791975, 3791980, 3791991, 3792038,
-        3792093, 3792098, 3792105, 3792112, 3792127, 3792139, 3792155, 3792217, 3792222, 3792235,
-        3792237, 3792258, 3792260, 3792265, 3792271, 3792272, 3792395, 3792506, 3792516, 3792519,
-        3792536, 3792571, 3792700, 3792716, 3792748, 3792789, 3792809, 3792850, 3792898, 3792903,
-        3792929, 3792944, 3792947, 3792953, 3792963, 3792983, 3792993, 3793017, 3793034, 3793053,
-        3793083, 3793110, 3793118, 3793129, 3793140, 3793162, 3793169, 3793179, 3793198, 3793229,
-        3793279, 3793281, 3793291, 3793327, 3793346, 3793404, 3793438, 3793440, 3793476, 3793549,
-        3793572, 3793594, 3793653, 3793685, 3793694, 3793725, 3793769, 3793769, 3793769, 3793769,
-        3793842, 3793868, 3793952, 3793979, 3793987, 3793989, 3794004, 3794013, 3794033, 3794033,
-        3794033, 3794033, 3794164, 3794208, 3794211, 3794221, 3794250, 3794325, 3794337, 3794351,
-        3794356, 3794431, 3794433, 3794458, 3794467, 3794506, 3794515, 3794550, 3794579, 3794616,
-        3794626, 3794644, 3794658, 3794667, 3794739, 3794769, 3794801, 3794821, 3794836, 3794841,
-        3794873, 3794897, 3794969, 3795001, 3795003, 3795011, 3795062, 3795070, 3795150, 3795187,
-        3795196, 3795241, 3795264, 3795274, 3795276, 3795381, 3795411, 3795412, 3795416, 3795488,
-        3795489, 3795494, 3795560, 3795610, 3795691, 3795703, 3795708, 3795741, 3795749, 3795782,
-        3795785, 3795794, 3795851, 3795859, 3795872, 3795885, 3795889, 3795929, 3795973, 3795987,
-        3795990, 3795997, 3796009, 3796019, 3796026, 3796082, 3796131, 3796137, 3796153, 3796196,
-        3796209, 3796257, 3796312, 3796328, 3796432, 3796480, 3796509, 3796565, 3796568, 3796605,
-        3796612, 3796641, 3796678, 3796696, 3796731, 3796734, 3796735, 3796751, 3796768, 3796806,
-        3796831, 3796853, 3796876, 3796893, 3796914, 3796988, 3797024, 3797058, 3797118, 3797130,
-        3797149, 3797219, 3797254, 3797300, 3797318, 3797324, 3797413, 3797436, 3797443, 3797444,
-        3797452, 3797487, 3797553, 3797574, 3797620, 3797660, 3797681, 3797682, 3797688, 3797688,
-        3797688, 3797688, 3797699, 3797711, 3797715, 3797724, 3797785, 3797807, 3797848, 3797869,
-        3797877, 3797891, 3797912, 3797934, 3797972, 3797980, 3797982, 3798012, 3798017, 3798035,
-        3798036, 3798056, 3798066, 3798075, 3798079, 3798079, 3798079, 3798079, 3798089, 3798110,
-        3798173, 3798179, 3798180, 3798187, 3798190, 3798241, 3798282, 3798301, 3798361, 3798383,
-        3798451, 3798476, 3798481, 3798488, 3798504, 3798516, 3798521, 3798537, 3798548, 3798592,
-        3798598, 3798631, 3798639, 3798640, 3798664, 3798693, 3798703, 3798708, 3798722, 3798735,
-        3798738, 3798751, 3798789, 3798797, 3798819, 3798867, 3798869, 3798880, 3798891, 3798892,
-        3798915, 3798988, 3799004, 3799026, 3799054, 3799075, 3799081, 3799096, 3799117, 3799129,
-        3799165, 3799218, 3799272, 3799301, 3799315, 3799320, 3799325, 3799355, 3799358, 3799360,
-        3799404, 3799422, 3799451, 3799486, 3799519, 3799520, 3799521, 3799544, 3799553, 3799557,
-        3799557, 3799557, 3799557, 3799563, 3799615, 3799639, 3799639, 3799659, 3799698, 3799702,
-        3799722, 3799737, 3799744, 3799803, 3799807, 3799808, 3799808, 3799820, 3799869, 3799876,
-        3799888, 3799891, 3799922, 3799976, 3799982, 3799987, 3800035, 3800043, 3800050, 3800057,
-        3800066, 3800067, 3800075, 3800087, 3800121, 3800139, 3800188, 3800194, 3800227, 3800261,
-        3800287, 3800327, 3800368, 3800377, 3800382, 3800391, 3800398, 3800406, 3800424, 3800424,
-        3800434, 3800437, 3800453, 3800473, 3800491, 3800561, 3800565, 3800571, 3800589, 3800594,
-        3800620, 3800669, 3800687, 3800705, 3800718, 3800755, 3800757, 3800824, 3800824, 3800866,
-        3800866, 3800866, 3800866, 3800866, 3800866, 3800873, 3800891, 3800908, 3800924, 3800924,
-        3800928, 3800951, 3800962, 3800962, 3800984, 3801002, 3801045, 3801078, 3801095, 3801116,
-        3801129, 3801141, 3801155, 3801162, 3801170, 3801186, 3801187, 3801205, 3801228, 3801247,
-        3801270, 3801291, 3801292, 3801309, 3801316, 3801379, 3801404, 3801404, 3801404, 3801404,
-        3801408, 3801413, 3801417, 3801468, 3801501, 3801514, 3801521, 3801559, 3801600, 3801611,
-        3801615, 3801649, 3801656, 3801658, 3801762, 3801782, 3801787, 3801792, 3801816, 3801824,
-        3801827, 3801857, 3801890, 3801915, 3801931, 3801938, 3801962, 3801994, 3802056, 3802064,
-        3802069, 3802097, 3802112, 3802126, 3802151, 3802159, 3802217, 3802236, 3802265, 3802291,
-        3802292, 3802292, 3802300, 3802308, 3802312, 3802359, 3802393, 3802393, 3802411, 3802428,
-        3802468, 3802486, 3802538, 3802563, 3802578, 3802578, 3802586, 3802587, 3802637, 3802644,
-        3802646, 3802686, 3802704, 3802727, 3802734, 3802791, 3802793, 3802863, 3802887, 3802940,
-        3802963, 3802968, 3802969, 3803004, 3803046, 3803057, 3803080, 3803133, 3803141, 3803148,
-        3803170, 3803191, 3803201, 3803208, 3803246, 3803263, 3803263, 3803267, 3803273, 3803297,
-        3803310, 3803366, 3803371, 3803371, 3803379, 3803433, 3803434, 3803441, 3803457, 3803539,
-        3803549, 3803576, 3803585, 3803596, 3803641, 3803691, 3803691, 3803691, 3803691, 3803709,
-        3803722, 3803739, 3803742, 3803792, 3803817, 3803825, 3803844, 3803868, 3803911, 3803975,
-        3803982, 3804001, 3804011, 3804015, 3804070, 3804075, 3804084, 3804091, 3804130, 3804161,
-        3804185, 3804187, 3804189, 3804192, 3804192, 3804192, 3804192, 3804214, 3804214, 3804262,
-        3804276, 3804283, 3804335, 3804336, 3804394, 3804482, 3804497, 3804520, 3804527, 3804540,
-        3804568, 3804569, 3804593, 3804611, 3804619, 3804652, 3804670, 3804672, 3804731, 3804750,
-        3804763, 3804778, 3804817, 3804818, 3804826, 3804826, 3804826, 3804826, 3804846, 3804850,
-        3804853, 3804899, 3804908, 3804923, 3804936, 3804962, 3804982, 3804990, 3805007, 3805018,
-        3805030, 3805058, 3805093, 3805126, 3805133, 3805143, 3805144, 3805162, 3805179, 3805184,
-        3805193, 3805193, 3805193, 3805193, 3805213, 3805233, 3805241, 3805306, 3805308, 3805329,
-        3805353, 3805359, 3805389, 3805432, 3805457, 3805458, 3805472, 3805473, 3805485, 3805501,
-        3805508, 3805530, 3805543, 3805545, 3805557, 3805557, 3805557, 3805557, 3805597, 3805620,
-        3805640, 3805652, 3805678, 3805695, 3805801, 3805810, 3805819, 3805826, 3805838, 3805839,
-        3805859, 3805907, 3805908, 3805936, 3805937, 3805975, 3805986, 3805990, 3806025, 3806043,
-        3806058, 3806071, 3806090, 3806104, 3806140, 3806149, 3806168, 3806169, 3806170, 3806172,
-        3806180, 3806181, 3806230, 3806259, 3806284, 3806322, 3806360, 3806379, 3806382, 3806384,
-        3806385, 3806396, 3806397, 3806397, 3806397, 3806397, 3806409, 3806440, 3806459, 3806465,
-        3806510, 3806526, 3806532, 3806540, 3806559, 3806563, 3806573, 3806583, 3806624, 3806649,
-        3806678, 3806696, 3806699, 3806707, 3806707, 3806749, 3806767, 3806770, 3806778, 3806823,
-        3806825, 3806825, 3806825, 3806825, 3806900, 3806910, 3806919, 3806924, 3806926, 3806926,
-        3806931, 3806952, 3806955, 3807030, 3807111, 3807122, 3807160, 3807160, 3807171, 3807186,
-        3807187, 3807197, 3807221, 3807221, 3807221, 3807221, 3807240, 3807265, 3807291, 3807314,
-        3807329, 3807334, 3807339, 3807362, 3807362, 3807406, 3807415, 3807424, 3807441, 3807449,
-        3807469, 3807475, 3807482, 3807483, 3807492, 3807542, 3807620, 3807629, 3807647, 3807657,
-        3807664, 3807734, 3807743, 3807743, 3807754, 3807754, 3807770, 3807782, 3807783, 3807794,
-        3807801, 3807858, 3807866, 3807909, 3807970, 3807971, 3807987, 3808003, 3808006, 3808012,
-        3808058, 3808061, 3808071, 3808071, 3808071, 3808071, 3808074, 3808120, 3808120, 3808120,
-        3808120, 3808124, 3808157, 3808160, 3808187, 3808225, 3808243, 3808245, 3808278, 3808285,
-        3808290, 3808301, 3808304, 3808317, 3808351, 3808368, 3808399, 3808424, 3808457, 3808459,
-        3808460, 3808481, 3808505, 3808506, 3808515, 3808539, 3808546, 3808575, 3808575, 3808601,
-        3808602, 3808622, 3808643, 3808658, 3808670, 3808675, 3808706, 3808733, 3808763, 3808823,
-        3808842, 3808843, 3808852, 3808853, 3808955, 3808995, 3808995, 3808995, 3808995, 3809014,
-        3809029, 3809067, 3809069, 3809071, 3809072, 3809091, 3809113, 3809114, 3809117, 3809126,
-        3809257, 3809278, 3809278, 3809279, 3809307, 3809318, 3809339, 3809354, 3809355, 3809365,
-        3809402, 3809404, 3809417, 3809420, 3809468, 3809493, 3809510, 3809516, 3809557, 3809604,
-        3809629, 3809648, 3809652, 3809686, 3809690, 3809704, 3809715, 3809728, 3809729, 3809730,
-        3809755, 3809789, 3809851, 3809855, 3809861, 3809896, 3809896, 3809896, 3809896, 3809913,
-        3809916, 3809928, 3809929, 3809934, 3809936, 3809938, 3809938, 3809938, 3809938, 3809956,
-        3809993, 3810024, 3810057, 3810058, 3810068, 3810070, 3810071, 3810100, 3810106, 3810176,
-        3810177, 3810185, 3810190, 3810196, 3810202, 3810212, 3810225, 3810315, 3810371, 3810442,
-        3810477, 3810481, 3810483, 3810493, 3810494, 3810542, 3810547, 3810576, 3810578, 3810582,
-        3810587, 3810618, 3810693, 3810702, 3810709, 3810715, 3810722, 3810723, 3810731, 3810762,
-        3810793, 3810793, 3810793, 3810793, 3810796, 3810876, 3810877, 3810896, 3810899, 3810906,
-        3810926, 3810933, 3810940, 3810981, 3811017, 3811023, 3811026, 3811042, 3811049, 3811076,
-        3811098, 3811144, 3811185, 3811189, 3811213, 3811223, 3811234, 3811279, 3811289, 3811298,
-        3811354, 3811382, 3811393, 3811429, 3811434, 3811441, 3811465, 3811487, 3811505, 3811530,
-        3811539, 3811554, 3811569, 3811595, 3811599, 3811604, 3811604, 3811604, 3811604, 3811625,
-        3811632, 3811636, 3811655, 3811658, 3811681, 3811687, 3811687, 3811687, 3811687, 3811773,
-        3811802, 3811836, 3811859, 3811861, 3811872, 3811887, 3811889, 3811947, 3811965, 3811989,
-        3811998, 3812035, 3812080, 3812087, 3812098, 3812105, 3812144, 3812160, 3812171, 3812196,
-        3812197, 3812212, 3812219, 3812234, 3812295, 3812300, 3812316, 3812323, 3812346, 3812367,
-        3812416, 3812455, 3812480, 3812486, 3812501, 3812501, 3812559, 3812578, 3812586, 3812591,
-        3812605, 3812677, 3812688, 3812702, 3812735, 3812747, 3812756, 3812769, 3812779, 3812791,
-        3812865, 3812869, 3812872, 3812889, 3812889, 3812889, 3812889, 3812895, 3812904, 3812908,
-        3812913, 3812922, 3812951, 3812951, 3812951, 3812951, 3812965, 3812966, 3813033, 3813039,
-        3813041, 3813043, 3813080, 3813088, 3813090, 3813104, 3813105, 3813115, 3813148, 3813159,
-        3813186, 3813190, 3813205, 3813212, 3813216, 3813248, 3813252, 3813269, 3813295, 3813310,
-        3813319, 3813338, 3813338, 3813338, 3813338, 3813369, 3813391, 3813419, 3813420, 3813450,
-        3813455, 3813509, 3813532, 3813551, 3813570, 3813581, 3813619, 3813636, 3813638, 3813676,
-        3813677, 3813680, 3813707, 3813713, 3813718, 3813728, 3813731, 3813740, 3813804, 3813840,
-        3813858, 3813863, 3813876, 3813922, 3813924, 3813966, 3813972, 3814001, 3814002, 3814014,
-        3814016, 3814037, 3814071, 3814096, 3814099, 3814141, 3814159, 3814163, 3814198, 3814213,
-        3814215, 3814218, 3814237, 3814252, 3814262, 3814289, 3814305, 3814305, 3814305, 3814305,
-        3814360, 3814401, 3814404, 3814412, 3814436, 3814456, 3814456, 3814459, 3814465, 3814516,
-        3814532, 3814541, 3814552, 3814552, 3814552, 3814552, 3814563, 3814575, 3814613, 3814616,
-        3814628, 3814641, 3814668, 3814691, 3814703, 3814703, 3814703, 3814703, 3814732, 3814732,
-        3814762, 3814782, 3814791, 3814792, 3814795, 3814803, 3814841, 3814859, 3814864, 3814868,
-        3814879, 3814913, 3814924, 3814937, 3814948, 3814985, 3814988, 3814995, 3815001, 3815001,
-        3815001, 3815001, 3815011, 3815016, 3815045, 3815102, 3815109, 3815134, 3815162, 3815185,
-        3815215, 3815218, 3815219, 3815228, 3815234, 3815281, 3815289, 3815345, 3815346, 3815356,
-        3815403, 3815421, 3815439, 3815460, 3815465, 3815468, 3815471, 3815523, 3815537, 3815560,
-        3815574, 3815576, 3815589, 3815590, 3815622, 3815667, 3815707, 3815711, 3815741, 3815768,
-        3815838, 3815864, 3815872, 3815881, 3815900, 3815905, 3815905, 3815916, 3815917, 3815921,
-        3815924, 3815931, 3815936, 3815949, 3815975, 3815987, 3816027, 3816051, 3816066, 3816076,
-        3816123, 3816133, 3816157, 3816189, 3816189, 3816200, 3816201, 3816203, 3816219, 3816225,
-        3816235, 3816264, 3816302, 3816304, 3816315, 3816337, 3816339, 3816348, 3816365, 3816383,
-        3816389, 3816396, 3816460, 3816471, 3816487, 3816513, 3816524, 3816535, 3816535, 3816550,
-        3816621, 3816623, 3816650, 3816662, 3816695, 3816730, 3816738, 3816741, 3816745, 3816770,
-        3816809, 3816822, 3816830, 3816836, 3816871, 3816882, 3816887, 3816900, 3816918, 3816940,
-        3816998, 3817018, 3817034, 3817039, 3817047, 3817071, 3817071, 3817071, 3817071, 3817081,
-        3817091, 3817095, 3817096, 3817101, 3817121, 3817133, 3817150, 3817167, 3817191, 3817195,
-        3817203, 3817218, 3817223, 3817223, 3817223, 3817223, 3817227, 3817299, 3817300, 3817302,
-        3817322, 3817339, 3817341, 3817342, 3817358, 3817361, 3817371, 3817401, 3817408, 3817426,
-        3817428, 3817496, 3817515, 3817523, 3817528, 3817532, 3817541, 3817551, 3817569, 3817581,
-        3817638, 3817640, 3817641, 3817645, 3817664, 3817680, 3817689, 3817711, 3817739, 3817745,
-        3817754, 3817761, 3817768, 3817786, 3817859, 3817862, 3817898, 3817905, 3817937, 3817951,
-        3817967, 3817975, 3817975, 3818018, 3818027, 3818036, 3818053, 3818055, 3818060, 3818067,
-        3818088, 3818099, 3818161, 3818162, 3818187, 3818196, 3818208, 3818235, 3818235, 3818235,
-        3818235, 3818237, 3818244, 3818261, 3818279, 3818288, 3818299, 3818325, 3818338, 3818352,
-        3818352, 3818354, 3818354, 3818363, 3818371, 3818378, 3818378, 3818378, 3818378, 3818378,
-        3818378, 3818378, 3818378, 3818378, 3818409, 3818420, 3818425, 3818426, 3818426, 3818426,
-        3818426, 3818432, 3818461, 3818481, 3818507, 3818516, 3818519, 3818519, 3818534, 3818534,
-        3818535, 3818547, 3818551, 3818557, 3818561, 3818567, 3818569, 3818578, 3818625, 3818629,
-        3818637, 3818690, 3818706, 3818729, 3818732, 3818735, 3818744, 3818747, 3818774, 3818774,
-        3818818, 3818820, 3818843, 3818848, 3818849, 3818886, 3818919, 3818957, 3818957, 3818957,
-        3818957, 3818980, 3818981, 3818985, 3818985, 3818985, 3818985, 3818998, 3819014, 3819015,
-        3819017, 3819027, 3819052, 3819063, 3819071, 3819084, 3819112, 3819113, 3819125, 3819151,
-        3819168, 3819168, 3819168, 3819168, 3819169, 3819172, 3819185, 3819196, 3819209, 3819213,
-        3819227, 3819235, 3819252, 3819269, 3819270, 3819274, 3819281, 3819281, 3819289, 3819291,
-        3819294, 3819295, 3819302, 3819303, 3819303, 3819306, 3819307, 3819320, 3819320, 3819340,
-        3819344, 3819356, 3819364, 3819403, 3819416, 3819455, 3819506, 3819512, 3819531, 3819537,
-        3819556, 3819565, 3819585, 3819586, 3819586, 3819586, 3819586, 3819605, 3819662, 3819692,
-        3819694, 3819694, 3819694, 3819694, 3819711, 3819713, 3819720, 3819727, 3819729, 3819736,
-        3819738, 3819741, 3819742, 3819742, 3819762, 3819764, 3819788, 3819797, 3819804, 3819815,
-        3819822, 3819848, 3819851, 3819867, 3819885, 3819896, 3819910, 3819929, 3819935, 3819967,
-        3819978, 3819991, 3820007, 3820014, 3820020, 3820020, 3820033, 3820037, 3820056, 3820064,
-        3820069, 3820074, 3820090, 3820095, 3820095, 3820109, 3820121, 3820132, 3820133, 3820181,
-        3820184, 3820207, 3820213, 3820259, 3820260, 3820261, 3820300, 3820303, 3820306, 3820340,
-        3820350, 3820367, 3820402, 3820421, 3820424, 3820429, 3820432, 3820441, 3820441, 3820441,
-        3820441, 3820476, 3820486, 3820504, 3820519, 3820535, 3820542, 3820559, 3820613, 3820619,
-        3820631, 3820634, 3820653, 3820661, 3820670, 3820683, 3820715, 3820739, 3820757, 3820779,
-        3820780, 3820781, 3820836, 3820856, 3820856, 3820856, 3820856, 3820869, 3820879, 3820888,
-        3820891, 3820902, 3820928, 3820934, 3820971, 3820975, 3820980, 3821002, 3821055, 3821061,
-        3821106, 3821117, 3821143, 3821149, 3821187, 3821241, 3821249, 3821288, 3821295, 3821307,
-        3821317, 3821317, 3821343, 3821352, 3821354, 3821396, 3821432, 3821433, 3821449, 3821466,
-        3821469, 3821481, 3821505, 3821529, 3821543, 3821548, 3821555, 3821555, 3821555, 3821555,
-        3821555, 3821555, 3821556, 3821569, 3821597, 3821630, 3821657, 3821663, 3821665, 3821668,
-        3821702, 3821706, 3821719, 3821722, 3821733, 3821770, 3821794, 3821833, 3821833, 3821844,
-        3821850, 3821870, 3821881, 3821908, 3821914, 3821954, 3821969, 3822031, 3822036, 3822085,
-        3822107, 3822111, 3822131, 3822140, 3822144, 3822177, 3822220, 3822242, 3822301, 3822344,
-        3822346, 3822351, 3822354, 3822417, 3822446, 3822495, 3822506, 3822510, 3822541, 3822545,
-        3822561, 3822575, 3822632, 3822633, 3822669, 3822687, 3822695, 3822707, 3822721, 3822750,
-        3822754, 3822775, 3822784, 3822797, 3822819, 3822859, 3822896, 3822897, 3822908, 3822908,
-        3822908, 3822908, 3822922, 3822969, 3822982, 3823008, 3823056, 3823070, 3823100, 3823104,
-        3823111, 3823118, 3823145, 3823201, 3823207, 3823225, 3823243, 3823243, 3823246, 3823249,
-        3823258, 3823262, 3823274, 3823278, 3823281, 3823329, 3823344, 3823350, 3823353, 3823376,
-        3823377, 3823380, 3823380, 3823389, 3823417, 3823427, 3823429, 3823429, 3823429, 3823429,
-        3823460, 3823470, 3823498, 3823499, 3823499, 3823499, 3823499, 3823500, 3823528, 3823532,
-        3823559, 3823559, 3823563, 3823598, 3823610, 3823639, 3823645, 3823657, 3823693, 3823695,
-        3823726, 3823749, 3823769, 3823770, 3823771, 3823808, 3823812, 3823817, 3823831, 3823891,
-        3823901, 3823909, 3823910, 3823910, 3823910, 3823910, 3823920, 3823944, 3823959, 3823988,
-        3823993, 3824014, 3824058, 3824063, 3824095, 3824152, 3824154, 3824157, 3824221, 3824255,
-        3824256, 3824295, 3824300, 3824315, 3824315, 3824322, 3824343, 3824383, 3824394, 3824428,
-        3824429, 3824448, 3824459, 3824486, 3824486, 3824506, 3824506, 3824506, 3824506, 3824511,
-        3824516, 3824529, 3824541, 3824549, 3824549, 3824559, 3824580, 3824585, 3824632, 3824644,
-        3824649, 3824676, 3824681, 3824686, 3824686, 3824701, 3824719, 3824725, 3824742, 3824747,
-        3824764, 3824782, 3824794, 3824795, 3824852, 3824861, 3824865, 3824873, 3824883, 3824903,
-        3824920, 3824949, 3824952, 3824958, 3824969, 3824969, 3824969, 3824969, 3824997, 3824997,
-        3824997, 3824997, 3825009, 3825009, 3825009, 3825009, 3825015, 3825027, 3825043, 3825049,
-        3825104, 3825104, 3825106, 3825108, 3825138, 3825138, 3825156, 3825174, 3825179, 3825183,
-        3825192, 3825197, 3825201, 3825201, 3825201, 3825201, 3825221, 3825223, 3825232, 3825255,
-        3825264, 3825281, 3825284, 3825286, 3825292, 3825308, 3825313, 3825332, 3825351, 3825351,
-        3825384, 3825393, 3825406, 3825406, 3825429, 3825434, 3825434, 3825447, 3825450, 3825458,
-        3825476, 3825496, 3825498, 3825533, 3825544, 3825547, 3825548, 3825570, 3825611, 3825626,
-        3825646, 3825650, 3825650, 3825650, 3825650, 3825651, 3825651, 3825651, 3825651, 3825664,
-        3825674, 3825677, 3825681, 3825697, 3825697, 3825697, 3825697, 3825702, 3825720, 3825723,
-        3825744, 3825746, 3825775, 3825776, 3825806, 3825808, 3825818, 3825824, 3825825, 3825829,
-        3825873, 3825882, 3825894, 3825943, 3825943, 3825955, 3825964, 3825965, 3825968, 3825971,
-        3825976, 3825992, 3826010, 3826018, 3826022, 3826022, 3826022, 3826022, 3826030, 3826065,
-        3826080, 3826092, 3826105, 3826144, 3826146, 3826173, 3826177, 3826178, 3826181, 3826209,
-        3826216, 3826243, 3826251, 3826266, 3826274, 3826293, 3826318, 3826329, 3826335, 3826369,
-        3826398, 3826400, 3826404, 3826424, 3826447, 3826460, 3826476, 3826496, 3826496, 3826509,
-        3826520, 3826521, 3826526, 3826526, 3826526, 3826526, 3826528, 3826560, 3826589, 3826610,
-        3826611, 3826618, 3826675, 3826675, 3826679, 3826689, 3826703, 3826705, 3826732, 3826742,
-        3826751, 3826761, 3826859, 3826873, 3826880, 3826901, 3826910, 3826913, 3826923, 3826935,
-        3827011, 3827039, 3827063, 3827073, 3827122, 3827149, 3827156, 3827158, 3827162, 3827193,
-        3827200, 3827205, 3827225, 3827226, 3827233, 3827253, 3827309, 3827322, 3827328, 3827350,
-        3827351, 3827364, 3827378, 3827400, 3827424, 3827453, 3827453, 3827453, 3827453, 3827461,
-        3827466, 3827466, 3827466, 3827466, 3827469, 3827479, 3827479, 3827487, 3827491, 3827491,
-        3827494, 3827522, 3827535, 3827537, 3827537, 3827588, 3827679, 3827701, 3827703, 3827724,
-        3827736, 3827759, 3827762, 3827768, 3827781, 3827809, 3827814, 3827859, 3827893, 3827944,
-        3827965, 3827967, 3827975, 3827987, 3827993, 3828004, 3828010, 3828015, 3828042, 3828047,
-        3828057, 3828076, 3828078, 3828078, 3828078, 3828078, 3828083, 3828116, 3828117, 3828118,
-        3828124, 3828127, 3828196, 3828215, 3828222, 3828227, 3828247, 3828261, 3828276, 3828287,
-        3828300, 3828306, 3828317, 3828317, 3828337, 3828337, 3828345, 3828349, 3828360, 3828360,
-        3828366, 3828368, 3828386, 3828419, 3828422, 3828434, 3828434, 3828475, 3828498, 3828517,
-        3828521, 3828540, 3828543, 3828547, 3828554, 3828573, 3828609, 3828652, 3828665, 3828694,
-        3828703, 3828708, 3828713, 3828725, 3828744, 3828789, 3828795, 3828822, 3828824, 3828830,
-        3828835, 3828868, 3828934, 3828959, 3828977, 3828990, 3829016, 3829016, 3829016, 3829016,
-        3829041, 3829067, 3829069, 3829081, 3829085, 3829087, 3829092, 3829102, 3829103, 3829173,
-        3829231, 3829233, 3829254, 3829254, 3829254, 3829254, 3829255, 3829256, 3829266, 3829287,
-        3829303, 3829313, 3829390, 3829401, 3829405, 3829419, 3829452, 3829478, 3829508, 3829513,
-        3829527, 3829528, 3829549, 3829568, 3829575, 3829591, 3829653, 3829714, 3829720, 3829728,
-        3829731, 3829732, 3829757, 3829773, 3829796, 3829797, 3829808, 3829812, 3829813, 3829845,
-        3829845, 3829845, 3829845, 3829846, 3829891, 3829896, 3829942, 3829951, 3829951, 3829951,
-        3829951, 3829951, 3829951, 3830000, 3830013, 3830034, 3830057, 3830063, 3830094, 3830102,
-        3830107, 3830107, 3830107, 3830107, 3830112, 3830132, 3830149, 3830149, 3830149, 3830149,
-        3830159, 3830166, 3830184, 3830184, 3830185, 3830187, 3830209, 3830209, 3830210, 3830213,
-        3830221, 3830229, 3830233, 3830252, 3830276, 3830276, 3830284, 3830294, 3830294, 3830294,
-        3830294, 3830309, 3830353, 3830365, 3830365, 3830377, 3830391, 3830405, 3830412, 3830412,
-        3830412, 3830412, 3830425, 3830434, 3830446, 3830453, 3830463, 3830500, 3830517, 3830522,
-        3830532, 3830564, 3830591, 3830600, 3830616, 3830630, 3830638, 3830643, 3830644, 3830655,
-        3830671, 3830681, 3830682, 3830709, 3830718, 3830736, 3830755, 3830756, 3830821, 3830827,
-        3830842, 3830863, 3830911, 3830911, 3830920, 3830924, 3830925, 3830929, 3830929, 3830944,
-        3830971, 3830972, 3830980, 3831012, 3831018, 3831028, 3831036, 3831056, 3831071, 3831096,
-        3831107, 3831113, 3831120, 3831127, 3831129, 3831133, 3831143, 3831147, 3831179, 3831191,
-        3831196, 3831197, 3831241, 3831241, 3831295, 3831310, 3831315, 3831315, 3831315, 3831315,
-        3831315, 3831327, 3831329, 3831339, 3831339, 3831351, 3831351, 3831351, 3831351, 3831354,
-        3831372, 3831400, 3831401, 3831401, 3831401, 3831401, 3831426, 3831450, 3831453, 3831458,
-        3831463, 3831472, 3831489, 3831497, 3831498, 3831510, 3831538, 3831540, 3831568, 3831591,
-        3831591, 3831591, 3831591, 3831620, 3831620, 3831622, 3831719, 3831755, 3831758, 3831766,
-        3831769, 3831774, 3831775, 3831775, 3831775, 3831775, 3831776, 3831776, 3831776, 3831776,
-        3831776, 3831776, 3831776, 3831776, 3831776, 3831784, 3831788, 3831837, 3831863, 3831863,
-        3831865, 3831865, 3831865, 3831865, 3831865, 3831865, 3831865, 3831865, 3831865, 3831878,
-        3831878, 3831881, 3831882, 3831884, 3831902, 3831921, 3831936, 3831937, 3831945, 3831945,
-        3831950, 3831985, 3832004, 3832036, 3832036, 3832036, 3832036, 3832042, 3832057, 3832072,
-        3832110, 3832115, 3832119, 3832128, 3832156, 3832161, 3832186, 3832235, 3832240, 3832246,
-        3832251, 3832260, 3832294, 3832297, 3832299, 3832299, 3832331, 3832338, 3832358, 3832362,
-        3832407, 3832414, 3832428, 3832438, 3832455, 3832498, 3832513, 3832524, 3832526, 3832526,
-        3832541, 3832541, 3832541, 3832541, 3832548, 3832553, 3832557, 3832598, 3832598, 3832598,
-        3832598, 3832646, 3832648, 3832668, 3832731, 3832735, 3832738, 3832764, 3832772, 3832774,
-        3832805, 3832807, 3832820, 3832828, 3832834, 3832842, 3832848, 3832848, 3832848, 3832848,
-        3832857, 3832866, 3832867, 3832906, 3832913, 3832914, 3832915, 3832947, 3832947, 3832962,
-        3832980, 3832986, 3833003, 3833007, 3833022, 3833045, 3833056, 3833062, 3833086, 3833091,
-        3833098, 3833098, 3833105, 3833111, 3833112, 3833114, 3833170, 3833185, 3833187, 3833196,
-        3833200, 3833236, 3833240, 3833251, 3833261, 3833283, 3833283, 3833303, 3833307, 3833324,
-        3833354, 3833369, 3833387, 3833388, 3833388, 3833388, 3833388, 3833388, 3833388, 3833388,
-        3833388, 3833388, 3833388, 3833388, 3833388, 3833388, 3833388, 3833388, 3833388, 3833389,
-        3833389, 3833389, 3833389, 3833389, 3833389, 3833390, 3833430, 3833434, 3833436, 3833445,
-        3833467, 3833480, 3833510, 3833518, 3833522, 3833531, 3833548, 3833551, 3833565, 3833578,
-        3833583, 3833586, 3833589, 3833591, 3833602, 3833603, 3833626, 3833644, 3833645, 3833657,
-        3833658, 3833662, 3833681, 3833685, 3833724, 3833730, 3833731, 3833768, 3833775, 3833776,
-        3833790, 3833799, 3833799, 3833799, 3833799, 3833799, 3833799, 3833803, 3833811, 3833826,
-        3833826, 3833839, 3833840, 3833842, 3833869, 3833891, 3833892, 3833892, 3833894, 3833896,
-        3833904, 3833930, 3833930, 3833938, 3833947, 3833965, 3833969, 3833993, 3833996, 3834008,
-        3834013, 3834018, 3834025, 3834030, 3834046, 3834046, 3834046, 3834046, 3834098, 3834098,
-        3834098, 3834098, 3834103, 3834144, 3834144, 3834152, 3834154, 3834175, 3834189, 3834190,
-        3834196, 3834196, 3834204, 3834204, 3834204, 3834204, 3834230, 3834238, 3834251, 3834261,
-        3834282, 3834297, 3834302, 3834302, 3834302, 3834302, 3834325, 3834366, 3834391, 3834397,
-        3834441, 3834443, 3834443, 3834443, 3834443, 3834444, 3834455, 3834465, 3834473, 3834506,
-        3834506, 3834533, 3834545, 3834572, 3834590, 3834609, 3834609, 3834632, 3834670, 3834711,
-        3834726, 3834731, 3834751, 3834778, 3834780, 3834782, 3834805, 3834808, 3834830, 3834860,
-        3834861, 3834885, 3834929, 3834937, 3834957, 3834959, 3834966, 3834973, 3834978, 3834990,
-        3834990, 3834990, 3834990, 3835004, 3835005, 3835033, 3835033, 3835033, 3835033, 3835040,
-        3835045, 3835046, 3835079, 3835113, 3835152, 3835177, 3835180, 3835190, 3835223, 3835238,
-        3835244, 3835247, 3835258, 3835263, 3835264, 3835273, 3835276, 3835284, 3835284, 3835284,
-        3835303, 3835323, 3835323, 3835342, 3835342, 3835348, 3835387, 3835399, 3835418, 3835418,
-        3835457, 3835464, 3835465, 3835495, 3835516, 3835518, 3835575, 3835602, 3835602, 3835602,
-        3835602, 3835603, 3835603, 3835603, 3835603, 3835608, 3835618, 3835622, 3835638, 3835649,
-        3835662, 3835678, 3835682, 3835698, 3835717, 3835750, 3835752, 3835761, 3835766, 3835796,
-        3835828, 3835844, 3835871, 3835878, 3835899, 3835911, 3835932, 3835932, 3835938, 3835946,
-        3835967, 3835984, 3836013, 3836013, 3836013, 3836013, 3836014, 3836016, 3836053, 3836069,
-        3836069, 3836069, 3836069, 3836069, 3836069, 3836071, 3836078, 3836079, 3836090, 3836090,
-        3836102, 3836102, 3836110, 3836115, 3836121, 3836122, 3836125, 3836127, 3836128, 3836157,
-        3836159, 3836166, 3836166, 3836188, 3836196, 3836196, 3836196, 3836196, 3836210, 3836217,
-        3836219, 3836227, 3836238, 3836277, 3836281, 3836288, 3836302, 3836304, 3836310, 3836310,
-        3836312, 3836318, 3836318, 3836351, 3836353, 3836365, 3836368, 3836383, 3836383, 3836383,
-        3836383, 3836398, 3836405, 3836429, 3836440, 3836460, 3836472, 3836473, 3836481, 3836490,
-        3836517, 3836518, 3836519, 3836523, 3836525, 3836562, 3836563, 3836566, 3836568, 3836571,
-        3836577, 3836611, 3836619, 3836622, 3836649, 3836654, 3836655, 3836659, 3836680, 3836725,
-        3836728, 3836730, 3836733, 3836800, 3836822, 3836823, 3836835, 3836853, 3836856, 3836872,
-        3836923, 3836931, 3836951, 3836951, 3836951, 3836951, 3836953, 3836953, 3836955, 3836964,
-        3836965, 3836974, 3836975, 3836993, 3837006, 3837025, 3837037, 3837062, 3837064, 3837064,
-        3837066, 3837072, 3837078, 3837082, 3837103, 3837105, 3837111, 3837137, 3837137, 3837137,
-        3837137, 3837138, 3837151, 3837195, 3837203, 3837213, 3837242, 3837251, 3837267, 3837270,
-        3837275, 3837281, 3837281, 3837284, 3837284, 3837284, 3837284, 3837290, 3837300, 3837300,
-        3837300, 3837300, 3837322, 3837334, 3837337, 3837349, 3837355, 3837357, 3837394, 3837407,
-        3837454, 3837454, 3837454, 3837454, 3837457, 3837457, 3837465, 3837476, 3837476, 3837476,
-        3837476, 3837481, 3837496, 3837498, 3837519, 3837527, 3837532, 3837533, 3837543, 3837547,
-        3837563, 3837568, 3837587, 3837595, 3837616, 3837618, 3837630, 3837630, 3837630, 3837630,
-        3837652, 3837657, 3837659, 3837682, 3837706, 3837711, 3837748, 3837770, 3837770, 3837770,
-        3837770, 3837771, 3837771, 3837771, 3837771, 3837778, 3837798, 3837805, 3837816, 3837831,
-        3837842, 3837848, 3837848, 3837848, 3837848, 3837870, 3837889, 3837906, 3837935, 3837936,
-        3837939, 3837939, 3837954, 3837960, 3837968, 3837985, 3838012, 3838012, 3838021, 3838026,
-        3838033, 3838044, 3838047, 3838047, 3838055, 3838077, 3838081, 3838081, 3838089, 3838136,
-        3838149, 3838174, 3838183, 3838183, 3838184, 3838189, 3838193, 3838198, 3838204, 3838215,
-        3838216, 3838224, 3838236, 3838299, 3838301, 3838303, 3838326, 3838336, 3838336, 3838336,
-        3838336, 3838336, 3838336, 3838355, 3838364, 3838370, 3838370, 3838384, 3838394, 3838410,
-        3838423, 3838423, 3838433, 3838443, 3838479, 3838487, 3838492, 3838492, 3838504, 3838517,
-        3838531, 3838532, 3838553, 3838553, 3838564, 3838564, 3838567, 3838575, 3838577, 3838584,
-        3838584, 3838591, 3838616, 3838625, 3838681, 3838681, 3838683, 3838683, 3838726, 3838733,
-        3838755, 3838763, 3838767, 3838795, 3838797, 3838797, 3838797, 3838797, 3838806, 3838818,
-        3838843, 3838857, 3838884, 3838914, 3838923, 3838949, 3838974, 3838981, 3838991, 3838993,
-        3838996, 3839007, 3839031, 3839054, 3839056, 3839069, 3839075, 3839087, 3839151, 3839159,
-        3839164, 3839164, 3839186, 3839187, 3839204, 3839209, 3839209, 3839227, 3839235, 3839247,
-        3839252, 3839263, 3839264, 3839267, 3839269, 3839270, 3839284, 3839341, 3839350, 3839370,
-        3839377, 3839397, 3839432, 3839452, 3839467, 3839476, 3839476, 3839476, 3839476, 3839477,
-        3839477, 3839478, 3839487, 3839518, 3839527, 3839535, 3839546, 3839556, 3839558, 3839587,
-        3839651, 3839667, 3839696, 3839726, 3839733, 3839746, 3839760, 3839765, 3839783, 3839795,
-        3839804, 3839811, 3839811, 3839811, 3839811, 3839829, 3839863, 3839877, 3839888, 3839907,
-        3839925, 3839931, 3839941, 3839941, 3839947, 3839990, 3839998, 3840021, 3840049, 3840051,
-        3840051, 3840051, 3840051, 3840061, 3840062, 3840085, 3840093, 3840096, 3840106, 3840114,
-        3840115, 3840117, 3840128, 3840149, 3840161, 3840166, 3840178, 3840197, 3840208, 3840210,
-        3840211, 3840213, 3840217, 3840217, 3840217, 3840217, 3840222, 3840222, 3840233, 3840251,
-        3840278, 3840286, 3840287, 3840287, 3840287, 3840287, 3840291, 3840306, 3840320, 3840320,
-        3840320, 3840320, 3840320, 3840320, 3840320, 3840320, 3840320, 3840328, 3840346, 3840348,
-        3840366, 3840368, 3840386, 3840395, 3840409, 3840425, 3840446, 3840459, 3840459, 3840459,
-        3840459, 3840461, 3840471, 3840480, 3840488, 3840505, 3840568, 3840585, 3840604, 3840604,
-        3840622, 3840623, 3840640, 3840654, 3840655, 3840670, 3840696, 3840697, 3840701, 3840705,
-        3840721, 3840735, 3840737, 3840792, 3840792, 3840811, 3840845, 3840845, 3840852, 3840862,
-        3840872, 3840872, 3840872, 3840873, 3840889, 3840890, 3840890, 3840915, 3840915, 3840918,
-        3840921, 3840932, 3840932, 3840932, 3840932, 3840949, 3840950, 3840955, 3840958, 3840971,
-        3840971, 3841022, 3841022, 3841022, 3841022, 3841023, 3841042, 3841053, 3841092, 3841103,
-        3841136, 3841137, 3841141, 3841147, 3841150, 3841159, 3841159, 3841162, 3841162, 3841165,
-        3841165, 3841182, 3841203, 3841225, 3841226, 3841249, 3841269, 3841288, 3841301, 3841333,
-        3841348, 3841359, 3841372, 3841379, 3841381, 3841384, 3841406, 3841439, 3841483, 3841489,
-        3841508, 3841512, 3841512, 3841518, 3841535, 3841536, 3841556, 3841556, 3841586, 3841619,
-        3841629, 3841676, 3841681, 3841690, 3841698, 3841712, 3841712, 3841723, 3841725, 3841753,
-        3841769, 3841794, 3841821, 3841829, 3841858, 3841864, 3841915, 3841939, 3841949, 3841949,
-        3841949, 3841949, 3841950, 3841950, 3841950, 3841950, 3841964, 3841990, 3842013, 3842013,
-        3842025, 3842057, 3842075, 3842110, 3842132, 3842139, 3842151, 3842170, 3842178, 3842179,
-        3842204, 3842206, 3842210, 3842210, 3842210, 3842210, 3842215, 3842218, 3842247, 3842278,
-        3842297, 3842322, 3842334, 3842334, 3842356, 3842357, 3842359, 3842371, 3842371, 3842381,
-        3842384, 3842409, 3842432, 3842453, 3842468, 3842477, 3842518, 3842520, 3842528, 3842545,
-        3842546, 3842549, 3842549, 3842553, 3842569, 3842570, 3842579, 3842579, 3842579, 3842609,
-        3842620, 3842639, 3842639, 3842645, 3842675, 3842681, 3842683, 3842683, 3842689, 3842695,
-        3842729, 3842756, 3842760, 3842765, 3842774, 3842791, 3842806, 3842822, 3842828, 3842847,
-        3842880, 3842881, 3842882, 3842893, 3842906, 3842916, 3842922, 3842946, 3842946, 3842946,
-        3842946, 3842953, 3842957, 3842963, 3842964, 3842971, 3842977, 3843027, 3843027, 3843029,
-        3843029, 3843029, 3843041, 3843051, 3843060, 3843093, 3843136, 3843152, 3843156, 3843165,
-        3843169, 3843170, 3843179, 3843179, 3843179, 3843179, 3843183, 3843188, 3843188, 3843188,
-        3843188, 3843226, 3843226, 3843227, 3843229, 3843229, 3843229, 3843229, 3843239, 3843310,
-        3843311, 3843335, 3843346, 3843346, 3843346, 3843346, 3843350, 3843354, 3843362, 3843383,
-        3843383, 3843418, 3843447, 3843448, 3843474, 3843479, 3843493, 3843505, 3843508, 3843518,
-        3843518, 3843518, 3843518, 3843535, 3843547, 3843571, 3843583, 3843590, 3843611, 3843646,
-        3843646, 3843651, 3843652, 3843659, 3843669, 3843687, 3843691, 3843778, 3843801, 3843804,
-        3843851, 3843874, 3843892, 3843909, 3843918, 3843940, 3843945, 3843966, 3843997, 3844029,
-        3844051, 3844137, 3844147, 3844159, 3844166, 3844168, 3844200, 3844206, 3844222, 3844253,
-        3844268, 3844280, 3844299, 3844320, 3844330, 3844342, 3844344, 3844350, 3844357, 3844357,
-        3844369, 3844411, 3844421, 3844438, 3844445, 3844465, 3844477, 3844481, 3844516, 3844533,
-        3844545, 3844561, 3844606, 3844621, 3844696, 3844713, 3844718, 3844722, 3844729, 3844730,
-        3844737, 3844763, 3844804, 3844813, 3844834, 3844836, 3844910, 3844914, 3844922, 3844922,
-        3844922, 3844922, 3844923, 3844926, 3844956, 3844964, 3844964, 3844968, 3844994, 3845028,
-        3845034, 3845049, 3845049, 3845050, 3845092, 3845115, 3845115, 3845115, 3845115, 3845122,
-        3845133, 3845167, 3845173, 3845173, 3845203, 3845225, 3845238, 3845255, 3845260, 3845299,
-        3845299, 3845299, 3845299, 3845329, 3845334, 3845368, 3845375, 3845380, 3845418, 3845427,
-        3845431, 3845433, 3845452, 3845456, 3845464, 3845478, 3845509, 3845519, 3845542, 3845550,
-        3845550, 3845560, 3845569, 3845578, 3845617, 3845622, 3845630, 3845632, 3845635, 3845643,
-        3845650, 3845653, 3845674, 3845679, 3845690, 3845711, 3845740, 3845740, 3845740, 3845740,
-        3845741, 3845761, 3845762, 3845779, 3845798, 3845798, 3845800, 3845821, 3845834, 3845859,
-        3845901, 3845903, 3845906, 3845916, 3845923, 3845925, 3845938, 3845949, 3846041, 3846056,
-        3846060, 3846078, 3846096, 3846171, 3846177, 3846186, 3846194, 3846194, 3846194, 3846195,
-        3846206, 3846215, 3846215, 3846215, 3846215, 3846217, 3846260, 3846300, 3846308, 3846320,
-        3846328, 3846332, 3846338, 3846340, 3846340, 3846347, 3846396, 3846397, 3846398, 3846399,
-        3846402, 3846445, 3846463, 3846472, 3846479, 3846482, 3846486, 3846509, 3846529, 3846561,
-        3846584, 3846590, 3846592, 3846593, 3846600, 3846606, 3846618, 3846708, 3846750, 3846755,
-        3846755, 3846779, 3846802, 3846834, 3846838, 3846890, 3846922, 3846946, 3846957, 3846960,
-        3846974, 3846978, 3846980, 3846982, 3846990, 3846990, 3846990, 3846990, 3846990, 3846990,
-        3846990, 3846990, 3846990, 3847036, 3847045, 3847054, 3847055, 3847061, 3847088, 3847103,
-        3847105, 3847107, 3847119, 3847185, 3847187, 3847214, 3847219, 3847242, 3847253, 3847258,
-        3847258, 3847258, 3847258, 3847271, 3847271, 3847285, 3847289, 3847290, 3847311, 3847326,
-        3847366, 3847368, 3847389, 3847405, 3847435, 3847453, 3847477, 3847496, 3847506, 3847519,
-        3847519, 3847519, 3847519, 3847541, 3847544, 3847547, 3847548, 3847558, 3847558, 3847558,
-        3847558, 3847603, 3847603, 3847611, 3847620, 3847633, 3847678, 3847681, 3847691, 3847766,
-        3847771, 3847787, 3847805, 3847806, 3847806, 3847823, 3847826, 3847843, 3847853, 3847902,
-        3847906, 3847931, 3847947, 3847947, 3847947, 3847947, 3847957, 3847969, 3847973, 3847994,
-        3848001, 3848003, 3848021, 3848021, 3848021, 3848021, 3848024, 3848036, 3848047, 3848049,
-        3848050, 3848053, 3848064, 3848071, 3848088, 3848098, 3848102, 3848102, 3848103, 3848103,
-        3848103, 3848104, 3848119, 3848142, 3848145, 3848145, 3848148, 3848168, 3848169, 3848207,
-        3848232, 3848232, 3848232, 3848232, 3848233, 3848236, 3848245, 3848249, 3848297, 3848310,
-        3848318, 3848332, 3848338, 3848353, 3848416, 3848420, 3848451, 3848452, 3848463, 3848472,
-        3848482, 3848502, 3848515, 3848520, 3848521, 3848521, 3848521, 3848521, 3848525, 3848527,
-        3848567, 3848571, 3848580, 3848603, 3848618, 3848618, 3848619, 3848643, 3848643, 3848669,
-        3848692, 3848710, 3848725, 3848729, 3848729, 3848731, 3848755, 3848756, 3848772, 3848794,
-        3848794, 3848794, 3848794, 3848796, 3848813, 3848838, 3848866, 3848866, 3848869, 3848877,
-        3848884, 3848893, 3848899, 3848908, 3848909, 3848929, 3848971, 3848999, 3849028, 3849058,
-        3849064, 3849068, 3849113, 3849119, 3849135, 3849146, 3849193, 3849213, 3849213, 3849213,
-        3849213, 3849233, 3849255, 3849274, 3849283, 3849287, 3849307, 3849354, 3849363, 3849368,
-        3849369, 3849377, 3849387, 3849417, 3849433, 3849440, 3849445, 3849501, 3849511, 3849512,
-        3849512, 3849526, 3849541, 3849558, 3849560, 3849565, 3849593, 3849614, 3849633, 3849645,
-        3849652, 3849652, 3849652, 3849657, 3849668, 3849672, 3849689, 3849703, 3849704, 3849723,
-        3849724, 3849755, 3849755, 3849798, 3849806, 3849811, 3849817, 3849825, 3849833, 3849841,
-        3849862, 3849877, 3849895, 3849908, 3849911, 3849918, 3849920, 3849934, 3849952, 3849974,
-        3849981, 3849986, 3849992, 3850007, 3850045, 3850056, 3850062, 3850067, 3850070, 3850085,
-        3850095, 3850130, 3850130, 3850130, 3850130, 3850130, 3850130, 3850130, 3850130, 3850130,
-        3850147, 3850150, 3850152, 3850156, 3850161, 3850162, 3850168, 3850172, 3850180, 3850202,
-        3850216, 3850246, 3850251, 3850287, 3850293, 3850295, 3850295, 3850295, 3850295, 3850295,
-        3850295, 3850309, 3850311, 3850346, 3850353, 3850392, 3850436, 3850439, 3850440, 3850451,
-        3850461, 3850474, 3850475, 3850481, 3850490, 3850511, 3850520, 3850616, 3850624, 3850686,
-        3850706, 3850742, 3850759, 3850777, 3850777, 3850786, 3850798, 3850801, 3850866, 3850867,
-        3850867, 3850867, 3850867, 3850873, 3850899, 3850899, 3850899, 3850899, 3850899, 3850899,
-        3850899, 3850899, 3850899, 3850899, 3850899, 3850899, 3850899, 3850899, 3850899, 3850899,
-        3850899, 3850899, 3850899, 3850899, 3850899, 3850899, 3850899, 3850899, 3850899, 3850907,
-        3850913, 3850917, 3850917, 3850917, 3850917, 3850923, 3850923, 3850923, 3850923, 3850923,
-        3850923, 3850923, 3850923, 3850923, 3850928, 3850964, 3850971, 3850980, 3850996, 3850996,
-        3851009, 3851031, 3851038, 3851044, 3851049, 3851060, 3851067, 3851075, 3851120, 3851167,
-        3851171, 3851226, 3851231, 3851247, 3851251, 3851256, 3851257, 3851271, 3851273, 3851292,
-        3851331, 3851335, 3851338, 3851375, 3851402, 3851410, 3851423, 3851464, 3851484, 3851485,
-        3851507, 3851514, 3851519, 3851523, 3851551, 3851555, 3851565, 3851625, 3851629, 3851641,
-        3851650, 3851668, 3851686, 3851712, 3851721, 3851725, 3851731, 3851759, 3851766, 3851775,
-        3851789, 3851800, 3851800,
-    ];
-
-    let mut problematic_blocks = vec![];
+fn verify_proof(args: &VerifyProofArgs) -> Result<Status> {
+    let json = if args.proof == Path::new("-") {
+        io::read_to_string(io::stdin())?
+    } else {
+        fs::read_to_string(&args.proof)
+            .with_context(|| format!("failed to read proof {}", args.proof.display()))?
+    };
+    let proof: MerkleProof = serde_json::from_str(&json)
+        .with_context(|| format!("failed to parse proof {}", args.proof.display()))?;
 
-    for block_height in block_heights {
-        // Query for the specific block
-        let query = BlockQuery::build(BlockQueryVariables {
-            height: Some(U32(block_height)),
+    let root = &args.transactions_root;
+    let (included, tx_id) = match (&args.tx, &args.tx_id) {
+        (Some(tx), _) => (proof.verify_leaf(root, tx), proof.tx_id),
+        (None, Some(tx_id)) => {
+            let chain_id = ChainId::new(args.chain_id.unwrap_or_default());
+            let included = proof.transaction_id(&chain_id)? == *tx_id && proof.verify(root);
+            (included, *tx_id)
+        }
+        (None, None) => (proof.verify(root), proof.tx_id),
+    };
+
+    if included {
+        println!(
+            "Transaction {tx_id} is included in block #{} with transactions root {root}",
+            proof.block_height
+        );
+        Ok(Status::Valid)
+    } else {
+        println!("Proof doesn't show transaction {tx_id} in transactions root {root}");
+        Ok(Status::Mismatch)
+    }
+}
+
+/// A validator for the endpoint, with the concurrency, retry policy, rate limit,
+/// timeout, receipt fallback, cancellation and cache of `cli` but none of the optional checks.
+fn connect(cli: &Cli) -> Result<BlockValidator> {
+    connect_to(cli, cli.endpoint())
+}
+
+/// [`connect`], for the endpoint at `url`.
+fn connect_to(cli: &Cli, url: &str) -> Result<BlockValidator> {
+    let validator = BlockValidator::new(url)?
+        .with_concurrency(cli.jobs as usize)
+        .with_page_size(cli.page_size)
+        .with_retry_policy(RetryPolicy {
+            max_attempts: cli.max_attempts,
+            initial_backoff: cli.retry_backoff,
+            ..RetryPolicy::default()
+        })
+        .with_rate_limit(RateLimit {
+            max_rps: cli.max_rps,
+            max_in_flight: cli.max_in_flight.map(|max| max as usize),
+        })
+        .with_receipt_fallback(!cli.no_receipt_fallback)
+        .with_cancellation(cli.cancel.clone());
+    let validator = match cli.timeout {
+        Some(timeout) => validator.with_timeout(timeout),
+        None => validator,
+    };
+    let validator = match &cli.cassette {
+        Some(cassette) => validator.with_cassette(cassette.clone()),
+        None => validator,
+    };
+    Ok(match open_cache(cli) {
+        Some(cache) => validator.with_cache(Arc::new(cache)),
+        None => validator,
+    })
+}
+
+/// The block cache of `cli`, unless it is disabled. Blocks are fetched
+/// anyway if it can't be opened.
+fn open_cache(cli: &Cli) -> Option<BlockCache> {
+    // Cached blocks wouldn't be recorded, or would hide the replayed ones.
+    if cli.no_cache || cli.follow || cli.cassette.is_some() {
+        return None;
+    }
+    let Some(dir) = cli.cache_dir.clone().or_else(BlockCache::default_dir) else {
+        tracing::debug!("no cache directory known, blocks won't be cached");
+        return None;
+    };
+    match BlockCache::open(&dir) {
+        Ok(cache) => Some(cache.with_max_size(cli.cache_max_size)),
+        Err(e) => {
+            tracing::warn!("{e}, blocks won't be cached");
+            None
+        }
+    }
+}
+
+#[cfg(feature = "rocksdb")]
+fn open_rocksdb(path: &Path) -> Result<fuel_root_validation::RocksDbSource> {
+    Ok(fuel_root_validation::RocksDbSource::open(path)?)
+}
+
+#[cfg(not(feature = "rocksdb"))]
+fn open_rocksdb(_: &Path) -> Result<fuel_root_validation::MemorySource> {
+    bail!("--rocksdb requires building with `--features rocksdb`")
+}
+
+async fn export(cli: &Cli, args: &ExportArgs) -> Result<Status> {
+    let validator = connect(cli)?;
+    fs::create_dir_all(&args.out)
+        .with_context(|| format!("failed to create {}", args.out.display()))?;
+
+    let mut exported = 0;
+    let mut save = |block: FullBlock| {
+        let dump = BlockDump::from(&block);
+        dump.save(args.out.join(dump.file_name(args.format)), args.format)?;
+        exported += 1;
+        Ok(())
+    };
+
+    if cli.from_height.is_some() && cli.to_height.is_none() {
+        bail!("--from-height requires --to-height");
+    }
+    match cli.range() {
+        Some((from, to)) => validator.fetch_range_with(from, to, &mut save).await?,
+        None if cli.block_heights.is_empty() => {
+            bail!("export requires --block-height or --from-height and --to-height")
+        }
+        None => {
+            for height in validator.resolve_targets(&cli.block_heights).await? {
+                save(validator.fetch_block(height).await?)?;
+            }
+        }
+    }
+
+    tracing::info!("exported {exported} blocks to {}", args.out.display());
+    Ok(Status::Valid)
+}
+
+async fn save_consensus_parameters(cli: &Cli, args: &ConsensusParametersArgs) -> Result<Status> {
+    let params = connect(cli)?.fetch_all_consensus_parameters().await?;
+    params.save(&args.out)?;
+
+    tracing::info!(
+        "wrote {} consensus parameters versions to {}",
+        params.versions().count(),
+        args.out.display()
+    );
+    Ok(Status::Valid)
+}
+
+async fn bisect(cli: &Cli, args: &BisectArgs) -> Result<Status> {
+    let validator = connect(cli)?;
+    let other = connect_to(cli, &args.against)?;
+    let from = cli.from_height.unwrap_or(0);
+    let to = match cli.to_height {
+        Some(to) => to,
+        None => validator
+            .latest_height()
+            .await?
+            .min(other.latest_height().await?),
+    };
+
+    let bisection =
+        bisect::bisect(validator.source(), other.source(), &args.against, from, to).await?;
+    if cli.output.is_text() {
+        match &bisection.divergence {
+            Some(divergence) => {
+                println!(
+                    "Endpoints first diverge at block #{} ({} blocks compared):",
+                    divergence.height,
+                    bisection.probes.len()
+                );
+                for difference in &divergence.differences {
+                    println!("  {difference}");
+                }
+            }
+            None => println!("Endpoints agree on every block of {from}..={to}"),
+        }
+    } else {
+        println!("{}", serde_json::to_string_pretty(&bisection)?);
+    }
+
+    Ok(match bisection.divergence {
+        Some(_) => Status::Disagreement,
+        None => Status::Valid,
+    })
+}
+
+async fn validate_tx(mut cli: Cli, args: &ValidateTxArgs) -> Result<Status> {
+    let target = cli.block_heights.first().copied();
+    let validator = build_validator(&mut cli).await?;
+    let block_height = match target {
+        Some(target) => validator.resolve_targets(&[target]).await?.pop(),
+        None => None,
+    };
+    let started = Instant::now();
+    let result = match block_height {
+        Some(height) => validator.validate_transaction_at(height, &args.tx_id).await,
+        None => validator.validate_transaction(&args.tx_id).await,
+    };
+    let outcome = BlockOutcome {
+        height: result
+            .as_ref()
+            .map_or(block_height.unwrap_or_default(), |report| report.height),
+        result,
+        duration: started.elapsed(),
+        timing: None,
+    };
+
+    match (&outcome.result, cli.output.is_text()) {
+        (Ok(report), true) => {
+            println!(
+                "Transaction {:#x} is part of block #{} ({})",
+                args.tx_id, report.height, report.block_id
+            );
+            for check in &report.checks {
+                println!("  {check}");
+            }
+        }
+        (Err(e), true) => println!("Transaction {:#x} failed validation: {e:#}", args.tx_id),
+        (_, false) => println!("{}", serde_json::to_string_pretty(&outcome)?),
+    }
+    Ok(Status::of_outcome(&outcome))
+}
+
+async fn export_proof(mut cli: Cli, args: &ExportProofArgs) -> Result<Status> {
+    let target = cli.block_heights.first().copied();
+    let validator = build_validator(&mut cli).await?;
+    let height = match target {
+        Some(target) => validator.resolve_targets(&[target]).await?[0],
+        None => validator.fetch_transaction_height(&args.tx).await?,
+    };
+    let bundle = validator.prove_transaction(height, &args.tx).await?;
+
+    match args.format {
+        ProofFormat::Json => println!("{}", serde_json::to_string_pretty(&bundle)?),
+        ProofFormat::Hex => println!("0x{}", hex::encode(bundle.to_bytes()?)),
+    }
+    tracing::info!(
+        block_height = height,
+        receipts = bundle.receipts.len(),
+        "exported the proof of transaction {:#x}",
+        args.tx
+    );
+    Ok(Status::Valid)
+}
+
+async fn inspect(mut cli: Cli, args: &InspectArgs) -> Result<Status> {
+    let validator = build_validator(&mut cli).await?;
+    let block = validator.fetch_block(args.height).await?;
+    let transactions = inspect::inspect(&block);
+
+    if cli.output.is_text() {
+        println!(
+            "Block #{} ({}), {} transactions:",
+            block.height(),
+            block.id,
+            transactions.len()
+        );
+        for tx in &transactions {
+            print!("{tx}");
+        }
+    } else {
+        let inspection = serde_json::json!({
+            "height": block.height(),
+            "block_id": block.id.to_string(),
+            "transactions": transactions,
         });
+        println!("{}", serde_json::to_string_pretty(&inspection)?);
+    }
+    Ok(Status::Valid)
+}
 
-        let response = fuel_client
-            .query(query)
-            .await
-            .context("failed to query block")?;
+fn corrupt(args: &CorruptArgs) -> Result<Status> {
+    let mut blocks = dump::load(&args.block)?;
+    if blocks.len() != 1 {
+        bail!(
+            "expected one block in {}, found {}",
+            args.block.display(),
+            blocks.len()
+        );
+    }
+    let mut block = BlockDump::from(&blocks.remove(0));
+    for mutation in &args.mutations {
+        mutation.apply(&mut block)?;
+    }
+    if let Some(dir) = args.out.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    block.save(&args.out, args.format)?;
+
+    tracing::info!(
+        block_height = block.header.height,
+        "wrote the block with {} mutations to {}",
+        args.mutations.len(),
+        args.out.display()
+    );
+    Ok(Status::Valid)
+}
+
+/// Compares the snapshot of `args` with the blocks of `--endpoint`, or of
+/// `--input` or `--rocksdb`, up to its height.
+async fn check_snapshot(cli: &Cli, args: &CheckSnapshotArgs) -> Result<Status> {
+    let snapshot = Snapshot::load(&args.snapshot)?;
+    let Some(height) = args.height.or(snapshot.height()) else {
+        bail!("the snapshot has no last block, give the --height it was taken at");
+    };
+    let validator = match (&cli.input, &cli.rocksdb) {
+        (Some(path), _) => BlockValidator::from_source(FileSource::open(path)?)
+            .with_cancellation(cli.cancel.clone()),
+        (None, Some(path)) => {
+            BlockValidator::from_source(open_rocksdb(path)?).with_cancellation(cli.cancel.clone())
+        }
+        (None, None) => connect(cli)?,
+    };
+    let validator = match &cli.consensus_parameters {
+        Some(path) => validator.with_consensus_parameters(ChainParameters::load(path)?),
+        None => validator,
+    };
+    let validator = match cli.chain_id {
+        Some(chain_id) => validator.with_chain_id(ChainId::new(chain_id)),
+        None => validator,
+    };
+
+    let comparison = tokio::select! {
+        compared = snapshot::check_snapshot(&validator, &snapshot, height) => compared?,
+        () = cli.cancel.cancelled() => return Err(ValidationError::Cancelled.into()),
+    };
+    match cli.output {
+        OutputFormat::Text | OutputFormat::Csv => {
+            for mismatch in &comparison.chain {
+                println!("Block #{} {}", mismatch.height, mismatch.result);
+            }
+            if !comparison.differences.is_empty() {
+                println!(
+                    "The snapshot differs from the chain in {} fields:",
+                    comparison.differences.len()
+                );
+            }
+            for difference in &comparison.differences {
+                let value =
+                    |value: &Option<String>| value.as_deref().unwrap_or("missing").to_string();
+                println!(
+                    "  {}: {} from the blocks, {} in the snapshot",
+                    difference.path,
+                    value(&difference.expected),
+                    value(&difference.actual)
+                );
+            }
+            println!(
+                "Compared the snapshot with {} blocks up to #{height}: {}",
+                comparison.blocks,
+                if comparison.is_consistent() {
+                    "consistent".to_string()
+                } else {
+                    format!(
+                        "{} blocks fail, {} fields differ",
+                        comparison.chain.len(),
+                        comparison.differences.len()
+                    )
+                }
+            );
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&comparison)?),
+        OutputFormat::Ndjson => println!("{}", serde_json::to_string(&comparison)?),
+    }
+    Ok(if comparison.is_consistent() {
+        Status::Valid
+    } else {
+        Status::Mismatch
+    })
+}
+
+fn self_test(args: &SelfTestArgs) -> Result<Status> {
+    let fixtures = self_test::fixtures();
+    let mut status = Status::Valid;
+    for fixture in &fixtures {
+        let report = fixture.validate();
+        let transactions = fixture.block.transactions.len();
+        if report.passed() {
+            println!(
+                "{}: block #{} with {transactions} transactions passed {} checks",
+                fixture.name,
+                report.height,
+                report.checks.len()
+            );
+        } else {
+            println!("{}: block #{} failed:", fixture.name, report.height);
+            for failure in report.failures() {
+                println!("  {failure}");
+            }
+            status = status.worst(Status::Mismatch);
+        }
+
+        if args.bench_merkle {
+            let started = Instant::now();
+            for _ in 0..args.iterations {
+                std::hint::black_box(checks::tx_root(&fixture.block));
+                std::hint::black_box(checks::receipt_roots(&fixture.block));
+            }
+            let per_block = started.elapsed() / args.iterations;
+            println!(
+                "  roots in {per_block:?} per block, {:.0} transactions/s",
+                transactions as f64 / per_block.as_secs_f64()
+            );
+            status = status.worst(bench_hash_backends(fixture, args.iterations));
+        }
+    }
 
-        let block = response.block.ok_or_else(|| anyhow!("no block found"))?;
+    if status == Status::Valid {
+        println!(
+            "Self-test passed: this build computes the known roots of {} blocks",
+            fixtures.len()
+        );
+    } else {
+        println!("Self-test failed: this build doesn't compute the known roots");
+    }
+    Ok(status)
+}
 
-        println!("Validating block height: {}", block_height);
+/// Times the root over the transactions of `fixture` with every hash
+/// backend of the build, and checks they agree with sha2.
+fn bench_hash_backends(fixture: &self_test::Fixture, iterations: u32) -> Status {
+    let leaves: Vec<&[u8]> = fixture
+        .block
+        .transactions
+        .iter()
+        .map(|tx| tx.raw_payload.0 .0.as_slice())
+        .collect();
+    let root_with = |backend: &dyn HashBackend| {
+        let hashes = leaves
+            .iter()
+            .map(|leaf| merkle::leaf_hash_with(backend, leaf));
+        merkle::root_with(backend, hashes)
+    };
+    let expected = root_with(&hash::Sha2);
 
-        // Validate transaction root
-        let tx_root: Bytes32 = block.header.transactions_root.clone().into();
-        let mut calculated_tx_root = MerkleRootCalculator::new();
+    let mut status = Status::Valid;
+    for backend in hash::backends() {
+        let started = Instant::now();
+        for _ in 0..iterations {
+            std::hint::black_box(root_with(backend));
+        }
+        let per_block = started.elapsed() / iterations;
+        if root_with(backend) == expected {
+            println!("  {}: transactions hashed in {per_block:?}", backend.name());
+        } else {
+            println!("  {}: computes a different root than sha2", backend.name());
+            status = Status::Mismatch;
+        }
+    }
+    status
+}
 
-        for tx in &block.transactions {
-            let tx_id = tx.id.to_string();
+async fn diff(cli: &Cli, args: &DiffArgs) -> Result<Status> {
+    let (from, to) = args.range;
+    let local: Box<dyn BlockSource> = match (&cli.input, &cli.rocksdb) {
+        (Some(path), _) => Box::new(FileSource::open(path)?),
+        (None, Some(path)) => Box::new(open_rocksdb(path)?),
+        (None, None) => {
+            bail!("diff requires --rocksdb or --input, the blocks --endpoint is compared with")
+        }
+    };
+    let validator = connect(cli)?;
 
-            let receipts = match &tx.status {
-                Some(TransactionStatus::SuccessStatus(status)) => &status.receipts,
-                Some(TransactionStatus::FailureStatus(status)) => {
+    let (mut compared, mut differing) = (0, vec![]);
+    let diffing = diff::diff_range(local.as_ref(), validator.source(), from, to, |block| {
+        compared += 1;
+        if block.is_identical() {
+            return;
+        }
+        match cli.output {
+            OutputFormat::Text => {
+                println!(
+                    "Block #{} differs in {} fields:",
+                    block.height,
+                    block.differences.len()
+                );
+                for difference in &block.differences {
+                    let value =
+                        |value: &Option<String>| value.as_deref().unwrap_or("missing").to_string();
                     println!(
-                        "Found failed transaction: {} with reason: {}",
-                        tx_id, status.reason
+                        "  {}: {} locally, {} on {}",
+                        difference.path,
+                        value(&difference.expected),
+                        value(&difference.actual),
+                        cli.endpoint()
                     );
-                    &status.receipts
                 }
-                _ => continue,
-            };
+            }
+            OutputFormat::Ndjson => match serde_json::to_string(&block) {
+                Ok(line) => println!("{line}"),
+                Err(e) => {
+                    tracing::error!(block_height = block.height, "failed to serialize diff: {e}")
+                }
+            },
+            OutputFormat::Json | OutputFormat::Csv => {}
+        }
+        differing.push(block);
+    });
+    tokio::select! {
+        diffed = diffing => diffed?,
+        () = cli.cancel.cancelled() => return Err(ValidationError::Cancelled.into()),
+    }
+
+    match cli.output {
+        OutputFormat::Text => println!(
+            "Compared {compared} blocks of {from}..={to}: {} differ",
+            differing.len()
+        ),
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "from": from,
+                "to": to,
+                "compared": compared,
+                "blocks": differing,
+            }))?
+        ),
+        OutputFormat::Ndjson | OutputFormat::Csv => {}
+    }
+    Ok(if differing.is_empty() {
+        Status::Valid
+    } else {
+        Status::Disagreement
+    })
+}
+
+async fn revalidate(mut cli: Cli, args: &RevalidateArgs) -> Result<Status> {
+    let path = args.from_report.display();
+    let report = FailureReport::load(&args.from_report)?;
+    if report.is_empty() {
+        if cli.output.is_text() {
+            println!("No failed blocks in {path}");
+        }
+        return Ok(Status::Valid);
+    }
+
+    let validator = build_validator(&mut cli).await?;
+    let registered: BTreeSet<_> = validator.checks().names().collect();
+    let endpoints: BTreeSet<_> = validator.cross_check_endpoints().collect();
+    for block in &report.blocks {
+        for check in block
+            .checks
+            .iter()
+            .filter(|check| !registered.contains(check.as_str()))
+        {
+            tracing::warn!(
+                block_height = block.height,
+                "check {check} isn't registered, it won't be run"
+            );
+        }
+        for url in block
+            .endpoints
+            .iter()
+            .filter(|url| !endpoints.contains(url.as_str()))
+        {
+            tracing::warn!(
+                block_height = block.height,
+                "endpoint {url} isn't given with --endpoint, it won't be queried"
+            );
+        }
+    }
+
+    let mut reporter = Reporter::new(cli.output);
+    reporter.quiet = cli.quiet;
+    if cli.output == OutputFormat::Csv {
+        reporter.csv = Some(CsvWriter::create(cli.out_file.as_deref())?);
+    }
+    let mut validation = RangeValidation::default();
+    for block in &report.blocks {
+        if reporter.announces_blocks() {
+            println!("Validating block height: {}", block.height);
+        }
+        let outcome = narrowed(&validator, block)?
+            .validate_height(block.height)
+            .await;
+        if matches!(&outcome.result, Err(e) if e.is_cancelled()) {
+            return Err(ValidationError::Cancelled.into());
+        }
+        reporter.block(&outcome);
+        validation.outcomes.push(outcome);
+    }
+    reporter.flush().await;
+
+    if reporter.format.is_text() {
+        let still_failing: Vec<_> = validation
+            .outcomes
+            .iter()
+            .filter(|outcome| !outcome.passed())
+            .map(|outcome| outcome.height)
+            .collect();
+        println!(
+            "Revalidated {} blocks from {path}: {} passed, {} still fail: {still_failing:?}",
+            validation.outcomes.len(),
+            validation.passed(),
+            validation.failed(),
+        );
+        print_timing(&validation);
+    }
+    reporter.format.finish(&validation)?;
+    Ok(Status::of_outcomes(&validation.outcomes))
+}
 
-            // Parse transaction
-            let tx_body = Transaction::from_bytes(tx.raw_payload.0 .0.as_slice())
-                .map_err(|e| anyhow!("{e}"))
-                .context("failed to parse transaction")?;
+/// `validator` running only the checks and querying only the cross-checked
+/// endpoints `block` failed, or `validator` itself when the report doesn't
+/// say what failed.
+fn narrowed(validator: &BlockValidator, block: &FailedBlock) -> Result<BlockValidator> {
+    if block.checks.is_empty() {
+        return Ok(validator.clone());
+    }
+    let narrowed = validator
+        .checks()
+        .names()
+        .try_fold(validator.clone(), |narrowed, name| {
+            narrowed.with_check_enabled(name, block.checks.contains(name))
+        })?;
+    Ok(narrowed.retain_cross_check_endpoints(|url| block.endpoints.contains(url)))
+}
 
-            // Add to merkle tree
-            calculated_tx_root.push(&tx_body.to_bytes());
+/// The validator of `cli`, with every check it enables. Selects every block
+/// of `--input` when no blocks are given.
+async fn build_validator(cli: &mut Cli) -> Result<BlockValidator> {
+    let validator = match (&cli.input, &cli.rocksdb) {
+        (Some(path), _) => {
+            let source = FileSource::open(path)?;
+            // Validate every block of the input unless told otherwise.
+            if cli.block_heights.is_empty() && cli.from_height.is_none() {
+                let (from, to) = source
+                    .bounds()
+                    .ok_or_else(|| anyhow!("no blocks found in {}", path.display()))?;
+                (cli.from_height, cli.to_height) = (Some(from), Some(to));
+            }
+            BlockValidator::from_source(source)
+                .with_concurrency(cli.jobs as usize)
+                .with_page_size(cli.page_size)
+                .with_cancellation(cli.cancel.clone())
+        }
+        (None, Some(path)) => BlockValidator::from_source(open_rocksdb(path)?)
+            .with_concurrency(cli.jobs as usize)
+            .with_page_size(cli.page_size)
+            .with_cancellation(cli.cancel.clone()),
+        (None, None) => connect(cli)?,
+    };
+    let validator = validator
+        .with_timing(cli.timing)
+        .with_keep_going(cli.keep_going)
+        .with_fail_on(cli.fail_on)
+        .with_headers_only(cli.headers_only)
+        .with_prev_root_check(cli.check_prev_root || cli.headers_only)
+        .with_event_inbox_check(cli.check_event_inbox || cli.l1_events.is_some());
+    let validator = cli.checks.iter().try_fold(validator, |validator, name| {
+        validator.with_check_enabled(name, true)
+    })?;
+    let validator = cli
+        .skip_checks
+        .iter()
+        .try_fold(validator, |validator, name| {
+            validator.with_check_enabled(name, false)
+        })?;
+    let validator = cli
+        .warn_checks
+        .iter()
+        .try_fold(validator, |validator, name| {
+            validator.with_check_severity(name, Severity::Warning)
+        })?;
+    let validator = cli
+        .cross_check_endpoints()
+        .iter()
+        .try_fold(validator, |validator, url| {
+            validator.with_cross_check_endpoint(url)
+        })?;
+    let validator = match &cli.l1_events {
+        Some(path) => validator.with_l1_events(EventInbox::load(path)?),
+        None => validator,
+    };
+    let validator = match cli.max_memory {
+        Some(bytes) => validator.with_max_memory(bytes),
+        None => validator,
+    };
+    let validator = match &cli.known_anomalies {
+        Some(path) => {
+            let anomalies = KnownAnomalies::load(path)?;
+            tracing::info!(
+                "acknowledging {} known anomalies from {}",
+                anomalies.len(),
+                path.display()
+            );
+            validator.with_known_anomalies(anomalies)
+        }
+        None => validator,
+    };
+    let validator = match cli.poa_authority {
+        Some(genesis_signing_key) => validator.with_poa_authority(PoaAuthority {
+            genesis_signing_key,
+            signing_key_overrides: cli.signing_key_overrides.clone(),
+        }),
+        None => validator,
+    };
+    let validator = match cli.l1_rpc_url.clone().zip(cli.l1_chain_state) {
+        Some((url, contract)) => validator.with_l1_chain_state(L1ChainState::new(url, contract)),
+        None => validator,
+    };
+    let validator = match &cli.consensus_parameters {
+        Some(path) => validator.with_consensus_parameters(ChainParameters::load(path)?),
+        None if cli.reads_local_blocks() => validator,
+        None => load_consensus_parameters(validator).await?,
+    };
+    Ok(match (cli.chain_id, validator.chain_id()) {
+        (Some(chain_id), _) => validator.with_chain_id(ChainId::new(chain_id)),
+        (None, Some(_)) => validator,
+        (None, None) => {
+            tracing::warn!("no --chain-id given, transaction ids won't be checked");
+            validator
+        }
+    })
+}
 
-            // Validate receipt root for Script transactions
-            if let Transaction::Script(tx_body) = tx_body {
-                let receipt_root = *tx_body.receipts_root();
-                let mut calculated_receipt_root = MerkleRootCalculator::new();
+/// Validates with the consensus parameters of the node. Parameters this
+/// version can't decode only leave the chain id to validate with.
+async fn load_consensus_parameters(validator: BlockValidator) -> Result<BlockValidator> {
+    match validator.clone().load_consensus_parameters().await {
+        Ok(validator) => Ok(validator),
+        Err(e @ ValidationError::InvalidConsensusParameters { .. }) => {
+            tracing::warn!("{e}, only the chain id of the node is used");
+            let chain_id = validator.fetch_chain_id().await?;
+            Ok(validator.with_chain_id(chain_id))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn serve(mut cli: Cli, args: &ServeArgs) -> Result<Status> {
+    let validator = Arc::new(build_validator(&mut cli).await?);
+    server::serve(args.addr, validator, cli.cancel.cancelled_owned())
+        .await
+        .with_context(|| format!("failed to serve the validation API on {}", args.addr))?;
+    Ok(Status::Valid)
+}
+
+/// Resolves on the next SIGINT, or never if it can't be listened for.
+async fn sigint() {
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        tracing::error!("failed to listen for SIGINT: {e}");
+        future::pending::<()>().await;
+    }
+}
+
+/// Cancels `cancel` on the first SIGINT, or once `deadline` has passed, so
+/// the run stops cleanly. A second SIGINT exits right away.
+async fn cancel_on_sigint(cancel: CancellationToken, deadline: Option<Duration>) {
+    let deadline_passed = async {
+        match deadline {
+            Some(deadline) => tokio::time::sleep(deadline).await,
+            None => future::pending().await,
+        }
+    };
+    tokio::select! {
+        () = sigint() => tracing::info!("received SIGINT, shutting down"),
+        () = deadline_passed => tracing::warn!(
+            "run deadline of {} reached, shutting down",
+            humantime::format_duration(deadline.unwrap_or_default())
+        ),
+    }
+    cancel.cancel();
+
+    sigint().await;
+    tracing::warn!("received a second SIGINT, exiting");
+    std::process::exit(130);
+}
+
+async fn run(mut cli: Cli, matches: &ArgMatches) -> Result<Status> {
+    let validator = build_validator(&mut cli).await?;
+    let metrics = cli
+        .metrics_addr
+        .map(|addr| (addr, Arc::new(Metrics::new())));
+    let validator = match &metrics {
+        Some((_, metrics)) => validator.with_metrics(metrics.clone()),
+        None => validator,
+    };
+
+    let mut reporter = reporter(&cli).await?;
+    let status = dispatch(&cli, matches, &validator, &mut reporter, metrics).await;
+    reporter.flush().await;
+    status
+}
+
+/// Where the results of `cli` are reported: stdout, and the dashboard,
+/// results database, webhook and event bus it sets up.
+async fn reporter(cli: &Cli) -> Result<Reporter> {
+    let mut reporter = Reporter::new(cli.output);
+    reporter.quiet = cli.quiet || cli.tui;
+    if cli.tui {
+        let source = match cli.input.as_ref().or(cli.rocksdb.as_ref()) {
+            Some(path) => path.display().to_string(),
+            None => cli.endpoint().to_string(),
+        };
+        reporter.dashboard = Some(Arc::new(Mutex::new(Dashboard::new(source))));
+    }
+    if cli.output == OutputFormat::Csv {
+        reporter.csv = Some(CsvWriter::create(cli.out_file.as_deref())?);
+    }
+    if let Some(path) = &cli.db {
+        let origin = match cli.input.as_ref().or(cli.rocksdb.as_ref()) {
+            Some(path) => path.display().to_string(),
+            None => cli.endpoint().to_string(),
+        };
+        reporter.store = Some(
+            match path.to_str().filter(|db| store::is_postgres_url(db)) {
+                Some(url) => ResultStore::connect(url, &cli.profile.name, &origin)?,
+                None => ResultStore::open(path, &origin)?,
+            },
+        );
+    }
+    if let Some(url) = &cli.webhook_url {
+        reporter.webhook = Some(Webhook::new(url, cli.webhook_format));
+    }
+    if let Some(url) = &cli.events_url {
+        let publisher = EventPublisher::connect(url, &cli.events_topic).await?;
+        reporter.events = Some(EventQueue::new(publisher));
+    }
+
+    Ok(reporter)
+}
+
+async fn dispatch(
+    cli: &Cli,
+    matches: &ArgMatches,
+    validator: &BlockValidator,
+    reporter: &mut Reporter,
+    metrics: Option<(SocketAddr, Arc<Metrics>)>,
+) -> Result<Status> {
+    if cli.follow {
+        return follow_chain(cli, matches, validator, reporter, metrics).await;
+    }
+
+    if cli.from_height.is_some() && cli.to_height.is_none() {
+        bail!("--from-height requires --to-height unless --follow is given");
+    }
+
+    if let Some(count) = cli.sample {
+        let (from, to) = cli
+            .range()
+            .ok_or_else(|| anyhow!("--sample requires --range or --from-height and --to-height"))?;
+        let seed = cli.seed.unwrap_or_else(sample::random_seed);
+        tracing::info!(seed, "sampling {count} blocks of {from}..={to}");
+        let heights = sample::sample_heights(from, to, count.try_into()?, seed);
+        if reporter.format.is_text() {
+            println!(
+                "Sampled {} blocks of {from}..={to}, rerun with --seed {seed} to check them again",
+                heights.len()
+            );
+        }
+        return validate_heights(validator, heights, reporter).await;
+    }
+
+    if let Some(path) = &cli.checkpoint {
+        let checkpoint = load_checkpoint(path, cli.resume, cli.range())?;
+        let (from, to) = (checkpoint.from, checkpoint.to);
+        log_upgrades(&cli.profile, from, to);
+        return validate_range(validator, from, to, reporter, Some((path, checkpoint))).await;
+    }
+
+    if let Some((from, to)) = cli.range() {
+        log_upgrades(&cli.profile, from, to);
+        return validate_range(validator, from, to, reporter, None).await;
+    }
+
+    let block_heights = if cli.block_heights.is_empty() {
+        REPRO_BLOCK_HEIGHTS.to_vec()
+    } else {
+        validator.resolve_targets(&cli.block_heights).await?
+    };
+
+    validate_heights(validator, block_heights, reporter).await
+}
+
+/// Logs the upgrades of the network the range `from..=to` crosses, where a
+/// mismatch is as likely to come from the validator as from the chain.
+fn log_upgrades(profile: &NetworkProfile, from: u32, to: u32) {
+    for upgrade in profile.upgrades_in(from, to) {
+        tracing::info!(
+            block_height = upgrade.height,
+            network = profile.name,
+            "range crosses the {} upgrade",
+            upgrade.name
+        );
+    }
+}
+
+async fn validate_heights(
+    validator: &BlockValidator,
+    block_heights: Vec<u32>,
+    reporter: &mut Reporter,
+) -> Result<Status> {
+    let mut validation = RangeValidation::default();
+
+    for block_height in block_heights {
+        if let Some(store) = &reporter.store {
+            if store.is_validated(block_height)? {
+                tracing::info!(block_height, "skipping block: already validated");
+                continue;
+            }
+        }
+        if reporter.announces_blocks() {
+            println!("Validating block height: {}", block_height);
+        }
+
+        let outcome = validator.validate_height(block_height).await;
+        if matches!(&outcome.result, Err(e) if e.is_cancelled()) {
+            return Err(ValidationError::Cancelled.into());
+        }
+        reporter.block(&outcome);
+        validation.outcomes.push(outcome);
+    }
+
+    if reporter.format.is_text() {
+        let problematic_blocks: Vec<_> = validation
+            .outcomes
+            .iter()
+            .filter(|outcome| !outcome.passed())
+            .map(|outcome| outcome.height)
+            .collect();
+        println!(
+            "Block validation completed successfully! These blocks have issues: {:?}",
+            problematic_blocks
+        );
+        print_timing(&validation);
+        if validator.keeps_going() {
+            print_failure_report(validation.outcomes.iter().filter_map(RecordedFailure::of));
+        }
+    }
+    reporter.format.finish(&validation)?;
+    Ok(Status::of_outcomes(&validation.outcomes))
+}
+
+/// The checkpoint a range scan starts from: the one saved at `path` when
+/// resuming, or a new one for `range`.
+fn load_checkpoint(path: &Path, resume: bool, range: Option<(u32, u32)>) -> Result<Checkpoint> {
+    if resume && path.exists() {
+        let checkpoint = Checkpoint::load(path)?;
+        if let Some((from, to)) = range.filter(|&range| range != (checkpoint.from, checkpoint.to)) {
+            bail!(
+                "checkpoint {} is for range {}..={}, not {from}..={to}",
+                path.display(),
+                checkpoint.from,
+                checkpoint.to
+            );
+        }
+        tracing::info!(
+            block_height = checkpoint.next_height(),
+            "resuming range {}..={}",
+            checkpoint.from,
+            checkpoint.to
+        );
+        return Ok(checkpoint);
+    }
+
+    let (from, to) = range.ok_or_else(|| {
+        anyhow!(
+            "no checkpoint at {}, --from-height and --to-height are required",
+            path.display()
+        )
+    })?;
+    Ok(Checkpoint::new(from, to))
+}
+
+async fn validate_range(
+    validator: &BlockValidator,
+    from: u32,
+    to: u32,
+    reporter: &mut Reporter,
+    mut checkpoint: Option<(&Path, Checkpoint)>,
+) -> Result<Status> {
+    if from > to {
+        bail!("--from-height {from} is greater than --to-height {to}");
+    }
 
-                for receipt in receipts {
-                    let receipt: Receipt = receipt.clone().try_into()?;
-                    calculated_receipt_root.push(receipt.to_bytes().as_slice());
+    let start = checkpoint
+        .as_ref()
+        .map_or(from, |(_, checkpoint)| checkpoint.next_height());
+    let mut range = RangeValidation::default();
+    let mut last_save = Instant::now();
+
+    let pending = match &reporter.store {
+        Some(store) if start <= to => store.pending_ranges(start, to)?,
+        _ if start <= to => vec![(start, to)],
+        _ => vec![],
+    };
+
+    reporter.start_progress(
+        pending
+            .iter()
+            .map(|&(start, end)| u64::from(end - start) + 1)
+            .sum(),
+    );
+    let mut result = Ok(());
+    // Status of the ranges that couldn't be validated when keeping going.
+    let mut aborted = Status::Valid;
+    for (start, end) in pending {
+        result = validator
+            .validate_range_with(start, end, |outcome| {
+                reporter.block(&outcome);
+                if let Some((path, checkpoint)) = &mut checkpoint {
+                    checkpoint.record(&outcome);
+                    if last_save.elapsed() >= CHECKPOINT_SAVE_INTERVAL {
+                        if let Err(e) = checkpoint.save(path) {
+                            tracing::warn!("failed to save checkpoint: {e}");
+                        }
+                        last_save = Instant::now();
+                    }
                 }
+                range.outcomes.push(outcome);
+            })
+            .await;
+        match &result {
+            Err(e) if validator.keeps_going() && !e.is_cancelled() => {
+                tracing::error!("failed to validate blocks {start}..={end}: {e}");
+                aborted = aborted.worst(Status::of_error(e));
+            }
+            Err(_) => break,
+            Ok(()) => {}
+        }
+    }
+    reporter.finish_progress();
 
-                let calculated_receipt_root: Bytes32 = calculated_receipt_root.root().into();
+    if let Some((path, checkpoint)) = &checkpoint {
+        checkpoint.save(path)?;
+    }
+    if !validator.keeps_going() || matches!(&result, Err(e) if e.is_cancelled()) {
+        result?;
+    }
 
-                if receipt_root != calculated_receipt_root {
-                    println!(
-                        "Receipt root mismatch for failed transaction {} [in block #{:?}]: expected {}, got {}",
-                        tx_id, block_height, receipt_root, calculated_receipt_root
-                    );
-                    problematic_blocks.push(block_height);
+    let (validated, passed, acknowledged, failed, status) = match &checkpoint {
+        Some((_, checkpoint)) => (
+            checkpoint.validated(),
+            checkpoint.passed,
+            checkpoint.acknowledged,
+            checkpoint.failures.len(),
+            Status::of_recorded(&checkpoint.failures),
+        ),
+        None => (
+            range.outcomes.len(),
+            range.passed(),
+            range.acknowledged(),
+            range.failed(),
+            Status::of_outcomes(&range.outcomes),
+        ),
+    };
+    let status = status.worst(aborted);
+
+    if reporter.format.is_text() {
+        let acknowledged = match acknowledged {
+            0 => String::new(),
+            blocks => format!(" ({blocks} with acknowledged anomalies only)"),
+        };
+        println!(
+            "Validated {validated} blocks in range {from}..={to}: {passed} passed{acknowledged}, {failed} failed"
+        );
+        print_timing(&range);
+        if validator.keeps_going() {
+            match &checkpoint {
+                Some((_, checkpoint)) => print_failure_report(checkpoint.failures.iter().cloned()),
+                None => print_failure_report(range.outcomes.iter().filter_map(RecordedFailure::of)),
+            }
+        }
+    }
+    reporter.format.finish(&range)?;
+    Ok(status)
+}
+
+/// Prints every failed block of the run, with the reason it failed.
+fn print_failure_report(failures: impl IntoIterator<Item = RecordedFailure>) {
+    let failures: Vec<_> = failures.into_iter().collect();
+    if failures.is_empty() {
+        return;
+    }
+    println!("{} blocks failed:", failures.len());
+    for failure in failures {
+        println!("  #{}: {}", failure.height, failure.reason);
+    }
+}
+
+/// Prints the timing percentiles of the blocks validated by this run, when
+/// they were timed.
+fn print_timing(validation: &RangeValidation) {
+    if let Some(timing) = validation.timing() {
+        println!("{timing}");
+    }
+}
+
+async fn follow_chain(
+    cli: &Cli,
+    matches: &ArgMatches,
+    validator: &BlockValidator,
+    reporter: &mut Reporter,
+    metrics: Option<(SocketAddr, Arc<Metrics>)>,
+) -> Result<Status> {
+    #[cfg(feature = "tui")]
+    let dashboard = reporter.dashboard.clone();
+    // What SIGUSR1 logs, kept apart from the dashboard which counts the same.
+    let stats = Mutex::new(Dashboard::new(cli.endpoint()));
+    let started = Instant::now();
+
+    let mut status = Status::Valid;
+    reporter.format = reporter.format.streaming();
+    let observer = metrics.as_ref().map(|(_, metrics)| metrics.clone());
+    let health = Arc::new(Health::new().with_max_lag(cli.max_lag));
+    let following = async {
+        let mut hangups = SignalListener::hangup();
+        let mut reloaded: Option<(Cli, BlockValidator)> = None;
+        let mut start_height = cli.from_height;
+        loop {
+            let next = {
+                let (cli, validator) = match &reloaded {
+                    Some((cli, validator)) => (cli, validator),
+                    None => (cli, validator),
+                };
+                let config = FollowConfig {
+                    start_height,
+                    ..follow_config(cli)
+                };
+                let shutdown = validator.cancellation_token().clone().cancelled_owned();
+                let followed = follow(validator, config, shutdown, |event| {
+                    let outcome = match event {
+                        FollowEvent::Block(outcome) => outcome,
+                        FollowEvent::Reorg(reorg) => {
+                            if let Some(metrics) = &observer {
+                                metrics.observe_reorg(&reorg);
+                            }
+                            output::lock(&stats).observe_reorg(&reorg);
+                            reporter.reorg(&reorg);
+                            return;
+                        }
+                    };
+                    if reporter.announces_blocks() {
+                        println!("Validating block height: {}", outcome.height);
+                    }
+                    if let Some(metrics) = &observer {
+                        metrics.observe(&outcome);
+                    }
+                    health.observe(&outcome);
+                    output::lock(&stats).observe(&outcome);
+                    reporter.block(&outcome);
+                    status = status.worst(Status::of_outcome(&outcome));
+                });
+                // Blocks keep being validated with the current settings
+                // until the new ones are ready.
+                let reloading = async {
+                    loop {
+                        hangups.recv().await;
+                        tracing::info!("received SIGHUP, reloading the settings");
+                        match reload(cli, matches).await {
+                            Ok(next) => return next,
+                            Err(e) => tracing::error!(
+                                "failed to reload the settings, keeping the current ones: {e:#}"
+                            ),
+                        }
+                    }
+                };
+                // Readiness is only served along with the metrics.
+                let probing = async {
+                    if observer.is_some() {
+                        let mut probes = tokio::time::interval(cli.poll_interval);
+                        loop {
+                            probes.tick().await;
+                            health.probe(validator).await;
+                        }
+                    }
+                    future::pending().await
+                };
+                tokio::select! {
+                    followed = followed => return followed.map_err(anyhow::Error::from),
+                    next = reloading => next,
+                    () = probing => unreachable!("the node is probed until following stops"),
                 }
+            };
+
+            let (cli, validator) = next;
+            start_height = output::lock(&stats)
+                .validated
+                .map(|height| height + 1)
+                .or(start_height);
+            reporter.webhook = cli
+                .webhook_url
+                .as_ref()
+                .map(|url| Webhook::new(url, cli.webhook_format));
+            let validator = match &observer {
+                Some(metrics) => validator.with_metrics(metrics.clone()),
+                None => validator,
+            };
+            tracing::info!(
+                block_height = start_height,
+                "following {} with the reloaded settings",
+                cli.endpoint()
+            );
+            reloaded = Some((cli, validator));
+        }
+    };
+    // The metrics server is dropped once following stops.
+    let serving = async {
+        match metrics {
+            Some((addr, metrics)) => {
+                metrics::serve(addr, metrics, health.clone(), future::pending()).await
+            }
+            None => future::pending().await,
+        }
+    };
+
+    // Following stops on its own once the dashboard is closed.
+    let drawing = async {
+        #[cfg(feature = "tui")]
+        if let Some(dashboard) = dashboard {
+            tui::run(validator, dashboard, cli.poll_interval).await?;
+        }
+        future::pending::<Result<()>>().await
+    };
+
+    let logging = async {
+        let mut usr1 = SignalListener::user_defined1();
+        loop {
+            usr1.recv().await;
+            log_statistics(&output::lock(&stats), started.elapsed());
+        }
+    };
+
+    tokio::select! {
+        followed = following => followed?,
+        served = serving => served?,
+        drawn = drawing => drawn?,
+        () = logging => {}
+    }
+
+    if reporter.format.is_text() {
+        let stats = output::lock(&stats);
+        println!(
+            "Followed {} blocks: {} passed, {} failed, {} reorgs",
+            stats.passed + stats.failed,
+            stats.passed,
+            stats.failed,
+            stats.reorgs
+        );
+    }
+    Ok(status)
+}
+
+/// Follows every `[[monitors]]` network of the config file at once, each
+/// with its own validator, reporter, checkpoint and metrics. A network whose
+/// follower fails is left behind while the others keep going.
+async fn monitor(cli: Cli, matches: &ArgMatches) -> Result<Status> {
+    let started = Instant::now();
+    let mut followers = vec![];
+    let mut monitored = vec![];
+    let mut stats = vec![];
+    for monitor in &cli.monitors {
+        let mut network = monitor_cli(&cli, matches, monitor)?;
+        let metrics = Arc::new(Metrics::for_network(&monitor.network));
+        let health = Arc::new(Health::new().with_max_lag(network.max_lag));
+        let validator = build_validator(&mut network)
+            .await
+            .with_context(|| format!("failed to set up network {}", monitor.network))?
+            .with_metrics(metrics.clone());
+        let mut reporter = reporter(&network).await?;
+        reporter.network = Some(monitor.network.clone());
+        reporter.format = reporter.format.streaming();
+        tracing::info!(
+            network = monitor.network,
+            "following {}",
+            network.endpoint()
+        );
+        monitored.push(Monitored {
+            network: monitor.network.clone(),
+            metrics,
+            health: health.clone(),
+        });
+        let span = tracing::info_span!("monitor", network = monitor.network);
+        let network_stats = Arc::new(Mutex::new(Dashboard::new(network.endpoint())));
+        stats.push((span.clone(), network_stats.clone()));
+        followers.push(
+            follow_network(network, monitor, validator, reporter, health, network_stats)
+                .instrument(span),
+        );
+    }
+
+    let following = async {
+        let mut status = Status::Valid;
+        for (monitor, followed) in cli
+            .monitors
+            .iter()
+            .zip(futures::future::join_all(followers).await)
+        {
+            status = status.worst(followed.unwrap_or_else(|e| {
+                tracing::error!(
+                    network = monitor.network,
+                    "failed to follow the chain: {e:#}"
+                );
+                Status::of_fatal(&e)
+            }));
+        }
+        status
+    };
+    let serving = async {
+        match cli.metrics_addr {
+            Some(addr) => metrics::serve_networks(addr, monitored, future::pending()).await,
+            None => future::pending().await,
+        }
+    };
+    let signals = async {
+        let mut hangups = SignalListener::hangup();
+        let mut usr1 = SignalListener::user_defined1();
+        loop {
+            tokio::select! {
+                () = hangups.recv() => tracing::warn!(
+                    "received SIGHUP, the settings can't be reloaded when following several networks"
+                ),
+                () = usr1.recv() => for (span, stats) in &stats {
+                    span.in_scope(|| log_statistics(&output::lock(stats), started.elapsed()));
+                },
             }
         }
+    };
+    tokio::select! {
+        status = following => Ok(status),
+        () = signals => unreachable!("signals are listened for until following stops"),
+        served = serving => {
+            served?;
+            unreachable!("the metrics are served until following stops")
+        }
+    }
+}
+
+/// The settings of the command line and the config file, for the network,
+/// endpoints, chain id, consensus parameters and first height of `monitor`.
+/// Its PoA authority is the one of its profile.
+fn monitor_cli(cli: &Cli, matches: &ArgMatches, monitor: &MonitorConfig) -> Result<Cli> {
+    let mut network = Cli::from_arg_matches(matches)?;
+    network.cancel = cli.cancel.clone();
+    let mut network = configure(network, matches)?;
+    network.monitors.clear();
+    network.network = monitor.network.clone();
+    network.endpoints = monitor.endpoints.clone().unwrap_or_default();
+    network.chain_id = monitor.chain_id;
+    network.poa_authority = None;
+    network.signing_key_overrides.clear();
+    network.consensus_parameters = monitor.consensus_parameters.clone();
+    network.from_height = monitor.from_height;
+    network.use_profile(monitor.profile.clone(), true);
+    Ok(network)
+}
+
+/// Follows the network of `monitor`, resuming from its checkpoint if it
+/// has one.
+async fn follow_network(
+    cli: Cli,
+    monitor: &MonitorConfig,
+    validator: BlockValidator,
+    mut reporter: Reporter,
+    health: Arc<Health>,
+    stats: Arc<Mutex<Dashboard>>,
+) -> Result<Status> {
+    let mut checkpoint = match &monitor.checkpoint {
+        Some(path) if path.exists() => {
+            let checkpoint = Checkpoint::load(path)?;
+            tracing::info!(
+                block_height = checkpoint.next_height(),
+                "resuming from checkpoint {}",
+                path.display()
+            );
+            Some((path, checkpoint))
+        }
+        // Following has no end, the range of the checkpoint neither.
+        Some(path) => Some((
+            path,
+            Checkpoint::new(cli.from_height.unwrap_or_default(), u32::MAX),
+        )),
+        None => None,
+    };
+    let start_height = checkpoint
+        .as_ref()
+        .and_then(|(_, checkpoint)| checkpoint.last_validated)
+        .map(|height| height + 1)
+        .or(cli.from_height);
 
-        // Validate final transaction root
-        let calculated_tx_root = calculated_tx_root.root().into();
-        if tx_root != calculated_tx_root {
+    let mut status = Status::Valid;
+    let mut last_save = Instant::now();
+    let metrics = validator.metrics().cloned();
+    let config = FollowConfig {
+        start_height,
+        ..follow_config(&cli)
+    };
+    let shutdown = validator.cancellation_token().clone().cancelled_owned();
+    let followed = follow(&validator, config, shutdown, |event| {
+        let outcome = match event {
+            FollowEvent::Block(outcome) => outcome,
+            FollowEvent::Reorg(reorg) => {
+                if let Some(metrics) = &metrics {
+                    metrics.observe_reorg(&reorg);
+                }
+                output::lock(&stats).observe_reorg(&reorg);
+                reporter.reorg(&reorg);
+                return;
+            }
+        };
+        if reporter.announces_blocks() {
             println!(
-                "Transaction root mismatch (with failed transactions): expected {}, got {}",
-                tx_root, calculated_tx_root
+                "Validating {} block height: {}",
+                monitor.network, outcome.height
             );
-            return Err(anyhow!(
-                "Transaction root mismatch: expected {}, got {}",
-                tx_root,
-                calculated_tx_root
-            ));
         }
+        if let Some(metrics) = &metrics {
+            metrics.observe(&outcome);
+        }
+        health.observe(&outcome);
+        output::lock(&stats).observe(&outcome);
+        if let Some((path, checkpoint)) = &mut checkpoint {
+            checkpoint.record(&outcome);
+            if last_save.elapsed() >= CHECKPOINT_SAVE_INTERVAL {
+                if let Err(e) = checkpoint.save(path) {
+                    tracing::warn!("failed to save checkpoint: {e}");
+                }
+                last_save = Instant::now();
+            }
+        }
+        reporter.block(&outcome);
+        status = status.worst(Status::of_outcome(&outcome));
+    });
+    // Readiness is only served along with the metrics.
+    let probing = async {
+        if cli.metrics_addr.is_some() {
+            let mut probes = tokio::time::interval(cli.poll_interval);
+            loop {
+                probes.tick().await;
+                health.probe(&validator).await;
+            }
+        }
+        future::pending().await
+    };
+    let followed = tokio::select! {
+        followed = followed => followed,
+        () = probing => unreachable!("the node is probed until following stops"),
+    };
+    reporter.flush().await;
+    if let Some((path, checkpoint)) = &checkpoint {
+        checkpoint.save(path)?;
     }
+    followed?;
+
+    if reporter.format.is_text() {
+        let stats = output::lock(&stats);
+        println!(
+            "Followed {} blocks of {}: {} passed, {} failed, {} reorgs",
+            stats.passed + stats.failed,
+            monitor.network,
+            stats.passed,
+            stats.failed,
+            stats.reorgs
+        );
+    }
+    Ok(status)
+}
+
+/// The follow settings of `cli`.
+fn follow_config(cli: &Cli) -> FollowConfig {
+    FollowConfig {
+        poll_interval: cli.poll_interval,
+        subscribe: !cli.no_subscribe,
+        start_height: cli.from_height,
+        max_reorg_depth: cli.max_reorg_depth,
+        confirmations: cli.confirmations,
+    }
+}
+
+/// Reads the command line and the `--config` file again, on SIGHUP, and
+/// builds the validator of the new settings.
+async fn reload(cli: &Cli, matches: &ArgMatches) -> Result<(Cli, BlockValidator)> {
+    let mut reloaded = Cli::from_arg_matches(matches)?;
+    reloaded.cancel = cli.cancel.clone();
+    let mut reloaded = configure(reloaded, matches)?;
+    if !reloaded.follow {
+        bail!("the new settings don't follow the chain");
+    }
+    let validator = build_validator(&mut reloaded).await?;
+    Ok((reloaded, validator))
+}
 
-    println!(
-        "Block validation completed successfully! These blocks have issues: {:?}",
-        problematic_blocks
+/// Logs what follow mode did so far, on SIGUSR1.
+fn log_statistics(stats: &Dashboard, uptime: Duration) {
+    let failing: Vec<_> = stats
+        .checks
+        .iter()
+        .filter(|(_, check)| check.failed > 0)
+        .map(|(name, check)| format!("{name}: {}", check.failed))
+        .collect();
+    tracing::info!(
+        uptime = %humantime::format_duration(Duration::from_secs(uptime.as_secs())),
+        validated = stats.passed + stats.failed,
+        passed = stats.passed,
+        failed = stats.failed,
+        reorgs = stats.reorgs,
+        block_height = stats.validated,
+        last_failure = stats.recent_failures.front().map(|failure| failure.height),
+        failed_checks = %failing.join(", "),
+        "statistics of follow mode"
     );
-    Ok(())
 }
@@ -0,0 +1,153 @@
+// src/memory.rs
+//! A bound on the memory held by the blocks being validated, so range scans
+//! fit on small instances.
+//!
+//! Every page of blocks reserves an estimate of its size from the
+//! [`MemoryBudget`] before it is fetched, and waits while the pages in
+//! flight hold the whole budget. Pages are sized from the largest blocks
+//! seen recently, down to a single block, and each block gives back its
+//! share as soon as it is validated. The budget is a hint: a block larger
+//! than the whole budget is still fetched, on its own.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use tokio::sync::Semaphore;
+
+use crate::checks;
+use crate::query::FullBlock;
+
+/// Size assumed for blocks until one is fetched.
+pub const DEFAULT_BLOCK_ESTIMATE: usize = 64 * 1024;
+
+/// Memory a block takes besides its payloads and receipts.
+const BLOCK_OVERHEAD: usize = 4 * 1024;
+
+/// Memory a transaction takes besides its payload and receipts.
+const TRANSACTION_OVERHEAD: usize = 1024;
+
+/// Memory a decoded receipt takes.
+const RECEIPT_SIZE: usize = 512;
+
+/// Size of the permits of the budget, so large budgets and pages fit their
+/// counts.
+const UNIT: usize = 1024;
+
+/// Memory the validation of `block` holds: its raw payloads, liable to be
+/// held along with the hex of the response they were decoded from, and its
+/// decoded receipts.
+pub fn estimated_size(block: &FullBlock) -> usize {
+    let payloads: usize = block
+        .transactions
+        .iter()
+        .map(|tx| TRANSACTION_OVERHEAD + 3 * tx.raw_payload.0.len())
+        .sum();
+    let receipts: usize = checks::executed_transactions(block)
+        .map(|(_, receipts)| receipts.len())
+        .sum();
+    BLOCK_OVERHEAD + payloads + receipts * RECEIPT_SIZE
+}
+
+/// Memory shared by the pages of blocks in flight.
+#[derive(Debug)]
+pub struct MemoryBudget {
+    /// The budget, in units.
+    units: usize,
+    permits: Arc<Semaphore>,
+    /// Estimated size of the next blocks, in bytes.
+    block_size: AtomicUsize,
+}
+
+impl MemoryBudget {
+    pub fn new(bytes: u64) -> Self {
+        let units = usize::try_from(bytes)
+            .unwrap_or(usize::MAX)
+            .div_ceil(UNIT)
+            .clamp(1, Semaphore::MAX_PERMITS);
+        Self {
+            units,
+            permits: Arc::new(Semaphore::new(units)),
+            block_size: AtomicUsize::new(DEFAULT_BLOCK_ESTIMATE),
+        }
+    }
+
+    pub fn bytes(&self) -> u64 {
+        (self.units * UNIT) as u64
+    }
+
+    /// Bytes of the budget no page holds.
+    pub fn available(&self) -> u64 {
+        (self.permits.available_permits() * UNIT) as u64
+    }
+
+    /// Estimated size of the next blocks.
+    pub fn block_size(&self) -> usize {
+        self.block_size.load(Ordering::Relaxed)
+    }
+
+    /// Number of blocks, up to `max`, a page can hold for `pages` of them to
+    /// fit the budget at once.
+    pub fn blocks_per_page(&self, max: u32, pages: usize) -> u32 {
+        let page_bytes = self.units * UNIT / pages.max(1);
+        let blocks = page_bytes / self.block_size().max(1);
+        u32::try_from(blocks)
+            .unwrap_or(u32::MAX)
+            .clamp(1, max.max(1))
+    }
+
+    /// Accounts for the size of a fetched block. The estimate follows larger
+    /// blocks right away, and smaller ones slowly.
+    pub fn observe(&self, block: &FullBlock) {
+        let size = estimated_size(block);
+        let _ = self
+            .block_size
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |estimate| {
+                Some(size.max((estimate * 3 + size) / 4))
+            });
+    }
+
+    /// Reserves the estimated size of `blocks` blocks, at most the whole
+    /// budget, waiting for the pages in flight to release enough of it.
+    pub async fn reserve(&self, blocks: u32) -> MemoryReservation {
+        let bytes = self.block_size().saturating_mul(blocks as usize);
+        let units = bytes
+            .div_ceil(UNIT)
+            .clamp(1, self.units)
+            .min(u32::MAX as usize);
+        self.permits
+            .acquire_many(units as u32)
+            .await
+            .expect("the budget is never closed")
+            .forget();
+        MemoryReservation {
+            permits: self.permits.clone(),
+            units,
+        }
+    }
+}
+
+/// Memory reserved from a [`MemoryBudget`], given back as blocks are
+/// validated, and entirely once dropped.
+#[derive(Debug)]
+pub struct MemoryReservation {
+    permits: Arc<Semaphore>,
+    units: usize,
+}
+
+impl MemoryReservation {
+    /// Gives back the memory a validated block held, up to what is left of
+    /// the reservation.
+    pub fn release(&mut self, bytes: usize) {
+        let units = bytes.div_ceil(UNIT).min(self.units);
+        self.units -= units;
+        self.permits.add_permits(units);
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.permits.add_permits(self.units);
+    }
+}
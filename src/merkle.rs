@@ -0,0 +1,116 @@
+// src/merkle.rs
+//! The binary Merkle tree of the block commitments, split into hashing the
+//! leaves and combining them so the leaves can be hashed in parallel, and the
+//! sparse Merkle tree of contract storage.
+//!
+//! With the `parallel` feature, the work mapped with [`map`] runs on the
+//! rayon thread pool. The tree over the leaf hashes is always built in leaf
+//! order, which is what the root commits to. The leaves and nodes are
+//! hashed with the backend of the process, see [`hash`](crate::hash).
+
+use fuel_merkle::sparse::{in_memory::MerkleTree, MerkleTreeKey};
+use fuel_tx::StorageSlot;
+use fuel_types::Bytes32;
+
+use crate::hash::{self, HashBackend};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash of the leaf `data`, as `MerkleRootCalculator::push` computes it.
+pub fn leaf_hash(data: &[u8]) -> Bytes32 {
+    leaf_hash_with(hash::backend(), data)
+}
+
+/// [`leaf_hash`] with `backend`.
+pub fn leaf_hash_with(backend: &dyn HashBackend, data: &[u8]) -> Bytes32 {
+    backend.sha256(&[&[LEAF_PREFIX], data])
+}
+
+fn node_hash(backend: &dyn HashBackend, left: &Bytes32, right: &Bytes32) -> Bytes32 {
+    backend.sha256(&[&[NODE_PREFIX], left.as_ref(), right.as_ref()])
+}
+
+/// Root of the tree over leaves with the hashes `leaf_hashes`, in order.
+/// Equal to the root `MerkleRootCalculator` computes over the leaves.
+pub fn root(leaf_hashes: impl IntoIterator<Item = Bytes32>) -> Bytes32 {
+    root_with(hash::backend(), leaf_hashes)
+}
+
+/// [`root`] with `backend`.
+pub fn root_with(
+    backend: &dyn HashBackend,
+    leaf_hashes: impl IntoIterator<Item = Bytes32>,
+) -> Bytes32 {
+    // Roots of the perfect subtrees built so far, with their heights, the
+    // highest first.
+    let mut stack: Vec<(u32, Bytes32)> = vec![];
+    for hash in leaf_hashes {
+        stack.push((0, hash));
+        while let [.., (left_height, left), (right_height, right)] = stack[..] {
+            if left_height != right_height {
+                break;
+            }
+            stack.truncate(stack.len() - 2);
+            stack.push((left_height + 1, node_hash(backend, &left, &right)));
+        }
+    }
+
+    let Some((_, mut root)) = stack.pop() else {
+        return backend.sha256(&[]);
+    };
+    while let Some((_, left)) = stack.pop() {
+        root = node_hash(backend, &left, &root);
+    }
+    root
+}
+
+/// Root of the sparse Merkle tree over the storage `slots` of a contract,
+/// keyed by the hash of the slot key: the state root a contract is created
+/// with. Equal to `Contract::initial_state_root`.
+pub fn contract_state_root<'a>(slots: impl IntoIterator<Item = &'a StorageSlot>) -> Bytes32 {
+    let leaves = slots
+        .into_iter()
+        .map(|slot| (MerkleTreeKey::new(slot.key()), slot.value()));
+    MerkleTree::root_from_set(leaves).into()
+}
+
+/// Fewer items than this are mapped on the calling thread: for the few
+/// transactions of most blocks, handing them to the pool costs more than
+/// hashing them.
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 64;
+
+/// Applies `f` to every item, on the rayon thread pool with the `parallel`
+/// feature. Results are in the order of `items`, and `f` runs in the span
+/// of the caller.
+#[cfg(feature = "parallel")]
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    use rayon::prelude::*;
+
+    if items.len() < PARALLEL_THRESHOLD {
+        return items.iter().map(f).collect();
+    }
+    let span = tracing::Span::current();
+    items
+        .par_iter()
+        .map(|item| {
+            let _span = span.enter();
+            f(item)
+        })
+        .collect()
+}
+
+/// Applies `f` to every item, in order.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    F: Fn(&T) -> R,
+{
+    items.iter().map(f).collect()
+}
@@ -0,0 +1,348 @@
+// src/metrics.rs
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::Infallible,
+    future::Future,
+    net::SocketAddr,
+    sync::Arc,
+};
+
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use prometheus::{
+    proto::MetricFamily, Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    Opts, Registry, TextEncoder,
+};
+use serde::Serialize;
+
+use crate::follow::Reorg;
+use crate::health::{Health, Readiness};
+use crate::validator::BlockOutcome;
+
+/// Prometheus metrics of a validator, meant to be scraped while following
+/// the chain.
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    registry: Registry,
+    blocks_validated: IntCounter,
+    blocks_failed: IntCounter,
+    check_failures: IntCounterVec,
+    block_errors: IntCounterVec,
+    query_errors: IntCounterVec,
+    validation_seconds: HistogramVec,
+    last_validated_height: IntGauge,
+    reorgs: IntCounter,
+    reorged_blocks: IntCounter,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::with_labels(None)
+    }
+
+    /// Metrics of one of the networks followed by the process, every one
+    /// labelled `network="<network>"`.
+    pub fn for_network(network: &str) -> Self {
+        Self::with_labels(Some(HashMap::from([(
+            "network".to_string(),
+            network.to_string(),
+        )])))
+    }
+
+    fn with_labels(labels: Option<HashMap<String, String>>) -> Self {
+        let registry = Registry::new_custom(Some("fuel_validator".into()), labels)
+            .expect("the metrics prefix and labels are valid");
+
+        let blocks_validated = IntCounter::new("blocks_validated_total", "Blocks validated")
+            .expect("metric options are valid");
+        let blocks_failed = IntCounter::new(
+            "blocks_failed_total",
+            "Blocks that failed at least one check or couldn't be validated",
+        )
+        .expect("metric options are valid");
+        let check_failures = IntCounterVec::new(
+            Opts::new("check_failures_total", "Failed checks, by check"),
+            &["check"],
+        )
+        .expect("metric options are valid");
+        let block_errors = IntCounterVec::new(
+            Opts::new(
+                "block_errors_total",
+                "Blocks whose validation errored, by error kind",
+            ),
+            &["kind"],
+        )
+        .expect("metric options are valid");
+        let query_errors = IntCounterVec::new(
+            Opts::new(
+                "query_errors_total",
+                "Queries that failed after every retry, by query",
+            ),
+            &["query"],
+        )
+        .expect("metric options are valid");
+        let validation_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "block_validation_seconds",
+                "Time spent validating a block, by result",
+            ),
+            &["result"],
+        )
+        .expect("metric options are valid");
+        let last_validated_height = IntGauge::new(
+            "last_validated_height",
+            "Height of the last validated block",
+        )
+        .expect("metric options are valid");
+        let reorgs = IntCounter::new("reorgs_total", "Reorgs of the followed chain")
+            .expect("metric options are valid");
+        let reorged_blocks = IntCounter::new(
+            "reorged_blocks_total",
+            "Validated blocks replaced by a reorg",
+        )
+        .expect("metric options are valid");
+
+        for collector in [
+            Box::new(blocks_validated.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(blocks_failed.clone()),
+            Box::new(check_failures.clone()),
+            Box::new(block_errors.clone()),
+            Box::new(query_errors.clone()),
+            Box::new(validation_seconds.clone()),
+            Box::new(last_validated_height.clone()),
+            Box::new(reorgs.clone()),
+            Box::new(reorged_blocks.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+
+        Self {
+            registry,
+            blocks_validated,
+            blocks_failed,
+            check_failures,
+            block_errors,
+            query_errors,
+            validation_seconds,
+            last_validated_height,
+            reorgs,
+            reorged_blocks,
+        }
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Accounts for the outcome of one block.
+    pub fn observe(&self, outcome: &BlockOutcome) {
+        self.blocks_validated.inc();
+        self.last_validated_height.set(outcome.height.into());
+
+        match &outcome.result {
+            Ok(report) => {
+                for failure in report.failures() {
+                    self.check_failures
+                        .with_label_values(&[failure.check.name()])
+                        .inc();
+                }
+            }
+            Err(e) => self
+                .block_errors
+                .with_label_values(&[e.kind().name()])
+                .inc(),
+        }
+
+        let result = if outcome.passed() {
+            "passed"
+        } else {
+            self.blocks_failed.inc();
+            "failed"
+        };
+        self.validation_seconds
+            .with_label_values(&[result])
+            .observe(outcome.duration.as_secs_f64());
+    }
+
+    /// Accounts for a reorg of the followed chain.
+    pub fn observe_reorg(&self, reorg: &Reorg) {
+        self.reorgs.inc();
+        self.reorged_blocks.inc_by(reorg.depth() as u64);
+    }
+
+    /// Accounts for a query that failed after every retry.
+    pub fn query_failed(&self, query: &str) {
+        self.query_errors.with_label_values(&[query]).inc();
+    }
+
+    /// Metrics in the Prometheus text format.
+    pub fn encode(&self) -> String {
+        encode(&self.registry.gather())
+    }
+}
+
+/// The metrics and health of one of the networks followed by the process.
+#[derive(Clone, Debug)]
+pub struct Monitored {
+    pub network: String,
+    pub metrics: Arc<Metrics>,
+    pub health: Arc<Health>,
+}
+
+/// Readiness of several networks, ready when all of them are.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct NetworksReadiness {
+    pub ready: bool,
+    pub networks: BTreeMap<String, Readiness>,
+}
+
+impl Monitored {
+    /// Metrics of every network in the Prometheus text format. The samples
+    /// of the networks are merged into one family per metric, as the format
+    /// requires.
+    pub fn encode_all(networks: &[Monitored]) -> String {
+        let mut families = BTreeMap::<String, MetricFamily>::new();
+        for mut family in networks
+            .iter()
+            .flat_map(|monitored| monitored.metrics.registry.gather())
+        {
+            match families.get_mut(family.get_name()) {
+                Some(merged) => merged.mut_metric().extend(family.take_metric()),
+                None => {
+                    families.insert(family.get_name().to_string(), family);
+                }
+            }
+        }
+        encode(&families.into_values().collect::<Vec<_>>())
+    }
+
+    pub fn readiness_all(networks: &[Monitored]) -> NetworksReadiness {
+        let networks: BTreeMap<_, _> = networks
+            .iter()
+            .map(|monitored| (monitored.network.clone(), monitored.health.readiness()))
+            .collect();
+        NetworksReadiness {
+            ready: networks.values().all(|readiness| readiness.ready),
+            networks,
+        }
+    }
+}
+
+fn encode(families: &[MetricFamily]) -> String {
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(families, &mut buffer)
+        .expect("metrics encode to text");
+    String::from_utf8(buffer).expect("the text format is UTF-8")
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serves `GET /metrics` on `addr` until `shutdown` resolves, along with
+/// the `/healthz` and `/readyz` probes of `health`.
+pub async fn serve<S>(
+    addr: SocketAddr,
+    metrics: Arc<Metrics>,
+    health: Arc<Health>,
+    shutdown: S,
+) -> hyper::Result<()>
+where
+    S: Future<Output = ()>,
+{
+    serve_with(addr, shutdown, move |path| match path {
+        "/metrics" => Some(metrics_response(metrics.encode())),
+        "/readyz" => {
+            let readiness = health.readiness();
+            Some(json_response(readiness.status(), &readiness))
+        }
+        _ => None,
+    })
+    .await
+}
+
+/// [`serve`] for several networks. `/metrics` serves the metrics of all of
+/// them, and `/readyz` is ready when every network is, with the
+/// [`NetworksReadiness`] of each.
+pub async fn serve_networks<S>(
+    addr: SocketAddr,
+    networks: Vec<Monitored>,
+    shutdown: S,
+) -> hyper::Result<()>
+where
+    S: Future<Output = ()>,
+{
+    serve_with(addr, shutdown, move |path| match path {
+        "/metrics" => Some(metrics_response(Monitored::encode_all(&networks))),
+        "/readyz" => {
+            let readiness = Monitored::readiness_all(&networks);
+            let status = if readiness.ready {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            Some(json_response(status, &readiness))
+        }
+        _ => None,
+    })
+    .await
+}
+
+/// Serves the `GET` requests `respond` answers, and `/healthz`, on `addr`
+/// until `shutdown` resolves.
+async fn serve_with<S, R>(addr: SocketAddr, shutdown: S, respond: R) -> hyper::Result<()>
+where
+    S: Future<Output = ()>,
+    R: Fn(&str) -> Option<Response<Body>> + Clone + Send + Sync + 'static,
+{
+    let make_service = make_service_fn(move |_| {
+        let respond = respond.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                let respond = respond.clone();
+                async move {
+                    let response = match (request.method(), request.uri().path()) {
+                        (&Method::GET, "/healthz") => Some(Response::new(Body::from("ok"))),
+                        (&Method::GET, path) => respond(path),
+                        _ => None,
+                    };
+                    Ok::<_, Infallible>(response.unwrap_or_else(|| {
+                        Response::builder()
+                            .status(StatusCode::NOT_FOUND)
+                            .body(Body::empty())
+                            .expect("responses are valid")
+                    }))
+                }
+            }))
+        }
+    });
+
+    let server = Server::try_bind(&addr)?.serve(make_service);
+    tracing::info!("serving metrics on http://{}/metrics", server.local_addr());
+    server.with_graceful_shutdown(shutdown).await
+}
+
+fn metrics_response(text: String) -> Response<Body> {
+    Response::builder()
+        .header(CONTENT_TYPE, TextEncoder::new().format_type())
+        .body(Body::from(text))
+        .expect("responses are valid")
+}
+
+fn json_response(status: StatusCode, body: &impl Serialize) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(body).expect("readiness serializes to JSON"),
+        ))
+        .expect("responses are valid")
+}
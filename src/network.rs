@@ -0,0 +1,241 @@
+// src/network.rs
+//! Public Fuel networks and the profiles describing them: where to query
+//! them and what their blocks are checked against. Custom profiles are
+//! built like the built-in ones, e.g. from the config file of the binary.
+
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use fuel_types::Address;
+
+use crate::{consensus::PoaAuthority, error::ValidationError};
+
+/// Public Fuel networks with a well-known GraphQL endpoint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Network {
+    Mainnet,
+    #[default]
+    Testnet,
+    Devnet,
+}
+
+impl Network {
+    pub const ALL: [Network; 3] = [Network::Mainnet, Network::Testnet, Network::Devnet];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+        }
+    }
+
+    pub fn endpoint(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "https://mainnet.fuel.network/v1/graphql",
+            Network::Testnet => "https://testnet.fuel.network/v1/graphql",
+            Network::Devnet => "https://devnet.fuel.network/v1/graphql",
+        }
+    }
+
+    /// Chain id of the network, used to recompute transaction ids without
+    /// asking the node.
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            // `chain_id` of the consensus parameters of the `ignition` chain
+            // config.
+            Network::Mainnet => Some(9889),
+            // `chain_id` of the consensus parameters of the `testnet` chain
+            // config.
+            Network::Testnet => Some(0),
+            // Devnet is reset with a new chain config too often to pin it, it
+            // is queried from the node instead.
+            Network::Devnet => None,
+        }
+    }
+
+    /// Authority signing the blocks of the network, from the
+    /// `consensus.PoAV2` section of its chain config in
+    /// FuelLabs/chain-configuration.
+    pub fn poa_authority(&self) -> Option<PoaAuthority> {
+        match self {
+            // `genesis_signing_key` and `signing_key_overrides` of the
+            // `ignition` chain config, not yet checked against mainnet blocks.
+            Network::Mainnet => None,
+            // `genesis_signing_key` and `signing_key_overrides` of the
+            // `testnet` chain config, not yet checked against testnet blocks.
+            Network::Testnet => None,
+            // Changes with every reset, like the chain id.
+            Network::Devnet => None,
+        }
+    }
+
+    /// Heights the network was upgraded at and the name of each upgrade,
+    /// from the release notes of fuel-core.
+    pub fn upgrades(&self) -> &'static [(u32, &'static str)] {
+        match self {
+            // Heights of the state transition function upgrades of mainnet,
+            // not yet checked against its blocks.
+            Network::Mainnet => &[],
+            // Heights of the state transition function upgrades of testnet,
+            // not yet checked against its blocks.
+            Network::Testnet => &[],
+            // Restarts from genesis with each release.
+            Network::Devnet => &[],
+        }
+    }
+
+    /// The built-in profile of the network. The `[networks.<name>]` section
+    /// of the config file overrides any of its values.
+    pub fn profile(&self) -> NetworkProfile {
+        let mut profile =
+            NetworkProfile::new(self.name()).with_endpoints(vec![self.endpoint().to_string()]);
+        profile.chain_id = self.chain_id();
+        if let Some(authority) = self.poa_authority() {
+            profile.poa_authority = Some(authority.genesis_signing_key);
+            profile.signing_key_overrides = authority.signing_key_overrides;
+        }
+        self.upgrades()
+            .iter()
+            .fold(profile, |profile, (height, name)| {
+                profile.with_upgrade(*height, *name)
+            })
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Network {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Network::ALL
+            .into_iter()
+            .find(|network| network.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ValidationError::UnknownNetwork(s.to_string()))
+    }
+}
+
+/// Everything known about a network before querying it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkProfile {
+    pub name: String,
+    /// GraphQL endpoints of the network. Blocks are fetched from the first
+    /// one and cross-checked against the others.
+    pub endpoints: Vec<String>,
+    pub chain_id: Option<u64>,
+    /// Address expected to have signed the blocks, up to the first of
+    /// [`Self::signing_key_overrides`].
+    pub poa_authority: Option<Address>,
+    /// Signing keys of the authority from the given heights on.
+    pub signing_key_overrides: BTreeMap<u32, Address>,
+    /// Heights the network was upgraded at, from the lowest up.
+    pub upgrades: Vec<NetworkUpgrade>,
+}
+
+/// A block at which the network changed, e.g. its consensus parameters or
+/// state transition function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkUpgrade {
+    pub height: u32,
+    /// What changed, as shown in logs and reports.
+    pub name: String,
+}
+
+impl NetworkProfile {
+    /// A profile without anything known about the network besides `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            endpoints: Vec::new(),
+            chain_id: None,
+            poa_authority: None,
+            signing_key_overrides: BTreeMap::new(),
+            upgrades: Vec::new(),
+        }
+    }
+
+    /// The built-in profile named `name`, if any.
+    pub fn builtin(name: &str) -> Option<Self> {
+        name.parse::<Network>()
+            .ok()
+            .map(|network| network.profile())
+    }
+
+    pub fn with_endpoints(mut self, endpoints: Vec<String>) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    pub fn with_poa_authority(mut self, address: Address) -> Self {
+        self.poa_authority = Some(address);
+        self
+    }
+
+    /// Signs the blocks from `height` on with `address`.
+    pub fn with_signing_key_override(mut self, height: u32, address: Address) -> Self {
+        self.signing_key_overrides.insert(height, address);
+        self
+    }
+
+    /// Records an upgrade at `height`, keeping them sorted.
+    pub fn with_upgrade(mut self, height: u32, name: impl Into<String>) -> Self {
+        let index = self
+            .upgrades
+            .partition_point(|upgrade| upgrade.height <= height);
+        self.upgrades.insert(
+            index,
+            NetworkUpgrade {
+                height,
+                name: name.into(),
+            },
+        );
+        self
+    }
+
+    /// The authority the block signatures are checked against, if the
+    /// profile has one.
+    pub fn authority(&self) -> Option<PoaAuthority> {
+        self.poa_authority.map(|genesis_signing_key| PoaAuthority {
+            genesis_signing_key,
+            signing_key_overrides: self.signing_key_overrides.clone(),
+        })
+    }
+
+    /// Endpoint blocks are fetched from.
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoints.first().map(String::as_str)
+    }
+
+    /// The upgrades between `from` and `to`, both inclusive.
+    pub fn upgrades_in(&self, from: u32, to: u32) -> impl Iterator<Item = &NetworkUpgrade> {
+        self.upgrades
+            .iter()
+            .filter(move |upgrade| (from..=to).contains(&upgrade.height))
+    }
+
+    /// The upgrade at `height`, if the network was upgraded there.
+    pub fn upgrade_at(&self, height: u32) -> Option<&NetworkUpgrade> {
+        self.upgrades_in(height, height).next()
+    }
+}
+
+impl Default for NetworkProfile {
+    fn default() -> Self {
+        Network::default().profile()
+    }
+}
+
+impl From<Network> for NetworkProfile {
+    fn from(network: Network) -> Self {
+        network.profile()
+    }
+}
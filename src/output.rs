@@ -0,0 +1,479 @@
+// src/output.rs
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use fuel_root_validation::{
+    Alert, BlockOutcome, Check, Dashboard, EventPublisher, Reorg, ResultStore, Webhook,
+};
+use indicatif::{ProgressBar, ProgressState, ProgressStyle};
+use serde::Serialize;
+use tokio::{
+    sync::mpsc,
+    task::{JoinHandle, JoinSet},
+};
+
+/// How validation results are written to stdout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable lines, only detailing failed checks.
+    #[default]
+    Text,
+    /// A single JSON document once every block has been validated. Behaves
+    /// like `ndjson` in follow mode, which never finishes.
+    Json,
+    /// One JSON object per validated block.
+    Ndjson,
+    /// One row per validated block, with the status of every check and the
+    /// roots they compared, written to `--out-file` or stdout.
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn is_text(self) -> bool {
+        self == OutputFormat::Text
+    }
+
+    /// This format for runs without an end to wait for before writing a
+    /// JSON document.
+    pub fn streaming(self) -> Self {
+        match self {
+            OutputFormat::Json => OutputFormat::Ndjson,
+            format => format,
+        }
+    }
+
+    /// Writes the result of one block as soon as it is known. JSON output is
+    /// deferred to [`OutputFormat::finish`].
+    /// Lines are labelled with the `network` of the block when several are
+    /// followed.
+    pub fn block(self, outcome: &BlockOutcome, network: Option<&str>) {
+        match self {
+            OutputFormat::Text => print_failures(outcome, network),
+            OutputFormat::Ndjson => print_line(outcome, network),
+            // Written by the `CsvWriter` of the reporter.
+            OutputFormat::Json | OutputFormat::Csv => {}
+        }
+    }
+
+    /// Writes the final JSON document, if this format has one.
+    pub fn finish(self, document: &impl Serialize) -> Result<()> {
+        if self == OutputFormat::Json {
+            println!("{}", serde_json::to_string_pretty(document)?);
+        }
+        Ok(())
+    }
+}
+
+/// Where the outcome of every validated block goes: stdout, in the chosen
+/// format, the results database when one is open, the event bus when one is
+/// connected and the webhook when a block fails.
+pub struct Reporter {
+    pub format: OutputFormat,
+    pub store: Option<ResultStore>,
+    pub webhook: Option<Webhook>,
+    pub events: Option<EventQueue>,
+    /// Where the rows go with `--output csv`.
+    pub csv: Option<CsvWriter>,
+    /// The state drawn by `--tui`, which replaces the results on stdout.
+    pub dashboard: Option<Arc<Mutex<Dashboard>>>,
+    /// Whether progress is kept off the terminal.
+    pub quiet: bool,
+    /// Network the results are labelled with, when several are followed.
+    pub network: Option<String>,
+    progress: Option<ProgressBar>,
+    alerts: JoinSet<()>,
+}
+
+impl Reporter {
+    pub fn new(format: OutputFormat) -> Self {
+        Self {
+            format,
+            store: None,
+            webhook: None,
+            events: None,
+            csv: None,
+            dashboard: None,
+            quiet: false,
+            network: None,
+            progress: None,
+            alerts: JoinSet::new(),
+        }
+    }
+
+    /// Whether to announce every block before validating it. Range scans
+    /// show a progress bar instead.
+    pub fn announces_blocks(&self) -> bool {
+        self.format.is_text() && !self.quiet
+    }
+
+    /// Shows a progress bar on stderr for the `blocks` about to be
+    /// validated, unless quiet. It isn't drawn when stderr isn't a terminal.
+    pub fn start_progress(&mut self, blocks: u64) {
+        if self.quiet {
+            return;
+        }
+        let style = ProgressStyle::with_template(
+            "{elapsed_precise} [{wide_bar}] {human_pos}/{human_len} blocks ({rate}, ETA {eta})",
+        )
+        .expect("the progress template is valid")
+        .with_key("rate", |state: &ProgressState, w: &mut dyn fmt::Write| {
+            let _ = write!(w, "{:.1} blocks/s", state.per_sec());
+        })
+        .progress_chars("=> ");
+        self.progress = Some(ProgressBar::new(blocks).with_style(style));
+    }
+
+    /// Removes the progress bar, if any.
+    pub fn finish_progress(&mut self) {
+        if let Some(progress) = self.progress.take() {
+            progress.finish_and_clear();
+        }
+    }
+
+    pub fn block(&mut self, outcome: &BlockOutcome) {
+        if let Some(dashboard) = &self.dashboard {
+            lock(dashboard).observe(outcome);
+        }
+        match &self.progress {
+            _ if self.dashboard.is_some() => {}
+            // Keeps the results from being drawn over by the bar.
+            Some(progress) => {
+                progress.suspend(|| self.format.block(outcome, self.network.as_deref()));
+                progress.inc(1);
+            }
+            None => self.format.block(outcome, self.network.as_deref()),
+        }
+        if let Some(csv) = &mut self.csv {
+            if let Err(e) = csv.row(outcome) {
+                tracing::error!(
+                    block_height = outcome.height,
+                    "failed to write CSV row: {e}"
+                );
+            }
+        }
+        if let Some(store) = &mut self.store {
+            if let Err(e) = store.record(outcome) {
+                tracing::warn!(block_height = outcome.height, "failed to record block: {e}");
+            }
+        }
+        if let Some(events) = &self.events {
+            events.send(outcome);
+        }
+        // Reap the alerts already sent so a long running follower doesn't
+        // accumulate them.
+        while self.alerts.try_join_next().is_some() {}
+        if let (Some(webhook), Some(alert)) = (&self.webhook, Alert::for_outcome(outcome)) {
+            let webhook = webhook.clone();
+            self.alerts.spawn(async move {
+                if let Err(e) = webhook.send(&alert).await {
+                    tracing::error!(block_height = alert.height, "failed to send alert: {e}");
+                }
+            });
+        }
+    }
+
+    /// Reports that validated blocks were replaced by another branch.
+    pub fn reorg(&mut self, reorg: &Reorg) {
+        if let Some(dashboard) = &self.dashboard {
+            lock(dashboard).observe_reorg(reorg);
+            return;
+        }
+        match self.format {
+            OutputFormat::Text => {
+                let (first, _) = reorg.abandoned[0];
+                let fork = match reorg.fork_height {
+                    Some(height) => format!("after block #{height}"),
+                    None => "deeper than the remembered blocks".to_string(),
+                };
+                println!(
+                    "{}Reorg of {} blocks from block #{first}, forked {fork}",
+                    label(self.network.as_deref()),
+                    reorg.depth()
+                );
+            }
+            OutputFormat::Csv => tracing::warn!(
+                abandoned = reorg.depth(),
+                fork_height = reorg.fork_height,
+                "reorg replaced validated blocks"
+            ),
+            OutputFormat::Ndjson | OutputFormat::Json => {
+                let line = match &self.network {
+                    Some(network) => serde_json::json!({ "network": network, "reorg": reorg }),
+                    None => serde_json::json!({ "reorg": reorg }),
+                };
+                match serde_json::to_string(&line) {
+                    Ok(line) => println!("{line}"),
+                    Err(e) => tracing::error!("failed to serialize reorg: {e}"),
+                }
+            }
+        }
+    }
+
+    /// Waits for the alerts and events still being sent.
+    pub async fn flush(&mut self) {
+        while self.alerts.join_next().await.is_some() {}
+        if let Some(events) = self.events.take() {
+            events.close().await;
+        }
+    }
+}
+
+/// Publishes the outcome of every block, in the order they were validated,
+/// from a task of its own so a slow bus doesn't hold up validation.
+pub struct EventQueue {
+    sender: mpsc::UnboundedSender<(u32, Vec<u8>)>,
+    task: JoinHandle<()>,
+}
+
+impl EventQueue {
+    pub fn new(publisher: EventPublisher) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<(u32, Vec<u8>)>();
+        let task = tokio::spawn(async move {
+            while let Some((height, payload)) = receiver.recv().await {
+                if let Err(e) = publisher.publish(height, payload).await {
+                    tracing::error!(block_height = height, "failed to publish block: {e}");
+                }
+            }
+            if let Err(e) = publisher.flush().await {
+                tracing::error!("failed to flush events: {e}");
+            }
+        });
+        Self { sender, task }
+    }
+
+    /// Queues the JSON of `outcome`, as written by `--output ndjson`.
+    pub fn send(&self, outcome: &BlockOutcome) {
+        match serde_json::to_vec(outcome) {
+            Ok(payload) => {
+                // The task only stops once the queue is closed.
+                let _ = self.sender.send((outcome.height, payload));
+            }
+            Err(e) => tracing::error!(
+                block_height = outcome.height,
+                "failed to serialize block: {e}"
+            ),
+        }
+    }
+
+    /// Waits for the queued events to be published.
+    pub async fn close(self) {
+        drop(self.sender);
+        if let Err(e) = self.task.await {
+            tracing::error!("event publisher failed: {e}");
+        }
+    }
+}
+
+/// Locks the `dashboard`, even if a thread drawing it panicked.
+pub fn lock(dashboard: &Mutex<Dashboard>) -> std::sync::MutexGuard<'_, Dashboard> {
+    dashboard
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Writes one outcome as a single line of JSON.
+fn print_line(outcome: &BlockOutcome, network: Option<&str>) {
+    let line = match network {
+        Some(network) => serde_json::to_value(outcome).map(|mut line| {
+            line["network"] = network.into();
+            line.to_string()
+        }),
+        None => serde_json::to_string(outcome),
+    };
+    match line {
+        Ok(line) => println!("{line}"),
+        Err(e) => tracing::error!(
+            block_height = outcome.height,
+            "failed to serialize block: {e}"
+        ),
+    }
+}
+
+/// The `[network] ` lines about its blocks start with, if any.
+fn label(network: Option<&str>) -> String {
+    network
+        .map(|network| format!("[{network}] "))
+        .unwrap_or_default()
+}
+
+fn print_failures(outcome: &BlockOutcome, network: Option<&str>) {
+    let label = label(network);
+    match &outcome.result {
+        Ok(report) => {
+            for failure in report.failures() {
+                println!("{label}{failure} [in block #{}]", report.height);
+                if let Some(diff) = &failure.receipt_diff {
+                    println!("{diff}");
+                }
+            }
+            for warning in report.warnings() {
+                println!("{label}warning: {warning} [in block #{}]", report.height);
+            }
+            for known in report.acknowledged() {
+                println!(
+                    "{label}acknowledged: {known} [in block #{}]: {}",
+                    report.height,
+                    known.acknowledged.as_deref().unwrap_or_default()
+                );
+            }
+        }
+        Err(e) => println!("{label}Block #{} failed validation: {e:#}", outcome.height),
+    }
+}
+
+/// The built-in checks, one CSV column each.
+const CSV_CHECKS: &[Check] = &[
+    Check::ApplicationHash,
+    Check::BlockId,
+    Check::PoaSignature,
+    Check::TxId,
+    Check::TxRoot,
+    Check::TxStatus,
+    Check::StatusHeight,
+    Check::StatusResult,
+    Check::StatusReason,
+    Check::StatusGas,
+    Check::MessageReceiptCount,
+    Check::MessageOutboxRoot,
+    Check::ReceiptRoot,
+    Check::MintPosition,
+    Check::MintAmount,
+    Check::MintAssetId,
+    Check::TxGas,
+    Check::TxFee,
+    Check::ContractStateRoot,
+    Check::ContractId,
+    Check::PrevRoot,
+    Check::EventInboxRoot,
+    Check::RelayedTx,
+    Check::L1DaHeight,
+    Check::L1Commit,
+    Check::EndpointAgreement,
+];
+
+/// The checks comparing roots, with a column for the root committed to and
+/// one for the root recomputed.
+const CSV_ROOTS: &[Check] = &[
+    Check::ApplicationHash,
+    Check::BlockId,
+    Check::TxRoot,
+    Check::MessageOutboxRoot,
+    Check::ReceiptRoot,
+    Check::ContractStateRoot,
+    Check::PrevRoot,
+    Check::EventInboxRoot,
+];
+
+/// Writes one CSV row per validated block: its height, id and result, the
+/// status of every built-in check (`pass`, `fail`, `warn`, `ack` for known
+/// anomalies, or empty when it wasn't run) and the roots compared. A check run for every transaction
+/// shows the roots of its first failure, or of its first transaction.
+pub struct CsvWriter {
+    out: Box<dyn Write + Send>,
+}
+
+impl CsvWriter {
+    /// Writes the header to `path`, or to stdout without one.
+    pub fn create(path: Option<&Path>) -> Result<Self> {
+        let out: Box<dyn Write + Send> = match path {
+            Some(path) => {
+                Box::new(BufWriter::new(File::create(path).with_context(|| {
+                    format!("failed to create {}", path.display())
+                })?))
+            }
+            None => Box::new(io::stdout()),
+        };
+        let mut writer = Self { out };
+        let mut header = vec![
+            "height".to_string(),
+            "block_id".to_string(),
+            "result".to_string(),
+            "error".to_string(),
+        ];
+        header.extend(CSV_CHECKS.iter().map(|check| check.name().to_string()));
+        for check in CSV_ROOTS {
+            header.push(format!("{}.expected", check.name()));
+            header.push(format!("{}.actual", check.name()));
+        }
+        writer.write(&header)?;
+        Ok(writer)
+    }
+
+    pub fn row(&mut self, outcome: &BlockOutcome) -> io::Result<()> {
+        let report = match &outcome.result {
+            Ok(report) => report,
+            Err(e) => {
+                let mut row = vec![
+                    outcome.height.to_string(),
+                    String::new(),
+                    "error".to_string(),
+                    format!("{e:#}"),
+                ];
+                row.resize(4 + CSV_CHECKS.len() + 2 * CSV_ROOTS.len(), String::new());
+                return self.write(&row);
+            }
+        };
+
+        let mut row = vec![
+            report.height.to_string(),
+            report.block_id.clone(),
+            if report.passed() { "passed" } else { "failed" }.to_string(),
+            String::new(),
+        ];
+        for check in CSV_CHECKS {
+            let status = if report.failures().any(|result| result.check == *check) {
+                "fail"
+            } else if report.warnings().any(|result| result.check == *check) {
+                "warn"
+            } else if report.acknowledged().any(|result| result.check == *check) {
+                "ack"
+            } else if report.checks.iter().any(|result| result.check == *check) {
+                "pass"
+            } else {
+                ""
+            };
+            row.push(status.to_string());
+        }
+        for check in CSV_ROOTS {
+            let mut results = report.checks.iter().filter(|result| result.check == *check);
+            let shown = results
+                .clone()
+                .find(|result| !result.passed)
+                .or_else(|| results.next());
+            row.push(
+                shown
+                    .and_then(|result| result.expected.clone())
+                    .unwrap_or_default(),
+            );
+            row.push(
+                shown
+                    .and_then(|result| result.actual.clone())
+                    .unwrap_or_default(),
+            );
+        }
+        self.write(&row)
+    }
+
+    /// Writes one record, flushed so rows show up as blocks are validated.
+    fn write(&mut self, fields: &[String]) -> io::Result<()> {
+        let line: Vec<_> = fields.iter().map(|field| csv_field(field)).collect();
+        writeln!(self.out, "{}", line.join(","))?;
+        self.out.flush()
+    }
+}
+
+/// `field`, quoted if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,55 @@
+// src/prev_root.rs
+use fuel_merkle::binary::root_calculator::MerkleRootCalculator;
+use fuel_types::Bytes32;
+
+use crate::error::{Result, ValidationError};
+
+/// Binary Merkle tree over the ids of consecutive blocks, starting at the
+/// first block served by the node (the genesis block).
+///
+/// The `prev_root` of the block at height `h` is the root of this tree once it
+/// contains the ids of every block below `h`.
+#[derive(Clone, Debug, Default)]
+pub struct PrevRoots {
+    calculator: MerkleRootCalculator,
+    next_height: Option<u32>,
+}
+
+impl PrevRoots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Height of the next block id expected by [`Self::push`], `None` while
+    /// the tree is empty.
+    pub fn next_height(&self) -> Option<u32> {
+        self.next_height
+    }
+
+    /// Appends the id of the block at `height`, which must directly follow the
+    /// last pushed block.
+    pub fn push(&mut self, height: u32, block_id: &Bytes32) -> Result<()> {
+        if let Some(next_height) = self.next_height {
+            if height != next_height {
+                return Err(ValidationError::NonContiguousHeight {
+                    expected: next_height,
+                    actual: height,
+                });
+            }
+        }
+
+        self.calculator.push(block_id.as_ref());
+        self.next_height = Some(height + 1);
+        Ok(())
+    }
+
+    pub fn root(&self) -> Bytes32 {
+        self.calculator.clone().root().into()
+    }
+
+    /// Expected `prev_root` of the block at `height`, if the tree currently
+    /// ends right below it.
+    pub fn expected_prev_root(&self, height: u32) -> Option<Bytes32> {
+        (self.next_height == Some(height)).then(|| self.root())
+    }
+}
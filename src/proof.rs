@@ -0,0 +1,111 @@
+// src/proof.rs
+//! Merkle inclusion proofs of transactions in a block's transactions root.
+
+use fuel_core_client::client::schema::Bytes;
+use fuel_merkle::binary::{self, in_memory::MerkleTree};
+use fuel_tx::{Transaction, UniqueIdentifier};
+use fuel_types::{
+    canonical::{Deserialize as _, Serialize as _},
+    Bytes32, ChainId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::checks::parse_transaction;
+use crate::error::{Result, ValidationError};
+use crate::query::FullBlock;
+
+/// Proof that a transaction is a leaf of the transactions root of a block.
+///
+/// The leaf is the canonical encoding of the transaction, which the proof
+/// carries so it can be checked on its own.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub block_height: u32,
+    pub tx_id: Bytes32,
+    /// Raw transaction, the leaf of the tree.
+    pub transaction: Bytes,
+    /// Transactions root of the block, which the proof leads to.
+    pub root: Bytes32,
+    /// Position of the transaction among the leaves.
+    pub proof_index: u64,
+    pub num_leaves: u64,
+    /// Sibling hashes from the leaf up to the root.
+    pub proof_set: Vec<Bytes32>,
+}
+
+impl MerkleProof {
+    /// Builds the transactions tree of `block` and proves that `tx_id` is
+    /// part of it.
+    ///
+    /// Fails if the block doesn't contain the transaction, or if the tree
+    /// doesn't lead to `header.transactions_root`, in which case no proof
+    /// against the committed root exists.
+    pub fn build(block: &FullBlock, tx_id: &Bytes32) -> Result<Self> {
+        let height = block.header.height.0;
+        let mut tree = MerkleTree::new();
+        let mut num_leaves = 0;
+        let mut found = None;
+
+        for tx in &block.transactions {
+            let transaction = parse_transaction(tx)?.to_bytes();
+            tree.push(&transaction);
+            if Bytes32::from(tx.id.clone()) == *tx_id {
+                found = Some((num_leaves, transaction));
+            }
+            num_leaves += 1;
+        }
+
+        let (proof_index, transaction) =
+            found.ok_or_else(|| ValidationError::TransactionNotFound {
+                height,
+                tx_id: tx_id.to_string(),
+            })?;
+
+        let committed: Bytes32 = block.header.transactions_root.clone().into();
+        let (root, proof_set) = tree
+            .prove(proof_index)
+            .expect("the transaction is one of the leaves");
+        let root = Bytes32::from(root);
+        if root != committed {
+            return Err(ValidationError::TxRootMismatch {
+                height,
+                expected: committed.to_string(),
+                actual: root.to_string(),
+            });
+        }
+
+        Ok(Self {
+            block_height: height,
+            tx_id: *tx_id,
+            transaction: Bytes(transaction),
+            root,
+            proof_index,
+            num_leaves,
+            proof_set: proof_set.into_iter().map(Bytes32::from).collect(),
+        })
+    }
+
+    /// Whether the proof shows that its transaction is included in `root`.
+    pub fn verify(&self, root: &Bytes32) -> bool {
+        self.verify_leaf(root, &self.transaction)
+    }
+
+    /// Id of the transaction carried by the proof, recomputed for `chain_id`.
+    /// Unlike [`MerkleProof::tx_id`], it can be trusted once the proof is
+    /// verified.
+    pub fn transaction_id(&self, chain_id: &ChainId) -> Result<Bytes32> {
+        let transaction = Transaction::from_bytes(&self.transaction).map_err(|e| {
+            ValidationError::ParseError {
+                tx_id: self.tx_id.to_string(),
+                reason: e.to_string(),
+            }
+        })?;
+        Ok(transaction.id(chain_id))
+    }
+
+    /// Whether the proof shows that `leaf` is included in `root`.
+    pub fn verify_leaf(&self, root: &Bytes32, leaf: &[u8]) -> bool {
+        let proof_set: Vec<_> = self.proof_set.iter().map(|hash| **hash).collect();
+        binary::verify(root, &leaf, &proof_set, self.proof_index, self.num_leaves)
+    }
+}
@@ -0,0 +1,361 @@
+// src/proof_bundle.rs
+//! Self-contained proofs of a transaction and its receipts, for consumers
+//! that only trust a block id or the key signing blocks: a bridge contract
+//! on another chain, or a light client.
+//!
+//! A [`ProofBundle`] carries the header of the block with the signature of
+//! its producer, the inclusion proof of the transaction in the transactions
+//! root, and every receipt of the transaction with its proof against the
+//! receipts root the transaction commits to. It can be checked with
+//! [`ProofBundle::verify`], or encoded with [`ProofBundle::to_bytes`] for
+//! verifiers that only hash bytes.
+
+use fuel_core_client::client::schema::{
+    block::Consensus,
+    tx::{transparent_receipt, TransactionStatus},
+    Bytes,
+};
+use fuel_merkle::binary::{self, in_memory::MerkleTree, root_calculator::MerkleRootCalculator};
+use fuel_tx::{field::ReceiptsRoot, Transaction};
+use fuel_types::{
+    canonical::{Deserialize as _, Serialize as _},
+    Bytes32, ChainId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::checks::{self, parse_receipt, parse_transaction};
+use crate::consensus::{self, PoaAuthority};
+use crate::dump::{ConsensusDump, HeaderDump, HeaderVersionDump};
+use crate::error::{Result, ValidationError};
+use crate::header;
+use crate::proof::MerkleProof;
+use crate::query::{FullBlock, Header};
+use crate::report::{Check, CheckResult};
+
+/// Everything needed to check that a transaction, and the receipts it
+/// produced, are part of a block, from its block id alone.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProofBundle {
+    pub block_id: Bytes32,
+    pub header: HeaderDump,
+    /// The signature of the producer over the block id.
+    pub consensus: ConsensusDump,
+    /// Ids of every transaction of the block, which V2 headers commit to.
+    /// Empty for V1 headers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tx_ids: Vec<Bytes32>,
+    /// Inclusion of the transaction in `header.transactions_root`.
+    pub transaction: MerkleProof,
+    /// Receipts root the transaction commits to. `None` for transactions
+    /// other than scripts, which don't commit to their receipts.
+    pub receipts_root: Option<Bytes32>,
+    /// Every receipt of the transaction, in order, with its proof against
+    /// `receipts_root`.
+    pub receipts: Vec<ReceiptProof>,
+}
+
+/// Proof that a receipt is a leaf of the receipts root of a transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReceiptProof {
+    /// Canonical encoding of the receipt, the leaf of the tree.
+    pub receipt: Bytes,
+    /// Position of the receipt among the leaves.
+    pub proof_index: u64,
+    pub num_leaves: u64,
+    /// Sibling hashes from the leaf up to the root.
+    pub proof_set: Vec<Bytes32>,
+}
+
+impl ProofBundle {
+    /// Proves that the transaction `tx_id` and its receipts are part of
+    /// `block`.
+    ///
+    /// Fails if the block doesn't contain the transaction, or if the block
+    /// id, the transactions root or the receipts root of the transaction
+    /// don't match what they commit to, in which case the bundle wouldn't
+    /// verify.
+    pub fn build(block: &FullBlock, tx_id: &Bytes32) -> Result<Self> {
+        let height = block.height();
+        if let Some(e) = checks::block_id(block)
+            .iter()
+            .find_map(|result| result.to_error(height))
+        {
+            return Err(e);
+        }
+        let transaction = MerkleProof::build(block, tx_id)?;
+
+        let tx = block
+            .transactions
+            .iter()
+            .find(|tx| Bytes32::from(tx.id.clone()) == *tx_id)
+            .expect("the proof was built over the transaction");
+        let receipts_root = match parse_transaction(tx)? {
+            Transaction::Script(script) => Some(*script.receipts_root()),
+            _ => None,
+        };
+        let receipts: &[transparent_receipt::Receipt] = match &tx.status {
+            Some(TransactionStatus::SuccessStatus(status)) => &status.receipts,
+            Some(TransactionStatus::FailureStatus(status)) => &status.receipts,
+            _ => &[],
+        };
+
+        let mut tree = MerkleTree::new();
+        let mut leaves = vec![];
+        for receipt in receipts {
+            let leaf = parse_receipt(tx, receipt)?.to_bytes();
+            tree.push(&leaf);
+            leaves.push(leaf);
+        }
+        let receipts = match receipts_root {
+            Some(committed) => {
+                let root = Bytes32::from(tree.root());
+                if root != committed {
+                    return Err(ValidationError::ReceiptRootMismatch {
+                        height,
+                        tx_id: tx_id.to_string(),
+                        expected: committed.to_string(),
+                        actual: root.to_string(),
+                    });
+                }
+                let num_leaves = leaves.len() as u64;
+                leaves
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, receipt)| {
+                        let (_, proof_set) = tree
+                            .prove(index as u64)
+                            .expect("the receipt is one of the leaves");
+                        ReceiptProof {
+                            receipt: Bytes(receipt),
+                            proof_index: index as u64,
+                            num_leaves,
+                            proof_set: proof_set.into_iter().map(Bytes32::from).collect(),
+                        }
+                    })
+                    .collect()
+            }
+            None => vec![],
+        };
+
+        let header = HeaderDump::from(&block.header);
+        let tx_ids = match header.version {
+            HeaderVersionDump::V2 => block
+                .transactions
+                .iter()
+                .map(|tx| tx.id.clone().into())
+                .collect(),
+            _ => vec![],
+        };
+        Ok(Self {
+            block_id: block.id.clone().into(),
+            header,
+            consensus: (&block.consensus).into(),
+            tx_ids,
+            transaction,
+            receipts_root,
+            receipts,
+        })
+    }
+
+    /// Checks the bundle on its own: the header must hash to `block_id`, the
+    /// transaction must be included in its transactions root and the
+    /// receipts must be the leaves of the receipts root of the transaction.
+    /// The id of the transaction is recomputed when `chain_id` is given, and
+    /// the signature of the block checked when `authority` is.
+    ///
+    /// The bundle only proves anything once `block_id` or the signer is
+    /// trusted.
+    pub fn verify(
+        &self,
+        chain_id: Option<&ChainId>,
+        authority: Option<&PoaAuthority>,
+    ) -> Vec<CheckResult> {
+        let height = self.header.height;
+        let tx_id = format!("{:#x}", self.transaction.tx_id);
+        let mut results = vec![match header::block_id(&self.full_header(), &self.tx_ids) {
+            Some(calculated) => CheckResult::compare(Check::BlockId, self.block_id, calculated),
+            None => CheckResult::from_error(
+                Check::BlockId,
+                &ValidationError::UnsupportedHeaderVersion { height },
+            ),
+        }];
+
+        if let Some(authority) = authority {
+            let consensus = Consensus::from(self.consensus.clone());
+            results.extend(consensus::signature(
+                &consensus,
+                height,
+                self.block_id,
+                authority,
+            ));
+        }
+
+        let transactions_root = self.header.transactions_root;
+        results.push(
+            if self.transaction.verify(&transactions_root) {
+                CheckResult::compare(Check::TxInclusion, transactions_root, self.transaction.root)
+            } else {
+                CheckResult::error(
+                    Check::TxInclusion,
+                    "the proof doesn't lead to the transactions root",
+                )
+            }
+            .with_tx_id(&tx_id),
+        );
+
+        if let Some(chain_id) = chain_id {
+            results.push(
+                match self.transaction.transaction_id(chain_id) {
+                    Ok(calculated) => {
+                        CheckResult::compare(Check::TxId, self.transaction.tx_id, calculated)
+                    }
+                    Err(e) => CheckResult::from_error(Check::TxId, &e),
+                }
+                .with_tx_id(&tx_id),
+            );
+        }
+
+        results.extend(
+            self.verify_receipts()
+                .map(|result| result.with_tx_id(&tx_id)),
+        );
+        results
+    }
+
+    /// The bundle in the binary encoding of verifiers that only hash bytes.
+    /// Numbers are big-endian, and every hash is the SHA-256 the chain uses:
+    ///
+    /// - the length of the application header as a `u32`, then the bytes
+    ///   hashed into the application hash, see
+    ///   [`header::application_header_bytes`],
+    /// - the 76 bytes hashed into the block id: the previous root, the
+    ///   height as a `u32`, the TAI64 time as a `u64` and the application
+    ///   hash,
+    /// - the 64 bytes signature of the block, zeroed for the genesis block,
+    /// - the inclusion proof of the transaction,
+    /// - the receipts root the transaction commits to, zeroed when it
+    ///   doesn't commit to one,
+    /// - the number of receipts as a `u32`, then the proof of each of them.
+    ///
+    /// Every proof is the index of the leaf and the number of leaves as
+    /// `u64`s, the number of sibling hashes as a `u32` followed by the
+    /// hashes from the leaf up, and the length of the leaf as a `u32`
+    /// followed by the leaf.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let header = self.full_header();
+        let application_header = header::application_header_bytes(&header, &self.tx_ids).ok_or(
+            ValidationError::UnsupportedHeaderVersion {
+                height: self.header.height,
+            },
+        )?;
+
+        let mut bytes = vec![];
+        bytes.extend((application_header.len() as u32).to_be_bytes());
+        bytes.extend(&application_header);
+        bytes.extend(header::consensus_header_bytes(
+            &header,
+            &fuel_crypto::Hasher::hash(&application_header),
+        ));
+        match &self.consensus {
+            ConsensusDump::Poa { signature } => bytes.extend(**signature),
+            _ => bytes.extend([0; 64]),
+        }
+
+        let transaction = &self.transaction;
+        encode_proof(
+            &mut bytes,
+            transaction.proof_index,
+            transaction.num_leaves,
+            &transaction.proof_set,
+            &transaction.transaction,
+        );
+        bytes.extend(*self.receipts_root.unwrap_or_default());
+        bytes.extend((self.receipts.len() as u32).to_be_bytes());
+        for receipt in &self.receipts {
+            encode_proof(
+                &mut bytes,
+                receipt.proof_index,
+                receipt.num_leaves,
+                &receipt.proof_set,
+                &receipt.receipt,
+            );
+        }
+        Ok(bytes)
+    }
+
+    fn full_header(&self) -> Header {
+        self.header.clone().into()
+    }
+
+    /// Checks the receipts against the root the transaction commits to,
+    /// `None` for transactions without receipts root nor receipts.
+    fn verify_receipts(&self) -> Option<CheckResult> {
+        let committed = match Transaction::from_bytes(&self.transaction.transaction) {
+            Ok(Transaction::Script(script)) => Some(*script.receipts_root()),
+            Ok(_) => None,
+            Err(e) => {
+                return Some(CheckResult::error(
+                    Check::ReceiptRoot,
+                    format!("invalid transaction: {e}"),
+                ))
+            }
+        };
+        if self.receipts_root != committed {
+            return Some(CheckResult::error(
+                Check::ReceiptRoot,
+                "the receipts root isn't the one the transaction commits to",
+            ));
+        }
+        let Some(receipts_root) = committed else {
+            return (!self.receipts.is_empty()).then(|| {
+                CheckResult::error(
+                    Check::ReceiptRoot,
+                    "the transaction doesn't commit to its receipts",
+                )
+            });
+        };
+
+        let mut calculated = MerkleRootCalculator::new();
+        for (index, receipt) in self.receipts.iter().enumerate() {
+            if !receipt.verify(&receipts_root) {
+                return Some(CheckResult::error(
+                    Check::ReceiptRoot,
+                    format!("the proof of receipt {index} doesn't lead to the receipts root"),
+                ));
+            }
+            calculated.push(&receipt.receipt);
+        }
+        // The proofs could all be of the same receipt; the root of the
+        // receipts shows they are all of them, in order.
+        Some(CheckResult::compare(
+            Check::ReceiptRoot,
+            receipts_root,
+            calculated.root().into(),
+        ))
+    }
+}
+
+impl ReceiptProof {
+    /// Whether the proof shows that its receipt is included in `root`.
+    pub fn verify(&self, root: &Bytes32) -> bool {
+        let proof_set: Vec<_> = self.proof_set.iter().map(|hash| **hash).collect();
+        binary::verify(
+            root,
+            &self.receipt.0,
+            &proof_set,
+            self.proof_index,
+            self.num_leaves,
+        )
+    }
+}
+
+fn encode_proof(bytes: &mut Vec<u8>, index: u64, leaves: u64, proof_set: &[Bytes32], leaf: &[u8]) {
+    bytes.extend(index.to_be_bytes());
+    bytes.extend(leaves.to_be_bytes());
+    bytes.extend((proof_set.len() as u32).to_be_bytes());
+    for hash in proof_set {
+        bytes.extend(**hash);
+    }
+    bytes.extend((leaf.len() as u32).to_be_bytes());
+    bytes.extend(leaf);
+}
@@ -0,0 +1,420 @@
+// src/query.rs
+use fuel_core_client::client::{
+    pagination::PaginatedResult,
+    schema::{
+        block::{BlockHeightFragment, Consensus},
+        chain::ConsensusParameters,
+        schema,
+        tx::{TransactionStatus, TxIdArgs},
+        BlockId, Bytes32, ConnectionArgs, HexString, PageInfo, Tai64Timestamp, TransactionId, U16,
+        U32, U64,
+    },
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Block header, of any [`HeaderVersion`].
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl")]
+pub struct Header {
+    pub version: HeaderVersion,
+    pub id: BlockId,
+    pub da_height: U64,
+    pub consensus_parameters_version: U32,
+    pub state_transition_bytecode_version: U32,
+    pub transactions_count: U16,
+    pub message_receipt_count: U32,
+    pub transactions_root: Bytes32,
+    pub message_outbox_root: Bytes32,
+    pub event_inbox_root: Bytes32,
+    pub height: U32,
+    pub prev_root: Bytes32,
+    pub time: Tai64Timestamp,
+    pub application_hash: Bytes32,
+}
+
+/// Layout of a block header. Each version commits to different fields in
+/// the application hash, see [`crate::header::application_hash`].
+///
+/// Versions introduced by later nodes decode as `Unknown`, so their blocks
+/// can still be fetched, and their ids are reported as unchecked rather than
+/// mismatching.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderVersion {
+    V1,
+    /// Also commits to the ids of the transactions of the block.
+    V2,
+    Unknown,
+}
+
+// Implemented by hand rather than derived: cynic can't decode the variants
+// it doesn't know to a fallback.
+impl cynic::Enum for HeaderVersion {
+    type SchemaType = schema::HeaderVersion;
+}
+
+cynic::impl_coercions!(HeaderVersion, schema::HeaderVersion);
+
+impl Serialize for HeaderVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            HeaderVersion::V1 => "V1",
+            HeaderVersion::V2 => "V2",
+            HeaderVersion::Unknown => "UNKNOWN",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for HeaderVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match String::deserialize(deserializer)?.as_str() {
+            "V1" => HeaderVersion::V1,
+            "V2" => HeaderVersion::V2,
+            _ => HeaderVersion::Unknown,
+        })
+    }
+}
+
+// Simple block query
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "Query",
+    variables = "BlockQueryVariables"
+)]
+pub struct BlockQuery {
+    #[arguments(height: $height)]
+    pub block: Option<FullBlock>,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct BlockQueryVariables {
+    pub height: Option<U32>,
+}
+
+// Block query without the statuses of the transactions, for nodes failing
+// the combined query
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "Query",
+    variables = "BlockQueryVariables"
+)]
+pub struct BlockWithoutStatusesQuery {
+    #[arguments(height: $height)]
+    pub block: Option<BlockWithoutStatuses>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Block")]
+pub struct BlockWithoutStatuses {
+    pub id: BlockId,
+    pub header: Header,
+    pub consensus: Consensus,
+    pub transactions: Vec<OpaqueTransactionWithId>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(graphql_type = "Transaction", schema_path = "./src/schema/schema.sdl")]
+pub struct OpaqueTransactionWithId {
+    pub id: TransactionId,
+    pub raw_payload: HexString,
+}
+
+/// The block, with transactions that have no status yet.
+impl From<BlockWithoutStatuses> for FullBlock {
+    fn from(block: BlockWithoutStatuses) -> Self {
+        Self {
+            id: block.id,
+            header: block.header,
+            consensus: block.consensus,
+            transactions: block
+                .transactions
+                .into_iter()
+                .map(|tx| OpaqueTransactionWithStatusAndId {
+                    id: tx.id,
+                    raw_payload: tx.raw_payload,
+                    status: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+// Header-only block query
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "Query",
+    variables = "BlockQueryVariables"
+)]
+pub struct BlockHeaderQuery {
+    #[arguments(height: $height)]
+    pub block: Option<BlockHeader>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Block")]
+pub struct BlockHeader {
+    pub header: Header,
+}
+
+// Header-only block query by block id
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "Query",
+    variables = "BlockByIdVariables"
+)]
+pub struct BlockHeaderByIdQuery {
+    #[arguments(id: $id)]
+    pub block: Option<BlockHeader>,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct BlockByIdVariables {
+    pub id: Option<BlockId>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(graphql_type = "Transaction", schema_path = "./src/schema/schema.sdl")]
+pub struct OpaqueTransactionWithStatusAndId {
+    pub id: TransactionId,
+    pub raw_payload: HexString,
+    pub status: Option<TransactionStatus>,
+}
+
+// Status of a transaction by id, for the height of its block
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "Query",
+    variables = "TxIdArgs"
+)]
+pub struct TransactionStatusQuery {
+    #[arguments(id: $id)]
+    pub transaction: Option<TransactionWithStatus>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Transaction")]
+pub struct TransactionWithStatus {
+    pub status: Option<TransactionStatus>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Block")]
+pub struct FullBlock {
+    pub id: BlockId,
+    pub header: Header,
+    pub consensus: Consensus,
+    pub transactions: Vec<OpaqueTransactionWithStatusAndId>,
+}
+
+// Height of the chain tip
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Query")]
+pub struct LatestBlockHeightQuery {
+    pub chain: LatestBlockChainInfo,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "ChainInfo")]
+pub struct LatestBlockChainInfo {
+    pub latest_block: BlockHeightFragment,
+}
+
+// Chain id from the current consensus parameters
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Query")]
+pub struct ChainIdQuery {
+    pub chain: ChainIdChainInfo,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "ChainInfo")]
+pub struct ChainIdChainInfo {
+    pub consensus_parameters: ChainIdConsensusParameters,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "ConsensusParameters"
+)]
+pub struct ChainIdConsensusParameters {
+    pub chain_id: U64,
+}
+
+// Current consensus parameters, with the version of the latest block
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Query")]
+pub struct ConsensusParametersQuery {
+    pub chain: ConsensusParametersChainInfo,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "ChainInfo")]
+pub struct ConsensusParametersChainInfo {
+    pub latest_block: ConsensusParametersVersionBlock,
+    pub consensus_parameters: ConsensusParameters,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Block")]
+pub struct ConsensusParametersVersionBlock {
+    pub header: ConsensusParametersVersionHeader,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Header")]
+pub struct ConsensusParametersVersionHeader {
+    pub consensus_parameters_version: U32,
+}
+
+// Paginated block query, the cursor is the block height
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "Query",
+    variables = "ConnectionArgs"
+)]
+pub struct FullBlocksQuery {
+    #[arguments(after: $after, before: $before, first: $first, last: $last)]
+    pub blocks: FullBlockConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "BlockConnection"
+)]
+pub struct FullBlockConnection {
+    pub edges: Vec<FullBlockEdge>,
+    pub page_info: PageInfo,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "BlockEdge")]
+pub struct FullBlockEdge {
+    pub cursor: String,
+    pub node: FullBlock,
+}
+
+impl From<FullBlockConnection> for PaginatedResult<FullBlock, String> {
+    fn from(conn: FullBlockConnection) -> Self {
+        PaginatedResult {
+            cursor: conn.page_info.end_cursor,
+            has_next_page: conn.page_info.has_next_page,
+            has_previous_page: conn.page_info.has_previous_page,
+            results: conn.edges.into_iter().map(|e| e.node).collect(),
+        }
+    }
+}
+
+impl FullBlock {
+    pub fn height(&self) -> u32 {
+        self.header.height.0
+    }
+}
+
+// Paginated block ids, used to rebuild the `prev_root` tree
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "Query",
+    variables = "ConnectionArgs"
+)]
+pub struct BlockIdsQuery {
+    #[arguments(after: $after, before: $before, first: $first, last: $last)]
+    pub blocks: BlockIdConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "BlockConnection"
+)]
+pub struct BlockIdConnection {
+    pub nodes: Vec<BlockIdAndHeight>,
+    pub page_info: PageInfo,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Block")]
+pub struct BlockIdAndHeight {
+    pub id: BlockId,
+    pub height: U32,
+}
+
+impl From<BlockIdConnection> for PaginatedResult<BlockIdAndHeight, String> {
+    fn from(conn: BlockIdConnection) -> Self {
+        PaginatedResult {
+            cursor: conn.page_info.end_cursor,
+            has_next_page: conn.page_info.has_next_page,
+            has_previous_page: conn.page_info.has_previous_page,
+            results: conn.nodes,
+        }
+    }
+}
+
+// Paginated blocks without their transactions, for header-only validation
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "Query",
+    variables = "ConnectionArgs"
+)]
+pub struct LightBlocksQuery {
+    #[arguments(after: $after, before: $before, first: $first, last: $last)]
+    pub blocks: LightBlockConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./src/schema/schema.sdl",
+    graphql_type = "BlockConnection"
+)]
+pub struct LightBlockConnection {
+    pub nodes: Vec<LightBlock>,
+    pub page_info: PageInfo,
+}
+
+/// A block without its transactions: the header, the consensus and the ids
+/// of the transactions, enough to recompute the block id at a fraction of
+/// the size of a [`FullBlock`].
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Block")]
+pub struct LightBlock {
+    pub id: BlockId,
+    pub header: Header,
+    pub consensus: Consensus,
+    pub transaction_ids: Vec<TransactionId>,
+}
+
+impl From<LightBlockConnection> for PaginatedResult<LightBlock, String> {
+    fn from(conn: LightBlockConnection) -> Self {
+        PaginatedResult {
+            cursor: conn.page_info.end_cursor,
+            has_next_page: conn.page_info.has_next_page,
+            has_previous_page: conn.page_info.has_previous_page,
+            results: conn.nodes,
+        }
+    }
+}
+
+impl LightBlock {
+    pub fn height(&self) -> u32 {
+        self.header.height.0
+    }
+}
+
+impl From<&FullBlock> for LightBlock {
+    fn from(block: &FullBlock) -> Self {
+        Self {
+            id: block.id.clone(),
+            header: block.header.clone(),
+            consensus: block.consensus.clone(),
+            transaction_ids: block.transactions.iter().map(|tx| tx.id.clone()).collect(),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// src/rate_limit.rs
+//! Politeness towards the nodes queried: a cap on the requests sent per
+//! second and on the requests in flight to every endpoint, and the delay a
+//! node asks for when it answers `429 Too Many Requests` with `Retry-After`.
+
+use std::{
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use tokio::{
+    sync::{Semaphore, SemaphorePermit},
+    time::Instant,
+};
+
+/// Longest a `Retry-After` holds the requests back. Nodes asking for more,
+/// or sending garbage, are queried again after this long.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+
+/// How many requests are sent to one endpoint. Unlimited by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RateLimit {
+    /// Most requests started per second, spread evenly.
+    pub max_rps: Option<f64>,
+    /// Most requests waiting for a response at once.
+    pub max_in_flight: Option<usize>,
+}
+
+impl RateLimit {
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+}
+
+/// Paces the requests to one endpoint by its [`RateLimit`], and holds them
+/// back for as long as the endpoint asked to with `Retry-After`.
+#[derive(Debug)]
+pub struct Limiter {
+    limit: RateLimit,
+    in_flight: Option<Semaphore>,
+    schedule: Mutex<Schedule>,
+}
+
+#[derive(Debug)]
+struct Schedule {
+    /// When the next request may start, by the requests per second.
+    next_slot: Instant,
+    /// When the endpoint accepts requests again.
+    paused_until: Instant,
+}
+
+/// A request allowed to start, in flight until dropped.
+#[derive(Debug)]
+pub struct Permit<'a> {
+    _in_flight: Option<SemaphorePermit<'a>>,
+}
+
+impl Limiter {
+    pub fn new(limit: RateLimit) -> Self {
+        let now = Instant::now();
+        Self {
+            limit,
+            in_flight: limit.max_in_flight.map(|max| Semaphore::new(max.max(1))),
+            schedule: Mutex::new(Schedule {
+                next_slot: now,
+                paused_until: now,
+            }),
+        }
+    }
+
+    pub fn limit(&self) -> &RateLimit {
+        &self.limit
+    }
+
+    /// Waits until another request may be sent.
+    pub async fn acquire(&self) -> Permit<'_> {
+        let in_flight = match &self.in_flight {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire()
+                    .await
+                    .expect("the in flight semaphore is never closed"),
+            ),
+            None => None,
+        };
+        let start = {
+            let mut schedule = self
+                .schedule
+                .lock()
+                .expect("the schedule lock isn't poisoned");
+            let start = Instant::now()
+                .max(schedule.next_slot)
+                .max(schedule.paused_until);
+            if let Some(rps) = self.limit.max_rps.filter(|rps| *rps > 0.0) {
+                schedule.next_slot = start + Duration::from_secs_f64(1.0 / rps);
+            }
+            start
+        };
+        tokio::time::sleep_until(start).await;
+        Permit {
+            _in_flight: in_flight,
+        }
+    }
+
+    /// Holds back the requests not started yet for `delay`, at most
+    /// [`MAX_RETRY_AFTER`].
+    pub fn pause(&self, delay: Duration) {
+        let now = Instant::now();
+        let until = now
+            .checked_add(delay.min(MAX_RETRY_AFTER))
+            .unwrap_or(now + MAX_RETRY_AFTER);
+        let mut schedule = self
+            .schedule
+            .lock()
+            .expect("the schedule lock isn't poisoned");
+        schedule.paused_until = schedule.paused_until.max(until);
+    }
+}
+
+impl Default for Limiter {
+    fn default() -> Self {
+        Self::new(RateLimit::unlimited())
+    }
+}
+
+/// The delay asked for by the `Retry-After` header of a response, in
+/// seconds or as an HTTP date, capped at [`MAX_RETRY_AFTER`].
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    let delay = match value.parse::<u64>() {
+        Ok(seconds) => Duration::from_secs(seconds),
+        Err(_) => httpdate::parse_http_date(value)
+            .ok()?
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    };
+    Some(delay.min(MAX_RETRY_AFTER))
+}
@@ -0,0 +1,194 @@
+// src/receipt_diff.rs
+//! Receipt by receipt breakdown of a receipts root mismatch, locating the
+//! first receipt the committed root doesn't account for.
+//!
+//! The root only commits to the hashes of the receipts, so the receipt that
+//! diverges can't always be told. It can when the node serves receipts the
+//! root doesn't cover: a prefix of the receipts, or all of them but one,
+//! hashing to the committed root. A receipt whose data doesn't match its own
+//! digest or length gives itself away too.
+
+use std::fmt;
+
+use fuel_crypto::Hasher;
+use fuel_tx::Receipt;
+use fuel_types::{canonical::Serialize as _, Bytes32};
+use serde::Serialize;
+
+use crate::merkle;
+
+/// Above this many receipts the committed ones aren't searched for: every
+/// candidate is a root of its own.
+const MAX_SEARCHED: usize = 1024;
+
+/// One receipt of a transaction, as a leaf of its receipts root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ReceiptLeaf {
+    pub index: usize,
+    pub receipt_type: &'static str,
+    /// Canonical encoding of the receipt, which is the leaf, in hex.
+    pub bytes: String,
+    pub leaf_hash: Bytes32,
+    /// What doesn't add up in the receipt on its own, if anything.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+}
+
+/// The receipts of a transaction whose root doesn't match the one it
+/// committed to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ReceiptDiff {
+    pub committed_root: Bytes32,
+    /// Root of the receipts served by the node.
+    pub calculated_root: Bytes32,
+    pub leaves: Vec<ReceiptLeaf>,
+    /// Index of the first receipt the committed root doesn't account for,
+    /// if it can be located.
+    pub first_divergent: Option<usize>,
+    /// How `first_divergent` was located, or why it couldn't be.
+    pub explanation: String,
+}
+
+impl ReceiptDiff {
+    /// Breaks the root of `receipts` down and compares it with
+    /// `committed_root`.
+    pub fn new(committed_root: Bytes32, receipts: &[Receipt]) -> Self {
+        let leaves: Vec<_> = receipts
+            .iter()
+            .enumerate()
+            .map(|(index, receipt)| {
+                let bytes = receipt.to_bytes();
+                ReceiptLeaf {
+                    index,
+                    receipt_type: receipt_type(receipt),
+                    leaf_hash: merkle::leaf_hash(&bytes),
+                    bytes: format!("0x{}", hex::encode(&bytes)),
+                    issue: issue(receipt),
+                }
+            })
+            .collect();
+        let hashes: Vec<_> = leaves.iter().map(|leaf| leaf.leaf_hash).collect();
+        let (first_divergent, explanation) = locate(&committed_root, &hashes, &leaves);
+        Self {
+            committed_root,
+            calculated_root: merkle::root(hashes),
+            leaves,
+            first_divergent,
+            explanation,
+        }
+    }
+}
+
+fn locate(
+    committed_root: &Bytes32,
+    hashes: &[Bytes32],
+    leaves: &[ReceiptLeaf],
+) -> (Option<usize>, String) {
+    if hashes.len() <= MAX_SEARCHED {
+        if let Some(len) = (0..hashes.len())
+            .find(|len| merkle::root(hashes[..*len].iter().copied()) == *committed_root)
+        {
+            return (
+                Some(len),
+                format!(
+                    "the first {len} receipts hash to the committed root, the receipts from #{len} on aren't committed to"
+                ),
+            );
+        }
+        if let Some(extra) = (0..hashes.len()).find(|extra| {
+            let others = hashes
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| index != extra);
+            merkle::root(others.map(|(_, hash)| *hash)) == *committed_root
+        }) {
+            return (
+                Some(extra),
+                format!(
+                    "the other receipts hash to the committed root, receipt #{extra} isn't committed to"
+                ),
+            );
+        }
+    }
+    if let Some(leaf) = leaves.iter().find(|leaf| leaf.issue.is_some()) {
+        let issue = leaf.issue.as_deref().unwrap_or_default();
+        return (
+            Some(leaf.index),
+            format!("receipt #{} is inconsistent: {issue}", leaf.index),
+        );
+    }
+    let reason = if hashes.len() > MAX_SEARCHED {
+        format!(
+            "{} receipts are too many to search for the committed ones",
+            hashes.len()
+        )
+    } else {
+        "the committed root isn't over any of the receipts or all of them but one: receipts may be missing, altered or reordered".to_string()
+    };
+    (None, reason)
+}
+
+/// The digest and the length a receipt carries for its data, checked
+/// against the data.
+fn issue(receipt: &Receipt) -> Option<String> {
+    let data = receipt.data()?;
+    if let Some(len) = receipt.len() {
+        if len != data.len() as u64 {
+            return Some(format!("len is {len} but the data is {} bytes", data.len()));
+        }
+    }
+    let digest = receipt.digest()?;
+    let hashed = Hasher::hash(data);
+    (*digest != hashed).then(|| format!("digest is {digest:#x} but the data hashes to {hashed:#x}"))
+}
+
+fn receipt_type(receipt: &Receipt) -> &'static str {
+    match receipt {
+        Receipt::Call { .. } => "Call",
+        Receipt::Return { .. } => "Return",
+        Receipt::ReturnData { .. } => "ReturnData",
+        Receipt::Panic { .. } => "Panic",
+        Receipt::Revert { .. } => "Revert",
+        Receipt::Log { .. } => "Log",
+        Receipt::LogData { .. } => "LogData",
+        Receipt::Transfer { .. } => "Transfer",
+        Receipt::TransferOut { .. } => "TransferOut",
+        Receipt::ScriptResult { .. } => "ScriptResult",
+        Receipt::MessageOut { .. } => "MessageOut",
+        Receipt::Mint { .. } => "Mint",
+        Receipt::Burn { .. } => "Burn",
+    }
+}
+
+/// One line per receipt, then the receipt that diverges, indented to go
+/// under the failure of the receipts root.
+impl fmt::Display for ReceiptDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  {} receipts, committed root {:#x}, calculated root {:#x}:",
+            self.leaves.len(),
+            self.committed_root,
+            self.calculated_root
+        )?;
+        for leaf in &self.leaves {
+            write!(
+                f,
+                "    #{} {} leaf {:#x} bytes {}",
+                leaf.index, leaf.receipt_type, leaf.leaf_hash, leaf.bytes
+            )?;
+            if let Some(issue) = &leaf.issue {
+                write!(f, " ({issue})")?;
+            }
+            writeln!(f)?;
+        }
+        match self.first_divergent {
+            Some(index) => write!(
+                f,
+                "  first divergent receipt: #{index}, {}",
+                self.explanation
+            ),
+            None => write!(f, "  no divergent receipt located: {}", self.explanation),
+        }
+    }
+}
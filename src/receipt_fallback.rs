@@ -0,0 +1,51 @@
+// src/receipt_fallback.rs
+//! Transactions whose receipts a block was served without, to be fetched
+//! one by one with the status of the transaction instead.
+//!
+//! Some nodes fail the combined query of a block with the statuses of its
+//! transactions, e.g. when the response would be too large, or leave out
+//! the receipts of some statuses. Rather than failing the whole block, the
+//! block is queried without the statuses, or as served, and the statuses
+//! lacking receipts are filled in from the status query of each
+//! transaction.
+
+use fuel_core_client::client::schema::tx::TransactionStatus;
+use fuel_tx::Transaction;
+
+use crate::checks;
+use crate::query::{FullBlock, OpaqueTransactionWithStatusAndId};
+
+/// Whether `tx` is missing its status, or its receipts. Executed scripts
+/// always have a `ScriptResult` receipt, while the other transactions have
+/// none.
+pub fn lacks_receipts(tx: &OpaqueTransactionWithStatusAndId) -> bool {
+    let receipts = match &tx.status {
+        None => return true,
+        Some(TransactionStatus::SuccessStatus(status)) => &status.receipts,
+        Some(TransactionStatus::FailureStatus(status)) => &status.receipts,
+        Some(_) => return false,
+    };
+    receipts.is_empty() && matches!(checks::parse_transaction(tx), Ok(Transaction::Script(_)))
+}
+
+/// Indices of the transactions of `block` lacking their receipts.
+pub fn missing_receipts(block: &FullBlock) -> Vec<usize> {
+    block
+        .transactions
+        .iter()
+        .enumerate()
+        .filter(|(_, tx)| lacks_receipts(tx))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Replaces the status of `tx` with `status`, fetched on its own, if that
+/// fills in what it lacks. Returns whether it did.
+pub fn fill_in(tx: &mut OpaqueTransactionWithStatusAndId, status: TransactionStatus) -> bool {
+    let original = tx.status.replace(status);
+    if lacks_receipts(tx) && original.is_some() {
+        tx.status = original;
+        return false;
+    }
+    true
+}
@@ -0,0 +1,331 @@
+// src/registry.rs
+//! Named checks, selected with `--check`/`--skip-check` and extended by
+//! library consumers with their own.
+//!
+//! Checks that only need the block are [`BlockCheck`]s, run by the registry
+//! in registration order. Checks that query more than the block (the
+//! `prev_root` tree, the event inbox, L1 and the cross-checked endpoints) are
+//! run by the validator, and only registered by name so they can be
+//! selected the same way.
+
+use std::{collections::BTreeSet, fmt, sync::Arc};
+
+use fuel_tx::ConsensusParameters;
+use fuel_types::ChainId;
+
+use crate::checks;
+use crate::consensus::{self, PoaAuthority};
+use crate::error::ValidationError;
+use crate::query::FullBlock;
+use crate::report::{BlockReport, Check, CheckResult, Severity};
+
+/// The application hash and the block id, see [`checks::block_id`].
+pub const BLOCK_ID: &str = "block-id";
+/// The PoA signature, once an authority is known.
+pub const POA_SIGNATURE: &str = "poa-signature";
+/// The transaction ids, once a chain id is known.
+pub const TX_ID: &str = "tx-id";
+pub const TX_ROOT: &str = "tx-root";
+/// The status of every transaction, see [`checks::tx_statuses`].
+pub const TX_STATUS: &str = "tx-status";
+/// The details of the status of every transaction against its receipts and
+/// block, see [`checks::status_receipts`].
+pub const STATUS_RECEIPTS: &str = "status-receipts";
+/// The message receipt count and the message outbox root.
+pub const MESSAGE_ROOT: &str = "message-root";
+pub const RECEIPT_ROOT: &str = "receipt-root";
+/// The position, amount and asset of the mint, see [`checks::mint`].
+pub const MINT: &str = "mint";
+/// The gas and the fee of every transaction, off by default.
+pub const FEES: &str = "fees";
+/// The state roots and ids of the contracts created, off by default.
+pub const CONTRACT_STATE: &str = "contract-state";
+/// `header.prev_root`, off by default.
+pub const PREV_ROOT: &str = "prev-root";
+/// `header.event_inbox_root` and the relayed transactions, off by default.
+pub const EVENT_INBOX: &str = "event-inbox";
+/// The DA height and the commitments of the block on L1, once L1 is known.
+pub const L1: &str = "l1";
+/// The blocks served by the cross-checked endpoints.
+pub const ENDPOINT_AGREEMENT: &str = "endpoint-agreement";
+
+/// The name of the registered check reporting results as `check`, e.g.
+/// `message-root` for `message-outbox-root`. Custom checks report under
+/// their own name.
+pub fn reported_by(check: &str) -> &str {
+    match check {
+        "application-hash" => BLOCK_ID,
+        "message-receipt-count" | "message-outbox-root" => MESSAGE_ROOT,
+        "mint-position" | "mint-amount" | "mint-asset-id" => MINT,
+        "tx-inclusion" => TX_ROOT,
+        "status-height" | "status-result" | "status-reason" | "status-gas" => STATUS_RECEIPTS,
+        "tx-gas" | "tx-fee" => FEES,
+        "contract-state-root" | "contract-id" => CONTRACT_STATE,
+        "event-inbox-root" | "relayed-tx" => EVENT_INBOX,
+        "l1-da-height" | "l1-commit" => L1,
+        check => check,
+    }
+}
+
+/// What a [`BlockCheck`] is run on.
+#[derive(Clone, Copy, Debug)]
+pub struct CheckContext<'a> {
+    pub block: &'a FullBlock,
+    /// The chain id transaction ids are computed for, if known.
+    pub chain_id: Option<&'a ChainId>,
+    /// The PoA authority that must have signed the block, if known.
+    pub authority: Option<&'a PoaAuthority>,
+    /// The consensus parameters of the block, if known.
+    pub params: Option<&'a ConsensusParameters>,
+}
+
+/// A check that only needs the block and what's known about its chain.
+///
+/// Results of custom checks are reported under [`Check::Custom`] with the
+/// name of the check.
+pub trait BlockCheck: fmt::Debug + Send + Sync {
+    /// The name the check is selected by, in kebab case.
+    fn name(&self) -> &'static str;
+
+    fn run(&self, context: &CheckContext<'_>) -> Vec<CheckResult>;
+}
+
+/// A registered check.
+#[derive(Clone, Debug)]
+struct Entry {
+    name: &'static str,
+    /// `None` for the checks run by the validator.
+    check: Option<Arc<dyn BlockCheck>>,
+    enabled: bool,
+    /// Severity of every failure of the check, instead of the default
+    /// severity of each result.
+    severity: Option<Severity>,
+}
+
+/// The checks validation runs, by name. [`Self::default`] has every
+/// built-in check, with `fees`, `contract-state`, `prev-root` and
+/// `event-inbox` disabled.
+#[derive(Clone, Debug)]
+pub struct CheckRegistry {
+    entries: Vec<Entry>,
+}
+
+impl CheckRegistry {
+    /// A registry without any check.
+    pub fn empty() -> Self {
+        Self { entries: vec![] }
+    }
+
+    /// Registers `check`, enabled, after the checks registered so far. A
+    /// check already registered under the same name is replaced.
+    pub fn register(mut self, check: impl BlockCheck + 'static) -> Self {
+        self.insert(check.name(), Some(Arc::new(check)), true);
+        self
+    }
+
+    fn insert(&mut self, name: &'static str, check: Option<Arc<dyn BlockCheck>>, enabled: bool) {
+        let entry = Entry {
+            name,
+            check,
+            enabled,
+            severity: None,
+        };
+        match self.entries.iter_mut().find(|entry| entry.name == name) {
+            Some(registered) => *registered = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// Enables or disables the check `name`, failing with
+    /// [`ValidationError::UnknownCheck`] if it isn't registered.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> Result<(), ValidationError> {
+        self.entry_mut(name)?.enabled = enabled;
+        Ok(())
+    }
+
+    /// Reports every failure of the check `name` with `severity`, e.g.
+    /// [`Severity::Warning`] to only warn about it.
+    pub fn set_severity(&mut self, name: &str, severity: Severity) -> Result<(), ValidationError> {
+        self.entry_mut(name)?.severity = Some(severity);
+        Ok(())
+    }
+
+    fn entry_mut(&mut self, name: &str) -> Result<&mut Entry, ValidationError> {
+        let known = self.names().collect::<Vec<_>>().join(", ");
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.name == name)
+            .ok_or(ValidationError::UnknownCheck {
+                name: name.to_string(),
+                known,
+            })
+    }
+
+    /// `results` of the check `name`, with the severity it was given.
+    pub(crate) fn rate(&self, name: &str, mut results: Vec<CheckResult>) -> Vec<CheckResult> {
+        let severity = self
+            .entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .and_then(|entry| entry.severity);
+        if let Some(severity) = severity {
+            for result in &mut results {
+                result.severity = severity;
+            }
+        }
+        results
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.name == name && entry.enabled)
+    }
+
+    /// Names of the registered checks, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|entry| entry.name)
+    }
+
+    /// Names of the enabled checks, sorted.
+    pub fn enabled(&self) -> BTreeSet<&'static str> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.enabled)
+            .map(|entry| entry.name)
+            .collect()
+    }
+
+    /// Runs the enabled block checks on the block of `context`.
+    pub fn run(&self, context: &CheckContext<'_>) -> BlockReport {
+        let checks = self
+            .entries
+            .iter()
+            .filter(|entry| entry.enabled)
+            .filter_map(|entry| Some((entry.name, entry.check.as_ref()?)))
+            .flat_map(|(name, check)| self.rate(name, check.run(context)))
+            .collect();
+        BlockReport {
+            height: context.block.height(),
+            block_id: context.block.id.to_string(),
+            checks,
+            fail_on: Severity::default(),
+        }
+    }
+}
+
+impl Default for CheckRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        for check in builtin::CHECKS {
+            let enabled = !matches!(check.name, FEES | CONTRACT_STATE);
+            registry.insert(check.name, Some(Arc::new(*check)), enabled);
+        }
+        for (name, enabled) in [
+            (PREV_ROOT, false),
+            (EVENT_INBOX, false),
+            (L1, true),
+            (ENDPOINT_AGREEMENT, true),
+        ] {
+            registry.insert(name, None, enabled);
+        }
+        registry
+    }
+}
+
+mod builtin {
+    use super::*;
+
+    /// A built-in block check.
+    #[derive(Clone, Copy)]
+    pub(super) struct Builtin {
+        pub(super) name: &'static str,
+        run: fn(&CheckContext<'_>) -> Vec<CheckResult>,
+    }
+
+    impl fmt::Debug for Builtin {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_tuple("Builtin").field(&self.name).finish()
+        }
+    }
+
+    impl BlockCheck for Builtin {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn run(&self, context: &CheckContext<'_>) -> Vec<CheckResult> {
+            (self.run)(context)
+        }
+    }
+
+    /// The built-in block checks, in the order their results are reported.
+    pub(super) const CHECKS: &[Builtin] = &[
+        Builtin {
+            name: BLOCK_ID,
+            run: |context| checks::block_id(context.block),
+        },
+        Builtin {
+            name: POA_SIGNATURE,
+            run: |context| match context.authority {
+                Some(authority) => consensus::poa_signature(context.block, authority)
+                    .into_iter()
+                    .collect(),
+                None => vec![],
+            },
+        },
+        Builtin {
+            name: TX_ID,
+            run: |context| match context.chain_id {
+                Some(chain_id) => checks::tx_ids(context.block, chain_id),
+                None => vec![],
+            },
+        },
+        Builtin {
+            name: TX_ROOT,
+            run: |context| vec![checks::tx_root(context.block)],
+        },
+        Builtin {
+            name: TX_STATUS,
+            run: |context| checks::tx_statuses(context.block),
+        },
+        Builtin {
+            name: STATUS_RECEIPTS,
+            run: |context| checks::status_receipts(context.block),
+        },
+        Builtin {
+            name: MESSAGE_ROOT,
+            run: |context| checks::message_outbox(context.block),
+        },
+        Builtin {
+            name: RECEIPT_ROOT,
+            run: |context| checks::receipt_roots(context.block),
+        },
+        Builtin {
+            name: MINT,
+            run: |context| {
+                let mut results = checks::mint(context.block);
+                if let Some(params) = context.params {
+                    results.extend(checks::mint_asset_id(context.block, params.base_asset_id()));
+                }
+                results
+            },
+        },
+        Builtin {
+            name: FEES,
+            run: |context| match context.params {
+                Some(params) => checks::fees(context.block, params),
+                None => {
+                    let e = ValidationError::ConsensusParametersNotFound {
+                        version: context.block.header.consensus_parameters_version.0,
+                    };
+                    vec![CheckResult::from_error(Check::TxFee, &e)]
+                }
+            },
+        },
+        Builtin {
+            name: CONTRACT_STATE,
+            run: |context| checks::contract_state_roots(context.block),
+        },
+    ];
+}
@@ -0,0 +1,559 @@
+// src/report.rs
+use std::{fmt, str::FromStr};
+
+use serde::Serialize;
+#[cfg(feature = "native")]
+use serde::{ser::SerializeStruct, Serializer};
+
+use crate::error::{ErrorKind, ValidationError};
+use crate::receipt_diff::ReceiptDiff;
+#[cfg(feature = "native")]
+use crate::validator::{BlockOutcome, RangeValidation};
+
+/// A single validation performed on a block, serialized as its name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Check {
+    ApplicationHash,
+    BlockId,
+    PoaSignature,
+    TxId,
+    TxRoot,
+    TxStatus,
+    StatusHeight,
+    StatusResult,
+    StatusReason,
+    StatusGas,
+    MessageReceiptCount,
+    MessageOutboxRoot,
+    ReceiptRoot,
+    MintPosition,
+    MintAmount,
+    MintAssetId,
+    TxGas,
+    TxFee,
+    ContractStateRoot,
+    ContractId,
+    TxInclusion,
+    PrevRoot,
+    EventInboxRoot,
+    RelayedTx,
+    L1DaHeight,
+    L1Commit,
+    EndpointAgreement,
+    /// A check registered by a library consumer, see
+    /// [`BlockCheck`](crate::registry::BlockCheck).
+    Custom(&'static str),
+}
+
+impl Check {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Check::ApplicationHash => "application-hash",
+            Check::BlockId => "block-id",
+            Check::PoaSignature => "poa-signature",
+            Check::TxId => "tx-id",
+            Check::TxRoot => "tx-root",
+            Check::TxStatus => "tx-status",
+            Check::StatusHeight => "status-height",
+            Check::StatusResult => "status-result",
+            Check::StatusReason => "status-reason",
+            Check::StatusGas => "status-gas",
+            Check::MessageReceiptCount => "message-receipt-count",
+            Check::MessageOutboxRoot => "message-outbox-root",
+            Check::ReceiptRoot => "receipt-root",
+            Check::MintPosition => "mint-position",
+            Check::MintAmount => "mint-amount",
+            Check::MintAssetId => "mint-asset-id",
+            Check::TxGas => "tx-gas",
+            Check::TxFee => "tx-fee",
+            Check::ContractStateRoot => "contract-state-root",
+            Check::ContractId => "contract-id",
+            Check::TxInclusion => "tx-inclusion",
+            Check::PrevRoot => "prev-root",
+            Check::EventInboxRoot => "event-inbox-root",
+            Check::RelayedTx => "relayed-tx",
+            Check::L1DaHeight => "l1-da-height",
+            Check::L1Commit => "l1-commit",
+            Check::EndpointAgreement => "endpoint-agreement",
+            Check::Custom(name) => name,
+        }
+    }
+}
+
+impl Check {
+    /// How bad a failure of this check is unless the registry says
+    /// otherwise. The gas and fee are the node's own accounting in the
+    /// transaction status, which no commitment covers, and so are the status
+    /// itself and its details, so they only warn.
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            Check::TxGas
+            | Check::TxFee
+            | Check::TxStatus
+            | Check::StatusHeight
+            | Check::StatusResult
+            | Check::StatusReason
+            | Check::StatusGas => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl Serialize for Check {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How bad a failed check is. Only failures at or above the
+/// [`BlockReport::fail_on`] severity fail the block, the others are
+/// reported as warnings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    #[default]
+    Error,
+}
+
+impl Severity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(format!(
+                "unknown severity `{s}`, expected one of info, warning, error"
+            )),
+        }
+    }
+}
+
+/// Result of one check. `expected` is the value committed to by the node,
+/// `actual` the value recomputed by the validator.
+#[derive(Clone, Debug, Serialize)]
+pub struct CheckResult {
+    pub check: Check,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_id: Option<String>,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// The cross-checked endpoint the result is about.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Why the check couldn't be computed, for failed checks without values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<ErrorKind>,
+    pub severity: Severity,
+    /// The receipts of a transaction failing its receipts root.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_diff: Option<Box<ReceiptDiff>>,
+    /// The note of the [known anomaly](crate::known_anomalies) the failure
+    /// is, in which case it doesn't fail the block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acknowledged: Option<String>,
+}
+
+impl CheckResult {
+    pub fn compare<T>(check: Check, expected: T, actual: T) -> Self
+    where
+        T: PartialEq + fmt::Display,
+    {
+        Self {
+            check,
+            tx_id: None,
+            passed: expected == actual,
+            expected: Some(expected.to_string()),
+            actual: Some(actual.to_string()),
+            message: None,
+            endpoint: None,
+            error_kind: None,
+            severity: check.default_severity(),
+            receipt_diff: None,
+            acknowledged: None,
+        }
+    }
+
+    /// A check that `actual` doesn't exceed `max`.
+    pub fn at_most<T>(check: Check, max: T, actual: T) -> Self
+    where
+        T: PartialOrd + fmt::Display,
+    {
+        Self {
+            passed: actual <= max,
+            expected: Some(format!("at most {max}")),
+            ..Self::compare(check, max.to_string(), actual.to_string())
+        }
+    }
+
+    /// A check that couldn't be computed, e.g. because the data it needs
+    /// doesn't decode. Whatever the check, that is an error.
+    pub fn error(check: Check, message: impl fmt::Display) -> Self {
+        Self {
+            check,
+            tx_id: None,
+            expected: None,
+            actual: None,
+            passed: false,
+            message: Some(message.to_string()),
+            endpoint: None,
+            error_kind: Some(ErrorKind::Mismatch),
+            severity: Severity::Error,
+            receipt_diff: None,
+            acknowledged: None,
+        }
+    }
+
+    /// A check that couldn't be computed because of `error`.
+    pub fn from_error(check: Check, error: &ValidationError) -> Self {
+        Self {
+            error_kind: Some(error.kind()),
+            ..Self::error(check, error)
+        }
+    }
+
+    pub fn with_tx_id(mut self, tx_id: impl fmt::Display) -> Self {
+        self.tx_id = Some(tx_id.to_string());
+        self
+    }
+
+    pub fn with_message(mut self, message: impl fmt::Display) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    pub fn with_endpoint(mut self, endpoint: impl fmt::Display) -> Self {
+        self.endpoint = Some(endpoint.to_string());
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn with_receipt_diff(mut self, diff: ReceiptDiff) -> Self {
+        self.receipt_diff = Some(Box::new(diff));
+        self
+    }
+
+    /// The error describing this check's failure in the block at `height`,
+    /// `None` if it passed.
+    pub fn to_error(&self, height: u32) -> Option<ValidationError> {
+        if self.passed {
+            return None;
+        }
+
+        let (Some(expected), Some(actual)) = (self.expected.clone(), self.actual.clone()) else {
+            return Some(ValidationError::CheckFailed {
+                check: self.check,
+                height,
+                tx_id: self.tx_id.clone(),
+                message: self.message.clone().unwrap_or_default(),
+                kind: self.error_kind.unwrap_or(ErrorKind::Mismatch),
+            });
+        };
+        let tx_id = self.tx_id.clone().unwrap_or_default();
+
+        Some(match self.check {
+            Check::ApplicationHash => ValidationError::ApplicationHashMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::BlockId => ValidationError::BlockIdMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::PoaSignature => ValidationError::PoaSignatureMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::TxId => ValidationError::TxIdMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::TxRoot => ValidationError::TxRootMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::TxStatus => ValidationError::TxStatusMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::StatusHeight => ValidationError::StatusHeightMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::StatusResult => ValidationError::StatusResultMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::StatusReason => ValidationError::StatusReasonMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::StatusGas => ValidationError::StatusGasMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::MessageReceiptCount => ValidationError::MessageReceiptCountMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::MessageOutboxRoot => ValidationError::MessageOutboxRootMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::ReceiptRoot => ValidationError::ReceiptRootMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::MintPosition => ValidationError::MintPositionMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::MintAmount => ValidationError::MintAmountMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::MintAssetId => ValidationError::MintAssetIdMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::TxGas => ValidationError::TxGasMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::TxFee => ValidationError::TxFeeMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::ContractStateRoot => ValidationError::ContractStateRootMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::ContractId => ValidationError::ContractIdMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::TxInclusion => ValidationError::TxInclusionMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::PrevRoot => ValidationError::PrevRootMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::EventInboxRoot => ValidationError::EventInboxRootMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::RelayedTx => ValidationError::RelayedTxMismatch {
+                height,
+                tx_id,
+                expected,
+                actual,
+            },
+            Check::L1DaHeight => ValidationError::L1DaHeightMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::L1Commit => ValidationError::L1CommitMismatch {
+                height,
+                expected,
+                actual,
+            },
+            Check::EndpointAgreement => ValidationError::EndpointDisagreement {
+                height,
+                detail: self.message.clone().unwrap_or_default(),
+                expected,
+                actual,
+            },
+            Check::Custom(_) => ValidationError::CheckFailed {
+                check: self.check,
+                height,
+                tx_id: self.tx_id.clone(),
+                message: format!("expected {expected}, got {actual}"),
+                kind: ErrorKind::Mismatch,
+            },
+        })
+    }
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.check)?;
+        if let Some(tx_id) = &self.tx_id {
+            write!(f, " of transaction {tx_id}")?;
+        }
+        if !self.passed {
+            f.write_str(" failed")?;
+        }
+        if let (Some(expected), Some(actual)) = (&self.expected, &self.actual) {
+            write!(f, ": expected {expected}, got {actual}")?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, " ({message})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Every check run on a block.
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockReport {
+    pub height: u32,
+    pub block_id: String,
+    pub checks: Vec<CheckResult>,
+    /// The least severity a failed check fails the block with.
+    #[serde(skip)]
+    pub fail_on: Severity,
+}
+
+impl BlockReport {
+    /// Whether no check failed at or above [`Self::fail_on`].
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// The failed checks that fail the block.
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.unacknowledged()
+            .filter(|check| check.severity >= self.fail_on)
+    }
+
+    /// The failed checks below [`Self::fail_on`], which don't fail the block.
+    pub fn warnings(&self) -> impl Iterator<Item = &CheckResult> {
+        self.unacknowledged()
+            .filter(|check| check.severity < self.fail_on)
+    }
+
+    /// The failed checks that are known anomalies, which don't fail the
+    /// block whatever their severity.
+    pub fn acknowledged(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks
+            .iter()
+            .filter(|check| !check.passed && check.acknowledged.is_some())
+    }
+
+    fn unacknowledged(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks
+            .iter()
+            .filter(|check| !check.passed && check.acknowledged.is_none())
+    }
+
+    /// The failures as errors that can be matched on.
+    pub fn errors(&self) -> Vec<ValidationError> {
+        self.failures()
+            .filter_map(|check| check.to_error(self.height))
+            .collect()
+    }
+}
+
+#[cfg(feature = "native")]
+impl Serialize for BlockOutcome {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("BlockOutcome", 6)?;
+        state.serialize_field("height", &self.height)?;
+        state.serialize_field("passed", &self.passed())?;
+        state.serialize_field("duration_ms", &(self.duration.as_secs_f64() * 1000.0))?;
+        match &self.result {
+            Ok(report) => {
+                state.serialize_field("report", report)?;
+                state.skip_field("error")?;
+            }
+            Err(e) => {
+                state.skip_field("report")?;
+                state.serialize_field("error", &format!("{e:#}"))?;
+            }
+        }
+        match &self.timing {
+            Some(timing) => state.serialize_field("timing", timing)?,
+            None => state.skip_field("timing")?,
+        }
+        state.end()
+    }
+}
+
+#[cfg(feature = "native")]
+impl Serialize for RangeValidation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("RangeValidation", 6)?;
+        state.serialize_field("blocks", &self.outcomes.len())?;
+        state.serialize_field("passed", &self.passed())?;
+        state.serialize_field("failed", &self.failed())?;
+        state.serialize_field("acknowledged", &self.acknowledged())?;
+        match self.timing() {
+            Some(timing) => state.serialize_field("timing", &timing)?,
+            None => state.skip_field("timing")?,
+        }
+        state.serialize_field("outcomes", &self.outcomes)?;
+        state.end()
+    }
+}
@@ -0,0 +1,106 @@
+// src/retry.rs
+use std::{future::Future, io, time::Duration};
+
+use cynic::http::CynicReqwestError;
+use rand::Rng;
+
+/// Default number of attempts made for every query.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// How failed GraphQL queries are retried.
+///
+/// Retryable errors are retried with exponential backoff and full jitter: the
+/// delay before retry `n` is drawn uniformly from
+/// `0..=min(max_backoff, initial_backoff * 2^n)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Upper bound of the delay before retry number `retry`, counting from 0.
+    pub fn max_delay(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+
+    /// Randomized delay before retry number `retry`.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let max = self.max_delay(retry);
+        max.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+    }
+
+    /// Runs `operation` until it succeeds, fails with a permanent error or
+    /// runs out of attempts. Returns the last error and the number of
+    /// attempts made.
+    pub async fn run<T, F, Fut>(&self, what: &str, mut operation: F) -> Result<T, (io::Error, u32)>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = io::Result<T>>,
+    {
+        let max_attempts = self.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < max_attempts && is_retryable(&e) => {
+                    let delay = self.delay(attempt - 1);
+                    tracing::debug!(
+                        query = what,
+                        attempt,
+                        max_attempts,
+                        "query failed, retrying in {delay:?}: {e}"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err((e, attempt)),
+            }
+        }
+    }
+}
+
+/// Whether a query error is transient: timeouts, connection failures, rate
+/// limiting and server errors. GraphQL errors and undecodable responses are
+/// permanent.
+pub fn is_retryable(error: &io::Error) -> bool {
+    // Queries that got no response within the timeout of the source.
+    if error.kind() == io::ErrorKind::TimedOut {
+        return true;
+    }
+    let Some(error) = error
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<CynicReqwestError>())
+    else {
+        return false;
+    };
+
+    match error {
+        CynicReqwestError::ErrorResponse(status, _) => {
+            status.is_server_error() || matches!(status.as_u16(), 408 | 429)
+        }
+        CynicReqwestError::ReqwestError(e) => !e.is_decode() && !e.is_builder(),
+    }
+}
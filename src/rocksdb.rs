@@ -0,0 +1,285 @@
+// src/rocksdb.rs
+//! Blocks read from the RocksDB database of a fuel-core node, bypassing its
+//! GraphQL API. Requires the `rocksdb` feature.
+//!
+//! The database is opened read-only, so it can be read while the node is
+//! running, but blocks the node imports after it was opened aren't seen.
+//! Blocks are converted to the [`BlockDump`] model and validated like the
+//! blocks served by the API; block ids are recomputed from the headers, as
+//! the node does when serving them.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use fuel_core::{
+    database::{
+        database_description::{off_chain::OffChain, on_chain::OnChain, DatabaseDescription},
+        metadata::MetadataTable,
+        Database,
+    },
+    fuel_core_graphql_api::storage::transactions::TransactionStatuses,
+    state::{
+        historical_rocksdb::{description::Historical, HistoricalRocksDB, StateRewindPolicy},
+        rocks_db::RocksDb,
+    },
+};
+use fuel_core_client::client::schema::Bytes;
+use fuel_core_storage::{
+    tables::{FuelBlocks, SealedBlockConsensus, Transactions},
+    transactional::AtomicView,
+    Error as StorageError, StorageAsRef, StorageInspect,
+};
+use fuel_core_types::{
+    blockchain::{consensus::Consensus, header::BlockHeader},
+    fuel_types::{canonical::Serialize, BlockHeight},
+    fuel_vm::ProgramState,
+    services::{executor::TransactionExecutionResult, txpool::TransactionStatus},
+};
+
+use crate::dump::{
+    BlockDump, ConsensusDump, HeaderDump, HeaderVersionDump, ProgramStateDump, ReturnTypeDump,
+    StatusDump, TransactionDump,
+};
+use crate::error::{Result, ValidationError};
+use crate::query::FullBlock;
+use crate::source::BlockSource;
+
+/// Blocks of the on-chain database of a node, with the statuses and receipts
+/// of their transactions from its off-chain database.
+#[derive(Clone)]
+pub struct RocksDbSource {
+    path: PathBuf,
+    on_chain: Arc<Database<OnChain>>,
+    off_chain: Arc<Database<OffChain>>,
+}
+
+impl fmt::Debug for RocksDbSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RocksDbSource")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RocksDbSource {
+    /// Opens the database of the node whose `--db-path` is `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        Ok(Self {
+            on_chain: Arc::new(open_read_only(&path)?),
+            off_chain: Arc::new(open_read_only(&path)?),
+            path,
+        })
+    }
+
+    /// Reads the block at `height`, `None` if the database doesn't have it.
+    fn read_block(&self, height: u32) -> Result<Option<FullBlock>> {
+        let error = |e: StorageError| self.error(e);
+        let on_chain = self.on_chain.latest_view().map_err(error)?;
+        let off_chain = self.off_chain.latest_view().map_err(error)?;
+
+        let height = BlockHeight::from(height);
+        let Some(block) = on_chain
+            .storage::<FuelBlocks>()
+            .get(&height)
+            .map_err(error)?
+        else {
+            return Ok(None);
+        };
+        let consensus = on_chain
+            .storage::<SealedBlockConsensus>()
+            .get(&height)
+            .map_err(error)?
+            .ok_or_else(|| self.error(format!("no consensus for block #{height}")))?;
+
+        let mut transactions = Vec::with_capacity(block.transactions().len());
+        for id in block.transactions() {
+            let tx = on_chain
+                .storage::<Transactions>()
+                .get(id)
+                .map_err(error)?
+                .ok_or_else(|| {
+                    self.error(format!("missing transaction {id} of block #{height}"))
+                })?;
+            let status = off_chain
+                .storage::<TransactionStatuses>()
+                .get(id)
+                .map_err(error)?;
+            transactions.push(TransactionDump {
+                id: *id,
+                raw_payload: Bytes(tx.to_bytes()),
+                status: status.map(|status| status_dump(status.into_owned())),
+            });
+        }
+
+        let header = block.header();
+        Ok(Some(
+            BlockDump {
+                id: header.id().into(),
+                header: header_dump(header),
+                consensus: match consensus.into_owned() {
+                    Consensus::Genesis(genesis) => ConsensusDump::Genesis {
+                        chain_config_hash: genesis.chain_config_hash,
+                        coins_root: genesis.coins_root,
+                        contracts_root: genesis.contracts_root,
+                        messages_root: genesis.messages_root,
+                        transactions_root: genesis.transactions_root,
+                    },
+                    Consensus::PoA(poa) => ConsensusDump::Poa {
+                        signature: poa.signature,
+                    },
+                },
+                transactions,
+            }
+            .into(),
+        ))
+    }
+
+    fn error(&self, reason: impl fmt::Display) -> ValidationError {
+        ValidationError::Database {
+            path: self.path.clone(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Runs the blocking database read `read` on the blocking thread pool.
+    async fn read<T, F>(&self, read: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Self) -> Result<T> + Send + 'static,
+    {
+        let source = self.clone();
+        tokio::task::spawn_blocking(move || read(&source))
+            .await
+            .map_err(|e| self.error(e))?
+    }
+}
+
+#[async_trait]
+impl BlockSource for RocksDbSource {
+    async fn fetch_block(&self, height: u32) -> Result<FullBlock> {
+        self.read(move |source| source.read_block(height))
+            .await?
+            .ok_or(ValidationError::BlockNotFound { height })
+    }
+
+    async fn fetch_range(&self, from: u32, to: u32) -> Result<Vec<FullBlock>> {
+        self.read(move |source| {
+            (from..=to)
+                .filter_map(|height| source.read_block(height).transpose())
+                .collect()
+        })
+        .await
+    }
+
+    async fn latest_height(&self) -> Result<u32> {
+        self.read(|source| {
+            let view = source.on_chain.latest_view().map_err(|e| source.error(e))?;
+            let height = view.latest_height().map_err(|e| source.error(e))?;
+            Ok(*height)
+        })
+        .await
+    }
+}
+
+/// Opens the `Description` database of the node at `path` without writing
+/// to it.
+fn open_read_only<Description>(path: &Path) -> Result<Database<Description>>
+where
+    Description: DatabaseDescription,
+    Database<Description>: StorageInspect<MetadataTable<Description>, Error = StorageError>,
+{
+    let error = |e: fuel_core::database::Error| ValidationError::Database {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    };
+    let columns =
+        enum_iterator::all::<<Historical<Description> as DatabaseDescription>::Column>().collect();
+    let db = RocksDb::<Historical<Description>>::open_read_only(path, columns, None, false)
+        .map_err(error)?;
+    let db = HistoricalRocksDB::new(db, StateRewindPolicy::NoRewind).map_err(error)?;
+    Ok(Database::new(Arc::new(db)))
+}
+
+fn header_dump(header: &BlockHeader) -> HeaderDump {
+    let application = header.application();
+    let consensus = header.consensus();
+    HeaderDump {
+        version: match header {
+            BlockHeader::V1(_) => HeaderVersionDump::V1,
+        },
+        id: header.id().into(),
+        da_height: application.da_height.0,
+        consensus_parameters_version: application.consensus_parameters_version,
+        state_transition_bytecode_version: application.state_transition_bytecode_version,
+        transactions_count: application.generated.transactions_count,
+        message_receipt_count: application.generated.message_receipt_count,
+        transactions_root: application.generated.transactions_root,
+        message_outbox_root: application.generated.message_outbox_root,
+        event_inbox_root: application.generated.event_inbox_root,
+        height: *consensus.height,
+        prev_root: consensus.prev_root,
+        time: consensus.time.to_unix(),
+        application_hash: consensus.generated.application_hash,
+    }
+}
+
+/// The status as the API serves it. The failure reason is derived from the
+/// receipts the same way.
+fn status_dump(status: TransactionStatus) -> StatusDump {
+    match status {
+        TransactionStatus::Submitted { time } => StatusDump::Submitted {
+            time: time.to_unix(),
+        },
+        TransactionStatus::Success {
+            block_height,
+            time,
+            result,
+            receipts,
+            total_gas,
+            total_fee,
+        } => StatusDump::Success {
+            block_height: *block_height,
+            time: time.to_unix(),
+            program_state: result.and_then(program_state_dump),
+            receipts: receipts.iter().map(Into::into).collect(),
+            total_gas,
+            total_fee,
+        },
+        TransactionStatus::SqueezedOut { reason } => StatusDump::SqueezedOut { reason },
+        TransactionStatus::Failed {
+            block_height,
+            time,
+            result,
+            receipts,
+            total_gas,
+            total_fee,
+        } => StatusDump::Failure {
+            block_height: *block_height,
+            time: time.to_unix(),
+            reason: TransactionExecutionResult::reason(&receipts, &result),
+            program_state: result.and_then(program_state_dump),
+            receipts: receipts.iter().map(Into::into).collect(),
+            total_gas,
+            total_fee,
+        },
+    }
+}
+
+/// Debugger states don't end a transaction and aren't served by the API.
+fn program_state_dump(state: ProgramState) -> Option<ProgramStateDump> {
+    let (return_type, data) = match state {
+        ProgramState::Return(value) => (ReturnTypeDump::Return, value.to_be_bytes().to_vec()),
+        ProgramState::ReturnData(digest) => (ReturnTypeDump::ReturnData, digest.to_vec()),
+        ProgramState::Revert(value) => (ReturnTypeDump::Revert, value.to_be_bytes().to_vec()),
+        ProgramState::RunProgram(_) | ProgramState::VerifyPredicate(_) => return None,
+    };
+    Some(ProgramStateDump {
+        return_type,
+        data: Bytes(data),
+    })
+}
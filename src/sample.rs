@@ -0,0 +1,32 @@
+// src/sample.rs
+//! Random heights to spot check a range of blocks without scanning all of it.
+
+use rand::{rngs::StdRng, seq::index, SeedableRng};
+
+/// `count` distinct heights of `from..=to`, picked at random with `seed` and
+/// sorted. The whole range is returned when it has at most `count` blocks.
+///
+/// The same seed always picks the same heights, so a spot check can be
+/// reproduced from the seed it logs.
+pub fn sample_heights(from: u32, to: u32, count: usize, seed: u64) -> Vec<u32> {
+    if from > to {
+        return vec![];
+    }
+    let len = u64::from(to - from) + 1;
+    if len <= count as u64 {
+        return (from..=to).collect();
+    }
+
+    // `len` exceeds `count`, which fits in a usize.
+    let mut heights: Vec<_> = index::sample(&mut StdRng::seed_from_u64(seed), len as usize, count)
+        .into_iter()
+        .map(|offset| from + offset as u32)
+        .collect();
+    heights.sort_unstable();
+    heights
+}
+
+/// A seed for [`sample_heights`] when none is given.
+pub fn random_seed() -> u64 {
+    rand::random()
+}
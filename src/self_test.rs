@@ -0,0 +1,77 @@
+// src/self_test.rs
+//! Blocks bundled with the crate, with the roots they commit to, so an
+//! installed build can check that it computes the same roots as fuel-core,
+//! e.g. after being compiled for another target or with other features.
+//!
+//! The fixtures were produced by a fuel-core 0.40.4 node running the local
+//! testnet chain config, and written with `export`: their roots were
+//! computed by the executor of the node, not by this crate, and the node
+//! signed their ids with the development consensus key of fuel-core, so the
+//! PoA signature check also tells whether the ids are recomputed right.
+//! They are a block with a single script transaction and one with 64 of
+//! them, as many as the `parallel` feature starts hashing on every core
+//! from, so both hashing paths are covered. Every script logs, sends a
+//! message to L1 and returns, so the receipts and message outbox roots
+//! aren't empty.
+
+use fuel_types::{Address, ChainId};
+
+use crate::checks;
+use crate::consensus::PoaAuthority;
+use crate::dump::BlockDump;
+use crate::query::FullBlock;
+use crate::report::BlockReport;
+
+/// Chain id the transaction ids of the fixtures are computed with.
+pub const CHAIN_ID: u64 = 0;
+
+/// Address of `fuel_core_chain_config::default_consensus_dev_key`, which
+/// signed the fixtures.
+pub const AUTHORITY: Address = Address::new([
+    0x22, 0xec, 0x92, 0xc3, 0x10, 0x5c, 0x94, 0x2a, 0x66, 0x40, 0xbd, 0xc4, 0xe4, 0x90, 0x72, 0x86,
+    0xec, 0x47, 0x28, 0xe8, 0xcf, 0xc0, 0xd8, 0xac, 0x59, 0xaa, 0xd4, 0xd8, 0xe1, 0xcc, 0xae, 0xfb,
+]);
+
+const FIXTURES: &[(&str, &str)] = &[
+    (
+        "block-1.json",
+        include_str!("../fixtures/self-test/block-1.json"),
+    ),
+    (
+        "block-2.json",
+        include_str!("../fixtures/self-test/block-2.json"),
+    ),
+];
+
+/// A bundled block.
+#[derive(Clone, Debug)]
+pub struct Fixture {
+    pub name: &'static str,
+    pub block: FullBlock,
+}
+
+impl Fixture {
+    /// Runs every default check on the block, transaction ids and the PoA
+    /// signature included.
+    pub fn validate(&self) -> BlockReport {
+        checks::validate_block(
+            &self.block,
+            Some(&ChainId::new(CHAIN_ID)),
+            Some(&PoaAuthority::new(AUTHORITY)),
+        )
+    }
+}
+
+/// The bundled blocks, in height order.
+pub fn fixtures() -> Vec<Fixture> {
+    FIXTURES
+        .iter()
+        .map(|&(name, json)| {
+            let dump: BlockDump = serde_json::from_str(json).expect("the fixtures are block dumps");
+            Fixture {
+                name,
+                block: dump.into(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,111 @@
+// src/server.rs
+//! HTTP API validating blocks on request, for services that would rather not
+//! embed the validator.
+//!
+//! `GET /validate/{height}` fetches and validates the block at `height` from
+//! the validator's source. `POST /validate` validates the block in the body,
+//! in the [`BlockDump`] JSON format written by `export`. Both respond with the
+//! [`BlockOutcome`] of the block, as written by `--output json`.
+//!
+//! `GET /healthz` answers as long as the server runs, and `GET /readyz` with
+//! the [`Readiness`](crate::health::Readiness) of the validator: it is ready while its source answers
+//! queries of the latest block.
+
+use std::{future::Future, net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::{
+    body::Bytes,
+    extract::{DefaultBodyLimit, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::json;
+
+use crate::dump::BlockDump;
+use crate::error::{ErrorKind, ValidationError};
+use crate::health::Health;
+use crate::query::FullBlock;
+use crate::validator::{BlockOutcome, BlockValidator};
+
+/// Largest block accepted by `POST /validate`. Blocks with many transactions
+/// are well above the default limit of axum.
+pub const MAX_BLOCK_BYTES: usize = 32 * 1024 * 1024;
+
+/// The routes of the API, validating blocks with `validator`.
+pub fn router(validator: Arc<BlockValidator>) -> Router {
+    Router::new()
+        .route("/validate/:height", get(validate_height))
+        .route("/validate", post(validate_dump))
+        .route("/healthz", get(|| async { "ok" }))
+        .route("/readyz", get(ready))
+        .layer(DefaultBodyLimit::max(MAX_BLOCK_BYTES))
+        .with_state(validator)
+}
+
+/// Serves the API of [`router`] on `addr` until `shutdown` resolves.
+pub async fn serve<S>(
+    addr: SocketAddr,
+    validator: Arc<BlockValidator>,
+    shutdown: S,
+) -> hyper::Result<()>
+where
+    S: Future<Output = ()>,
+{
+    let server = hyper::Server::try_bind(&addr)?.serve(router(validator).into_make_service());
+    tracing::info!(
+        "serving the validation API on http://{}",
+        server.local_addr()
+    );
+    server.with_graceful_shutdown(shutdown).await
+}
+
+async fn validate_height(
+    State(validator): State<Arc<BlockValidator>>,
+    Path(height): Path<u32>,
+) -> Response {
+    respond(validator.validate_height(height).await)
+}
+
+async fn validate_dump(State(validator): State<Arc<BlockValidator>>, body: Bytes) -> Response {
+    let dump: BlockDump = match serde_json::from_slice(&body) {
+        Ok(dump) => dump,
+        Err(e) => {
+            let error = json!({ "error": format!("invalid block: {e}") });
+            return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+        }
+    };
+
+    let block = FullBlock::from(dump);
+    respond(
+        validator
+            .validate_fetched_outcome(&block, None, Duration::ZERO)
+            .await,
+    )
+}
+
+async fn ready(State(validator): State<Arc<BlockValidator>>) -> Response {
+    let health = Health::new();
+    health.probe(&validator).await;
+    let readiness = health.readiness();
+    (readiness.status(), Json(readiness)).into_response()
+}
+
+/// The outcome as JSON. Blocks that were validated are `200 OK` whether they
+/// passed or not; errors that kept them from being validated aren't.
+fn respond(outcome: BlockOutcome) -> Response {
+    let status = match &outcome.result {
+        Ok(_) => StatusCode::OK,
+        Err(e) => error_status(e),
+    };
+    (status, Json(outcome)).into_response()
+}
+
+fn error_status(error: &ValidationError) -> StatusCode {
+    match (error, error.kind()) {
+        (ValidationError::BlockNotFound { .. }, _) => StatusCode::NOT_FOUND,
+        (_, ErrorKind::Query | ErrorKind::Disagreement) => StatusCode::BAD_GATEWAY,
+        (_, ErrorKind::Parse | ErrorKind::Mismatch) => StatusCode::UNPROCESSABLE_ENTITY,
+    }
+}
@@ -0,0 +1,48 @@
+// src/signals.rs
+//! The Unix signals controlling follow mode at runtime: SIGHUP reloads the
+//! settings and SIGUSR1 logs statistics.
+
+#[cfg(unix)]
+use tokio::signal::unix::{signal, Signal, SignalKind};
+
+/// A signal that is listened for. It never arrives on platforms without Unix
+/// signals, or when it can't be listened for.
+pub struct SignalListener {
+    #[cfg(unix)]
+    signal: Option<Signal>,
+}
+
+impl SignalListener {
+    pub fn hangup() -> Self {
+        #[cfg(unix)]
+        return Self::listen(SignalKind::hangup(), "SIGHUP");
+        #[cfg(not(unix))]
+        Self {}
+    }
+
+    pub fn user_defined1() -> Self {
+        #[cfg(unix)]
+        return Self::listen(SignalKind::user_defined1(), "SIGUSR1");
+        #[cfg(not(unix))]
+        Self {}
+    }
+
+    #[cfg(unix)]
+    fn listen(kind: SignalKind, name: &str) -> Self {
+        let signal = signal(kind)
+            .inspect_err(|e| tracing::error!("failed to listen for {name}: {e}"))
+            .ok();
+        Self { signal }
+    }
+
+    /// Resolves on the next signal.
+    pub async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = &mut self.signal {
+            if signal.recv().await.is_some() {
+                return;
+            }
+        }
+        std::future::pending().await
+    }
+}
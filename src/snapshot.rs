@@ -0,0 +1,433 @@
+// src/snapshot.rs
+//! Comparison of a fuel-core state snapshot with the chain it was taken
+//! from, for teams regenerating snapshots to start new nodes from.
+//!
+//! A snapshot directory holds a `metadata.json` naming its chain config and
+//! its state, as written by `fuel-core snapshot`. Its last block is compared
+//! with the header of the block at its height, its `blocks_root` with the
+//! root of the ids of the blocks up to that height, each recomputed from its
+//! header along the way, and its chain config with the chain id, consensus
+//! parameters and block producer of that block. Coins, contracts and
+//! messages created after the last block are reported too. Only snapshots
+//! encoded as JSON can be read.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use fuel_tx::ConsensusParameters;
+use fuel_types::{Address, Bytes32};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::checks;
+use crate::consensus::{self, PoaAuthority};
+use crate::diff::{self, FieldDifference};
+use crate::error::{Result, ValidationError};
+use crate::prev_root::PrevRoots;
+use crate::query::LightBlock;
+use crate::report::{Check, CheckResult};
+use crate::validator::BlockValidator;
+
+/// Number of blocks fetched at once while deriving the blocks root.
+pub const SNAPSHOT_PAGE_SIZE: u32 = 1000;
+
+const METADATA_FILE: &str = "metadata.json";
+
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    chain_config: PathBuf,
+    table_encoding: TableEncoding,
+}
+
+#[derive(Debug, Deserialize)]
+enum TableEncoding {
+    Json { filepath: PathBuf },
+    Parquet(IgnoredAny),
+}
+
+/// The chain config of a snapshot, as far as the chain can tell.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ChainConfig {
+    #[serde(default)]
+    pub chain_name: String,
+    pub consensus_parameters: ConsensusParameters,
+    pub consensus: ConsensusConfig,
+}
+
+/// Who produces the blocks of the chain, by height.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum ConsensusConfig {
+    PoA {
+        signing_key: Address,
+    },
+    PoAV2 {
+        genesis_signing_key: Address,
+        #[serde(default)]
+        signing_key_overrides: BTreeMap<u32, Address>,
+    },
+}
+
+impl ConsensusConfig {
+    pub fn authority(&self) -> PoaAuthority {
+        match self {
+            ConsensusConfig::PoA { signing_key } => PoaAuthority::new(*signing_key),
+            ConsensusConfig::PoAV2 {
+                genesis_signing_key,
+                signing_key_overrides,
+            } => PoaAuthority {
+                genesis_signing_key: *genesis_signing_key,
+                signing_key_overrides: signing_key_overrides.clone(),
+            },
+        }
+    }
+}
+
+/// The state of a snapshot, without the balances and storage of contracts.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct StateConfig {
+    #[serde(default)]
+    pub coins: Vec<CoinConfig>,
+    #[serde(default)]
+    pub messages: Vec<MessageConfig>,
+    #[serde(default)]
+    pub contracts: Vec<ContractConfig>,
+    pub last_block: Option<LastBlockConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CoinConfig {
+    pub tx_id: Bytes32,
+    pub output_index: u16,
+    pub tx_pointer_block_height: u32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MessageConfig {
+    pub nonce: Bytes32,
+    pub da_height: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ContractConfig {
+    pub contract_id: Bytes32,
+    pub tx_pointer_block_height: u32,
+}
+
+/// The block the snapshot was taken at.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct LastBlockConfig {
+    pub block_height: u32,
+    pub da_block_height: u64,
+    pub consensus_parameters_version: u32,
+    pub state_transition_version: u32,
+    /// Root of the ids of the blocks up to and including the last one, the
+    /// `prev_root` of the next block.
+    pub blocks_root: Bytes32,
+}
+
+/// A fuel-core state snapshot.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub chain_config: ChainConfig,
+    pub state: StateConfig,
+}
+
+impl Snapshot {
+    /// Reads the snapshot in `dir`, from the files its `metadata.json` names.
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let metadata: Metadata = read_json(&dir.join(METADATA_FILE))?;
+        let state = match metadata.table_encoding {
+            TableEncoding::Json { filepath } => read_json(&dir.join(filepath))?,
+            TableEncoding::Parquet(_) => {
+                return Err(ValidationError::UnsupportedSnapshot {
+                    path: dir.to_path_buf(),
+                    reason: "its state is encoded as Parquet, only JSON can be read".to_string(),
+                })
+            }
+        };
+        Ok(Self {
+            chain_config: read_json(&dir.join(metadata.chain_config))?,
+            state,
+        })
+    }
+
+    /// Height the snapshot was taken at, if it has a last block.
+    pub fn height(&self) -> Option<u32> {
+        Some(self.state.last_block.as_ref()?.block_height)
+    }
+
+    /// The fields of the snapshot differing from `chain`, along with the
+    /// state created after its last block.
+    pub fn compare(&self, chain: &ChainState) -> Vec<FieldDifference> {
+        let header = &chain.block.header;
+        let height = header.height.0;
+        let mut differences = vec![];
+        let mut compare = |path: &str, expected: String, actual: Option<String>| {
+            if Some(&expected) != actual.as_ref() {
+                differences.push(FieldDifference {
+                    path: path.to_string(),
+                    expected: Some(expected),
+                    actual,
+                });
+            }
+        };
+
+        let last_block = self.state.last_block.as_ref();
+        compare(
+            "last_block.block_height",
+            height.to_string(),
+            last_block.map(|last| last.block_height.to_string()),
+        );
+        if let Some(last) = last_block {
+            compare(
+                "last_block.da_block_height",
+                header.da_height.0.to_string(),
+                Some(last.da_block_height.to_string()),
+            );
+            compare(
+                "last_block.consensus_parameters_version",
+                header.consensus_parameters_version.0.to_string(),
+                Some(last.consensus_parameters_version.to_string()),
+            );
+            compare(
+                "last_block.state_transition_version",
+                header.state_transition_bytecode_version.0.to_string(),
+                Some(last.state_transition_version.to_string()),
+            );
+            if let Some(blocks_root) = chain.blocks_root {
+                compare(
+                    "last_block.blocks_root",
+                    blocks_root.to_string(),
+                    Some(last.blocks_root.to_string()),
+                );
+            }
+        }
+
+        let params = &self.chain_config.consensus_parameters;
+        compare(
+            "chain_config.consensus_parameters.chain_id",
+            chain.chain_id.to_string(),
+            Some(params.chain_id().to_string()),
+        );
+        if let Some(expected) = &chain.consensus_parameters {
+            diff::diff_values(
+                "chain_config.consensus_parameters",
+                Some(&to_value(expected)),
+                Some(&to_value(params)),
+                &mut differences,
+            );
+        }
+        let authority = self.chain_config.consensus.authority();
+        if let Some(result) = consensus::light_poa_signature(&chain.block, &authority) {
+            if !result.passed {
+                differences.push(FieldDifference {
+                    path: "chain_config.consensus.signing_key".to_string(),
+                    expected: result.actual.or(result.message),
+                    actual: Some(authority.signing_key_at(height).to_string()),
+                });
+            }
+        }
+
+        // State can't have been created by blocks after the last one.
+        let after = |path: String, created_at: u64, last: u64| {
+            (created_at > last).then(|| FieldDifference {
+                path,
+                expected: Some(format!("at most {last}")),
+                actual: Some(created_at.to_string()),
+            })
+        };
+        for (index, coin) in self.state.coins.iter().enumerate() {
+            differences.extend(after(
+                format!("coins[{index}].tx_pointer_block_height"),
+                coin.tx_pointer_block_height.into(),
+                height.into(),
+            ));
+        }
+        for (index, contract) in self.state.contracts.iter().enumerate() {
+            differences.extend(after(
+                format!("contracts[{index}].tx_pointer_block_height"),
+                contract.tx_pointer_block_height.into(),
+                height.into(),
+            ));
+        }
+        for (index, message) in self.state.messages.iter().enumerate() {
+            differences.extend(after(
+                format!("messages[{index}].da_height"),
+                message.da_height,
+                header.da_height.0,
+            ));
+        }
+        differences
+    }
+}
+
+/// What the chain says the last block of a snapshot at its height is.
+#[derive(Clone, Debug)]
+pub struct ChainState {
+    /// The block at the height of the snapshot.
+    pub block: LightBlock,
+    /// Root of the ids of the blocks up to and including `block`, `None`
+    /// when some of them weren't served.
+    pub blocks_root: Option<Bytes32>,
+    pub chain_id: fuel_types::ChainId,
+    /// The consensus parameters of the version `block` was produced with,
+    /// if the source of the validator serves them.
+    pub consensus_parameters: Option<ConsensusParameters>,
+}
+
+/// A check of the block id chain failed while deriving the blocks root, or
+/// blocks of the chain weren't served.
+#[derive(Clone, Debug, Serialize)]
+pub struct ChainMismatch {
+    pub height: u32,
+    #[serde(flatten)]
+    pub result: CheckResult,
+}
+
+/// Comparison of a snapshot with the chain up to its height.
+#[derive(Clone, Debug, Serialize)]
+pub struct SnapshotComparison {
+    pub height: u32,
+    pub blocks: u32,
+    /// Blocks whose id or `prev_root` don't match their header and the
+    /// previous block ids, which the blocks root is derived from.
+    pub chain: Vec<ChainMismatch>,
+    /// Fields of the snapshot differing from the chain, the chain's values
+    /// being the expected ones.
+    pub differences: Vec<FieldDifference>,
+}
+
+impl SnapshotComparison {
+    pub fn is_consistent(&self) -> bool {
+        self.chain.is_empty() && self.differences.is_empty()
+    }
+}
+
+/// Derives what the snapshot taken at `height` has to hold from the blocks
+/// of `validator` up to it, and compares the snapshot with it.
+pub async fn check_snapshot(
+    validator: &BlockValidator,
+    snapshot: &Snapshot,
+    height: u32,
+) -> Result<SnapshotComparison> {
+    let (chain, mismatches, blocks) = chain_state(validator, height).await?;
+    Ok(SnapshotComparison {
+        height,
+        blocks,
+        chain: mismatches,
+        differences: snapshot.compare(&chain),
+    })
+}
+
+/// The chain state at `height`, with the failed checks of the block id
+/// chain and the number of blocks it was derived from.
+async fn chain_state(
+    validator: &BlockValidator,
+    height: u32,
+) -> Result<(ChainState, Vec<ChainMismatch>, u32)> {
+    let mut roots = PrevRoots::new();
+    let mut mismatches = vec![];
+    let mut last = None;
+    let mut blocks = 0;
+    // Whether a block below the current one wasn't served.
+    let mut gap = false;
+    let mut from: u32 = 0;
+    loop {
+        let to = from.saturating_add(SNAPSHOT_PAGE_SIZE - 1).min(height);
+        let page = validator.source().fetch_light_range(from, to).await?;
+        for block in page {
+            let block_height = block.height();
+            let expected = roots.next_height().unwrap_or(0);
+            if block_height < expected {
+                mismatches.push(ChainMismatch {
+                    height: block_height,
+                    result: CheckResult::error(
+                        Check::PrevRoot,
+                        format!("block #{block_height} was served again after #{expected}"),
+                    ),
+                });
+                continue;
+            }
+            if block_height > expected && !gap {
+                // The ids above the gap can't be appended to the tree, so
+                // neither the blocks root nor the `prev_root`s above it can be
+                // derived any more.
+                let missing = if block_height - expected == 1 {
+                    format!("block #{expected} isn't served")
+                } else {
+                    format!("blocks #{expected}..=#{} aren't served", block_height - 1)
+                };
+                mismatches.push(ChainMismatch {
+                    height: expected,
+                    result: CheckResult::error(
+                        Check::PrevRoot,
+                        format!("{missing}, the blocks root can't be derived"),
+                    ),
+                });
+                gap = true;
+            }
+            let prev_root = checks::light_prev_root(&block, roots.expected_prev_root(block_height))
+                .filter(|_| !gap);
+            let results = checks::light_block_id(&block).into_iter().chain(prev_root);
+            mismatches.extend(results.filter(|result| !result.passed).map(|result| {
+                ChainMismatch {
+                    height: block_height,
+                    result,
+                }
+            }));
+            if !gap {
+                roots.push(block_height, &block.id.clone().into())?;
+            }
+            blocks += 1;
+            last = Some(block);
+        }
+        tracing::debug!(to, blocks, "derived the blocks root");
+        if to >= height {
+            break;
+        }
+        from = to + 1;
+    }
+
+    let block = last
+        .filter(|block| block.height() == height)
+        .ok_or(ValidationError::BlockNotFound { height })?;
+    let chain_id = match validator.chain_id() {
+        Some(chain_id) => chain_id,
+        None => validator.fetch_chain_id().await?,
+    };
+    let version = block.header.consensus_parameters_version.0;
+    let pinned = validator
+        .consensus_parameters()
+        .and_then(|params| params.get(version).cloned());
+    let consensus_parameters = match (pinned, validator.node()) {
+        (Some(params), _) => Some(params),
+        (None, Some(node)) => Some(node.fetch_consensus_parameters(version).await?),
+        (None, None) => None,
+    };
+    let chain = ChainState {
+        block,
+        blocks_root: (!gap).then(|| roots.root()),
+        chain_id,
+        consensus_parameters,
+    };
+    Ok((chain, mismatches, blocks))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let json = fs::read(path).map_err(|source| ValidationError::ReadSnapshot {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_json::from_slice(&json).map_err(|source| ValidationError::ParseSnapshot {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn to_value(params: &ConsensusParameters) -> Value {
+    serde_json::to_value(params).unwrap_or(Value::Null)
+}
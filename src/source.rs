@@ -0,0 +1,839 @@
+// src/source.rs
+//! Where blocks are read from: a node's GraphQL API, block dumps on disk, or
+//! blocks held in memory.
+
+use std::{collections::BTreeMap, fmt::Debug, io, path::Path, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use cynic::{http::CynicReqwestError, GraphQlResponse, Operation, QueryBuilder};
+use fuel_core_client::client::{
+    from_strings_errors_to_std_error,
+    pagination::{PageDirection, PaginatedResult, PaginationRequest},
+    schema::{
+        chain,
+        relayed_tx::{
+            RelayedTransactionStatus, RelayedTransactionStatusArgs, RelayedTransactionStatusQuery,
+        },
+        tx::{TransactionStatus, TxIdArgs},
+        upgrades::{ConsensusParametersByVersionArgs, ConsensusParametersByVersionQuery},
+        ConversionError, U32,
+    },
+    FuelClient,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::{Bytes32, ChainId};
+use reqwest::{StatusCode, Url};
+use tokio_util::sync::CancellationToken;
+
+use crate::cache::BlockCache;
+use crate::cassette::{Cassette, Interaction};
+use crate::dump;
+use crate::error::{Result, ValidationError};
+use crate::metrics::Metrics;
+use crate::query::{
+    BlockByIdVariables, BlockHeaderByIdQuery, BlockHeaderQuery, BlockIdAndHeight, BlockIdsQuery,
+    BlockQuery, BlockQueryVariables, BlockWithoutStatusesQuery, ChainIdQuery,
+    ConsensusParametersQuery, FullBlock, FullBlocksQuery, Header, LatestBlockHeightQuery,
+    LightBlock, LightBlocksQuery, TransactionStatusQuery,
+};
+use crate::rate_limit::{self, Limiter, RateLimit};
+use crate::receipt_fallback;
+use crate::retry::RetryPolicy;
+use crate::subscription::{self, NewBlocks};
+
+/// A source of full blocks to validate.
+#[async_trait]
+pub trait BlockSource: Debug + Send + Sync {
+    /// The block at `height`, failing with [`ValidationError::BlockNotFound`]
+    /// if the source doesn't have it.
+    async fn fetch_block(&self, height: u32) -> Result<FullBlock>;
+
+    /// The blocks of `from..=to` the source has, in height order.
+    async fn fetch_range(&self, from: u32, to: u32) -> Result<Vec<FullBlock>>;
+
+    /// Height of the latest block of the source.
+    async fn latest_height(&self) -> Result<u32>;
+
+    /// The header of the block at `height`.
+    async fn fetch_header(&self, height: u32) -> Result<Header> {
+        Ok(self.fetch_block(height).await?.header)
+    }
+
+    /// The blocks of `from..=to` the source has without their transactions,
+    /// in height order. Sources serving full blocks anyway strip them.
+    async fn fetch_light_range(&self, from: u32, to: u32) -> Result<Vec<LightBlock>> {
+        Ok(self
+            .fetch_range(from, to)
+            .await?
+            .iter()
+            .map(LightBlock::from)
+            .collect())
+    }
+
+    /// Notifications of the blocks added to the source from now on, `None`
+    /// if it can't push them and has to be polled.
+    async fn subscribe_new_blocks(&self) -> Result<Option<NewBlocks>> {
+        Ok(None)
+    }
+}
+
+/// Blocks queried from a node's GraphQL API. Transient query failures are
+/// retried according to its [`RetryPolicy`], and blocks already fetched are
+/// read from its [`BlockCache`], if any. Queries in flight are abandoned
+/// once its [`CancellationToken`] is cancelled.
+#[derive(Clone, Debug)]
+pub struct GraphqlSource {
+    client: FuelClient,
+    /// The GraphQL endpoint, which subscriptions and the cache entries of
+    /// the node are derived from.
+    endpoint: Option<Arc<Url>>,
+    retry: RetryPolicy,
+    /// How long every attempt of a query waits for its response.
+    timeout: Option<Duration>,
+    cancel: CancellationToken,
+    /// Paces the queries, shared by the clones of the source.
+    limiter: Arc<Limiter>,
+    /// Sends the queries when the endpoint is known, to see the
+    /// `Retry-After` of rate limited responses.
+    http: reqwest::Client,
+    /// Where the responses are recorded, or replayed from.
+    cassette: Option<Arc<Cassette>>,
+    metrics: Option<Arc<Metrics>>,
+    cache: Option<Arc<BlockCache>>,
+    /// Whether receipts missing from blocks are fetched per transaction.
+    receipt_fallback: bool,
+}
+
+impl GraphqlSource {
+    pub fn new(url: impl AsRef<str>) -> Result<Self> {
+        let client =
+            FuelClient::new(url.as_ref()).map_err(|e| ValidationError::InvalidEndpoint {
+                url: url.as_ref().to_string(),
+                reason: e.to_string(),
+            })?;
+        // Parsed the way the client does, which doesn't expose its URL.
+        let url = url.as_ref();
+        let endpoint = if url.starts_with("http") {
+            Url::parse(url)
+        } else {
+            Url::parse(&format!("http://{url}"))
+        };
+        Ok(Self {
+            endpoint: endpoint.ok().map(Arc::new),
+            ..Self::from_client(client)
+        })
+    }
+
+    /// A source querying `client`. New blocks aren't subscribed to or
+    /// cached, as the endpoint of the client isn't known.
+    pub fn from_client(client: FuelClient) -> Self {
+        Self {
+            client,
+            endpoint: None,
+            retry: RetryPolicy::default(),
+            timeout: None,
+            cancel: CancellationToken::new(),
+            limiter: Arc::default(),
+            http: reqwest::Client::new(),
+            cassette: None,
+            metrics: None,
+            cache: None,
+            receipt_fallback: true,
+        }
+    }
+
+    pub fn client(&self) -> &FuelClient {
+        &self.client
+    }
+
+    /// Sets how failed queries are retried.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// Fails every attempt of a query that got no response within
+    /// `timeout` with a timeout error, which is retried like the others.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Fails the queries with [`ValidationError::Cancelled`] once `cancel` is
+    /// cancelled, including the ones waiting for a response or a retry.
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Paces the queries to the node by `limit`.
+    pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.limiter = Arc::new(Limiter::new(limit));
+        self
+    }
+
+    pub fn rate_limit(&self) -> &RateLimit {
+        self.limiter.limit()
+    }
+
+    /// Records the responses of the node in `cassette`, or answers the
+    /// queries from it if it is being replayed.
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Sets the metrics failed queries are accounted in.
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Sets the cache fetched blocks and block ids are kept in.
+    pub fn with_cache(mut self, cache: Arc<BlockCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Whether a block whose query fails is queried again without the
+    /// statuses of its transactions, and statuses served without their
+    /// receipts are fetched on their own, see [`receipt_fallback`]. On by
+    /// default.
+    ///
+    /// [`receipt_fallback`]: crate::receipt_fallback
+    pub fn with_receipt_fallback(mut self, enabled: bool) -> Self {
+        self.receipt_fallback = enabled;
+        self
+    }
+
+    pub fn receipt_fallback(&self) -> bool {
+        self.receipt_fallback
+    }
+
+    /// Fetches the statuses of the transactions of `block` lacking their
+    /// receipts, returning how many were filled in. Transactions whose
+    /// status can't be fetched are left as they are, for the checks to
+    /// report.
+    async fn fill_in_receipts(&self, block: &mut FullBlock) -> Result<usize> {
+        let missing = receipt_fallback::missing_receipts(block);
+        if missing.is_empty() {
+            return Ok(0);
+        }
+        let height = block.height();
+        let mut filled = 0;
+        for index in &missing {
+            let tx = &mut block.transactions[*index];
+            match self.fetch_transaction_status(tx.id.clone().into()).await {
+                Ok(Some(status)) => filled += receipt_fallback::fill_in(tx, status) as usize,
+                Ok(None) => {}
+                Err(ValidationError::Cancelled) => return Err(ValidationError::Cancelled),
+                Err(e) => tracing::warn!(
+                    height,
+                    tx_id = %tx.id,
+                    error = %e,
+                    "the status of the transaction couldn't be fetched"
+                ),
+            }
+        }
+        tracing::info!(
+            height,
+            missing = missing.len(),
+            filled,
+            "fetched the receipts missing from the block per transaction"
+        );
+        Ok(filled)
+    }
+
+    /// The cache of the node, with the endpoint its entries are keyed by.
+    fn cache(&self) -> Option<(&BlockCache, &str)> {
+        Some((self.cache.as_deref()?, self.endpoint.as_deref()?.as_str()))
+    }
+
+    /// Sends a query built by `build`, retrying transient failures, until
+    /// the source is cancelled.
+    async fn query<ResponseData, Vars, F>(
+        &self,
+        what: &'static str,
+        build: F,
+    ) -> Result<ResponseData>
+    where
+        F: Fn() -> Operation<ResponseData, Vars>,
+        Vars: serde::Serialize,
+        ResponseData: serde::de::DeserializeOwned + 'static,
+    {
+        let attempts = self.retry.run(what, || async {
+            let _permit = self.limiter.acquire().await;
+            let response = async {
+                match &self.endpoint {
+                    Some(endpoint) => self.send(endpoint, build()).await,
+                    None => self.client.query(build()).await,
+                }
+            };
+            match self.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, response)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!("no response within {timeout:?}"),
+                        ))
+                    }),
+                None => response.await,
+            }
+        });
+        let result = tokio::select! {
+            result = attempts => result,
+            _ = self.cancel.cancelled() => return Err(ValidationError::Cancelled),
+        };
+        result.map_err(|(source, attempts)| {
+            if let Some(metrics) = &self.metrics {
+                metrics.query_failed(what);
+            }
+            ValidationError::Query {
+                query: what,
+                attempts,
+                source,
+            }
+        })
+    }
+
+    /// Sends `operation` to the GraphQL endpoint of the node like the client
+    /// does, pausing the queries to the node for as long as it asks to when
+    /// it rate limits them. With a cassette, the response is recorded, or
+    /// replayed instead of querying the node.
+    async fn send<ResponseData, Vars>(
+        &self,
+        endpoint: &Url,
+        operation: Operation<ResponseData, Vars>,
+    ) -> io::Result<ResponseData>
+    where
+        Vars: serde::Serialize,
+        ResponseData: serde::de::DeserializeOwned + 'static,
+    {
+        let error = |e: CynicReqwestError| io::Error::other(e);
+        let request = serde_json::to_value(&operation)?;
+        let (status, body) = match self.cassette.as_deref().filter(|c| c.is_replaying()) {
+            Some(cassette) => {
+                let (status, body) = cassette
+                    .replay(endpoint.as_str(), &request)
+                    .ok_or_else(|| io::Error::other("no response to the query in the cassette"))?;
+                (
+                    StatusCode::from_u16(status).map_err(io::Error::other)?,
+                    body,
+                )
+            }
+            None => {
+                let mut url = endpoint.clone();
+                url.set_path("/v1/graphql");
+                let response = self
+                    .http
+                    .post(url)
+                    .json(&request)
+                    .send()
+                    .await
+                    .map_err(|e| error(e.into()))?;
+
+                let status = response.status();
+                if matches!(status.as_u16(), 429 | 503) {
+                    if let Some(delay) = rate_limit::retry_after(response.headers()) {
+                        tracing::warn!(
+                            status = status.as_u16(),
+                            "node asked to back off for {delay:?}"
+                        );
+                        self.limiter.pause(delay);
+                    }
+                }
+                let body = response.text().await.map_err(|e| error(e.into()))?;
+                if let Some(cassette) = &self.cassette {
+                    cassette.record(Interaction {
+                        endpoint: endpoint.to_string(),
+                        request,
+                        status: status.as_u16(),
+                        body: body.clone(),
+                    });
+                }
+                (status, body)
+            }
+        };
+
+        let response: GraphQlResponse<ResponseData> = match serde_json::from_str(&body) {
+            Ok(response) => response,
+            Err(e) if status.is_success() => return Err(e.into()),
+            Err(_) => return Err(error(CynicReqwestError::ErrorResponse(status, body))),
+        };
+        match (response.data, response.errors) {
+            (Some(data), _) => Ok(data),
+            (_, Some(errors)) => Err(from_strings_errors_to_std_error(
+                errors.into_iter().map(|e| e.message).collect(),
+            )),
+            _ => Err(io::Error::other("Invalid response")),
+        }
+    }
+
+    /// Queries the chain id from the node's current consensus parameters.
+    pub async fn fetch_chain_id(&self) -> Result<ChainId> {
+        let response = self.query("chain id", || ChainIdQuery::build(())).await?;
+
+        Ok(ChainId::new(response.chain.consensus_parameters.chain_id.0))
+    }
+
+    /// Queries the node's current consensus parameters, with their version.
+    pub async fn fetch_latest_consensus_parameters(&self) -> Result<(u32, ConsensusParameters)> {
+        let response = self
+            .query("consensus parameters", || {
+                ConsensusParametersQuery::build(())
+            })
+            .await?;
+
+        let chain = response.chain;
+        let version = chain.latest_block.header.consensus_parameters_version.0;
+        Ok((
+            version,
+            convert_consensus_parameters(version, chain.consensus_parameters)?,
+        ))
+    }
+
+    /// Queries the consensus parameters of `version`.
+    pub async fn fetch_consensus_parameters(&self, version: u32) -> Result<ConsensusParameters> {
+        let response = self
+            .query("consensus parameters", || {
+                ConsensusParametersByVersionQuery::build(ConsensusParametersByVersionArgs {
+                    version: version as i32,
+                })
+            })
+            .await?;
+
+        let params = response
+            .consensus_parameters
+            .ok_or(ValidationError::ConsensusParametersNotFound { version })?;
+        convert_consensus_parameters(version, params)
+    }
+
+    /// Queries one page of full blocks. The cursor is the height of the last
+    /// block of the previous page.
+    pub async fn fetch_blocks(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> Result<PaginatedResult<FullBlock, String>> {
+        let response = self
+            .query("blocks", || FullBlocksQuery::build(request.clone().into()))
+            .await?;
+
+        Ok(response.blocks.into())
+    }
+
+    /// Queries one page of blocks without their transactions. The cursor is
+    /// the height of the last block of the previous page.
+    pub async fn fetch_light_blocks(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> Result<PaginatedResult<LightBlock, String>> {
+        let response = self
+            .query("light blocks", || {
+                LightBlocksQuery::build(request.clone().into())
+            })
+            .await?;
+
+        Ok(response.blocks.into())
+    }
+
+    /// Queries one page of block ids. Forward pages start with the ids
+    /// already cached, and are only queried from the first one that isn't.
+    pub async fn fetch_block_ids(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> Result<PaginatedResult<BlockIdAndHeight, String>> {
+        let start = match &request.cursor {
+            Some(cursor) => cursor.parse::<u32>().ok().and_then(|c| c.checked_add(1)),
+            None => Some(0),
+        };
+        if let (Some((cache, endpoint)), Some(start), PageDirection::Forward) =
+            (self.cache(), start, &request.direction)
+        {
+            let cached: Vec<_> = (start..)
+                .take(request.results.max(0) as usize)
+                .map_while(|height| {
+                    let id = cache.block_id(endpoint, height)?;
+                    Some(BlockIdAndHeight {
+                        id: id.into(),
+                        height: U32(height),
+                    })
+                })
+                .collect();
+            if let Some(last) = cached.last() {
+                return Ok(PaginatedResult {
+                    cursor: Some(last.height.0.to_string()),
+                    results: cached,
+                    has_next_page: true,
+                    has_previous_page: start > 0,
+                });
+            }
+        }
+
+        let response = self
+            .query("block ids", || BlockIdsQuery::build(request.clone().into()))
+            .await?;
+
+        let page: PaginatedResult<BlockIdAndHeight, String> = response.blocks.into();
+        if let Some((cache, endpoint)) = self.cache() {
+            for block in &page.results {
+                cache.insert_block_id(endpoint, block.height.0, block.id.clone().into());
+            }
+        }
+        Ok(page)
+    }
+
+    /// Queries the header of the block with id `id`, if the node has it.
+    pub async fn fetch_header_by_id(&self, id: Bytes32) -> Result<Option<Header>> {
+        let response = self
+            .query("block header by id", || {
+                BlockHeaderByIdQuery::build(BlockByIdVariables {
+                    id: Some(id.into()),
+                })
+            })
+            .await?;
+
+        Ok(response.block.map(|block| block.header))
+    }
+
+    /// Queries the height of the block the transaction `id` is part of, if
+    /// it is part of one. Transactions the node doesn't know, or didn't
+    /// include yet, have none.
+    pub async fn fetch_transaction_height(&self, id: Bytes32) -> Result<Option<u32>> {
+        Ok(match self.fetch_transaction_status(id).await? {
+            Some(TransactionStatus::SuccessStatus(status)) => Some(status.block_height.0),
+            Some(TransactionStatus::FailureStatus(status)) => Some(status.block_height.0),
+            _ => None,
+        })
+    }
+
+    /// Queries the status of the transaction `id`, with its receipts, if the
+    /// node knows about it.
+    pub async fn fetch_transaction_status(&self, id: Bytes32) -> Result<Option<TransactionStatus>> {
+        let response = self
+            .query("transaction status", || {
+                TransactionStatusQuery::build(TxIdArgs { id: id.into() })
+            })
+            .await?;
+
+        Ok(response.transaction.and_then(|tx| tx.status))
+    }
+
+    /// Queries the status of the relayed transaction `id`, if the node knows
+    /// about it.
+    pub async fn fetch_relayed_transaction_status(
+        &self,
+        id: Bytes32,
+    ) -> Result<Option<RelayedTransactionStatus>> {
+        let response = self
+            .query("relayed transaction status", || {
+                RelayedTransactionStatusQuery::build(RelayedTransactionStatusArgs { id: id.into() })
+            })
+            .await?;
+
+        Ok(response.relayed_transaction_status)
+    }
+}
+
+/// Decodes the consensus parameters of `version` served by a node.
+fn convert_consensus_parameters(
+    version: u32,
+    params: chain::ConsensusParameters,
+) -> Result<ConsensusParameters> {
+    params.try_into().map_err(
+        |e: ConversionError| ValidationError::InvalidConsensusParameters {
+            version,
+            reason: e.to_string(),
+        },
+    )
+}
+
+#[async_trait]
+impl BlockSource for GraphqlSource {
+    /// Queries the block at `height` together with its transactions and
+    /// statuses, unless it is cached. With the receipt fallback, a block
+    /// whose query fails is queried without the statuses, and the receipts
+    /// it lacks are fetched per transaction.
+    async fn fetch_block(&self, height: u32) -> Result<FullBlock> {
+        if let Some(block) = self
+            .cache()
+            .and_then(|(cache, endpoint)| cache.block(endpoint, height))
+        {
+            return Ok(block);
+        }
+
+        let variables = || BlockQueryVariables {
+            height: Some(U32(height)),
+        };
+        let block = match self.query("block", || BlockQuery::build(variables())).await {
+            Ok(response) => response.block,
+            Err(e @ ValidationError::Query { .. }) if self.receipt_fallback => {
+                tracing::warn!(
+                    height,
+                    error = %e,
+                    "querying the block without the statuses of its transactions"
+                );
+                self.query("block without statuses", || {
+                    BlockWithoutStatusesQuery::build(variables())
+                })
+                .await?
+                .block
+                .map(FullBlock::from)
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut block = block.ok_or(ValidationError::BlockNotFound { height })?;
+        if self.receipt_fallback {
+            self.fill_in_receipts(&mut block).await?;
+        }
+        if let Some((cache, endpoint)) = self.cache() {
+            cache.insert_block(endpoint, &block);
+        }
+        Ok(block)
+    }
+
+    /// Queries the range as a single page of the blocks connection, from the
+    /// first block that isn't cached. Nodes serving fewer blocks per page are
+    /// paged through. With the receipt fallback, the rest of a range whose
+    /// page fails to be queried is fetched block by block.
+    async fn fetch_range(&self, from: u32, to: u32) -> Result<Vec<FullBlock>> {
+        let mut blocks = vec![];
+        if let Some((cache, endpoint)) = self.cache() {
+            blocks.extend((from..=to).map_while(|height| cache.block(endpoint, height)));
+        }
+
+        // The cached blocks are the first heights of the range.
+        let fetched_from = blocks.len();
+        let Some(mut next) = from.checked_add(fetched_from as u32) else {
+            return Ok(blocks);
+        };
+        // Blocks fetched one by one are filled in and cached as they are.
+        let mut paged_to = fetched_from;
+        while next <= to {
+            let page = match self
+                .fetch_blocks(PaginationRequest {
+                    cursor: next.checked_sub(1).map(|height| height.to_string()),
+                    results: (to - next).saturating_add(1).min(i32::MAX as u32) as i32,
+                    direction: PageDirection::Forward,
+                })
+                .await
+            {
+                Ok(page) => page,
+                Err(e @ ValidationError::Query { .. }) if self.receipt_fallback => {
+                    tracing::warn!(
+                        from = next,
+                        to,
+                        error = %e,
+                        "fetching the blocks one by one"
+                    );
+                    for height in next..=to {
+                        match self.fetch_block(height).await {
+                            Ok(block) => blocks.push(block),
+                            Err(ValidationError::BlockNotFound { .. }) => break,
+                            Err(e) => return Err(e),
+                        }
+                    }
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let has_next_page = page.has_next_page;
+            blocks.extend(page.results.into_iter().take_while(|b| b.height() <= to));
+            paged_to = blocks.len();
+            match blocks.last() {
+                Some(last) if has_next_page && last.height() >= next => {
+                    next = last.height().saturating_add(1);
+                }
+                _ => break,
+            }
+        }
+        if self.receipt_fallback {
+            for block in &mut blocks[fetched_from..paged_to] {
+                self.fill_in_receipts(block).await?;
+            }
+        }
+        if let Some((cache, endpoint)) = self.cache() {
+            for block in &blocks[fetched_from..paged_to] {
+                cache.insert_block(endpoint, block);
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Queries the range as a single page of the blocks connection, without
+    /// the transactions. Nodes serving fewer blocks per page are paged
+    /// through. Light blocks aren't cached.
+    async fn fetch_light_range(&self, from: u32, to: u32) -> Result<Vec<LightBlock>> {
+        let mut blocks: Vec<LightBlock> = vec![];
+        let mut next = from;
+        while next <= to {
+            let page = self
+                .fetch_light_blocks(PaginationRequest {
+                    cursor: next.checked_sub(1).map(|height| height.to_string()),
+                    results: (to - next).saturating_add(1).min(i32::MAX as u32) as i32,
+                    direction: PageDirection::Forward,
+                })
+                .await?;
+
+            let has_next_page = page.has_next_page;
+            blocks.extend(page.results.into_iter().take_while(|b| b.height() <= to));
+            match blocks.last() {
+                Some(last) if has_next_page && last.height() >= next => {
+                    next = last.height().saturating_add(1);
+                }
+                _ => break,
+            }
+        }
+        Ok(blocks)
+    }
+
+    async fn latest_height(&self) -> Result<u32> {
+        let response = self
+            .query("latest block height", || LatestBlockHeightQuery::build(()))
+            .await?;
+
+        Ok(response.chain.latest_block.height.0)
+    }
+
+    /// Queries only the header of the block at `height`, unless the block
+    /// is cached.
+    async fn fetch_header(&self, height: u32) -> Result<Header> {
+        if let Some(block) = self
+            .cache()
+            .and_then(|(cache, endpoint)| cache.block(endpoint, height))
+        {
+            return Ok(block.header);
+        }
+
+        let response = self
+            .query("block header", || {
+                BlockHeaderQuery::build(BlockQueryVariables {
+                    height: Some(U32(height)),
+                })
+            })
+            .await?;
+
+        response
+            .block
+            .map(|block| block.header)
+            .ok_or(ValidationError::BlockNotFound { height })
+    }
+
+    /// Subscribes to the blocks the node imports. Nodes that don't push new
+    /// blocks fail the subscription, or end it with an error. The request
+    /// opening the stream is paced like the queries, but the stream doesn't
+    /// count as in flight once open.
+    async fn subscribe_new_blocks(&self) -> Result<Option<NewBlocks>> {
+        let Some(endpoint) = &self.endpoint else {
+            return Ok(None);
+        };
+        let _permit = self.limiter.acquire().await;
+        Ok(Some(subscription::subscribe(&self.http, endpoint).await?))
+    }
+}
+
+/// Blocks held in memory, e.g. to validate hand-crafted blocks in tests.
+#[derive(Clone, Debug, Default)]
+pub struct MemorySource {
+    blocks: BTreeMap<u32, FullBlock>,
+}
+
+impl MemorySource {
+    pub fn new(blocks: impl IntoIterator<Item = FullBlock>) -> Self {
+        Self {
+            blocks: blocks
+                .into_iter()
+                .map(|block| (block.height(), block))
+                .collect(),
+        }
+    }
+
+    /// Adds `block`, replacing the block of the same height if any.
+    pub fn insert(&mut self, block: FullBlock) {
+        self.blocks.insert(block.height(), block);
+    }
+
+    /// Heights of the first and last block, if there are any.
+    pub fn bounds(&self) -> Option<(u32, u32)> {
+        let first = *self.blocks.keys().next()?;
+        let last = *self.blocks.keys().next_back()?;
+        Some((first, last))
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+}
+
+#[async_trait]
+impl BlockSource for MemorySource {
+    async fn fetch_block(&self, height: u32) -> Result<FullBlock> {
+        self.blocks
+            .get(&height)
+            .cloned()
+            .ok_or(ValidationError::BlockNotFound { height })
+    }
+
+    async fn fetch_range(&self, from: u32, to: u32) -> Result<Vec<FullBlock>> {
+        Ok(self
+            .blocks
+            .range(from..=to)
+            .map(|(_, b)| b.clone())
+            .collect())
+    }
+
+    async fn latest_height(&self) -> Result<u32> {
+        self.bounds()
+            .map(|(_, last)| last)
+            .ok_or(ValidationError::BlockNotFound { height: 0 })
+    }
+}
+
+/// Blocks read from dumps written by `export`, see [`dump::load`]. Every
+/// block is loaded when the source is opened.
+#[derive(Clone, Debug)]
+pub struct FileSource {
+    blocks: MemorySource,
+}
+
+impl FileSource {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            blocks: MemorySource::new(dump::load(path)?),
+        })
+    }
+
+    /// Heights of the first and last block, if there are any.
+    pub fn bounds(&self) -> Option<(u32, u32)> {
+        self.blocks.bounds()
+    }
+}
+
+#[async_trait]
+impl BlockSource for FileSource {
+    async fn fetch_block(&self, height: u32) -> Result<FullBlock> {
+        self.blocks.fetch_block(height).await
+    }
+
+    async fn fetch_range(&self, from: u32, to: u32) -> Result<Vec<FullBlock>> {
+        self.blocks.fetch_range(from, to).await
+    }
+
+    async fn latest_height(&self) -> Result<u32> {
+        self.blocks.latest_height().await
+    }
+}
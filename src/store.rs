@@ -0,0 +1,430 @@
+// src/store.rs
+#[cfg(feature = "postgres")]
+use std::sync::Mutex;
+use std::{
+    collections::BTreeSet,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rusqlite::{params, Connection};
+
+use crate::error::Result;
+#[cfg(not(feature = "postgres"))]
+use crate::error::ValidationError;
+use crate::report::CheckResult;
+use crate::validator::BlockOutcome;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    started_at INTEGER NOT NULL,
+    endpoint TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS blocks (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    height INTEGER NOT NULL,
+    block_id TEXT,
+    validated_at INTEGER NOT NULL,
+    duration_ms REAL NOT NULL,
+    passed INTEGER NOT NULL,
+    error TEXT
+);
+CREATE INDEX IF NOT EXISTS blocks_height ON blocks(height);
+
+CREATE TABLE IF NOT EXISTS checks (
+    block INTEGER NOT NULL REFERENCES blocks(id),
+    name TEXT NOT NULL,
+    tx_id TEXT,
+    expected TEXT,
+    actual TEXT,
+    passed INTEGER NOT NULL,
+    message TEXT
+);
+CREATE INDEX IF NOT EXISTS checks_block ON checks(block);
+";
+
+/// Migrations of the PostgreSQL schema, applied in order. The version of a
+/// migration is its position in the list, starting at 1.
+///
+/// Unlike SQLite databases, which keep the blocks of every run, a block has
+/// a single row per network, replaced whenever it is validated again.
+#[cfg(feature = "postgres")]
+const POSTGRES_MIGRATIONS: &[&str] = &["
+CREATE TABLE runs (
+    id BIGSERIAL PRIMARY KEY,
+    started_at BIGINT NOT NULL,
+    network TEXT NOT NULL,
+    endpoint TEXT NOT NULL
+);
+
+CREATE TABLE blocks (
+    id BIGSERIAL PRIMARY KEY,
+    run_id BIGINT NOT NULL REFERENCES runs(id),
+    network TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    block_id TEXT,
+    validated_at BIGINT NOT NULL,
+    duration_ms DOUBLE PRECISION NOT NULL,
+    passed BOOLEAN NOT NULL,
+    error TEXT,
+    UNIQUE (network, height)
+);
+
+CREATE TABLE checks (
+    block BIGINT NOT NULL REFERENCES blocks(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    tx_id TEXT,
+    expected TEXT,
+    actual TEXT,
+    passed BOOLEAN NOT NULL,
+    message TEXT
+);
+CREATE INDEX checks_block ON checks(block);
+"];
+
+/// Key of the advisory lock serializing the migrations of validators
+/// starting at the same time.
+#[cfg(feature = "postgres")]
+const MIGRATION_LOCK: i64 = 0x6675_656c;
+
+/// Whether `db` names a PostgreSQL database rather than a SQLite file.
+pub fn is_postgres_url(db: &str) -> bool {
+    db.starts_with("postgres://") || db.starts_with("postgresql://")
+}
+
+/// Database recording every validated block and the result of each of its
+/// checks, for audits after the fact: a SQLite file, or a PostgreSQL database
+/// shared by several validators with the `postgres` feature.
+///
+/// Every [`ResultStore::open`] starts a new run, identified by the time it
+/// started and the endpoint blocks were fetched from.
+pub struct ResultStore {
+    backend: Backend,
+    run_id: i64,
+}
+
+enum Backend {
+    Sqlite(Connection),
+    #[cfg(feature = "postgres")]
+    Postgres {
+        client: Box<Mutex<PgClient>>,
+        network: String,
+    },
+}
+
+impl ResultStore {
+    /// Opens the SQLite database at `path`.
+    pub fn open(path: impl AsRef<Path>, endpoint: &str) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        conn.execute(
+            "INSERT INTO runs (started_at, endpoint) VALUES (?1, ?2)",
+            params![unix_time(), endpoint],
+        )?;
+        let run_id = conn.last_insert_rowid();
+        Ok(Self {
+            backend: Backend::Sqlite(conn),
+            run_id,
+        })
+    }
+
+    /// Connects to the PostgreSQL database at `url`, migrating its schema,
+    /// to record the blocks of `network`. Validators of the same network
+    /// writing to the database at the same time keep the last result of
+    /// each block, and don't replace a full validation with a failed one.
+    #[cfg(feature = "postgres")]
+    pub fn connect(url: &str, network: &str, endpoint: &str) -> Result<Self> {
+        let (client, run_id) = blocking(|| {
+            let mut client = postgres::Client::connect(url, postgres::NoTls)?;
+            migrate(&mut client)?;
+            let run_id: i64 = client
+                .query_one(
+                    "INSERT INTO runs (started_at, network, endpoint) VALUES ($1, $2, $3)
+                     RETURNING id",
+                    &[&unix_time(), &network, &endpoint],
+                )?
+                .get(0);
+            Ok::<_, postgres::Error>((client, run_id))
+        })?;
+        Ok(Self {
+            backend: Backend::Postgres {
+                client: Box::new(Mutex::new(PgClient(Some(client)))),
+                network: network.to_string(),
+            },
+            run_id,
+        })
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn connect(url: &str, _network: &str, _endpoint: &str) -> Result<Self> {
+        Err(ValidationError::InvalidEndpoint {
+            url: url.to_string(),
+            reason: "recording results in PostgreSQL requires the `postgres` feature".to_string(),
+        })
+    }
+
+    pub fn run_id(&self) -> i64 {
+        self.run_id
+    }
+
+    /// Records the outcome of a block and its checks.
+    pub fn record(&mut self, outcome: &BlockOutcome) -> Result<()> {
+        let (block_id, error) = match &outcome.result {
+            Ok(report) => (Some(report.block_id.as_str()), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        let block = BlockRow {
+            height: outcome.height,
+            block_id,
+            error,
+            duration_ms: outcome.duration.as_secs_f64() * 1000.0,
+            passed: outcome.passed(),
+            checks: outcome
+                .result
+                .as_ref()
+                .map_or(&[][..], |report| &report.checks),
+        };
+        match &mut self.backend {
+            Backend::Sqlite(conn) => record_sqlite(conn, self.run_id, &block),
+            #[cfg(feature = "postgres")]
+            Backend::Postgres { client, network } => {
+                let client = client.get_mut().unwrap_or_else(|e| e.into_inner()).get();
+                Ok(blocking(|| {
+                    record_postgres(client, network, self.run_id, &block)
+                })?)
+            }
+        }
+    }
+
+    /// Whether the block at `height` was fully validated by a previous run,
+    /// whatever the result of its checks. Blocks whose validation errored
+    /// don't count.
+    pub fn is_validated(&self, height: u32) -> Result<bool> {
+        Ok(!self.validated_heights(height, height)?.is_empty())
+    }
+
+    /// Heights in `from..=to` that were fully validated by a previous run.
+    pub fn validated_heights(&self, from: u32, to: u32) -> Result<BTreeSet<u32>> {
+        match &self.backend {
+            Backend::Sqlite(conn) => {
+                let mut query = conn.prepare(
+                    "SELECT DISTINCT height FROM blocks
+                     WHERE height BETWEEN ?1 AND ?2 AND error IS NULL",
+                )?;
+                let heights = query
+                    .query_map(params![from, to], |row| row.get(0))?
+                    .collect::<rusqlite::Result<_>>()?;
+                Ok(heights)
+            }
+            #[cfg(feature = "postgres")]
+            Backend::Postgres { client, network } => {
+                let mut client = client.lock().unwrap_or_else(|e| e.into_inner());
+                let client = client.get();
+                let rows = blocking(|| {
+                    client.query(
+                        "SELECT height FROM blocks
+                         WHERE network = $1 AND height BETWEEN $2 AND $3 AND error IS NULL",
+                        &[network, &i64::from(from), &i64::from(to)],
+                    )
+                })?;
+                Ok(rows.iter().map(|row| row.get::<_, i64>(0) as u32).collect())
+            }
+        }
+    }
+    /// Sub-ranges of `from..=to` left once the blocks validated by previous
+    /// runs are skipped.
+    pub fn pending_ranges(&self, from: u32, to: u32) -> Result<Vec<(u32, u32)>> {
+        let validated = self.validated_heights(from, to)?;
+        let mut ranges = vec![];
+        let mut start = from;
+        for height in validated.into_iter().chain([to.saturating_add(1)]) {
+            if height > start {
+                ranges.push((start, height - 1));
+            }
+            start = height.saturating_add(1);
+        }
+        Ok(ranges)
+    }
+}
+
+fn unix_time() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+/// A PostgreSQL client closed on a thread of its own when dropped, see
+/// [`blocking`].
+#[cfg(feature = "postgres")]
+struct PgClient(Option<postgres::Client>);
+
+#[cfg(feature = "postgres")]
+impl PgClient {
+    fn get(&mut self) -> &mut postgres::Client {
+        self.0
+            .as_mut()
+            .expect("the client is only taken when dropped")
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl Drop for PgClient {
+    fn drop(&mut self) {
+        if let Some(client) = self.0.take() {
+            blocking(|| drop(client));
+        }
+    }
+}
+
+/// The columns of a validated block.
+struct BlockRow<'a> {
+    height: u32,
+    block_id: Option<&'a str>,
+    error: Option<String>,
+    duration_ms: f64,
+    passed: bool,
+    checks: &'a [CheckResult],
+}
+
+fn record_sqlite(conn: &mut Connection, run_id: i64, block: &BlockRow) -> Result<()> {
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT INTO blocks (run_id, height, block_id, validated_at, duration_ms, passed, error)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            run_id,
+            block.height,
+            block.block_id,
+            unix_time(),
+            block.duration_ms,
+            block.passed,
+            block.error,
+        ],
+    )?;
+    let id = tx.last_insert_rowid();
+
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO checks (block, name, tx_id, expected, actual, passed, message)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        for check in block.checks {
+            insert.execute(params![
+                id,
+                check.check.name(),
+                check.tx_id,
+                check.expected,
+                check.actual,
+                check.passed,
+                check.message,
+            ])?;
+        }
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
+#[cfg(feature = "postgres")]
+fn record_postgres(
+    client: &mut postgres::Client,
+    network: &str,
+    run_id: i64,
+    block: &BlockRow,
+) -> std::result::Result<(), postgres::Error> {
+    let mut tx = client.transaction()?;
+    // A block validated before, by this run or another validator, is
+    // replaced unless only the new validation errored.
+    let Some(row) = tx.query_opt(
+        "INSERT INTO blocks
+             (run_id, network, height, block_id, validated_at, duration_ms, passed, error)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         ON CONFLICT (network, height) DO UPDATE SET
+             run_id = EXCLUDED.run_id,
+             block_id = EXCLUDED.block_id,
+             validated_at = EXCLUDED.validated_at,
+             duration_ms = EXCLUDED.duration_ms,
+             passed = EXCLUDED.passed,
+             error = EXCLUDED.error
+         WHERE blocks.error IS NOT NULL OR EXCLUDED.error IS NULL
+         RETURNING id",
+        &[
+            &run_id,
+            &network,
+            &i64::from(block.height),
+            &block.block_id,
+            &unix_time(),
+            &block.duration_ms,
+            &block.passed,
+            &block.error,
+        ],
+    )?
+    else {
+        return tx.commit();
+    };
+    let id: i64 = row.get(0);
+    tx.execute("DELETE FROM checks WHERE block = $1", &[&id])?;
+    let insert = tx.prepare(
+        "INSERT INTO checks (block, name, tx_id, expected, actual, passed, message)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )?;
+    for check in block.checks {
+        tx.execute(
+            &insert,
+            &[
+                &id,
+                &check.check.name(),
+                &check.tx_id,
+                &check.expected,
+                &check.actual,
+                &check.passed,
+                &check.message,
+            ],
+        )?;
+    }
+    tx.commit()
+}
+
+/// Applies the migrations missing from the database, holding a lock so
+/// validators starting together don't apply them twice.
+#[cfg(feature = "postgres")]
+fn migrate(client: &mut postgres::Client) -> std::result::Result<(), postgres::Error> {
+    let mut tx = client.transaction()?;
+    tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK])?;
+    tx.batch_execute(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+             version INTEGER PRIMARY KEY,
+             applied_at BIGINT NOT NULL
+         )",
+    )?;
+    let applied: i32 = tx
+        .query_one(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+            &[],
+        )?
+        .get(0);
+    for (version, migration) in (1..).zip(POSTGRES_MIGRATIONS).skip(applied as usize) {
+        tx.batch_execute(migration)?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version, applied_at) VALUES ($1, $2)",
+            &[&version, &unix_time()],
+        )?;
+    }
+    tx.commit()
+}
+
+/// Runs `f` on a thread of its own: the synchronous PostgreSQL client drives
+/// a runtime of its own, which can't be started from within the async
+/// runtime validating blocks.
+#[cfg(feature = "postgres")]
+fn blocking<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(f)
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
@@ -0,0 +1,125 @@
+// src/subscription.rs
+//! New blocks pushed by a node, so followers don't have to poll its tip.
+//!
+//! Nodes serve GraphQL subscriptions as server-sent events on
+//! `/v1/graphql-sub`. Recent nodes push every block they import on the
+//! `alpha__new_blocks` subscription; older ones answer it with an error,
+//! which ends the stream like any other failure.
+
+use std::pin::Pin;
+
+use futures::{stream, Stream};
+use reqwest::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Url,
+};
+use serde_json::{json, Value};
+
+use crate::error::{Result, ValidationError};
+
+/// The subscription the notifications are received from. The pushed blocks
+/// are only used as a signal, the validated blocks are queried as usual.
+pub const NEW_BLOCKS_SUBSCRIPTION: &str = "subscription { alpha__new_blocks }";
+
+/// One item per block pushed by the node. Ends, or fails, when the node
+/// stops pushing blocks.
+pub type NewBlocks = Pin<Box<dyn Stream<Item = Result<()>> + Send>>;
+
+/// Subscribes to the new blocks of the node whose GraphQL endpoint is
+/// `endpoint`.
+pub async fn subscribe(client: &reqwest::Client, endpoint: &Url) -> Result<NewBlocks> {
+    let mut url = endpoint.clone();
+    url.set_path("/v1/graphql-sub");
+    let response = client
+        .post(url)
+        .header(ACCEPT, "text/event-stream")
+        .json(&json!({ "query": NEW_BLOCKS_SUBSCRIPTION }))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(subscription_error)?;
+
+    let is_event_stream = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    if !is_event_stream {
+        return Err(subscription_error(
+            "the node didn't answer with an event stream",
+        ));
+    }
+
+    let events = Events {
+        response,
+        buffer: String::new(),
+    };
+    Ok(Box::pin(stream::unfold(
+        Some(events),
+        |events| async move {
+            let mut events = events?;
+            match events.next().await {
+                // A failed stream doesn't recover, so it ends with the error.
+                Ok(Some(())) => Some((Ok(()), Some(events))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        },
+    )))
+}
+
+/// The events of a server-sent events response.
+struct Events {
+    response: reqwest::Response,
+    buffer: String,
+}
+
+impl Events {
+    /// Waits for the next block, `None` once the node closed the stream.
+    async fn next(&mut self) -> Result<Option<()>> {
+        loop {
+            while let Some(end) = self.buffer.find("\n\n") {
+                let event: String = self.buffer.drain(..end + 2).collect();
+                let data: Vec<_> = event
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("data:"))
+                    .map(str::trim_start)
+                    .collect();
+                // Comments and keep-alives carry no data.
+                if !data.is_empty() {
+                    return parse_event(&data.join("\n")).map(Some);
+                }
+            }
+
+            match self.response.chunk().await.map_err(subscription_error)? {
+                Some(chunk) => self
+                    .buffer
+                    .push_str(&String::from_utf8_lossy(&chunk).replace('\r', "")),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Checks that the GraphQL response of an event is a block, not an error.
+fn parse_event(data: &str) -> Result<()> {
+    let response: Value = serde_json::from_str(data)
+        .map_err(|e| subscription_error(format!("invalid event `{data}`: {e}")))?;
+    if let Some(errors) = response["errors"].as_array().filter(|e| !e.is_empty()) {
+        let messages: Vec<_> = errors
+            .iter()
+            .map(|error| error["message"].as_str().unwrap_or("unknown error"))
+            .collect();
+        return Err(subscription_error(messages.join(", ")));
+    }
+    if response["data"]["alpha__new_blocks"].is_null() {
+        return Err(subscription_error(format!("unexpected event `{data}`")));
+    }
+    Ok(())
+}
+
+fn subscription_error(reason: impl ToString) -> ValidationError {
+    ValidationError::Subscription {
+        reason: reason.to_string(),
+    }
+}
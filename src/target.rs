@@ -0,0 +1,82 @@
+// src/target.rs
+//! Blocks named relative to the tip of the chain, resolved to a height once
+//! the latest block is known.
+
+use std::{fmt, str::FromStr};
+
+use crate::error::{Result, ValidationError};
+
+/// A block to validate: a fixed height, or the latest block or one at some
+/// depth below it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlockTarget {
+    Height(u32),
+    /// The block `behind` blocks below the latest one.
+    Latest {
+        behind: u32,
+    },
+}
+
+impl BlockTarget {
+    /// Whether the latest height has to be known to resolve the target.
+    pub fn is_relative(&self) -> bool {
+        matches!(self, BlockTarget::Latest { .. })
+    }
+
+    /// The height of the target once the latest block is `latest`.
+    pub fn resolve(&self, latest: u32) -> Result<u32> {
+        match *self {
+            BlockTarget::Height(height) => Ok(height),
+            BlockTarget::Latest { behind } => {
+                latest
+                    .checked_sub(behind)
+                    .ok_or_else(|| ValidationError::InvalidBlockTarget {
+                        target: self.to_string(),
+                        reason: format!("the latest block is #{latest}"),
+                    })
+            }
+        }
+    }
+}
+
+impl From<u32> for BlockTarget {
+    fn from(height: u32) -> Self {
+        BlockTarget::Height(height)
+    }
+}
+
+impl fmt::Display for BlockTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockTarget::Height(height) => write!(f, "{height}"),
+            BlockTarget::Latest { behind: 0 } => f.write_str("latest"),
+            BlockTarget::Latest { behind } => write!(f, "latest-{behind}"),
+        }
+    }
+}
+
+impl FromStr for BlockTarget {
+    type Err = ValidationError;
+
+    /// Parses a height, `latest` or `latest-N`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| ValidationError::InvalidBlockTarget {
+            target: s.to_string(),
+            reason: reason.to_string(),
+        };
+        let Some(relative) = s.strip_prefix("latest") else {
+            return s
+                .parse()
+                .map(BlockTarget::Height)
+                .map_err(|_| invalid("expected a height, `latest` or `latest-N`"));
+        };
+        let behind = match relative.strip_prefix('-') {
+            Some(depth) => depth
+                .parse()
+                .map_err(|_| invalid("expected a number of blocks after `latest-`"))?,
+            None if relative.is_empty() => 0,
+            None => return Err(invalid("expected a height, `latest` or `latest-N`")),
+        };
+        Ok(BlockTarget::Latest { behind })
+    }
+}
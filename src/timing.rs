@@ -0,0 +1,142 @@
+// src/timing.rs
+//! Where the time validating blocks goes, to tell network-bound scans from
+//! CPU-bound ones.
+
+use std::{fmt, time::Duration};
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+/// Time spent on one block, recorded when
+/// [`BlockValidator::with_timing`](crate::BlockValidator::with_timing) is set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BlockTiming {
+    /// Fetching the block, including decoding the response. In range mode,
+    /// the block's share of the page query it was fetched with.
+    pub fetch: Duration,
+    /// Decoding the transactions and receipts of the block on their own.
+    pub parse: Duration,
+    /// Recomputing the ids and Merkle roots of the block and comparing them,
+    /// which decodes the transactions again.
+    pub merkle: Duration,
+    /// The queries of the checks that need more than the block: the event
+    /// inbox, L1, cross-checked endpoints and the `prev_root` tree.
+    pub queries: Duration,
+}
+
+impl Serialize for BlockTiming {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("BlockTiming", 4)?;
+        state.serialize_field("fetch_ms", &millis(self.fetch))?;
+        state.serialize_field("parse_ms", &millis(self.parse))?;
+        state.serialize_field("merkle_ms", &millis(self.merkle))?;
+        state.serialize_field("queries_ms", &millis(self.queries))?;
+        state.end()
+    }
+}
+
+/// Distribution of the time spent in one phase over many blocks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Percentiles {
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    pub total: Duration,
+}
+
+impl Percentiles {
+    /// The percentiles of `durations`, nearest-rank.
+    pub fn of(mut durations: Vec<Duration>) -> Self {
+        durations.sort_unstable();
+        let rank = |percentile: usize| {
+            let index = (durations.len() * percentile).div_ceil(100).max(1) - 1;
+            durations.get(index).copied().unwrap_or_default()
+        };
+        Self {
+            p50: rank(50),
+            p90: rank(90),
+            p99: rank(99),
+            max: durations.last().copied().unwrap_or_default(),
+            total: durations.iter().sum(),
+        }
+    }
+}
+
+impl Serialize for Percentiles {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Percentiles", 5)?;
+        state.serialize_field("p50_ms", &millis(self.p50))?;
+        state.serialize_field("p90_ms", &millis(self.p90))?;
+        state.serialize_field("p99_ms", &millis(self.p99))?;
+        state.serialize_field("max_ms", &millis(self.max))?;
+        state.serialize_field("total_ms", &millis(self.total))?;
+        state.end()
+    }
+}
+
+/// Percentiles of every phase over the blocks of a scan.
+#[derive(Clone, Debug, Serialize)]
+pub struct TimingSummary {
+    /// Number of blocks timed.
+    pub blocks: usize,
+    pub fetch: Percentiles,
+    pub parse: Percentiles,
+    pub merkle: Percentiles,
+    pub queries: Percentiles,
+}
+
+impl TimingSummary {
+    /// The summary of `timings`, `None` if there are none.
+    pub fn of<'a>(timings: impl IntoIterator<Item = &'a BlockTiming>) -> Option<Self> {
+        let timings: Vec<_> = timings.into_iter().collect();
+        if timings.is_empty() {
+            return None;
+        }
+        let phase = |duration: fn(&BlockTiming) -> Duration| {
+            Percentiles::of(timings.iter().map(|timing| duration(timing)).collect())
+        };
+        Some(Self {
+            blocks: timings.len(),
+            fetch: phase(|timing| timing.fetch),
+            parse: phase(|timing| timing.parse),
+            merkle: phase(|timing| timing.merkle),
+            queries: phase(|timing| timing.queries),
+        })
+    }
+
+    fn phases(&self) -> [(&'static str, &Percentiles); 4] {
+        [
+            ("fetch", &self.fetch),
+            ("parse", &self.parse),
+            ("merkle", &self.merkle),
+            ("queries", &self.queries),
+        ]
+    }
+}
+
+impl fmt::Display for TimingSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Timing of {} blocks, in ms:", self.blocks)?;
+        write!(
+            f,
+            "  {:<8} {:>10} {:>10} {:>10} {:>10} {:>12}",
+            "phase", "p50", "p90", "p99", "max", "total"
+        )?;
+        for (name, percentiles) in self.phases() {
+            write!(
+                f,
+                "\n  {name:<8} {:>10.2} {:>10.2} {:>10.2} {:>10.2} {:>12.2}",
+                millis(percentiles.p50),
+                millis(percentiles.p90),
+                millis(percentiles.p99),
+                millis(percentiles.max),
+                millis(percentiles.total),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
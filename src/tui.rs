@@ -0,0 +1,99 @@
+// src/tui.rs
+//! The terminal dashboard of `--follow --tui`.
+
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use fuel_root_validation::{BlockValidator, Dashboard};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use tokio_util::sync::CancellationToken;
+
+use crate::output::lock;
+
+/// Delay between two redraws of the dashboard.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long the key reader waits for a key before checking whether the
+/// dashboard was closed.
+const KEY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Draws `dashboard` until the validator is cancelled, probing the heads of
+/// its endpoints every `probe_interval`. Pressing `q`, `Esc` or Ctrl-C
+/// cancels the validator: the terminal is in raw mode, so Ctrl-C doesn't
+/// send SIGINT.
+pub async fn run(
+    validator: &BlockValidator,
+    dashboard: Arc<Mutex<Dashboard>>,
+    probe_interval: Duration,
+) -> Result<()> {
+    let cancel = validator.cancellation_token().clone();
+    let mut terminal = ratatui::try_init().context("failed to set up the terminal")?;
+    let closed = Restore(CancellationToken::new());
+    read_keys(cancel.clone(), closed.0.clone());
+
+    let drawing = async {
+        let mut redraws = tokio::time::interval(REDRAW_INTERVAL);
+        while !cancel.is_cancelled() {
+            redraws.tick().await;
+            terminal
+                .draw(|frame| lock(&dashboard).render(frame))
+                .context("failed to draw the dashboard")?;
+        }
+        anyhow::Ok(())
+    };
+    let probing = async {
+        let mut probes = tokio::time::interval(probe_interval);
+        loop {
+            probes.tick().await;
+            let heads = validator.probe_heads().await;
+            lock(&dashboard).observe_probes(&heads);
+        }
+    };
+    tokio::select! {
+        drawn = drawing => drawn,
+        () = probing => Ok(()),
+    }
+}
+
+/// Stops the key reader and gives the terminal back once the dashboard is
+/// dropped, whether it returned or following stopped first.
+struct Restore(CancellationToken);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        self.0.cancel();
+        ratatui::restore();
+    }
+}
+
+/// Reads keys on a thread of its own, cancelling `cancel` when asked to
+/// quit, until `closed` is cancelled.
+fn read_keys(cancel: CancellationToken, closed: CancellationToken) {
+    thread::spawn(move || {
+        while !closed.is_cancelled() {
+            let Event::Key(key) = (match event::poll(KEY_POLL_INTERVAL) {
+                Ok(true) => match event::read() {
+                    Ok(event) => event,
+                    Err(_) => break,
+                },
+                Ok(false) => continue,
+                Err(_) => break,
+            }) else {
+                continue;
+            };
+            let ctrl_c =
+                key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+            if key.kind == KeyEventKind::Press
+                && (ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc))
+            {
+                tracing::info!("dashboard closed, shutting down");
+                cancel.cancel();
+                break;
+            }
+        }
+    });
+}
@@ -0,0 +1,1589 @@
+// src/validator.rs
+use fuel_core_client::client::{
+    pagination::{PageDirection, PaginatedResult, PaginationRequest},
+    schema::{block::Consensus, relayed_tx::RelayedTransactionStatus},
+    FuelClient,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::{Bytes32, ChainId};
+use futures::{future, stream, Future, Stream, StreamExt, TryStreamExt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::cache::BlockCache;
+use crate::cassette::Cassette;
+use crate::checks;
+use crate::consensus::{self, PoaAuthority};
+use crate::consensus_parameters::ChainParameters;
+use crate::error::{Result, ValidationError};
+use crate::event_inbox::{EventInbox, RelayedEvent};
+use crate::known_anomalies::KnownAnomalies;
+use crate::l1::L1ChainState;
+use crate::memory::{self, MemoryBudget};
+use crate::metrics::Metrics;
+use crate::prev_root::PrevRoots;
+use crate::proof::MerkleProof;
+use crate::proof_bundle::ProofBundle;
+use crate::query::{BlockIdAndHeight, FullBlock, Header, LightBlock};
+use crate::rate_limit::RateLimit;
+use crate::registry::{self, BlockCheck, CheckContext, CheckRegistry};
+use crate::report::{BlockReport, Check, CheckResult, Severity};
+use crate::retry::RetryPolicy;
+use crate::source::{BlockSource, GraphqlSource};
+use crate::subscription::NewBlocks;
+use crate::target::BlockTarget;
+use crate::timing::{BlockTiming, TimingSummary};
+
+/// Default number of full blocks requested per page when validating a range.
+pub const BLOCKS_PAGE_SIZE: u32 = 10;
+
+/// Number of block ids requested per page when rebuilding the `prev_root` tree.
+pub const BLOCK_IDS_PAGE_SIZE: i32 = 100;
+
+/// The latest height an endpoint reported, see
+/// [`BlockValidator::probe_heads`].
+#[derive(Debug)]
+pub struct HeadProbe {
+    /// The cross-check endpoint queried, `None` for the source.
+    pub endpoint: Option<String>,
+    pub height: Result<u32>,
+    /// How long the query took, retries included.
+    pub latency: Duration,
+}
+
+/// Result of validating one block of a range. Failed checks are part of the
+/// report; `result` is only an error when the checks couldn't be run.
+#[derive(Debug)]
+pub struct BlockOutcome {
+    pub height: u32,
+    pub result: Result<BlockReport>,
+    /// Time spent validating the block, including the queries it needed but
+    /// not the page query it was fetched with in range mode.
+    pub duration: Duration,
+    /// Where the time went, when the validator records it.
+    pub timing: Option<BlockTiming>,
+}
+
+impl BlockOutcome {
+    pub fn passed(&self) -> bool {
+        matches!(&self.result, Ok(report) if report.passed())
+    }
+
+    /// Whether the block passed only because its failures are known
+    /// anomalies.
+    pub fn acknowledged(&self) -> bool {
+        matches!(&self.result, Ok(report) if report.passed() && report.acknowledged().next().is_some())
+    }
+}
+
+/// Per-block outcomes of a range validation, in height order.
+#[derive(Debug, Default)]
+pub struct RangeValidation {
+    pub outcomes: Vec<BlockOutcome>,
+}
+
+impl RangeValidation {
+    pub fn passed(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.passed()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.outcomes.len() - self.passed()
+    }
+
+    /// Blocks that passed only because their failures are known anomalies.
+    pub fn acknowledged(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.acknowledged()).count()
+    }
+
+    /// Percentiles of the time spent on the blocks that were timed, if any.
+    pub fn timing(&self) -> Option<TimingSummary> {
+        TimingSummary::of(self.outcomes.iter().filter_map(|o| o.timing.as_ref()))
+    }
+}
+
+/// Fetches blocks from a node, or any other [`BlockSource`], and recomputes
+/// their Merkle roots.
+#[derive(Clone, Debug)]
+pub struct BlockValidator {
+    backend: Backend,
+    concurrency: usize,
+    page_size: u32,
+    chain_id: Option<ChainId>,
+    consensus_parameters: Option<Arc<RwLock<ChainParameters>>>,
+    checks: CheckRegistry,
+    l1_events: Option<Arc<EventInbox>>,
+    poa_authority: Option<PoaAuthority>,
+    l1: Option<L1ChainState>,
+    retry: RetryPolicy,
+    rate_limit: RateLimit,
+    timeout: Option<Duration>,
+    receipt_fallback: bool,
+    cancel: CancellationToken,
+    cassette: Option<Arc<Cassette>>,
+    cross_check: Vec<(String, GraphqlSource)>,
+    metrics: Option<Arc<Metrics>>,
+    cache: Option<Arc<BlockCache>>,
+    timing: bool,
+    keep_going: bool,
+    headers_only: bool,
+    fail_on: Severity,
+    known_anomalies: Option<Arc<KnownAnomalies>>,
+    memory: Option<Arc<MemoryBudget>>,
+}
+
+/// Where a validator reads blocks from.
+#[derive(Clone, Debug)]
+enum Backend {
+    /// A node, which also answers the queries of the checks that need more
+    /// than the blocks themselves.
+    Node(Box<GraphqlSource>),
+    Source(Arc<dyn BlockSource>),
+}
+
+impl BlockValidator {
+    pub fn new(url: impl AsRef<str>) -> Result<Self> {
+        Ok(Self::with_backend(Backend::Node(Box::new(
+            GraphqlSource::new(url)?,
+        ))))
+    }
+
+    pub fn from_client(client: FuelClient) -> Self {
+        Self::with_backend(Backend::Node(Box::new(GraphqlSource::from_client(client))))
+    }
+
+    /// A validator reading blocks from `source` instead of a node. Checks
+    /// that query more than blocks (the chain id, `prev_root` and the event
+    /// inbox) fail with [`ValidationError::NodeRequired`].
+    pub fn from_source(source: impl BlockSource + 'static) -> Self {
+        Self::with_backend(Backend::Source(Arc::new(source)))
+    }
+
+    fn with_backend(backend: Backend) -> Self {
+        Self {
+            backend,
+            concurrency: 1,
+            page_size: BLOCKS_PAGE_SIZE,
+            chain_id: None,
+            consensus_parameters: None,
+            checks: CheckRegistry::default(),
+            l1_events: None,
+            poa_authority: None,
+            l1: None,
+            retry: RetryPolicy::default(),
+            rate_limit: RateLimit::unlimited(),
+            timeout: None,
+            receipt_fallback: true,
+            cancel: CancellationToken::new(),
+            cassette: None,
+            cross_check: vec![],
+            metrics: None,
+            cache: None,
+            timing: false,
+            keep_going: false,
+            headers_only: false,
+            fail_on: Severity::default(),
+            known_anomalies: None,
+            memory: None,
+        }
+    }
+
+    /// Sets the PoA authority whose signature every block must carry. Block
+    /// signatures are only verified once an authority is known.
+    pub fn with_poa_authority(mut self, authority: PoaAuthority) -> Self {
+        self.poa_authority = Some(authority);
+        self
+    }
+
+    pub fn poa_authority(&self) -> Option<&PoaAuthority> {
+        self.poa_authority.as_ref()
+    }
+
+    /// Enables validation of `header.event_inbox_root`.
+    ///
+    /// Blocks that didn't advance the DA height must have an empty inbox.
+    /// Blocks that did can only be checked against the L1 events given to
+    /// [`Self::with_l1_events`], and are skipped with a warning otherwise.
+    pub fn with_event_inbox_check(mut self, enabled: bool) -> Self {
+        self.set_check_enabled(registry::EVENT_INBOX, enabled);
+        self
+    }
+
+    /// Sets the L1 events used to recompute `header.event_inbox_root`.
+    pub fn with_l1_events(mut self, events: EventInbox) -> Self {
+        self.l1_events = Some(Arc::new(events));
+        self
+    }
+
+    pub fn checks_event_inbox(&self) -> bool {
+        self.checks.is_enabled(registry::EVENT_INBOX)
+    }
+
+    /// Cross-checks every block with the chain state committed on L1: its DA
+    /// height can't be ahead of L1, and the block committed for its commit
+    /// interval must be served by the node.
+    pub fn with_l1_chain_state(mut self, l1: L1ChainState) -> Self {
+        self.l1 = Some(l1);
+        self
+    }
+
+    pub fn l1_chain_state(&self) -> Option<&L1ChainState> {
+        self.l1.as_ref()
+    }
+
+    /// Enables validation of `header.prev_root`. This requires the ids of all
+    /// blocks since genesis, so it is opt-in.
+    pub fn with_prev_root_check(mut self, enabled: bool) -> Self {
+        self.set_check_enabled(registry::PREV_ROOT, enabled);
+        self
+    }
+
+    pub fn checks_prev_root(&self) -> bool {
+        self.checks.is_enabled(registry::PREV_ROOT)
+    }
+
+    /// Replaces the checks blocks are validated with, [`CheckRegistry::default`]
+    /// by default. The checks that query more than the block only run if
+    /// `checks` has them, under their names in [`registry`].
+    pub fn with_checks(mut self, checks: CheckRegistry) -> Self {
+        self.checks = checks;
+        self
+    }
+
+    /// Registers a custom check run on every block after the built-in ones.
+    pub fn with_check(mut self, check: impl BlockCheck + 'static) -> Self {
+        self.checks = self.checks.register(check);
+        self
+    }
+
+    /// Enables or disables the check `name` of [`Self::checks`].
+    pub fn with_check_enabled(mut self, name: &str, enabled: bool) -> Result<Self> {
+        self.checks.set_enabled(name, enabled)?;
+        Ok(self)
+    }
+
+    pub fn checks(&self) -> &CheckRegistry {
+        &self.checks
+    }
+
+    /// Sets a built-in check, which a registry set with [`Self::with_checks`]
+    /// may not have: such checks just don't run.
+    fn set_check_enabled(&mut self, name: &str, enabled: bool) {
+        let _ = self.checks.set_enabled(name, enabled);
+    }
+
+    /// Enables recomputing the gas and the fee charged for every transaction.
+    /// This needs the consensus parameters of every block, see
+    /// [`Self::with_consensus_parameters`], so it is opt-in.
+    pub fn with_fee_check(mut self, enabled: bool) -> Self {
+        self.set_check_enabled(registry::FEES, enabled);
+        self
+    }
+
+    pub fn checks_fees(&self) -> bool {
+        self.checks.is_enabled(registry::FEES)
+    }
+
+    /// Sets the chain id used to recompute transaction ids. Transaction ids
+    /// are only validated once a chain id is known. Blocks validated with
+    /// their consensus parameters use the chain id of the parameters.
+    pub fn with_chain_id(mut self, chain_id: ChainId) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    pub fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    /// Validates every block with the consensus parameters of the version in
+    /// its header, e.g. pinned from a file. Versions missing from `params`
+    /// are queried from the node on first use, and fail the block without
+    /// one. The chain id is taken from the latest version unless one is set.
+    pub fn with_consensus_parameters(mut self, params: ChainParameters) -> Self {
+        self.chain_id = self.chain_id.or(params.chain_id());
+        self.consensus_parameters = Some(Arc::new(RwLock::new(params)));
+        self
+    }
+
+    /// The consensus parameters known so far, if they are used.
+    pub fn consensus_parameters(&self) -> Option<ChainParameters> {
+        let params = self.consensus_parameters.as_ref()?;
+        Some(params.read().unwrap().clone())
+    }
+
+    /// Queries the node's current consensus parameters and validates with
+    /// them, see [`Self::with_consensus_parameters`].
+    pub async fn load_consensus_parameters(self) -> Result<Self> {
+        let (version, current) = self
+            .require_node("consensus parameters")?
+            .fetch_latest_consensus_parameters()
+            .await?;
+        let mut params = self.consensus_parameters().unwrap_or_default();
+        params.insert(version, current);
+        Ok(self.with_consensus_parameters(params))
+    }
+
+    /// Queries every version of the consensus parameters up to the node's
+    /// current one, e.g. to pin them for offline runs.
+    pub async fn fetch_all_consensus_parameters(&self) -> Result<ChainParameters> {
+        let node = self.require_node("consensus parameters")?;
+        let (latest, current) = node.fetch_latest_consensus_parameters().await?;
+        let mut params = ChainParameters::new();
+        for version in 0..latest {
+            params.insert(version, node.fetch_consensus_parameters(version).await?);
+        }
+        params.insert(latest, current);
+        Ok(params)
+    }
+
+    /// The consensus parameters `block` was produced with, `None` unless
+    /// they are used.
+    async fn block_consensus_parameters(
+        &self,
+        block: &FullBlock,
+    ) -> Result<Option<ConsensusParameters>> {
+        let Some(known) = &self.consensus_parameters else {
+            return Ok(None);
+        };
+        let version = block.header.consensus_parameters_version.0;
+        if let Some(params) = known.read().unwrap().get(version) {
+            return Ok(Some(params.clone()));
+        }
+        let params = match self.node() {
+            Some(node) => node.fetch_consensus_parameters(version).await?,
+            None => return Err(ValidationError::ConsensusParametersNotFound { version }),
+        };
+        known.write().unwrap().insert(version, params.clone());
+        Ok(Some(params))
+    }
+
+    /// Records the [`BlockTiming`] of every validated block in its outcome.
+    /// Decoding is then timed on its own, which decodes every block once more.
+    pub fn with_timing(mut self, enabled: bool) -> Self {
+        self.timing = enabled;
+        self
+    }
+
+    pub fn records_timing(&self) -> bool {
+        self.timing
+    }
+
+    /// Keeps validating a range when a page of blocks can't be fetched: the
+    /// blocks of the page are then validated one by one, and the ones that
+    /// still can't be fetched are reported as failed instead of aborting
+    /// the range.
+    pub fn with_keep_going(mut self, enabled: bool) -> Self {
+        self.keep_going = enabled;
+        self
+    }
+
+    pub fn keeps_going(&self) -> bool {
+        self.keep_going
+    }
+
+    /// Validates only the headers of the blocks, fetched without their
+    /// transactions: the block ids and application hashes over the
+    /// transaction ids reported by the node, the PoA signatures, and the
+    /// `prev_root` of every block over the ids of the headers before it. The
+    /// other checks are skipped. Much cheaper than full validation, to
+    /// pre-screen large ranges.
+    pub fn with_headers_only(mut self, enabled: bool) -> Self {
+        self.headers_only = enabled;
+        self
+    }
+
+    pub fn validates_headers_only(&self) -> bool {
+        self.headers_only
+    }
+
+    /// Fails blocks on failed checks of `severity` or worse, e.g.
+    /// [`Severity::Warning`] to also fail them on warnings. Defaults to
+    /// [`Severity::Error`].
+    pub fn with_fail_on(mut self, severity: Severity) -> Self {
+        self.fail_on = severity;
+        self
+    }
+
+    pub fn fail_on(&self) -> Severity {
+        self.fail_on
+    }
+
+    /// Acknowledges the failed checks that are `anomalies`, so they don't
+    /// fail their block.
+    pub fn with_known_anomalies(mut self, anomalies: KnownAnomalies) -> Self {
+        self.known_anomalies = Some(Arc::new(anomalies));
+        self
+    }
+
+    pub fn known_anomalies(&self) -> Option<&KnownAnomalies> {
+        self.known_anomalies.as_deref()
+    }
+
+    /// Reports the failures of the check `name` with `severity`, failing
+    /// with [`ValidationError::UnknownCheck`] if there is no such check.
+    pub fn with_check_severity(mut self, name: &str, severity: Severity) -> Result<Self> {
+        self.checks.set_severity(name, severity)?;
+        Ok(self)
+    }
+
+    /// Sets how many pages of a range are fetched and validated concurrently.
+    pub fn with_concurrency(mut self, jobs: usize) -> Self {
+        self.concurrency = jobs.max(1);
+        self
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Sets how many blocks of a range are fetched with a single query,
+    /// [`BLOCKS_PAGE_SIZE`] by default. Larger pages need fewer round trips,
+    /// but nodes limit how complex a query may be.
+    pub fn with_page_size(mut self, blocks: u32) -> Self {
+        self.page_size = blocks.max(1);
+        self
+    }
+
+    pub fn page_size(&self) -> u32 {
+        self.page_size
+    }
+
+    /// Bounds the memory held by the blocks of a range being validated to
+    /// about `bytes`, see [`MemoryBudget`]. Pages hold fewer blocks than
+    /// [`Self::page_size`] when their blocks are large.
+    pub fn with_max_memory(mut self, bytes: u64) -> Self {
+        self.memory = Some(Arc::new(MemoryBudget::new(bytes)));
+        self
+    }
+
+    pub fn memory_budget(&self) -> Option<&MemoryBudget> {
+        self.memory.as_deref()
+    }
+
+    /// The source blocks are read from.
+    pub fn source(&self) -> &dyn BlockSource {
+        match &self.backend {
+            Backend::Node(node) => node.as_ref(),
+            Backend::Source(source) => source.as_ref(),
+        }
+    }
+
+    /// The node blocks are read from, unless they come from another source.
+    pub fn node(&self) -> Option<&GraphqlSource> {
+        match &self.backend {
+            Backend::Node(node) => Some(node),
+            Backend::Source(_) => None,
+        }
+    }
+
+    fn require_node(&self, query: &'static str) -> Result<&GraphqlSource> {
+        self.node().ok_or(ValidationError::NodeRequired { query })
+    }
+
+    /// Sets how failed queries are retried, on the main endpoint and the
+    /// cross-check endpoints.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.backend = match self.backend {
+            Backend::Node(node) => Backend::Node(Box::new(node.with_retry_policy(retry.clone()))),
+            source => source,
+        };
+        self.cross_check = self
+            .cross_check
+            .into_iter()
+            .map(|(url, node)| (url, node.with_retry_policy(retry.clone())))
+            .collect();
+        self.retry = retry;
+        self
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// Paces the queries to the node and to every cross-checked endpoint by
+    /// `limit`, each endpoint on its own.
+    pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.backend = match self.backend {
+            Backend::Node(node) => Backend::Node(Box::new(node.with_rate_limit(limit))),
+            source => source,
+        };
+        self.cross_check = self
+            .cross_check
+            .into_iter()
+            .map(|(url, node)| (url, node.with_rate_limit(limit)))
+            .collect();
+        self.rate_limit = limit;
+        self
+    }
+
+    pub fn rate_limit(&self) -> &RateLimit {
+        &self.rate_limit
+    }
+
+    /// Fails every attempt of a query to the node or to a cross-checked
+    /// endpoint that got no response within `timeout`, see
+    /// [`GraphqlSource::with_timeout`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.backend = match self.backend {
+            Backend::Node(node) => Backend::Node(Box::new(node.with_timeout(timeout))),
+            source => source,
+        };
+        self.cross_check = self
+            .cross_check
+            .into_iter()
+            .map(|(url, node)| (url, node.with_timeout(timeout)))
+            .collect();
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Whether receipts missing from the blocks of the node or of a
+    /// cross-checked endpoint are fetched per transaction, see
+    /// [`GraphqlSource::with_receipt_fallback`]. On by default.
+    pub fn with_receipt_fallback(mut self, enabled: bool) -> Self {
+        self.backend = match self.backend {
+            Backend::Node(node) => Backend::Node(Box::new(node.with_receipt_fallback(enabled))),
+            source => source,
+        };
+        self.cross_check = self
+            .cross_check
+            .into_iter()
+            .map(|(url, node)| (url, node.with_receipt_fallback(enabled)))
+            .collect();
+        self.receipt_fallback = enabled;
+        self
+    }
+
+    pub fn receipt_fallback(&self) -> bool {
+        self.receipt_fallback
+    }
+
+    /// Stops fetching and validating blocks once `cancel` is cancelled:
+    /// queries in flight and blocks not fetched yet fail with
+    /// [`ValidationError::Cancelled`], which ends ranges and follow mode.
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.backend = match self.backend {
+            Backend::Node(node) => Backend::Node(Box::new(node.with_cancellation(cancel.clone()))),
+            source => source,
+        };
+        self.cross_check = self
+            .cross_check
+            .into_iter()
+            .map(|(url, node)| (url, node.with_cancellation(cancel.clone())))
+            .collect();
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    /// `fetch`, unless the validator is cancelled first.
+    async fn cancellable<T>(&self, fetch: impl Future<Output = Result<T>>) -> Result<T> {
+        tokio::select! {
+            biased;
+            _ = self.cancel.cancelled() => Err(ValidationError::Cancelled),
+            result = fetch => result,
+        }
+    }
+
+    /// Records the responses of the node and of every cross-checked
+    /// endpoint in `cassette`, or replays them from it.
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.backend = match self.backend {
+            Backend::Node(node) => Backend::Node(Box::new(node.with_cassette(cassette.clone()))),
+            source => source,
+        };
+        self.cross_check = self
+            .cross_check
+            .into_iter()
+            .map(|(url, node)| (url, node.with_cassette(cassette.clone())))
+            .collect();
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Adds an endpoint every validated block is also fetched from. The block
+    /// it serves must be identical to the one served by the main endpoint.
+    pub fn with_cross_check_endpoint(mut self, url: impl AsRef<str>) -> Result<Self> {
+        let mut node = GraphqlSource::new(url.as_ref())?
+            .with_retry_policy(self.retry.clone())
+            .with_rate_limit(self.rate_limit)
+            .with_receipt_fallback(self.receipt_fallback)
+            .with_cancellation(self.cancel.clone());
+        if let Some(timeout) = self.timeout {
+            node = node.with_timeout(timeout);
+        }
+        if let Some(metrics) = &self.metrics {
+            node = node.with_metrics(metrics.clone());
+        }
+        if let Some(cache) = &self.cache {
+            node = node.with_cache(cache.clone());
+        }
+        if let Some(cassette) = &self.cassette {
+            node = node.with_cassette(cassette.clone());
+        }
+        self.cross_check.push((url.as_ref().to_string(), node));
+        Ok(self)
+    }
+
+    /// Only keeps the cross-check endpoints `keep` returns true for.
+    pub fn retain_cross_check_endpoints(mut self, mut keep: impl FnMut(&str) -> bool) -> Self {
+        self.cross_check.retain(|(url, _)| keep(url));
+        self
+    }
+
+    pub fn cross_check_endpoints(&self) -> impl Iterator<Item = &str> {
+        self.cross_check.iter().map(|(url, _)| url.as_str())
+    }
+
+    /// Sets the metrics failed queries are accounted in.
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.backend = match self.backend {
+            Backend::Node(node) => Backend::Node(Box::new(node.with_metrics(metrics.clone()))),
+            source => source,
+        };
+        self.cross_check = self
+            .cross_check
+            .into_iter()
+            .map(|(url, node)| (url, node.with_metrics(metrics.clone())))
+            .collect();
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn metrics(&self) -> Option<&Arc<Metrics>> {
+        self.metrics.as_ref()
+    }
+
+    /// Keeps the blocks and block ids fetched from the main endpoint and the
+    /// cross-check endpoints in `cache`, and reads them from it in later
+    /// runs. Blocks read from another source aren't cached.
+    pub fn with_cache(mut self, cache: Arc<BlockCache>) -> Self {
+        self.backend = match self.backend {
+            Backend::Node(node) => Backend::Node(Box::new(node.with_cache(cache.clone()))),
+            source => source,
+        };
+        self.cross_check = self
+            .cross_check
+            .into_iter()
+            .map(|(url, node)| (url, node.with_cache(cache.clone())))
+            .collect();
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn cache(&self) -> Option<&Arc<BlockCache>> {
+        self.cache.as_ref()
+    }
+
+    /// The block at `height` together with its transactions and statuses.
+    pub async fn fetch_block(&self, height: u32) -> Result<FullBlock> {
+        self.cancellable(self.source().fetch_block(height)).await
+    }
+
+    /// Only the header of the block at `height`.
+    pub async fn fetch_header(&self, height: u32) -> Result<Header> {
+        self.cancellable(self.source().fetch_header(height)).await
+    }
+
+    /// Queries the chain id from the node's current consensus parameters.
+    pub async fn fetch_chain_id(&self) -> Result<ChainId> {
+        self.require_node("chain id")?.fetch_chain_id().await
+    }
+
+    /// Height of the latest block of the source.
+    pub async fn latest_height(&self) -> Result<u32> {
+        self.cancellable(self.source().latest_height()).await
+    }
+
+    /// The heights of `targets`, querying the latest block once if any of
+    /// them is relative to it.
+    pub async fn resolve_targets(&self, targets: &[BlockTarget]) -> Result<Vec<u32>> {
+        let latest = if targets.iter().any(BlockTarget::is_relative) {
+            self.latest_height().await?
+        } else {
+            0
+        };
+        targets
+            .iter()
+            .map(|target| target.resolve(latest))
+            .collect()
+    }
+
+    /// Queries the latest height of the source and of every cross-check
+    /// endpoint, concurrently, to see which of them are lagging or down.
+    pub async fn probe_heads(&self) -> Vec<HeadProbe> {
+        let sources = std::iter::once((None, self.source())).chain(
+            self.cross_check
+                .iter()
+                .map(|(url, node)| (Some(url), node as &dyn BlockSource)),
+        );
+        future::join_all(sources.map(|(endpoint, source)| async move {
+            let started = Instant::now();
+            let height = self.cancellable(source.latest_height()).await;
+            HeadProbe {
+                endpoint: endpoint.cloned(),
+                height,
+                latency: started.elapsed(),
+            }
+        }))
+        .await
+    }
+
+    /// Notifications of the blocks the source adds from now on, `None` if it
+    /// has to be polled.
+    pub async fn subscribe_new_blocks(&self) -> Result<Option<NewBlocks>> {
+        self.source().subscribe_new_blocks().await
+    }
+
+    /// Queries one page of full blocks from the node. The cursor is the
+    /// height of the last block of the previous page.
+    pub async fn fetch_blocks(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> Result<PaginatedResult<FullBlock, String>> {
+        self.require_node("blocks")?.fetch_blocks(request).await
+    }
+
+    /// Queries one page of block ids from the node.
+    pub async fn fetch_block_ids(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> Result<PaginatedResult<BlockIdAndHeight, String>> {
+        self.require_node("block ids")?
+            .fetch_block_ids(request)
+            .await
+    }
+
+    /// Pushes the ids of all blocks below `height` that aren't in `roots` yet.
+    ///
+    /// `before_push` is called with the tree as it is right before each block
+    /// id is appended, i.e. when its root is the `prev_root` of that block.
+    pub async fn extend_prev_roots<F>(
+        &self,
+        roots: &mut PrevRoots,
+        height: u32,
+        mut before_push: F,
+    ) -> Result<()>
+    where
+        F: FnMut(u32, &PrevRoots),
+    {
+        loop {
+            let next = roots.next_height();
+            if next.is_some_and(|next| next >= height) {
+                return Ok(());
+            }
+
+            let page = self
+                .fetch_block_ids(PaginationRequest {
+                    cursor: next
+                        .and_then(|next| next.checked_sub(1))
+                        .map(|h| h.to_string()),
+                    results: BLOCK_IDS_PAGE_SIZE,
+                    direction: PageDirection::Forward,
+                })
+                .await?;
+
+            for block in page.results.iter().take_while(|b| b.height.0 < height) {
+                before_push(block.height.0, roots);
+                roots.push(block.height.0, &block.id.clone().into())?;
+            }
+
+            if !page.has_next_page || page.results.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Like [`Self::validate_block`], but keeps errors and the time spent in
+    /// the returned outcome.
+    pub async fn validate_height(&self, height: u32) -> BlockOutcome {
+        if self.headers_only {
+            let mut outcomes = std::pin::pin!(self.validate_light_stream(height, height));
+            return match outcomes.next().await {
+                Some(Ok(outcome)) => outcome,
+                Some(Err(e)) => BlockOutcome {
+                    height,
+                    result: Err(e),
+                    duration: Duration::ZERO,
+                    timing: None,
+                },
+                None => missing_block(height),
+            };
+        }
+
+        let started = Instant::now();
+        let mut timing = BlockTiming::default();
+        let result = self.validate_block_timed(height, &mut timing).await;
+        BlockOutcome {
+            height,
+            result,
+            duration: started.elapsed(),
+            timing: self.timing.then_some(timing),
+        }
+    }
+
+    /// Fetches the block at `height` and proves that the transaction `tx_id`
+    /// is part of its transactions root.
+    pub async fn prove_tx_inclusion(&self, height: u32, tx_id: &Bytes32) -> Result<MerkleProof> {
+        let block = self.fetch_block(height).await?;
+        MerkleProof::build(&block, tx_id)
+    }
+
+    /// Fetches the block at `height` and builds the [`ProofBundle`] of the
+    /// transaction `tx_id` and its receipts.
+    pub async fn prove_transaction(&self, height: u32, tx_id: &Bytes32) -> Result<ProofBundle> {
+        let block = self.fetch_block(height).await?;
+        ProofBundle::build(&block, tx_id)
+    }
+
+    /// Looks up the height of the block the transaction `tx_id` is part of
+    /// on the node.
+    pub async fn fetch_transaction_height(&self, tx_id: &Bytes32) -> Result<u32> {
+        self.require_node("transaction status")?
+            .fetch_transaction_height(*tx_id)
+            .await?
+            .ok_or_else(|| ValidationError::TransactionNotIncluded {
+                tx_id: tx_id.to_string(),
+            })
+    }
+
+    /// Looks up the block of the transaction `tx_id` on the node and
+    /// validates the transaction in it, see [`Self::validate_transaction_at`].
+    pub async fn validate_transaction(&self, tx_id: &Bytes32) -> Result<BlockReport> {
+        let height = self.fetch_transaction_height(tx_id).await?;
+        self.validate_transaction_at(height, tx_id).await
+    }
+
+    /// Fetches the block at `height` and validates the transaction `tx_id`
+    /// in it: its id, its receipts root and its inclusion in the
+    /// transactions root. The report only has the checks of the transaction.
+    pub async fn validate_transaction_at(
+        &self,
+        height: u32,
+        tx_id: &Bytes32,
+    ) -> Result<BlockReport> {
+        let block = self.fetch_block(height).await?;
+        let tx = block
+            .transactions
+            .iter()
+            .find(|tx| Bytes32::from(tx.id.clone()) == *tx_id)
+            .ok_or_else(|| ValidationError::TransactionNotFound {
+                height,
+                tx_id: tx_id.to_string(),
+            })?;
+        let params = self.block_consensus_parameters(&block).await?;
+        let chain_id = params.map(|params| params.chain_id()).or(self.chain_id);
+
+        let served_id = tx.id.to_string();
+        let of_tx = |result: &CheckResult| result.tx_id.as_deref() == Some(served_id.as_str());
+        let mut checks: Vec<_> = chain_id
+            .map(|chain_id| checks::tx_ids(&block, &chain_id))
+            .unwrap_or_default();
+        checks.extend(checks::receipt_roots(&block));
+        checks.retain(of_tx);
+        checks.push(checks::tx_inclusion(&block, tx_id));
+        Ok(self.acknowledge(BlockReport {
+            height,
+            block_id: block.id.to_string(),
+            checks,
+            fail_on: self.fail_on,
+        }))
+    }
+
+    /// Fetches the block at `height` and runs every validation on it.
+    pub async fn validate_block(&self, height: u32) -> Result<BlockReport> {
+        self.validate_block_timed(height, &mut BlockTiming::default())
+            .await
+    }
+
+    async fn validate_block_timed(
+        &self,
+        height: u32,
+        timing: &mut BlockTiming,
+    ) -> Result<BlockReport> {
+        let started = Instant::now();
+        let block = self.fetch_block(height).await?;
+        timing.fetch = started.elapsed();
+
+        let started = Instant::now();
+        let mut calculated_prev_root = None;
+        if self.checks_prev_root() {
+            let mut roots = PrevRoots::new();
+            self.extend_prev_roots(&mut roots, height, |_, _| {})
+                .await?;
+            calculated_prev_root = roots.expected_prev_root(height);
+        }
+        timing.queries = started.elapsed();
+
+        self.validate_timed(&block, calculated_prev_root, timing)
+            .await
+    }
+
+    /// Runs every enabled validation on an already fetched block, including
+    /// the ones that need more data from the node.
+    ///
+    /// `calculated_prev_root` is only used when the prev root check is enabled.
+    pub async fn validate_fetched_block(
+        &self,
+        block: &FullBlock,
+        calculated_prev_root: Option<Bytes32>,
+    ) -> Result<BlockReport> {
+        self.validate_timed(block, calculated_prev_root, &mut BlockTiming::default())
+            .await
+    }
+
+    /// Like [`Self::validate_fetched_block`], but keeps errors and the time
+    /// spent in the returned outcome. `fetch` is the time it took to fetch
+    /// the block.
+    pub async fn validate_fetched_outcome(
+        &self,
+        block: &FullBlock,
+        calculated_prev_root: Option<Bytes32>,
+        fetch: Duration,
+    ) -> BlockOutcome {
+        let started = Instant::now();
+        let mut timing = BlockTiming {
+            fetch,
+            ..BlockTiming::default()
+        };
+        let result = self
+            .validate_timed(block, calculated_prev_root, &mut timing)
+            .await;
+        BlockOutcome {
+            height: block.height(),
+            result,
+            duration: started.elapsed(),
+            timing: self.timing.then_some(timing),
+        }
+    }
+
+    /// Validates `block`, adding the time spent on each phase to `timing`.
+    #[tracing::instrument(name = "block", skip_all, fields(block_height = block.height()))]
+    async fn validate_timed(
+        &self,
+        block: &FullBlock,
+        calculated_prev_root: Option<Bytes32>,
+        timing: &mut BlockTiming,
+    ) -> Result<BlockReport> {
+        if self.timing {
+            let started = Instant::now();
+            checks::decode(block);
+            timing.parse += started.elapsed();
+        }
+
+        let params = self.block_consensus_parameters(block).await?;
+
+        let started = Instant::now();
+        let mut report = self.validate_with_parameters(block, params.as_ref());
+        if self.checks_prev_root() {
+            let results = checks::prev_root(block, calculated_prev_root);
+            report.checks.extend(
+                self.checks
+                    .rate(registry::PREV_ROOT, results.into_iter().collect()),
+            );
+        }
+        timing.merkle += started.elapsed();
+
+        let started = Instant::now();
+        let queried = self.validate_queried(block, &mut report).await;
+        timing.queries += started.elapsed();
+        queried?;
+        let report = self.acknowledge(report);
+
+        for failure in report.failures() {
+            tracing::debug!(
+                check = %failure.check,
+                tx_id = failure.tx_id,
+                expected = failure.expected,
+                actual = failure.actual,
+                message = failure.message,
+                "check failed"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Runs the checks of `block` that query more than the block itself.
+    async fn validate_queried(&self, block: &FullBlock, report: &mut BlockReport) -> Result<()> {
+        if self.checks_event_inbox() {
+            let results = self.validate_event_inbox(block).await?;
+            report
+                .checks
+                .extend(self.checks.rate(registry::EVENT_INBOX, results));
+        }
+        if let Some(l1) = self
+            .l1
+            .as_ref()
+            .filter(|_| self.checks.is_enabled(registry::L1))
+        {
+            let results = self.validate_l1(l1, block).await?;
+            report
+                .checks
+                .extend(self.checks.rate(registry::L1, results));
+        }
+        if self.checks.is_enabled(registry::ENDPOINT_AGREEMENT) {
+            let results = self.cross_check(block).await;
+            report
+                .checks
+                .extend(self.checks.rate(registry::ENDPOINT_AGREEMENT, results));
+        }
+        Ok(())
+    }
+
+    /// Fetches `block` from every cross-check endpoint and compares it with
+    /// the block served by the main endpoint.
+    async fn cross_check(&self, block: &FullBlock) -> Vec<CheckResult> {
+        let fetches = self.cross_check.iter().map(|(endpoint, node)| async move {
+            match node.fetch_block(block.height()).await {
+                Ok(other) => checks::endpoint_agreement(block, endpoint, &other),
+                Err(e) => vec![CheckResult::from_error(Check::EndpointAgreement, &e)
+                    .with_message(format!("{endpoint}: {e}"))
+                    .with_endpoint(endpoint)],
+            }
+        });
+
+        future::join_all(fetches)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Runs every validation that only needs the block itself.
+    pub fn validate_full_block(&self, block: &FullBlock) -> BlockReport {
+        self.acknowledge(self.validate_with_parameters(block, None))
+    }
+
+    /// [`Self::validate_full_block`], with the checks that need the
+    /// consensus parameters of the block when they are known.
+    fn validate_with_parameters(
+        &self,
+        block: &FullBlock,
+        params: Option<&ConsensusParameters>,
+    ) -> BlockReport {
+        let chain_id = params.map(|params| params.chain_id()).or(self.chain_id);
+        let report = self.checks.run(&CheckContext {
+            block,
+            chain_id: chain_id.as_ref(),
+            authority: self.poa_authority.as_ref(),
+            params,
+        });
+        BlockReport {
+            fail_on: self.fail_on,
+            ..report
+        }
+    }
+
+    /// `report`, with the failures that are known anomalies acknowledged.
+    fn acknowledge(&self, mut report: BlockReport) -> BlockReport {
+        if let Some(anomalies) = &self.known_anomalies {
+            anomalies.acknowledge(&mut report);
+        }
+        report
+    }
+
+    /// Recomputes the transactions root from the raw payloads and compares it
+    /// with `header.transactions_root`.
+    pub fn validate_tx_root(block: &FullBlock) -> CheckResult {
+        checks::tx_root(block)
+    }
+
+    /// Recomputes the receipts root of every transaction that commits to one.
+    pub fn validate_receipt_roots(block: &FullBlock) -> Vec<CheckResult> {
+        checks::receipt_roots(block)
+    }
+
+    /// Validates every block in `from..=to`, paging through the blocks
+    /// connection. Query errors abort the range, unless the validator
+    /// [keeps going](Self::with_keep_going), validation errors don't.
+    ///
+    /// Up to [`Self::concurrency`] pages are fetched and validated at the same
+    /// time; outcomes are always returned in height order.
+    pub async fn validate_range(&self, from: u32, to: u32) -> Result<RangeValidation> {
+        let mut range = RangeValidation::default();
+        self.validate_range_with(from, to, |outcome| range.outcomes.push(outcome))
+            .await?;
+        Ok(range)
+    }
+
+    /// Like [`Self::validate_range`], but hands every outcome to `on_outcome`
+    /// in height order as soon as its page is validated, e.g. to record
+    /// progress while a long range is being scanned.
+    pub async fn validate_range_with<F>(&self, from: u32, to: u32, mut on_outcome: F) -> Result<()>
+    where
+        F: FnMut(BlockOutcome),
+    {
+        let mut outcomes = std::pin::pin!(self.validate_stream(from, to));
+        while let Some(outcome) = outcomes.try_next().await? {
+            on_outcome(outcome);
+        }
+
+        Ok(())
+    }
+
+    /// Validates every block in `from..=to` like [`Self::validate_range`],
+    /// as a stream of outcomes in height order.
+    ///
+    /// Pages are only fetched as the stream is polled, at most
+    /// [`Self::concurrency`] ahead of the consumer, and dropping the stream
+    /// cancels the pages in flight. A query error is the last item.
+    pub fn validate_stream(
+        &self,
+        from: u32,
+        to: u32,
+    ) -> impl Stream<Item = Result<BlockOutcome>> + Send + '_ {
+        if self.headers_only {
+            return self.validate_light_stream(from, to).left_stream();
+        }
+
+        stream::once(self.expected_prev_roots(from, to))
+            .map_ok(move |expected| {
+                let expected = expected.map(Arc::new);
+                self.page_stream(from, to)
+                    .map(move |(start, end)| {
+                        let expected = expected.clone();
+                        async move { self.validate_page(start, end, expected.as_deref()).await }
+                    })
+                    .buffered(self.concurrency)
+                    .map_ok(|outcomes| stream::iter(outcomes.into_iter().map(Ok)))
+                    .try_flatten()
+            })
+            .try_flatten()
+            .scan(false, |failed, outcome| {
+                let item = (!*failed).then_some(outcome);
+                *failed = matches!(item, Some(Err(_)));
+                future::ready(item)
+            })
+            .right_stream()
+    }
+
+    /// The pages of `from..=to`. Under a memory budget, each page is sized
+    /// when it is about to be fetched, from the blocks fetched so far.
+    fn page_stream(&self, from: u32, to: u32) -> impl Stream<Item = (u32, u32)> + Send + '_ {
+        stream::unfold(Some(from), move |start| async move {
+            let start = start.filter(|&start| start <= to)?;
+            let size = match &self.memory {
+                Some(budget) => budget.blocks_per_page(self.page_size, self.concurrency),
+                None => self.page_size,
+            };
+            let end = start.saturating_add(size - 1).min(to);
+            Some(((start, end), end.checked_add(1)))
+        })
+    }
+
+    /// [`Self::validate_stream`] when [validating headers
+    /// only](Self::with_headers_only). Pages are fetched concurrently, but
+    /// checked in height order to extend the `prev_root` tree with the ids
+    /// of the headers.
+    fn validate_light_stream(
+        &self,
+        from: u32,
+        to: u32,
+    ) -> impl Stream<Item = Result<BlockOutcome>> + Send + '_ {
+        stream::once(self.prev_roots_below(from))
+            .map_ok(move |roots| {
+                stream::iter(pages(from, to, self.page_size))
+                    .map(move |(start, end)| self.fetch_light_page(start, end))
+                    .buffered(self.concurrency)
+                    .scan(roots, move |roots, page| {
+                        let outcomes = page.map(|(blocks, fetch)| {
+                            let outcomes: Vec<_> = blocks
+                                .into_iter()
+                                .map(|(height, block)| {
+                                    self.validate_light(height, block, fetch, roots)
+                                })
+                                .collect();
+                            stream::iter(outcomes.into_iter().map(Ok))
+                        });
+                        future::ready(Some(outcomes))
+                    })
+                    .try_flatten()
+            })
+            .try_flatten()
+            .scan(false, |failed, outcome| {
+                let item = (!*failed).then_some(outcome);
+                *failed = matches!(item, Some(Err(_)));
+                future::ready(item)
+            })
+    }
+
+    /// The `prev_root` tree up to `height`, when the check is enabled.
+    async fn prev_roots_below(&self, height: u32) -> Result<Option<PrevRoots>> {
+        if !self.checks_prev_root() {
+            return Ok(None);
+        }
+
+        let mut roots = PrevRoots::new();
+        if height > 0 {
+            self.extend_prev_roots(&mut roots, height, |_, _| {})
+                .await?;
+        }
+        Ok(Some(roots))
+    }
+
+    /// Fetches the blocks in `from..=to` without their transactions, with
+    /// each block's share of the time it took. Every height of the page is
+    /// returned, with an error if its block couldn't be fetched. Query
+    /// errors abort the page, unless the validator keeps going.
+    async fn fetch_light_page(
+        &self,
+        from: u32,
+        to: u32,
+    ) -> Result<(Vec<(u32, Result<LightBlock>)>, Duration)> {
+        let started = Instant::now();
+        let fetched = self
+            .cancellable(self.source().fetch_light_range(from, to))
+            .await;
+        let mut blocks: BTreeMap<_, _> = match fetched {
+            Ok(blocks) => blocks.into_iter().map(|b| (b.height(), b)).collect(),
+            Err(e) if self.keep_going && !e.is_cancelled() => {
+                tracing::warn!(
+                    "failed to fetch headers {from}..={to}, fetching them one by one: {e}"
+                );
+                let mut page = vec![];
+                for height in from..=to {
+                    let block = self
+                        .cancellable(self.source().fetch_light_range(height, height))
+                        .await
+                        .and_then(|blocks| {
+                            blocks
+                                .into_iter()
+                                .find(|b| b.height() == height)
+                                .ok_or(ValidationError::BlockNotFound { height })
+                        });
+                    if matches!(&block, Err(e) if e.is_cancelled()) {
+                        return Err(ValidationError::Cancelled);
+                    }
+                    page.push((height, block));
+                }
+                let fetch = started.elapsed() / page.len().max(1) as u32;
+                return Ok((page, fetch));
+            }
+            Err(e) => return Err(e),
+        };
+        let fetch = started.elapsed() / blocks.len().max(1) as u32;
+        let page = (from..=to)
+            .map(|height| {
+                let block = blocks
+                    .remove(&height)
+                    .ok_or(ValidationError::BlockNotFound { height });
+                (height, block)
+            })
+            .collect();
+        Ok((page, fetch))
+    }
+
+    /// Checks the header of `block`, then appends its id to `roots` when it
+    /// directly follows the blocks of the tree.
+    fn validate_light(
+        &self,
+        height: u32,
+        block: Result<LightBlock>,
+        fetch: Duration,
+        roots: &mut Option<PrevRoots>,
+    ) -> BlockOutcome {
+        let started = Instant::now();
+        let result = block.and_then(|block| self.check_light(&block, roots));
+        let duration = started.elapsed();
+        BlockOutcome {
+            height,
+            result,
+            duration,
+            timing: self.timing.then_some(BlockTiming {
+                fetch,
+                merkle: duration,
+                ..BlockTiming::default()
+            }),
+        }
+    }
+
+    fn check_light(
+        &self,
+        block: &LightBlock,
+        roots: &mut Option<PrevRoots>,
+    ) -> Result<BlockReport> {
+        let mut checks = vec![];
+        if self.checks.is_enabled(registry::BLOCK_ID) {
+            let results = checks::light_block_id(block);
+            checks.extend(self.checks.rate(registry::BLOCK_ID, results));
+        }
+        if let Some(authority) = self
+            .poa_authority
+            .as_ref()
+            .filter(|_| self.checks.is_enabled(registry::POA_SIGNATURE))
+        {
+            let results = consensus::light_poa_signature(block, authority);
+            checks.extend(
+                self.checks
+                    .rate(registry::POA_SIGNATURE, results.into_iter().collect()),
+            );
+        }
+        if let Some(roots) = roots {
+            let height = block.height();
+            let results = checks::light_prev_root(block, roots.expected_prev_root(height));
+            checks.extend(
+                self.checks
+                    .rate(registry::PREV_ROOT, results.into_iter().collect()),
+            );
+
+            let follows = match roots.next_height() {
+                Some(next) => next == height,
+                None => matches!(block.consensus, Consensus::Genesis(_)),
+            };
+            if follows {
+                roots.push(height, &block.id.clone().into())?;
+            }
+        }
+        Ok(self.acknowledge(BlockReport {
+            height: block.height(),
+            block_id: block.id.to_string(),
+            checks,
+            fail_on: self.fail_on,
+        }))
+    }
+
+    /// The `prev_root` every block of `from..=to` is expected to have, when
+    /// the check is enabled.
+    async fn expected_prev_roots(
+        &self,
+        from: u32,
+        to: u32,
+    ) -> Result<Option<HashMap<u32, Option<Bytes32>>>> {
+        if !self.checks_prev_root() {
+            return Ok(None);
+        }
+
+        let mut roots = PrevRoots::new();
+        let mut expected = HashMap::new();
+        self.extend_prev_roots(&mut roots, to.saturating_add(1), |height, roots| {
+            if height >= from {
+                expected.insert(height, roots.expected_prev_root(height));
+            }
+        })
+        .await?;
+        Ok(Some(expected))
+    }
+
+    /// Fetches every block in `from..=to` without validating it, handing
+    /// them to `on_block` in height order. Pages are fetched like in
+    /// [`Self::validate_range_with`].
+    pub async fn fetch_range_with<F>(&self, from: u32, to: u32, mut on_block: F) -> Result<()>
+    where
+        F: FnMut(FullBlock) -> Result<()>,
+    {
+        let mut pages = stream::iter(pages(from, to, self.page_size))
+            .map(|(start, end)| self.fetch_page(start, end))
+            .buffered(self.concurrency);
+
+        while let Some(blocks) = pages.try_next().await? {
+            blocks.into_iter().try_for_each(&mut on_block)?;
+        }
+
+        Ok(())
+    }
+
+    /// Fetches the blocks in `from..=to`, with a single page query from a
+    /// node unless it serves fewer blocks per page.
+    async fn fetch_page(&self, from: u32, to: u32) -> Result<Vec<FullBlock>> {
+        self.cancellable(self.source().fetch_range(from, to)).await
+    }
+
+    /// Fetches the blocks in `from..=to` with a single page query and
+    /// validates them.
+    async fn validate_page(
+        &self,
+        from: u32,
+        to: u32,
+        expected_prev_roots: Option<&HashMap<u32, Option<Bytes32>>>,
+    ) -> Result<Vec<BlockOutcome>> {
+        let mut outcomes = vec![];
+        let mut next = from;
+        let mut reservation = match &self.memory {
+            Some(budget) => Some(budget.reserve(to - from + 1).await),
+            None => None,
+        };
+        let started = Instant::now();
+        let blocks = match self.fetch_page(from, to).await {
+            Ok(blocks) => blocks,
+            Err(e) if self.keep_going && !e.is_cancelled() => {
+                tracing::warn!(
+                    "failed to fetch blocks {from}..={to}, validating them one by one: {e}"
+                );
+                return self.validate_heights(from, to).await;
+            }
+            Err(e) => return Err(e),
+        };
+        let fetch = started.elapsed() / blocks.len().max(1) as u32;
+        if let Some(budget) = &self.memory {
+            blocks.iter().for_each(|block| budget.observe(block));
+        }
+        // Each block is dropped once validated, rather than with the page.
+        for block in blocks {
+            // Heights the source skipped are reported rather than silently
+            // left out of the range.
+            outcomes.extend((next..block.height()).map(missing_block));
+            next = block.height().saturating_add(1);
+
+            let calculated_prev_root = expected_prev_roots
+                .and_then(|expected| expected.get(&block.height()).copied().flatten());
+            outcomes.push(
+                self.validate_fetched_outcome(&block, calculated_prev_root, fetch)
+                    .await,
+            );
+            if let Some(reservation) = &mut reservation {
+                reservation.release(memory::estimated_size(&block));
+            }
+        }
+        if next <= to {
+            outcomes.extend((next..=to).map(missing_block));
+        }
+
+        Ok(outcomes)
+    }
+
+    /// Validates the blocks in `from..=to` with a query each, in height
+    /// order, until the validator is cancelled.
+    async fn validate_heights(&self, from: u32, to: u32) -> Result<Vec<BlockOutcome>> {
+        let mut outcomes = vec![];
+        for height in from..=to {
+            let outcome = self.validate_height(height).await;
+            if matches!(&outcome.result, Err(e) if e.is_cancelled()) {
+                return Err(ValidationError::Cancelled);
+            }
+            outcomes.push(outcome);
+        }
+        Ok(outcomes)
+    }
+
+    /// Compares the DA height of `block` and the block committed for its
+    /// commit interval with the chain state on L1.
+    ///
+    /// The commitment is checked when `block` is the committed block, or
+    /// when the committed block isn't served by the node although the node
+    /// is past the interval. An interval without a commit yet, or whose
+    /// commit slot was reused by a later interval, isn't checked.
+    async fn validate_l1(&self, l1: &L1ChainState, block: &FullBlock) -> Result<Vec<CheckResult>> {
+        let da_height = block.header.da_height.0;
+        let latest = l1.latest_block_at_least(da_height).await?;
+        let mut results = vec![CheckResult::at_most(Check::L1DaHeight, latest, da_height)];
+
+        let interval = l1.commit_interval().await?;
+        let commit_height = u64::from(block.height()) / interval;
+        let committed = l1.block_hash_at_commit(commit_height).await?;
+        let block_id: Bytes32 = block.id.clone().into();
+        if committed == block_id {
+            results.push(CheckResult::compare(Check::L1Commit, committed, block_id));
+            return Ok(results);
+        }
+        let Some(node) = self.node() else {
+            return Ok(results);
+        };
+        if committed == Bytes32::zeroed() || l1.is_served(&committed) {
+            return Ok(results);
+        }
+
+        if node.fetch_header_by_id(committed).await?.is_some() {
+            l1.mark_served(committed);
+            return Ok(results);
+        }
+        let (first, last) = (commit_height * interval, (commit_height + 1) * interval - 1);
+        if u64::from(node.latest_height().await?) >= last {
+            results.push(
+                CheckResult::compare(Check::L1Commit, committed, block_id).with_message(format!(
+                    "the block committed on L1 for blocks {first}..={last} isn't served by the node"
+                )),
+            );
+        }
+        Ok(results)
+    }
+
+    /// Fetches the parent header and the relayed transaction statuses needed
+    /// to validate `header.event_inbox_root`.
+    async fn validate_event_inbox(&self, block: &FullBlock) -> Result<Vec<CheckResult>> {
+        if matches!(block.consensus, Consensus::Genesis(_)) {
+            return Ok(vec![]);
+        }
+
+        let Some(parent_height) = block.height().checked_sub(1) else {
+            return Ok(vec![CheckResult::error(
+                Check::EventInboxRoot,
+                "block 0 isn't a genesis block, it has no parent to relay events after",
+            )]);
+        };
+        let parent = self.fetch_header(parent_height).await?;
+        let events = self.l1_events.as_deref();
+        let mut results: Vec<_> = checks::event_inbox_root(block, parent.da_height.0, events)
+            .into_iter()
+            .collect();
+
+        if let Some(events) = events {
+            results.extend(
+                self.validate_relayed_transactions(block, parent.da_height.0, events)
+                    .await?,
+            );
+        }
+
+        Ok(results)
+    }
+
+    /// Checks that forced transactions included by the block and reported as
+    /// failed by the node failed in this block.
+    async fn validate_relayed_transactions(
+        &self,
+        block: &FullBlock,
+        parent_da_height: u64,
+        events: &EventInbox,
+    ) -> Result<Vec<CheckResult>> {
+        let da_height = block.header.da_height.0;
+        let relayed_txs = events
+            .events_between(parent_da_height, da_height)
+            .filter(|event| matches!(event, RelayedEvent::Transaction { .. }));
+
+        let mut results = vec![];
+        for event in relayed_txs {
+            let id = event.hash();
+            let status = self
+                .require_node("relayed transaction status")?
+                .fetch_relayed_transaction_status(id)
+                .await?;
+
+            if let Some(RelayedTransactionStatus::Failed(failed)) = status {
+                results.push(
+                    CheckResult::compare(Check::RelayedTx, block.height(), failed.block_height.0)
+                        .with_tx_id(id)
+                        .with_message(format!("failure block height: {}", failed.failure)),
+                );
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+/// Bounds of the pages of `size` blocks `from..=to` is fetched in.
+fn pages(from: u32, to: u32, size: u32) -> impl Iterator<Item = (u32, u32)> {
+    (from..=to)
+        .step_by(size as usize)
+        .map(move |start| (start, start.saturating_add(size - 1).min(to)))
+}
+
+fn missing_block(height: u32) -> BlockOutcome {
+    BlockOutcome {
+        height,
+        result: Err(ValidationError::BlockNotFound { height }),
+        duration: Duration::ZERO,
+        timing: None,
+    }
+}
@@ -0,0 +1,22 @@
+// src/wasm.rs
+//! `wasm-bindgen` bindings, so explorers can run the same validation in the
+//! browser on blocks they already fetched. Requires the `wasm` feature.
+
+use fuel_types::ChainId;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::checks;
+use crate::dump::BlockDump;
+
+/// Validates a block in the [`BlockDump`] JSON format, as written by
+/// `export`, and returns its [`BlockReport`](crate::BlockReport) as a plain
+/// object. Transaction ids are only checked when `chain_id` is given.
+#[wasm_bindgen(js_name = validateBlockJson)]
+pub fn validate_block_json(bytes: &[u8], chain_id: Option<u64>) -> Result<JsValue, JsError> {
+    let block: BlockDump = serde_json::from_slice(bytes)?;
+    let chain_id = chain_id.map(ChainId::new);
+    let report = checks::validate_block(&block.into(), chain_id.as_ref(), None);
+
+    Ok(report.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
@@ -0,0 +1,53 @@
+// tests/bisect.rs
+//! The first block two endpoints disagree on.
+
+mod common;
+
+use fuel_root_validation::{bisect::bisect, Check, FullBlock, MemorySource};
+
+use common::{block_with_transactions, valid_block};
+
+/// A chain of blocks `1..=100`, which forks at `fork` when given.
+fn chain(fork: Option<u32>) -> MemorySource {
+    MemorySource::new((1..=100).map(|height| {
+        let block = match fork {
+            Some(fork) if height >= fork => block_with_transactions(height, 2),
+            _ => valid_block(height),
+        };
+        FullBlock::from(block)
+    }))
+}
+
+#[tokio::test]
+async fn finds_the_first_divergent_block() {
+    let (source, other) = (chain(None), chain(Some(37)));
+
+    let bisection = bisect(&source, &other, "other", 1, 100).await.unwrap();
+
+    let divergence = bisection.divergence.unwrap();
+    assert_eq!(divergence.height, 37);
+    assert!(divergence
+        .differences
+        .iter()
+        .all(|difference| difference.check == Check::EndpointAgreement && !difference.passed));
+    assert!(bisection.probes.len() <= 8, "{:?}", bisection.probes);
+}
+
+#[tokio::test]
+async fn divergence_can_start_at_the_first_block() {
+    let (source, other) = (chain(None), chain(Some(1)));
+
+    let bisection = bisect(&source, &other, "other", 1, 100).await.unwrap();
+
+    assert_eq!(bisection.divergence.unwrap().height, 1);
+}
+
+#[tokio::test]
+async fn agreeing_endpoints_only_compare_the_last_block() {
+    let (source, other) = (chain(None), chain(None));
+
+    let bisection = bisect(&source, &other, "other", 1, 100).await.unwrap();
+
+    assert!(bisection.divergence.is_none());
+    assert_eq!(bisection.probes, [100]);
+}
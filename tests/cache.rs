@@ -0,0 +1,88 @@
+// tests/cache.rs
+//! Blocks kept on disk between runs, served back to the endpoints that
+//! served them.
+
+mod common;
+
+use fuel_root_validation::{BlockCache, BlockDump, FullBlock};
+use fuel_types::Bytes32;
+
+use common::{temp_dir, valid_block};
+
+const NODE: &str = "http://node.example/v1/graphql";
+const OTHER_NODE: &str = "http://other.example/v1/graphql";
+
+fn json(block: &FullBlock) -> serde_json::Value {
+    serde_json::to_value(BlockDump::from(block)).unwrap()
+}
+
+#[test]
+fn cached_blocks_outlive_the_cache() {
+    let dir = temp_dir("cache-reopen");
+    let block = FullBlock::from(valid_block(10));
+    BlockCache::open(&dir).unwrap().insert_block(NODE, &block);
+
+    let cache = BlockCache::open(&dir).unwrap();
+
+    let cached = cache.block(NODE, 10).expect("the block is cached");
+    assert_eq!(json(&cached), json(&block));
+    assert_eq!(cache.block_id(NODE, 10), Some(block.id.clone().into()));
+    assert!(cache.block(NODE, 11).is_none());
+    assert!(cache.size() > 0);
+}
+
+#[test]
+fn blocks_are_only_served_to_the_endpoints_that_served_them() {
+    let dir = temp_dir("cache-endpoints");
+    let cache = BlockCache::open(&dir).unwrap();
+    let block = valid_block(10);
+    cache.insert_block(NODE, &FullBlock::from(block.clone()));
+    assert!(cache.block(OTHER_NODE, 10).is_none());
+
+    // Same id, different transactions.
+    let mut tampered = block.clone();
+    tampered.transactions.clear();
+    cache.insert_block(OTHER_NODE, &FullBlock::from(tampered));
+    assert!(cache.block(OTHER_NODE, 10).is_none());
+
+    cache.insert_block(OTHER_NODE, &FullBlock::from(block.clone()));
+    let cached = cache
+        .block(OTHER_NODE, 10)
+        .expect("the same block is shared");
+    assert_eq!(json(&cached), json(&FullBlock::from(block)));
+}
+
+#[test]
+fn block_ids_are_indexed_per_endpoint() {
+    let dir = temp_dir("cache-ids");
+    let id = Bytes32::new([7; 32]);
+    BlockCache::open(&dir).unwrap().insert_block_id(NODE, 3, id);
+
+    let cache = BlockCache::open(&dir).unwrap();
+
+    assert_eq!(cache.block_id(NODE, 3), Some(id));
+    assert_eq!(cache.block_id(OTHER_NODE, 3), None);
+    assert!(cache.block(NODE, 3).is_none(), "only the id is known");
+}
+
+#[test]
+fn least_recently_used_blocks_are_evicted() {
+    let dir = temp_dir("cache-eviction");
+    let sizing = BlockCache::open(temp_dir("cache-eviction-sizing")).unwrap();
+    sizing.insert_block(NODE, &FullBlock::from(valid_block(10)));
+    let block_size = sizing.size();
+
+    let cache = BlockCache::open(&dir)
+        .unwrap()
+        .with_max_size(block_size * 2);
+    cache.insert_block(NODE, &FullBlock::from(valid_block(10)));
+    cache.insert_block(NODE, &FullBlock::from(valid_block(11)));
+    // Reading block 10 makes block 11 the least recently used.
+    assert!(cache.block(NODE, 10).is_some());
+    cache.insert_block(NODE, &FullBlock::from(valid_block(12)));
+
+    assert!(cache.size() <= block_size * 2);
+    assert!(cache.block(NODE, 11).is_none());
+    assert!(cache.block(NODE, 10).is_some());
+    assert!(cache.block(NODE, 12).is_some());
+}
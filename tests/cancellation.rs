@@ -0,0 +1,137 @@
+// tests/cancellation.rs
+//! Queries that time out, and validation stopped by its cancellation token.
+
+mod common;
+
+use std::{future, time::Duration};
+
+use fuel_root_validation::{
+    error::Result, follow, BlockSource, BlockValidator, FollowConfig, FollowEvent, FullBlock,
+    GraphqlSource, MemorySource, RetryPolicy, ValidationError,
+};
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+use common::valid_block;
+
+/// Serves a node that accepts connections but never answers.
+async fn stuck_node() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut connections = vec![];
+        while let Ok((stream, _)) = listener.accept().await {
+            connections.push(stream);
+        }
+    });
+    url
+}
+
+/// A source whose queries never complete.
+#[derive(Debug)]
+struct Hanging;
+
+#[async_trait::async_trait]
+impl BlockSource for Hanging {
+    async fn fetch_block(&self, _: u32) -> Result<FullBlock> {
+        future::pending().await
+    }
+
+    async fn fetch_range(&self, _: u32, _: u32) -> Result<Vec<FullBlock>> {
+        future::pending().await
+    }
+
+    async fn latest_height(&self) -> Result<u32> {
+        future::pending().await
+    }
+}
+
+fn cancel_after(cancel: &CancellationToken, delay: Duration) {
+    let cancel = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        cancel.cancel();
+    });
+}
+
+#[tokio::test]
+async fn unanswered_queries_time_out_and_are_retried() {
+    let node = GraphqlSource::new(stuck_node().await)
+        .unwrap()
+        .with_timeout(Duration::from_millis(50))
+        .with_retry_policy(RetryPolicy {
+            max_attempts: 2,
+            initial_backoff: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        });
+
+    let error = node.latest_height().await.unwrap_err();
+
+    let ValidationError::Query {
+        attempts, source, ..
+    } = error
+    else {
+        panic!("unexpected {error:?}");
+    };
+    assert_eq!(attempts, 2);
+    assert_eq!(source.kind(), std::io::ErrorKind::TimedOut);
+}
+
+#[tokio::test]
+async fn cancelled_queries_stop_waiting_for_the_node() {
+    let cancel = CancellationToken::new();
+    let node = GraphqlSource::new(stuck_node().await)
+        .unwrap()
+        .with_cancellation(cancel.clone());
+    cancel_after(&cancel, Duration::from_millis(50));
+
+    let error = tokio::time::timeout(Duration::from_secs(10), node.latest_height())
+        .await
+        .expect("the query wasn't cancelled")
+        .unwrap_err();
+
+    assert!(error.is_cancelled(), "{error:?}");
+}
+
+#[tokio::test]
+async fn cancelling_stops_a_range_scan() {
+    let cancel = CancellationToken::new();
+    let validator = BlockValidator::from_source(Hanging)
+        .with_keep_going(true)
+        .with_cancellation(cancel.clone());
+    cancel_after(&cancel, Duration::from_millis(50));
+
+    let error = tokio::time::timeout(Duration::from_secs(10), validator.validate_range(1, 100))
+        .await
+        .expect("the range wasn't cancelled")
+        .unwrap_err();
+
+    assert!(error.is_cancelled(), "{error:?}");
+}
+
+#[tokio::test]
+async fn cancelling_shuts_the_follower_down() {
+    let cancel = CancellationToken::new();
+    let validator =
+        BlockValidator::from_source(MemorySource::new([FullBlock::from(valid_block(10))]))
+            .with_cancellation(cancel.clone());
+    let config = FollowConfig {
+        start_height: Some(10),
+        subscribe: false,
+        ..FollowConfig::default()
+    };
+
+    let mut heights = vec![];
+    let following = follow(&validator, config, future::pending(), |event| {
+        if let FollowEvent::Block(outcome) = event {
+            heights.push(outcome.height);
+            cancel.cancel();
+        }
+    });
+    tokio::time::timeout(Duration::from_secs(10), following)
+        .await
+        .expect("the follower didn't shut down")
+        .expect("cancelling isn't an error");
+
+    assert_eq!(heights, [10]);
+}
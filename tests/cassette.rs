@@ -0,0 +1,109 @@
+// tests/cassette.rs
+//! Recording the responses of a node, and answering the same queries from the
+//! recording once the node is gone.
+
+mod common;
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use fuel_root_validation::{BlockSource, Cassette, GraphqlSource};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    task::JoinHandle,
+};
+
+use common::temp_dir;
+
+/// Serves a node answering every query with a latest block at height 7, until
+/// the returned task is aborted. Counts the queries in `queries`.
+async fn node(queries: Arc<AtomicUsize>) -> (String, JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let serving = tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = vec![];
+            let mut buffer = [0; 4096];
+            loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length: ")
+                                .map(str::to_owned)
+                        })
+                        .and_then(|length| length.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+            }
+
+            queries.fetch_add(1, Ordering::SeqCst);
+            let body = r#"{"data":{"chain":{"latestBlock":{"height":"7"}}}}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    (url, serving)
+}
+
+#[tokio::test]
+async fn recorded_responses_are_replayed_without_the_node() {
+    let queries = Arc::new(AtomicUsize::new(0));
+    let (url, serving) = node(queries.clone()).await;
+    let path = temp_dir("cassette").join("cassette.json");
+
+    let recording = Arc::new(Cassette::recording());
+    let live = GraphqlSource::new(url.clone())
+        .unwrap()
+        .with_cassette(recording.clone());
+    assert_eq!(live.latest_height().await.unwrap(), 7);
+    recording.save(&path).unwrap();
+    serving.abort();
+
+    let replay = Arc::new(Cassette::load(&path).unwrap());
+    assert!(replay.is_replaying());
+    assert_eq!(replay.len(), 1);
+    let replayed = GraphqlSource::new(url).unwrap().with_cassette(replay);
+
+    // Queries sent again get the last response.
+    assert_eq!(replayed.latest_height().await.unwrap(), 7);
+    assert_eq!(replayed.latest_height().await.unwrap(), 7);
+    assert_eq!(queries.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn queries_missing_from_the_cassette_fail() {
+    let path = temp_dir("empty-cassette").join("cassette.json");
+    Cassette::recording().save(&path).unwrap();
+
+    let replay = Arc::new(Cassette::load(&path).unwrap());
+    let node = GraphqlSource::new("http://127.0.0.1:1")
+        .unwrap()
+        .with_cassette(replay);
+
+    let error = node.latest_height().await.unwrap_err();
+    assert!(error.to_string().contains("cassette"), "{error}");
+}
+
+#[test]
+fn unreadable_cassettes_are_reported() {
+    let dir = temp_dir("bad-cassette");
+    let path = dir.join("cassette.json");
+    std::fs::write(&path, "not json").unwrap();
+
+    assert!(Cassette::load(&path).is_err());
+    assert!(Cassette::load(&dir.join("missing.json")).is_err());
+}
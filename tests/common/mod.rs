@@ -0,0 +1,207 @@
+// tests/common/mod.rs
+//! Hand-built blocks for the integration tests, consistent with everything
+//! they commit to, and helpers to corrupt them.
+
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+use fuel_core_client::client::schema::Bytes;
+use fuel_merkle::binary::root_calculator::MerkleRootCalculator;
+use fuel_root_validation::{
+    dump::{
+        BlockDump, ConsensusDump, HeaderDump, HeaderVersionDump, ReceiptDump, StatusDump,
+        TransactionDump,
+    },
+    header, FullBlock,
+};
+use fuel_tx::{
+    field::ReceiptsRoot, input, output, policies::Policies, Address, AssetId, Receipt,
+    ScriptExecutionResult, Transaction, TxPointer, UniqueIdentifier,
+};
+use fuel_types::{canonical::Serialize, Bytes32, Bytes64, ChainId, ContractId};
+
+pub const CHAIN_ID: u64 = 0;
+
+/// Contract the blocks mint their fees to.
+pub const COINBASE: ContractId = ContractId::new([4; 32]);
+
+/// A block with one script transaction sending a message to L1, whose header,
+/// id and transaction commit to its actual contents.
+pub fn valid_block(height: u32) -> BlockDump {
+    block_with_transactions(height, 1)
+}
+
+/// [`valid_block`] whose first transaction fails its receipts root.
+pub fn bad_block(height: u32) -> FullBlock {
+    let mut block = valid_block(height);
+    receipts(&mut block, 0)[1].gas_used = Some(1);
+    block.into()
+}
+
+/// A block with `count` script transactions each sending a message to L1,
+/// followed by the mint of their fees, whose header, id and transactions
+/// commit to its actual contents.
+pub fn block_with_transactions(height: u32, count: u32) -> BlockDump {
+    let time = 1_730_000_000 + i64::from(height);
+    let mut transactions = vec![];
+    let mut message_ids = vec![];
+    for index in 0..count {
+        let mut nonce = [height as u8; 32];
+        nonce[28..].copy_from_slice(&index.to_be_bytes());
+        let message = Receipt::message_out(
+            &Bytes32::new(nonce),
+            0,
+            Address::new([1; 32]),
+            Address::new([2; 32]),
+            100,
+            vec![0xde, 0xad, 0xbe, 0xef],
+        );
+        let result = Receipt::script_result(ScriptExecutionResult::Success, 1000);
+
+        let mut tx = Transaction::script(
+            1_000_000,
+            vec![],
+            index.to_be_bytes().to_vec(),
+            Policies::new(),
+            vec![],
+            vec![],
+            vec![],
+        );
+        *tx.receipts_root_mut() = root([message.to_bytes(), result.to_bytes()]);
+        let tx = Transaction::from(tx);
+        message_ids.push(message.message_id().expect("message receipts have an id"));
+
+        transactions.push(TransactionDump {
+            id: tx.id(&ChainId::new(CHAIN_ID)),
+            raw_payload: Bytes(tx.to_bytes()),
+            status: Some(StatusDump::Success {
+                block_height: height,
+                time,
+                program_state: None,
+                receipts: vec![(&message).into(), (&result).into()],
+                total_gas: 1000,
+                total_fee: 1,
+            }),
+        });
+    }
+    transactions.push(mint(
+        height,
+        count,
+        COINBASE,
+        u64::from(count),
+        AssetId::BASE,
+    ));
+
+    let mut block = BlockDump {
+        id: Bytes32::zeroed(),
+        header: HeaderDump {
+            version: HeaderVersionDump::V1,
+            id: Bytes32::zeroed(),
+            da_height: 100,
+            consensus_parameters_version: 1,
+            state_transition_bytecode_version: 1,
+            transactions_count: transactions.len() as u16,
+            message_receipt_count: count,
+            transactions_root: root(transactions.iter().map(|tx| tx.raw_payload.0.clone())),
+            message_outbox_root: root(message_ids),
+            event_inbox_root: root::<Vec<u8>>([]),
+            height,
+            prev_root: Bytes32::new([3; 32]),
+            time,
+            application_hash: Bytes32::zeroed(),
+        },
+        consensus: ConsensusDump::Poa {
+            signature: Bytes64::zeroed(),
+        },
+        transactions,
+    };
+    seal(&mut block);
+    block
+}
+
+/// The mint of `amount` of `asset_id` to `coinbase`, the transaction at
+/// `index` of the block at `height`.
+pub fn mint(
+    height: u32,
+    index: u32,
+    coinbase: ContractId,
+    amount: u64,
+    asset_id: AssetId,
+) -> TransactionDump {
+    let input = input::contract::Contract {
+        contract_id: coinbase,
+        ..Default::default()
+    };
+    let output = output::contract::Contract {
+        input_index: 0,
+        balance_root: Bytes32::zeroed(),
+        state_root: Bytes32::zeroed(),
+    };
+    let tx = Transaction::from(Transaction::mint(
+        TxPointer::new(height.into(), index as u16),
+        input,
+        output,
+        amount,
+        asset_id,
+        0,
+    ));
+    TransactionDump {
+        id: tx.id(&ChainId::new(CHAIN_ID)),
+        raw_payload: Bytes(tx.to_bytes()),
+        status: Some(StatusDump::Success {
+            block_height: height,
+            time: 1_730_000_000 + i64::from(height),
+            program_state: None,
+            receipts: vec![],
+            total_gas: 0,
+            total_fee: 0,
+        }),
+    }
+}
+
+/// Recomputes the application hash and the block id of `block` from its
+/// header, as a node committing to corrupted contents would.
+pub fn seal(block: &mut BlockDump) {
+    let full = FullBlock::from(block.clone());
+    let tx_ids: Vec<_> = block.transactions.iter().map(|tx| tx.id).collect();
+    block.header.application_hash =
+        header::application_hash(&full.header, &tx_ids).expect("the header version is known");
+    block.header.id = header::block_id(&full.header, &tx_ids).expect("the header version is known");
+    block.id = block.header.id;
+}
+
+/// Flips the bits of the first byte of `hash`.
+pub fn flip(hash: &mut Bytes32) {
+    hash[0] ^= 0xff;
+}
+
+/// The receipts of the transaction at `index` of `block`.
+pub fn receipts(block: &mut BlockDump, index: usize) -> &mut Vec<ReceiptDump> {
+    match &mut block.transactions[index].status {
+        Some(StatusDump::Success { receipts, .. } | StatusDump::Failure { receipts, .. }) => {
+            receipts
+        }
+        status => panic!("transaction {index} wasn't executed: {status:?}"),
+    }
+}
+
+/// A fresh directory for the dumps of one test.
+pub fn temp_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "fuel-block-validator-{}-{test}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("failed to create the test directory");
+    dir
+}
+
+/// The root `MerkleRootCalculator` computes over `leaves`.
+pub fn root<T: AsRef<[u8]>>(leaves: impl IntoIterator<Item = T>) -> Bytes32 {
+    let mut root = MerkleRootCalculator::new();
+    for leaf in leaves {
+        root.push(leaf.as_ref());
+    }
+    root.root().into()
+}
@@ -0,0 +1,80 @@
+// tests/consensus_parameters.rs
+//! Consensus parameters pinned from a file, validating blocks with the
+//! version in their header.
+
+mod common;
+
+use fuel_root_validation::{
+    BlockValidator, ChainParameters, Check, FullBlock, MemorySource, ValidationError,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::ChainId;
+
+use common::{temp_dir, valid_block, CHAIN_ID};
+
+fn pinned(versions: impl IntoIterator<Item = (u32, u64)>) -> ChainParameters {
+    let mut pinned = ChainParameters::new();
+    for (version, chain_id) in versions {
+        pinned.insert(
+            version,
+            ConsensusParameters::standard_with_id(ChainId::new(chain_id)),
+        );
+    }
+    pinned
+}
+
+fn validator(params: ChainParameters) -> BlockValidator {
+    BlockValidator::from_source(MemorySource::new([FullBlock::from(valid_block(10))]))
+        .with_consensus_parameters(params)
+}
+
+#[test]
+fn pinned_parameters_outlive_their_file() {
+    let path = temp_dir("consensus-parameters").join("params.json");
+    let params = pinned([(0, 9), (1, CHAIN_ID)]);
+
+    params.save(&path).unwrap();
+    let loaded = ChainParameters::load(&path).unwrap();
+
+    assert_eq!(loaded, params);
+    assert_eq!(loaded.versions().collect::<Vec<_>>(), [0, 1]);
+    assert_eq!(loaded.chain_id(), Some(ChainId::new(CHAIN_ID)));
+}
+
+#[tokio::test]
+async fn chain_id_comes_from_the_pinned_parameters() {
+    let validator = validator(pinned([(1, CHAIN_ID)]));
+    assert_eq!(validator.chain_id(), Some(ChainId::new(CHAIN_ID)));
+
+    let report = validator.validate_block(10).await.unwrap();
+
+    assert!(report.passed());
+    assert!(report.checks.iter().any(|check| check.check == Check::TxId));
+}
+
+#[tokio::test]
+async fn blocks_use_the_parameters_of_their_version() {
+    // The block is of version 1, produced on chain `CHAIN_ID`.
+    let validator = validator(pinned([(1, 7), (2, CHAIN_ID)]));
+
+    let report = validator.validate_block(10).await.unwrap();
+
+    // The ids of the script and of the mint.
+    let failed: Vec<_> = report.failures().map(|failure| failure.check).collect();
+    assert_eq!(failed, [Check::TxId, Check::TxId]);
+}
+
+#[tokio::test]
+async fn offline_blocks_of_unpinned_versions_fail() {
+    let validator = validator(pinned([(0, CHAIN_ID)]));
+
+    let error = validator.validate_block(10).await.unwrap_err();
+
+    assert!(
+        matches!(
+            error,
+            ValidationError::ConsensusParametersNotFound { version: 1 }
+        ),
+        "{error}"
+    );
+}
@@ -0,0 +1,117 @@
+// tests/contract_state.rs
+//! The state roots and ids of the contracts created by a block.
+
+mod common;
+
+use fuel_core_client::client::schema::Bytes;
+use fuel_root_validation::{checks, dump::TransactionDump, merkle, Check, FullBlock};
+use fuel_tx::{
+    policies::Policies, Contract, Output, Salt, StorageSlot, Transaction, UniqueIdentifier, Witness,
+};
+use fuel_types::{canonical::Serialize, Bytes32, ChainId};
+
+use common::{valid_block, CHAIN_ID};
+
+fn slots() -> Vec<StorageSlot> {
+    vec![
+        StorageSlot::new(Bytes32::new([1; 32]), Bytes32::new([2; 32])),
+        StorageSlot::new(Bytes32::new([3; 32]), Bytes32::new([4; 32])),
+    ]
+}
+
+/// A transaction creating a contract with the storage `slots`, whose
+/// `ContractCreated` output is `output`.
+fn create(slots: Vec<StorageSlot>, output: Output) -> TransactionDump {
+    let tx = Transaction::from(Transaction::create(
+        0,
+        Policies::new(),
+        Salt::new([5; 32]),
+        slots,
+        vec![],
+        vec![output],
+        vec![Witness::from(vec![0x12, 0x34, 0x56, 0x78])],
+    ));
+    TransactionDump {
+        id: tx.id(&ChainId::new(CHAIN_ID)),
+        raw_payload: Bytes(tx.to_bytes()),
+        status: None,
+    }
+}
+
+/// The `ContractCreated` output a node would set for `slots`.
+fn created(slots: &[StorageSlot]) -> Output {
+    let contract = Contract::from(vec![0x12, 0x34, 0x56, 0x78]);
+    let state_root = Contract::initial_state_root(slots.iter());
+    Output::contract_created(
+        contract.id(&Salt::new([5; 32]), &contract.root(), &state_root),
+        state_root,
+    )
+}
+
+/// The `contract-state` results of a block with `create` before its mint.
+fn results(create: TransactionDump) -> Vec<(Check, bool)> {
+    let mut block = valid_block(10);
+    block.transactions.insert(0, create);
+    checks::contract_state_roots(&FullBlock::from(block))
+        .iter()
+        .map(|result| (result.check, result.passed))
+        .collect()
+}
+
+#[test]
+fn sparse_root_matches_the_vm() {
+    let slots = slots();
+
+    assert_eq!(
+        merkle::contract_state_root(&slots),
+        Contract::initial_state_root(slots.iter())
+    );
+    assert_eq!(
+        merkle::contract_state_root(&[]),
+        Contract::default_state_root()
+    );
+}
+
+#[test]
+fn created_contract_matches_its_storage() {
+    let output = created(&slots());
+
+    assert_eq!(
+        results(create(slots(), output)),
+        [(Check::ContractStateRoot, true), (Check::ContractId, true)]
+    );
+}
+
+#[test]
+fn blocks_without_contracts_have_no_results() {
+    let block = valid_block(10);
+
+    assert!(checks::contract_state_roots(&FullBlock::from(block)).is_empty());
+}
+
+#[test]
+fn state_root_not_committing_to_the_storage_fails() {
+    // The contract claims the state of a single slot.
+    let output = created(&slots()[..1]);
+
+    assert_eq!(
+        results(create(slots(), output)),
+        [
+            (Check::ContractStateRoot, false),
+            (Check::ContractId, false)
+        ]
+    );
+}
+
+#[test]
+fn contract_id_not_committing_to_the_bytecode_fails() {
+    let Output::ContractCreated { state_root, .. } = created(&slots()) else {
+        unreachable!()
+    };
+    let output = Output::contract_created(Default::default(), state_root);
+
+    assert_eq!(
+        results(create(slots(), output)),
+        [(Check::ContractStateRoot, true), (Check::ContractId, false)]
+    );
+}
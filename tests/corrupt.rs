@@ -0,0 +1,87 @@
+// tests/corrupt.rs
+//! Mutations of exported blocks, and the checks that catch each of them.
+
+mod common;
+
+use fuel_root_validation::{
+    BlockDump, BlockReport, BlockValidator, Check, Corruption, FullBlock, MemorySource,
+    ValidationError,
+};
+use fuel_types::ChainId;
+
+use common::{block_with_transactions, valid_block, CHAIN_ID};
+
+async fn failed_checks(block: BlockDump) -> Vec<Check> {
+    let height = block.header.height;
+    let report: BlockReport =
+        BlockValidator::from_source(MemorySource::new([FullBlock::from(block)]))
+            .with_chain_id(ChainId::new(CHAIN_ID))
+            .validate_block(height)
+            .await
+            .expect("the block is in the source");
+    report.failures().map(|failure| failure.check).collect()
+}
+
+async fn corrupted(mut block: BlockDump, corruption: &str) -> Vec<Check> {
+    let corruption: Corruption = corruption.parse().unwrap();
+    corruption.apply(&mut block).unwrap();
+    failed_checks(block).await
+}
+
+#[test]
+fn corruptions_round_trip_through_their_name() {
+    for name in [
+        "flip-payload-byte:0:12",
+        "drop-receipt:0:1",
+        "swap-transactions:0:1",
+        "swap-receipts:1:0:1",
+    ] {
+        assert_eq!(name.parse::<Corruption>().unwrap().to_string(), name);
+    }
+    for name in ["flip-payload-byte:0", "drop-receipt:a:1", "truncate:0"] {
+        assert!(
+            matches!(
+                name.parse::<Corruption>(),
+                Err(ValidationError::InvalidCorruption { corruption, .. }) if corruption == name
+            ),
+            "{name} was accepted"
+        );
+    }
+}
+
+#[tokio::test]
+async fn each_corruption_is_caught() {
+    let dropped = corrupted(valid_block(10), "drop-receipt:0:1").await;
+    assert!(dropped.contains(&Check::ReceiptRoot), "{dropped:?}");
+
+    let swapped = corrupted(valid_block(10), "swap-receipts:0:0:1").await;
+    assert!(swapped.contains(&Check::ReceiptRoot), "{swapped:?}");
+
+    let reordered = corrupted(block_with_transactions(10, 2), "swap-transactions:0:1").await;
+    assert!(reordered.contains(&Check::TxRoot), "{reordered:?}");
+
+    let flipped = corrupted(valid_block(10), "flip-payload-byte:0:8").await;
+    assert!(flipped.contains(&Check::TxId), "{flipped:?}");
+}
+
+#[test]
+fn corruptions_out_of_the_block_are_rejected() {
+    for corruption in [
+        Corruption::FlipPayloadByte {
+            tx: 0,
+            offset: 100_000,
+        },
+        Corruption::DropReceipt { tx: 0, receipt: 2 },
+        Corruption::SwapTransactions { a: 0, b: 2 },
+        Corruption::SwapReceipts { tx: 5, a: 0, b: 1 },
+    ] {
+        let mut block = valid_block(10);
+        assert!(
+            matches!(
+                corruption.apply(&mut block),
+                Err(ValidationError::InvalidCorruption { .. })
+            ),
+            "{corruption} was applied"
+        );
+    }
+}
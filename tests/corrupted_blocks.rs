@@ -0,0 +1,443 @@
+// tests/corrupted_blocks.rs
+//! Runs the validator on consistent and deliberately corrupted blocks and
+//! checks which checks fail, and how.
+
+mod common;
+
+use fuel_core_client::client::schema::tx::transparent_receipt;
+use fuel_root_validation::{
+    checks,
+    dump::{DumpFormat, ReceiptDump},
+    BlockDump, BlockReport, BlockValidator, Check, ErrorKind, FileSource, FullBlock, MemorySource,
+    MerkleProof, ValidationError,
+};
+use fuel_tx::{PanicInstruction, PanicReason, Receipt, ScriptExecutionResult};
+use fuel_types::{Address, AssetId, Bytes32, ChainId, ContractId};
+use futures::{StreamExt, TryStreamExt};
+
+use common::{flip, receipts, seal, temp_dir, valid_block, CHAIN_ID};
+
+fn validator(blocks: impl IntoIterator<Item = BlockDump>) -> BlockValidator {
+    BlockValidator::from_source(MemorySource::new(blocks.into_iter().map(FullBlock::from)))
+        .with_chain_id(ChainId::new(CHAIN_ID))
+}
+
+async fn validate(block: BlockDump) -> BlockReport {
+    let height = block.header.height;
+    validator([block])
+        .validate_block(height)
+        .await
+        .expect("the block is in the source")
+}
+
+fn failed_checks(report: &BlockReport) -> Vec<Check> {
+    report.failures().map(|failure| failure.check).collect()
+}
+
+#[tokio::test]
+async fn valid_block_passes_every_check() {
+    let report = validate(valid_block(10)).await;
+
+    assert!(
+        report.passed(),
+        "{:?}",
+        report.failures().collect::<Vec<_>>()
+    );
+    for check in [
+        Check::ApplicationHash,
+        Check::BlockId,
+        Check::TxId,
+        Check::TxRoot,
+        Check::MessageReceiptCount,
+        Check::MessageOutboxRoot,
+        Check::ReceiptRoot,
+    ] {
+        assert!(
+            report.checks.iter().any(|result| result.check == check),
+            "{check} wasn't run"
+        );
+    }
+}
+
+#[tokio::test]
+async fn flipped_transactions_root_breaks_the_block_id() {
+    let mut block = valid_block(10);
+    flip(&mut block.header.transactions_root);
+
+    let report = validate(block).await;
+
+    assert_eq!(
+        failed_checks(&report),
+        [Check::ApplicationHash, Check::BlockId, Check::TxRoot]
+    );
+}
+
+#[tokio::test]
+async fn flipped_transactions_root_of_a_sealed_block() {
+    let mut block = valid_block(10);
+    flip(&mut block.header.transactions_root);
+    seal(&mut block);
+    let committed = block.header.transactions_root;
+    let valid = valid_block(10).header.transactions_root;
+
+    let report = validate(block).await;
+
+    assert_eq!(failed_checks(&report), [Check::TxRoot]);
+    match report.errors().as_slice() {
+        [ValidationError::TxRootMismatch {
+            height: 10,
+            expected,
+            actual,
+        }] => {
+            assert_eq!(*expected, committed.to_string());
+            assert_eq!(*actual, valid.to_string());
+        }
+        errors => panic!("unexpected errors: {errors:?}"),
+    }
+}
+
+#[tokio::test]
+async fn flipped_message_outbox_root() {
+    let mut block = valid_block(10);
+    flip(&mut block.header.message_outbox_root);
+    seal(&mut block);
+
+    let report = validate(block).await;
+
+    assert_eq!(failed_checks(&report), [Check::MessageOutboxRoot]);
+    assert!(matches!(
+        report.errors().as_slice(),
+        [ValidationError::MessageOutboxRootMismatch { height: 10, .. }]
+    ));
+}
+
+#[tokio::test]
+async fn flipped_block_id() {
+    let mut block = valid_block(10);
+    flip(&mut block.id);
+
+    let report = validate(block).await;
+
+    assert_eq!(failed_checks(&report), [Check::BlockId]);
+    assert!(matches!(
+        report.errors().as_slice(),
+        [ValidationError::BlockIdMismatch { height: 10, .. }]
+    ));
+}
+
+#[tokio::test]
+async fn missing_receipt_breaks_the_receipts_root() {
+    let mut block = valid_block(10);
+    let tx_id = block.transactions[0].id;
+    receipts(&mut block, 0).pop();
+
+    let report = validate(block).await;
+
+    assert_eq!(failed_checks(&report), [Check::ReceiptRoot]);
+    match report.errors().as_slice() {
+        [ValidationError::ReceiptRootMismatch {
+            height: 10,
+            tx_id: failed,
+            ..
+        }] => assert_eq!(*failed, format!("{tx_id:#x}")),
+        errors => panic!("unexpected errors: {errors:?}"),
+    }
+}
+
+#[tokio::test]
+async fn missing_message_receipt_breaks_the_outbox() {
+    let mut block = valid_block(10);
+    receipts(&mut block, 0).remove(0);
+
+    let report = validate(block).await;
+
+    assert_eq!(
+        failed_checks(&report),
+        [
+            Check::MessageReceiptCount,
+            Check::MessageOutboxRoot,
+            Check::ReceiptRoot
+        ]
+    );
+}
+
+#[tokio::test]
+async fn receipt_missing_a_field_is_a_parse_failure() {
+    let mut block = valid_block(10);
+    receipts(&mut block, 0)[1].gas_used = None;
+
+    let report = validate(block).await;
+
+    assert_eq!(
+        failed_checks(&report),
+        [Check::MessageOutboxRoot, Check::ReceiptRoot]
+    );
+    for failure in report.failures() {
+        assert_eq!(failure.error_kind, Some(ErrorKind::Parse), "{failure}");
+        assert!(failure.expected.is_none() && failure.actual.is_none());
+    }
+}
+
+#[tokio::test]
+async fn truncated_payload_is_a_parse_failure() {
+    let mut block = valid_block(10);
+    let tx_id = block.transactions[0].id;
+    let payload = &mut block.transactions[0].raw_payload.0;
+    payload.truncate(payload.len() / 2);
+
+    let report = validate(block).await;
+
+    // The mint can't be told apart from a transaction that doesn't decode.
+    assert_eq!(
+        failed_checks(&report),
+        [
+            Check::TxId,
+            Check::TxRoot,
+            Check::ReceiptRoot,
+            Check::MintPosition
+        ]
+    );
+    for error in report.errors() {
+        assert_eq!(error.kind(), ErrorKind::Parse, "{error}");
+        match error {
+            ValidationError::CheckFailed {
+                height: 10,
+                tx_id: Some(failed),
+                ..
+            } => assert_eq!(failed, format!("{tx_id:#x}")),
+            error => panic!("unexpected error: {error:?}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn dumps_on_disk_are_validated_like_the_original_blocks() {
+    let dir = temp_dir("dumps");
+    let mut corrupted = valid_block(12);
+    receipts(&mut corrupted, 0).pop();
+    for (block, format) in [
+        (valid_block(10), DumpFormat::Json),
+        (valid_block(11), DumpFormat::Cbor),
+        (corrupted, DumpFormat::Json),
+    ] {
+        block
+            .save(dir.join(block.file_name(format)), format)
+            .expect("failed to write the dump");
+    }
+
+    let source = FileSource::open(&dir).expect("failed to read the dumps");
+    assert_eq!(source.bounds(), Some((10, 12)));
+    let range = BlockValidator::from_source(source)
+        .with_chain_id(ChainId::new(CHAIN_ID))
+        .validate_range(10, 12)
+        .await
+        .expect("every block is in the source");
+    std::fs::remove_dir_all(&dir).ok();
+
+    let passed: Vec<_> = range
+        .outcomes
+        .iter()
+        .map(|outcome| (outcome.height, outcome.passed()))
+        .collect();
+    assert_eq!(passed, [(10, true), (11, true), (12, false)]);
+}
+
+#[test]
+fn vm_receipts_survive_the_dump_model() {
+    let contract = ContractId::new([4; 32]);
+    let receipts = [
+        Receipt::call(contract, contract, 1, AssetId::BASE, 2, 3, 4, 5, 6),
+        Receipt::ret(contract, 7, 8, 9),
+        Receipt::return_data(contract, 1, 2, 3, vec![1, 2, 3]),
+        Receipt::panic(
+            contract,
+            PanicInstruction::error(PanicReason::OutOfGas, 0),
+            1,
+            2,
+        ),
+        Receipt::revert(contract, 1, 2, 3),
+        Receipt::log(contract, 1, 2, 3, 4, 5, 6),
+        Receipt::log_data(contract, 1, 2, 3, 4, 5, vec![6]),
+        Receipt::transfer(contract, contract, 1, AssetId::BASE, 2, 3),
+        Receipt::transfer_out(contract, Address::new([5; 32]), 1, AssetId::BASE, 2, 3),
+        Receipt::script_result(ScriptExecutionResult::Revert, 1),
+        Receipt::message_out(
+            &Bytes32::new([6; 32]),
+            0,
+            Address::new([1; 32]),
+            Address::new([2; 32]),
+            100,
+            vec![1],
+        ),
+        Receipt::mint(Bytes32::new([7; 32]), contract, 1, 2, 3),
+        Receipt::burn(Bytes32::new([7; 32]), contract, 1, 2, 3),
+    ];
+
+    for receipt in receipts {
+        let served = transparent_receipt::Receipt::from(ReceiptDump::from(&receipt));
+        let parsed = Receipt::try_from(served).expect("the receipt has every field");
+        assert_eq!(parsed, receipt);
+    }
+}
+
+#[test]
+fn dump_json_is_validated_on_its_own() {
+    let mut block = valid_block(10);
+    flip(&mut block.header.message_outbox_root);
+    seal(&mut block);
+    let json = serde_json::to_vec(&block).unwrap();
+
+    let block: BlockDump = serde_json::from_slice(&json).expect("the dump is valid JSON");
+    let report = checks::validate_block(&block.into(), Some(&ChainId::new(CHAIN_ID)), None);
+
+    assert_eq!(failed_checks(&report), [Check::MessageOutboxRoot]);
+    assert!(report
+        .checks
+        .iter()
+        .any(|result| result.check == Check::TxId));
+}
+
+#[tokio::test]
+async fn missing_block_is_not_found() {
+    let result = validator([valid_block(10)]).validate_block(11).await;
+
+    assert!(matches!(
+        result,
+        Err(ValidationError::BlockNotFound { height: 11 })
+    ));
+}
+
+#[test]
+fn inclusion_proof_of_a_valid_block() {
+    let block = valid_block(10);
+    let tx_id = block.transactions[0].id;
+    let root = block.header.transactions_root;
+
+    let proof = MerkleProof::build(&block.into(), &tx_id).expect("failed to build the proof");
+
+    assert!(proof.verify(&root));
+    assert!(!proof.verify(&Bytes32::zeroed()));
+    assert_eq!(
+        proof.transaction_id(&ChainId::new(CHAIN_ID)).unwrap(),
+        tx_id
+    );
+}
+
+#[test]
+fn no_inclusion_proof_against_a_flipped_root() {
+    let mut block = valid_block(10);
+    flip(&mut block.header.transactions_root);
+    let tx_id = block.transactions[0].id;
+
+    let result = MerkleProof::build(&block.into(), &tx_id);
+
+    assert!(matches!(
+        result,
+        Err(ValidationError::TxRootMismatch { height: 10, .. })
+    ));
+}
+
+#[tokio::test]
+async fn blocks_missing_from_a_page_are_reported() {
+    let range = validator([10, 11, 13, 16].map(valid_block))
+        .with_page_size(3)
+        .validate_range(10, 17)
+        .await
+        .expect("the source can be read");
+
+    let found: Vec<_> = range
+        .outcomes
+        .iter()
+        .map(|outcome| match &outcome.result {
+            Ok(report) => (outcome.height, report.passed()),
+            Err(ValidationError::BlockNotFound { height }) => (*height, false),
+            Err(e) => panic!("unexpected error: {e}"),
+        })
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (10, true),
+            (11, true),
+            (12, false),
+            (13, true),
+            (14, false),
+            (15, false),
+            (16, true),
+            (17, false)
+        ]
+    );
+}
+
+/// Serves the blocks of `blocks` below `broken_from` and fails to query
+/// the others.
+#[derive(Debug)]
+struct BrokenSource {
+    blocks: MemorySource,
+    broken_from: u32,
+}
+
+#[async_trait::async_trait]
+impl fuel_root_validation::BlockSource for BrokenSource {
+    async fn fetch_block(&self, height: u32) -> fuel_root_validation::error::Result<FullBlock> {
+        self.fetch_range(height, height)
+            .await?
+            .pop()
+            .ok_or(ValidationError::BlockNotFound { height })
+    }
+
+    async fn fetch_range(
+        &self,
+        from: u32,
+        to: u32,
+    ) -> fuel_root_validation::error::Result<Vec<FullBlock>> {
+        if to >= self.broken_from {
+            return Err(ValidationError::Query {
+                query: "blocks",
+                attempts: 1,
+                source: std::io::Error::other("connection reset"),
+            });
+        }
+        self.blocks.fetch_range(from, to).await
+    }
+
+    async fn latest_height(&self) -> fuel_root_validation::error::Result<u32> {
+        Ok(self.broken_from)
+    }
+}
+
+#[tokio::test]
+async fn stream_yields_blocks_as_they_are_validated() {
+    let validator = validator((10..20).map(valid_block)).with_page_size(2);
+
+    let first: Vec<_> = validator
+        .validate_stream(10, 19)
+        .take(3)
+        .map_ok(|outcome| outcome.height)
+        .try_collect()
+        .await
+        .expect("the source can be read");
+
+    assert_eq!(first, [10, 11, 12]);
+}
+
+#[tokio::test]
+async fn stream_ends_with_the_query_error() {
+    let source = BrokenSource {
+        blocks: MemorySource::new((10..20).map(valid_block).map(FullBlock::from)),
+        broken_from: 14,
+    };
+    let validator = BlockValidator::from_source(source).with_page_size(2);
+
+    let items: Vec<_> = validator.validate_stream(10, 19).collect().await;
+
+    assert_eq!(items.len(), 5);
+    assert!(items[..4].iter().all(|item| item.is_ok()));
+    assert!(matches!(
+        items[4],
+        Err(ValidationError::Query {
+            query: "blocks",
+            ..
+        })
+    ));
+}
@@ -0,0 +1,80 @@
+// tests/dashboard.rs
+//! The state of the terminal dashboard of follow mode.
+
+mod common;
+
+use std::time::Duration;
+
+use fuel_root_validation::{
+    dashboard::RECENT_FAILURES, BlockOutcome, BlockValidator, Dashboard, MemorySource, Reorg,
+    ValidationError,
+};
+use fuel_types::Bytes32;
+
+use common::{bad_block, valid_block};
+
+#[tokio::test]
+async fn outcomes_are_tallied_by_check() {
+    let blocks = [valid_block(10).into(), bad_block(11)];
+    let validator = BlockValidator::from_source(MemorySource::new(blocks));
+    let mut dashboard = Dashboard::new("memory");
+
+    for outcome in validator.validate_range(10, 11).await.unwrap().outcomes {
+        dashboard.observe(&outcome);
+    }
+    dashboard.observe_reorg(&Reorg {
+        fork_height: Some(10),
+        abandoned: vec![(11, Bytes32::zeroed())],
+    });
+
+    assert_eq!(
+        (dashboard.passed, dashboard.failed, dashboard.reorgs),
+        (1, 1, 1)
+    );
+    assert_eq!(dashboard.validated, Some(11));
+    assert_eq!(dashboard.recent_failures.len(), 1);
+    assert_eq!(dashboard.recent_failures[0].height, 11);
+    let receipts = dashboard.checks["receipt-root"];
+    assert_eq!((receipts.passed, receipts.failed), (1, 1));
+    assert_eq!(receipts.pass_rate(), 0.5);
+    assert_eq!(dashboard.checks["tx-root"].pass_rate(), 1.0);
+}
+
+#[test]
+fn only_the_latest_failures_are_kept() {
+    let mut dashboard = Dashboard::default();
+
+    for height in 0..RECENT_FAILURES as u32 + 5 {
+        dashboard.observe(&BlockOutcome {
+            height,
+            result: Err(ValidationError::BlockNotFound { height }),
+            duration: Duration::ZERO,
+            timing: None,
+        });
+    }
+
+    assert_eq!(dashboard.recent_failures.len(), RECENT_FAILURES);
+    assert_eq!(
+        dashboard.recent_failures.front().unwrap().height,
+        RECENT_FAILURES as u32 + 4
+    );
+    assert!(dashboard.recent_failures[0].reason.contains("not found"));
+    assert!(dashboard.checks.is_empty());
+}
+
+#[tokio::test]
+async fn probes_report_the_head_and_the_lag() {
+    let blocks = [10, 11, 12].map(|height| valid_block(height).into());
+    let validator = BlockValidator::from_source(MemorySource::new(blocks));
+    let mut dashboard = Dashboard::new("memory");
+    dashboard.observe(&validator.validate_height(10).await);
+
+    dashboard.observe_probes(&validator.probe_heads().await);
+
+    assert_eq!(dashboard.head, Some(12));
+    assert_eq!(dashboard.lag(), Some(2));
+    assert_eq!(dashboard.endpoints.len(), 1);
+    assert_eq!(dashboard.endpoints[0].endpoint, "memory");
+    assert_eq!(dashboard.endpoints[0].head, Some(12));
+    assert_eq!(dashboard.endpoints[0].error, None);
+}
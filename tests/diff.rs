@@ -0,0 +1,70 @@
+// tests/diff.rs
+//! Field by field comparison of the blocks of two sources.
+
+mod common;
+
+use fuel_root_validation::{
+    diff::{self, FieldDifference},
+    FullBlock, MemorySource,
+};
+
+use common::{receipts, valid_block};
+
+#[test]
+fn identical_blocks_have_no_differences() {
+    let block = FullBlock::from(valid_block(10));
+
+    assert!(diff::diff_blocks(&block, &block.clone()).is_empty());
+}
+
+#[test]
+fn differences_are_named_by_their_path() {
+    let expected = valid_block(10);
+    let mut actual = expected.clone();
+    receipts(&mut actual, 0)[1].gas_used = Some(1);
+    actual.transactions[0].raw_payload.0.push(0);
+
+    let differences = diff::diff_blocks(&expected.into(), &actual.into());
+
+    let paths: Vec<_> = differences.iter().map(|d| d.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            "transactions[0].raw_payload",
+            "transactions[0].status.receipts[1].gas_used"
+        ]
+    );
+    // Payloads are too long to report, their hashes tell them apart.
+    assert!(differences[0]
+        .actual
+        .as_deref()
+        .unwrap()
+        .contains("chars with hash 0x"));
+    assert_eq!(differences[1].actual.as_deref(), Some("1"));
+}
+
+#[tokio::test]
+async fn blocks_missing_from_a_source_are_reported() {
+    let local = MemorySource::new([10, 11].map(|height| valid_block(height).into()));
+    let mut changed = valid_block(11);
+    receipts(&mut changed, 0)[1].gas_used = Some(1);
+    let served = MemorySource::new([changed.into(), valid_block(12).into()]);
+
+    let mut diffs = vec![];
+    diff::diff_range(&local, &served, 10, 20, |diff| diffs.push(diff))
+        .await
+        .unwrap();
+
+    let heights: Vec<_> = diffs.iter().map(|diff| diff.height).collect();
+    assert_eq!(heights, [10, 11, 12]);
+    assert_eq!(
+        diffs[0].differences,
+        [FieldDifference {
+            path: "block".to_string(),
+            expected: Some(FullBlock::from(valid_block(10)).id.to_string()),
+            actual: None,
+        }]
+    );
+    assert_eq!(diffs[1].differences.len(), 1);
+    assert_eq!(diffs[2].differences[0].expected, None);
+}
@@ -0,0 +1,19 @@
+// tests/event_inbox.rs
+//! Validating `header.event_inbox_root` against the parent of a block.
+
+mod common;
+
+use fuel_root_validation::{BlockValidator, Check, MemorySource};
+
+use common::valid_block;
+
+#[tokio::test]
+async fn non_genesis_blocks_at_height_0_fail_the_event_inbox_check() {
+    let validator = BlockValidator::from_source(MemorySource::new([valid_block(0).into()]))
+        .with_event_inbox_check(true);
+
+    let report = validator.validate_block(0).await.unwrap();
+    let failures: Vec<_> = report.failures().collect();
+    assert_eq!(failures.len(), 1, "{failures:?}");
+    assert_eq!(failures[0].check, Check::EventInboxRoot);
+}
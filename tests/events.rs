@@ -0,0 +1,118 @@
+// tests/events.rs
+//! The report of every validated block published to a message bus.
+
+#[cfg(feature = "nats")]
+mod common;
+
+use fuel_root_validation::{ErrorKind, EventPublisher, ValidationError};
+
+#[tokio::test]
+async fn unknown_buses_are_rejected() {
+    let error = EventPublisher::connect("amqp://127.0.0.1:5672", "blocks")
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, ValidationError::InvalidEndpoint { .. }),
+        "{error:?}"
+    );
+    assert_eq!(error.kind(), ErrorKind::Parse);
+}
+
+#[cfg(not(feature = "nats"))]
+#[tokio::test]
+async fn nats_needs_its_feature() {
+    let error = EventPublisher::connect("nats://127.0.0.1:4222", "blocks")
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("`nats` feature"), "{error}");
+}
+
+#[cfg(feature = "nats")]
+mod nats {
+    use fuel_root_validation::{BlockValidator, EventPublisher, FullBlock, MemorySource};
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+        sync::mpsc,
+    };
+
+    use super::common::{receipts, valid_block};
+
+    /// A message published to the fake server.
+    struct Published {
+        subject: String,
+        headers: String,
+        payload: serde_json::Value,
+    }
+
+    /// Serves just enough of the NATS protocol to receive messages.
+    async fn nats_server() -> (String, mpsc::UnboundedReceiver<Published>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (read, mut write) = stream.into_split();
+            let info = format!(
+                "INFO {{\"server_id\":\"test\",\"server_name\":\"test\",\"version\":\"2.10.0\",\
+                 \"go\":\"go1.22\",\"host\":\"127.0.0.1\",\"port\":{},\"headers\":true,\
+                 \"max_payload\":1048576,\"proto\":1}}\r\n",
+                address.port()
+            );
+            write.write_all(info.as_bytes()).await.unwrap();
+            let mut read = BufReader::new(read);
+            let mut line = String::new();
+            while read.read_line(&mut line).await.unwrap_or(0) > 0 {
+                let words: Vec<_> = line.split_whitespace().collect();
+                match words.as_slice() {
+                    ["PING"] => write.write_all(b"PONG\r\n").await.unwrap(),
+                    ["HPUB", subject, header_len, total_len] => {
+                        let header_len: usize = header_len.parse().unwrap();
+                        let total_len: usize = total_len.parse().unwrap();
+                        let mut message = vec![0; total_len + 2];
+                        read.read_exact(&mut message).await.unwrap();
+                        let _ = sender.send(Published {
+                            subject: subject.to_string(),
+                            headers: String::from_utf8_lossy(&message[..header_len]).into(),
+                            payload: serde_json::from_slice(&message[header_len..total_len])
+                                .unwrap(),
+                        });
+                    }
+                    _ => {}
+                }
+                line.clear();
+            }
+        });
+        (format!("nats://{address}"), receiver)
+    }
+
+    #[tokio::test]
+    async fn every_block_is_published_in_order() {
+        let (url, mut published) = nats_server().await;
+        let publisher = EventPublisher::connect(&url, "fuel.blocks").await.unwrap();
+        let mut bad = valid_block(11);
+        receipts(&mut bad, 0)[1].gas_used = Some(1);
+        let blocks = [valid_block(10), bad].map(FullBlock::from);
+        let validation = BlockValidator::from_source(MemorySource::new(blocks))
+            .validate_range(10, 11)
+            .await
+            .unwrap();
+
+        for outcome in &validation.outcomes {
+            let payload = serde_json::to_vec(outcome).unwrap();
+            publisher.publish(outcome.height, payload).await.unwrap();
+        }
+        publisher.flush().await.unwrap();
+
+        let first = published.recv().await.unwrap();
+        let second = published.recv().await.unwrap();
+        assert_eq!(first.subject, "fuel.blocks");
+        assert!(first.headers.contains("Fuel-Block-Height: 10"));
+        assert_eq!(first.payload["height"], 10);
+        assert_eq!(first.payload["passed"], true);
+        assert_eq!(second.payload["height"], 11);
+        assert_eq!(second.payload["passed"], false);
+    }
+}
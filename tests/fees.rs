@@ -0,0 +1,164 @@
+// tests/fees.rs
+//! The gas and the fees charged for the transactions of a block, recomputed
+//! with its consensus parameters.
+
+mod common;
+
+use fuel_root_validation::{
+    checks,
+    dump::{BlockDump, StatusDump},
+    BlockValidator, ChainParameters, Check, FullBlock, MemorySource, Severity,
+};
+use fuel_tx::{
+    field::{MintAmount, MintGasPrice},
+    Chargeable, ConsensusParameters, Transaction, UniqueIdentifier,
+};
+use fuel_types::{
+    canonical::{Deserialize, Serialize},
+    ChainId,
+};
+
+use common::{block_with_transactions, root, seal, CHAIN_ID};
+
+const GAS_PRICE: u64 = 3;
+
+/// Parameters charging `GAS_PRICE` per unit of gas.
+fn params() -> ConsensusParameters {
+    let mut params = ConsensusParameters::standard_with_id(ChainId::new(CHAIN_ID));
+    params.set_fee_params(params.fee_params().with_gas_price_factor(1));
+    params
+}
+
+/// A block of `count` scripts charged as the executor would at `GAS_PRICE`,
+/// minting their fees.
+fn charged_block(count: u32) -> BlockDump {
+    let params = params();
+    let mut block = block_with_transactions(10, count);
+    let mut fees = 0;
+    for tx in &mut block.transactions[..count as usize] {
+        let Ok(Transaction::Script(script)) = Transaction::from_bytes(&tx.raw_payload.0) else {
+            panic!("the block has scripts");
+        };
+        let Some(StatusDump::Success {
+            total_gas,
+            total_fee,
+            ..
+        }) = &mut tx.status
+        else {
+            panic!("the scripts succeeded");
+        };
+        // Every script of the block uses 1000 gas.
+        *total_gas = script.min_gas(params.gas_costs(), params.fee_params()) + 1000;
+        *total_fee = *total_gas * GAS_PRICE;
+        fees += *total_fee;
+    }
+
+    let mint = block.transactions.last_mut().unwrap();
+    let Ok(Transaction::Mint(mut payload)) = Transaction::from_bytes(&mint.raw_payload.0) else {
+        panic!("the block ends with its mint");
+    };
+    *payload.gas_price_mut() = GAS_PRICE;
+    *payload.mint_amount_mut() = fees;
+    let payload = Transaction::from(payload);
+    mint.id = payload.id(&ChainId::new(CHAIN_ID));
+    mint.raw_payload.0 = payload.to_bytes();
+
+    block.header.transactions_root =
+        root(block.transactions.iter().map(|tx| tx.raw_payload.0.clone()));
+    seal(&mut block);
+    block
+}
+
+fn total_fee(block: &mut BlockDump, index: usize) -> &mut u64 {
+    match &mut block.transactions[index].status {
+        Some(StatusDump::Success { total_fee, .. }) => total_fee,
+        status => panic!("transaction {index} didn't succeed: {status:?}"),
+    }
+}
+
+#[test]
+fn fees_are_the_gas_used_at_the_gas_price() {
+    let block = FullBlock::from(charged_block(2));
+
+    let results = checks::fees(&block, &params());
+
+    let checks: Vec<_> = results.iter().map(|result| result.check).collect();
+    assert_eq!(
+        checks,
+        [Check::TxGas, Check::TxFee, Check::TxGas, Check::TxFee]
+    );
+    assert!(results.iter().all(|result| result.passed), "{results:?}");
+    assert!(checks::mint(&block).iter().all(|result| result.passed));
+}
+
+#[test]
+fn overcharged_transaction_fails_its_fee() {
+    let mut block = charged_block(2);
+    *total_fee(&mut block, 1) += 1;
+    let tx_id = format!("{:#x}", block.transactions[1].id);
+
+    let results = checks::fees(&FullBlock::from(block), &params());
+
+    let failed: Vec<_> = results.iter().filter(|result| !result.passed).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].check, Check::TxFee);
+    assert_eq!(failed[0].tx_id.as_deref(), Some(tx_id.as_str()));
+}
+
+#[tokio::test]
+async fn fee_check_uses_the_parameters_of_the_block() {
+    let block = charged_block(1);
+    let mut pinned = ChainParameters::new();
+    pinned.insert(block.header.consensus_parameters_version, params());
+    let validator = BlockValidator::from_source(MemorySource::new([FullBlock::from(block)]))
+        .with_fee_check(true);
+
+    // Without parameters the fees can't be recomputed.
+    let report = validator.validate_block(10).await.unwrap();
+    let failed: Vec<_> = report.failures().map(|failure| failure.check).collect();
+    assert_eq!(failed, [Check::TxFee]);
+
+    let report = validator
+        .with_consensus_parameters(pinned)
+        .validate_block(10)
+        .await
+        .unwrap();
+    assert!(
+        report.passed(),
+        "{:?}",
+        report.failures().collect::<Vec<_>>()
+    );
+    assert!(report
+        .checks
+        .iter()
+        .any(|check| check.check == Check::TxFee));
+}
+
+#[tokio::test]
+async fn fee_mismatches_only_warn_unless_failing_on_warnings() {
+    let mut block = charged_block(1);
+    // The mint still matches the fees paid.
+    match &mut block.transactions[0].status {
+        Some(StatusDump::Success { total_gas, .. }) => *total_gas += 1,
+        status => panic!("the script didn't succeed: {status:?}"),
+    }
+    let mut pinned = ChainParameters::new();
+    pinned.insert(block.header.consensus_parameters_version, params());
+    let validator = BlockValidator::from_source(MemorySource::new([FullBlock::from(block)]))
+        .with_consensus_parameters(pinned)
+        .with_fee_check(true);
+
+    let report = validator.validate_block(10).await.unwrap();
+    assert!(report.passed());
+    let warnings: Vec<_> = report.warnings().map(|warning| warning.check).collect();
+    assert_eq!(warnings, [Check::TxGas]);
+
+    let report = validator
+        .with_fail_on(Severity::Warning)
+        .validate_block(10)
+        .await
+        .unwrap();
+    let failed: Vec<_> = report.failures().map(|failure| failure.check).collect();
+    assert_eq!(failed, [Check::TxGas]);
+    assert_eq!(report.warnings().count(), 0);
+}
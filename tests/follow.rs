@@ -0,0 +1,344 @@
+// tests/follow.rs
+//! Follows a chain whose blocks are replaced while it is being validated, or
+//! pushed by the node.
+
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use fuel_root_validation::{
+    error::Result, follow, subscription::NewBlocks, BlockDump, BlockSource, BlockValidator,
+    FollowConfig, FollowEvent, FullBlock, GraphqlSource, MemorySource, ValidationError,
+};
+use fuel_types::Bytes32;
+use futures::{channel::mpsc, stream, StreamExt};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    sync::oneshot,
+};
+
+use common::{seal, valid_block};
+
+/// A chain the test can reorg.
+#[derive(Clone, Debug, Default)]
+struct Chain(Arc<Mutex<MemorySource>>);
+
+impl Chain {
+    fn insert(&self, block: BlockDump) {
+        self.0.lock().unwrap().insert(block.into());
+    }
+
+    fn blocks(&self) -> MemorySource {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockSource for Chain {
+    async fn fetch_block(&self, height: u32) -> Result<FullBlock> {
+        self.blocks().fetch_block(height).await
+    }
+
+    async fn fetch_range(&self, from: u32, to: u32) -> Result<Vec<FullBlock>> {
+        self.blocks().fetch_range(from, to).await
+    }
+
+    async fn latest_height(&self) -> Result<u32> {
+        self.blocks().latest_height().await
+    }
+}
+
+/// A chain pushing every block inserted after the follower subscribed, or
+/// failing the subscription when `push` isn't set.
+#[derive(Clone, Debug, Default)]
+struct PushingChain {
+    chain: Chain,
+    push: bool,
+    subscribers: Arc<Mutex<Vec<mpsc::UnboundedSender<Result<()>>>>>,
+}
+
+impl PushingChain {
+    fn insert(&self, block: BlockDump) {
+        self.chain.insert(block);
+        for subscriber in self.subscribers.lock().unwrap().iter() {
+            subscriber.unbounded_send(Ok(())).ok();
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockSource for PushingChain {
+    async fn fetch_block(&self, height: u32) -> Result<FullBlock> {
+        self.chain.fetch_block(height).await
+    }
+
+    async fn fetch_range(&self, from: u32, to: u32) -> Result<Vec<FullBlock>> {
+        self.chain.fetch_range(from, to).await
+    }
+
+    async fn latest_height(&self) -> Result<u32> {
+        self.chain.latest_height().await
+    }
+
+    async fn subscribe_new_blocks(&self) -> Result<Option<NewBlocks>> {
+        if !self.push {
+            let error = ValidationError::Subscription {
+                reason: "unknown field".to_owned(),
+            };
+            return Ok(Some(Box::pin(stream::once(async { Err(error) }))));
+        }
+        let (sender, receiver) = mpsc::unbounded();
+        self.subscribers.lock().unwrap().push(sender);
+        Ok(Some(Box::pin(receiver)))
+    }
+}
+
+/// Follows `chain` from height 10 until block 11, inserted once block 10 is
+/// validated, and returns the validated heights.
+async fn follow_until_11(chain: PushingChain, poll_interval: Duration) -> Vec<u32> {
+    chain.insert(valid_block(10));
+    let validator = BlockValidator::from_source(chain.clone());
+    let config = FollowConfig {
+        poll_interval,
+        start_height: Some(10),
+        ..FollowConfig::default()
+    };
+
+    let (stop, stopped) = oneshot::channel();
+    let mut stop = Some(stop);
+    let mut heights = vec![];
+    let following = follow(
+        &validator,
+        config,
+        async {
+            stopped.await.ok();
+        },
+        |event| {
+            let FollowEvent::Block(outcome) = event else {
+                panic!("unexpected {event:?}");
+            };
+            match outcome.height {
+                10 => chain.insert(valid_block(11)),
+                _ => {
+                    stop.take().map(|stop| stop.send(()));
+                }
+            }
+            heights.push(outcome.height);
+        },
+    );
+    tokio::time::timeout(Duration::from_secs(10), following)
+        .await
+        .expect("the follower didn't reach block 11")
+        .expect("the chain can be read");
+    heights
+}
+
+/// A valid block of another branch than [`valid_block`].
+fn forked_block(height: u32) -> BlockDump {
+    let mut block = valid_block(height);
+    block.header.prev_root = Bytes32::new([9; 32]);
+    seal(&mut block);
+    block
+}
+
+#[tokio::test]
+async fn reorg_is_reported_and_the_new_branch_validated() {
+    let chain = Chain::default();
+    (10..=12)
+        .map(valid_block)
+        .for_each(|block| chain.insert(block));
+    let validator = BlockValidator::from_source(chain.clone());
+    let config = FollowConfig {
+        poll_interval: Duration::from_millis(1),
+        start_height: Some(10),
+        ..FollowConfig::default()
+    };
+
+    let (stop, stopped) = oneshot::channel();
+    let mut stop = Some(stop);
+    let mut events = vec![];
+    let following = follow(
+        &validator,
+        config,
+        async {
+            stopped.await.ok();
+        },
+        |event| {
+            match &event {
+                FollowEvent::Block(outcome) if outcome.height == 12 && events.len() == 2 => {
+                    (11..=13)
+                        .map(forked_block)
+                        .for_each(|block| chain.insert(block));
+                }
+                FollowEvent::Block(outcome) if outcome.height == 13 => {
+                    stop.take().map(|stop| stop.send(()));
+                }
+                _ => {}
+            }
+            events.push(event);
+        },
+    );
+    tokio::time::timeout(Duration::from_secs(10), following)
+        .await
+        .expect("the follower didn't reach the new tip")
+        .expect("the chain can be read");
+
+    let summary: Vec<_> = events
+        .iter()
+        .map(|event| match event {
+            FollowEvent::Block(outcome) => {
+                assert!(outcome.passed(), "block #{} failed", outcome.height);
+                format!("block {}", outcome.height)
+            }
+            FollowEvent::Reorg(reorg) => {
+                let heights: Vec<_> = reorg.abandoned.iter().map(|(height, _)| *height).collect();
+                format!("reorg after {:?} of {heights:?}", reorg.fork_height)
+            }
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            "block 10",
+            "block 11",
+            "block 12",
+            "reorg after Some(10) of [11, 12]",
+            "block 11",
+            "block 12",
+            "block 13",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn pushed_blocks_are_validated_without_polling() {
+    let chain = PushingChain {
+        push: true,
+        ..PushingChain::default()
+    };
+
+    let heights = follow_until_11(chain, Duration::from_secs(3600)).await;
+
+    assert_eq!(heights, [10, 11]);
+}
+
+#[tokio::test]
+async fn failed_subscription_falls_back_to_polling() {
+    let heights = follow_until_11(PushingChain::default(), Duration::from_millis(1)).await;
+
+    assert_eq!(heights, [10, 11]);
+}
+
+#[tokio::test]
+async fn only_confirmed_blocks_are_validated() {
+    let chain = Chain::default();
+    (10..=12)
+        .map(valid_block)
+        .for_each(|block| chain.insert(block));
+    let validator = BlockValidator::from_source(chain.clone());
+    let config = FollowConfig {
+        poll_interval: Duration::from_millis(1),
+        subscribe: false,
+        confirmations: 2,
+        ..FollowConfig::default()
+    };
+
+    let (stop, stopped) = oneshot::channel();
+    let mut stop = Some(stop);
+    let mut heights = vec![];
+    let following = follow(
+        &validator,
+        config,
+        async {
+            stopped.await.ok();
+        },
+        |event| {
+            let FollowEvent::Block(outcome) = event else {
+                panic!("unexpected {event:?}");
+            };
+            match outcome.height {
+                // Block 11 is only confirmed once block 13 is on top of it.
+                10 => chain.insert(valid_block(13)),
+                _ => {
+                    stop.take().map(|stop| stop.send(()));
+                }
+            }
+            heights.push(outcome.height);
+        },
+    );
+    tokio::time::timeout(Duration::from_secs(10), following)
+        .await
+        .expect("the follower didn't reach block 11")
+        .expect("the chain can be read");
+
+    assert_eq!(heights, [10, 11]);
+}
+
+/// Serves the subscription endpoint of a node, answering with `events`.
+async fn stub_node(events: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = vec![];
+            let mut buffer = [0; 4096];
+            while !String::from_utf8_lossy(&request).contains("alpha__new_blocks") {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            assert!(String::from_utf8_lossy(&request).starts_with("POST /v1/graphql-sub "));
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{events}",
+                events.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn node_events_are_pushed_blocks() {
+    let url = stub_node(
+        ": keep-alive\n\n\
+         data: {\"data\":{\"alpha__new_blocks\":\"0x00\"}}\n\n\
+         data: {\"data\":{\"alpha__new_blocks\":\"0x01\"}}\r\n\r\n",
+    )
+    .await;
+
+    let new_blocks = GraphqlSource::new(url)
+        .unwrap()
+        .subscribe_new_blocks()
+        .await
+        .expect("the node accepts the subscription")
+        .expect("nodes can push blocks");
+
+    let pushed: Vec<_> = new_blocks.collect().await;
+    assert!(matches!(pushed[..], [Ok(()), Ok(())]), "{pushed:?}");
+}
+
+#[tokio::test]
+async fn subscription_error_of_an_old_node_ends_the_stream() {
+    let url = stub_node(
+        "data: {\"data\":null,\"errors\":[{\"message\":\"Unknown field \\\"alpha__new_blocks\\\"\"}]}\n\n",
+    )
+    .await;
+
+    let new_blocks = GraphqlSource::new(url)
+        .unwrap()
+        .subscribe_new_blocks()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let pushed: Vec<_> = new_blocks.collect().await;
+    assert!(
+        matches!(&pushed[..], [Err(ValidationError::Subscription { reason })] if reason.contains("Unknown field")),
+        "{pushed:?}"
+    );
+}
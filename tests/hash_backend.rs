@@ -0,0 +1,81 @@
+// tests/hash_backend.rs
+//! The SHA-256 backends the Merkle roots are hashed with, and checking one
+//! against sha2.
+
+use fuel_merkle::binary::root_calculator::MerkleRootCalculator;
+use fuel_root_validation::{
+    hash::{self, CrossCheck, HashBackend, Sha2},
+    merkle,
+};
+use fuel_types::Bytes32;
+
+/// A backend getting every hash wrong.
+#[derive(Debug)]
+struct Broken;
+
+impl HashBackend for Broken {
+    fn name(&self) -> &'static str {
+        "broken"
+    }
+
+    fn sha256(&self, parts: &[&[u8]]) -> Bytes32 {
+        let mut hash = Sha2.sha256(parts);
+        hash[0] ^= 1;
+        hash
+    }
+}
+
+static BROKEN: Broken = Broken;
+
+fn leaves(count: u8) -> Vec<Vec<u8>> {
+    (0..count).map(|i| vec![i; usize::from(i) * 7]).collect()
+}
+
+fn root_with(backend: &dyn HashBackend, leaves: &[Vec<u8>]) -> Bytes32 {
+    let hashes = leaves
+        .iter()
+        .map(|leaf| merkle::leaf_hash_with(backend, leaf));
+    merkle::root_with(backend, hashes)
+}
+
+#[test]
+fn every_backend_computes_the_roots_of_fuel_merkle() {
+    assert_eq!(hash::backends().count(), hash::BACKENDS.len());
+    for count in [0, 1, 2, 3, 7, 64, 100] {
+        let leaves = leaves(count);
+        let mut calculator = MerkleRootCalculator::new();
+        for leaf in &leaves {
+            calculator.push(leaf);
+        }
+        let expected = Bytes32::from(calculator.root());
+        for backend in hash::backends() {
+            assert_eq!(root_with(backend, &leaves), expected, "{}", backend.name());
+        }
+    }
+}
+
+#[test]
+fn backends_are_named() {
+    assert_eq!(hash::BACKENDS[0], "sha2");
+    for name in hash::BACKENDS {
+        assert_eq!(hash::named(name).unwrap().name(), *name);
+    }
+    assert!(hash::named("md5").is_none());
+}
+
+#[test]
+fn cross_checks_count_and_correct_wrong_hashes() {
+    let leaves = leaves(5);
+    let expected = root_with(&Sha2, &leaves);
+    assert_ne!(root_with(&BROKEN, &leaves), expected);
+
+    let check = CrossCheck::new(&BROKEN);
+    assert_eq!(check.name(), "broken");
+    assert_eq!(root_with(&check, &leaves), expected);
+    // Five leaves and four nodes.
+    assert_eq!(check.mismatches(), 9);
+
+    let check = CrossCheck::new(&Sha2);
+    assert_eq!(root_with(&check, &leaves), expected);
+    assert_eq!(check.mismatches(), 0);
+}
@@ -0,0 +1,105 @@
+// tests/header_versions.rs
+//! Blocks of every header version, and ranges across the upgrades between
+//! them.
+
+mod common;
+
+use fuel_root_validation::{
+    checks, dump::HeaderVersionDump, BlockDump, BlockValidator, Check, ErrorKind, FullBlock,
+    MemorySource,
+};
+use fuel_types::ChainId;
+
+use common::{block_with_transactions, flip, seal, valid_block, CHAIN_ID};
+
+const CHAIN: ChainId = ChainId::new(CHAIN_ID);
+
+/// `block` with a V2 header, sealed.
+fn v2(mut block: BlockDump) -> BlockDump {
+    block.header.version = HeaderVersionDump::V2;
+    seal(&mut block);
+    block
+}
+
+#[test]
+fn v2_header_commits_to_the_transaction_ids() {
+    let v1 = block_with_transactions(10, 3);
+    let block = v2(v1.clone());
+
+    let report = checks::validate_block(&FullBlock::from(block.clone()), Some(&CHAIN), None);
+
+    assert!(
+        report.passed(),
+        "{:?}",
+        report.failures().collect::<Vec<_>>()
+    );
+    assert_ne!(block.id, v1.id);
+
+    // A wrong id breaks the commitment, where V1 headers only fail the id.
+    for (block, failed_header) in [(block, true), (v1, false)] {
+        let mut block = block;
+        flip(&mut block.transactions[1].id);
+        let report = checks::validate_block(&FullBlock::from(block), Some(&CHAIN), None);
+        let failed: Vec<_> = report.failures().map(|failure| failure.check).collect();
+        if failed_header {
+            assert_eq!(
+                failed,
+                [Check::ApplicationHash, Check::BlockId, Check::TxId]
+            );
+        } else {
+            assert_eq!(failed, [Check::TxId]);
+        }
+    }
+}
+
+#[test]
+fn v1_block_claiming_v2_mismatches() {
+    let mut block = valid_block(10);
+    block.header.version = HeaderVersionDump::V2;
+
+    let report = checks::validate_block(&FullBlock::from(block), Some(&CHAIN), None);
+
+    let failed: Vec<_> = report.failures().map(|failure| failure.check).collect();
+    assert_eq!(failed, [Check::ApplicationHash, Check::BlockId]);
+}
+
+#[test]
+fn unknown_header_version_is_unchecked_rather_than_mismatching() {
+    let mut json = serde_json::to_value(valid_block(10)).unwrap();
+    json["header"]["version"] = "V9".into();
+
+    let block: BlockDump = serde_json::from_value(json).expect("unknown versions decode");
+    let report = checks::validate_block(&FullBlock::from(block), Some(&CHAIN), None);
+
+    let failures: Vec<_> = report.failures().collect();
+    assert_eq!(failures.len(), 2);
+    for failure in failures {
+        assert!(matches!(
+            failure.check,
+            Check::ApplicationHash | Check::BlockId
+        ));
+        assert_eq!(failure.error_kind, Some(ErrorKind::Parse));
+    }
+    assert!(report
+        .checks
+        .iter()
+        .filter(|result| result.check == Check::TxRoot)
+        .all(|result| result.passed));
+}
+
+#[tokio::test]
+async fn range_across_an_upgrade_passes() {
+    let mut upgraded: Vec<_> = (12..=14).map(valid_block).map(v2).collect();
+    for block in &mut upgraded {
+        block.header.consensus_parameters_version = 2;
+        block.header.state_transition_bytecode_version = 2;
+        seal(block);
+    }
+    let blocks = (10..=11).map(valid_block).chain(upgraded);
+    let validator = BlockValidator::from_source(MemorySource::new(blocks.map(FullBlock::from)))
+        .with_chain_id(CHAIN);
+
+    let range = validator.validate_range(10, 14).await.unwrap();
+
+    assert_eq!(range.passed(), 5, "{:?}", range.outcomes);
+}
@@ -0,0 +1,149 @@
+// tests/headers_only.rs
+//! Validating the header chain of a range without the transactions of its
+//! blocks.
+
+mod common;
+
+use fuel_root_validation::{
+    dump::{BlockDump, ConsensusDump},
+    BlockValidator, Check, FullBlock, MemorySource, PrevRoots,
+};
+use fuel_types::Bytes32;
+
+use common::{flip, receipts, seal, valid_block};
+
+/// A chain of blocks `0..=9` starting at a genesis block, each committing to
+/// the ids of the blocks before it. `corrupt` is applied to each block before
+/// it is sealed.
+fn chain(mut corrupt: impl FnMut(&mut BlockDump)) -> Vec<BlockDump> {
+    let mut roots = PrevRoots::new();
+    let mut blocks = vec![];
+    for height in 0..=9 {
+        let mut block = valid_block(height);
+        if height == 0 {
+            block.consensus = ConsensusDump::Genesis {
+                chain_config_hash: Bytes32::zeroed(),
+                coins_root: Bytes32::zeroed(),
+                contracts_root: Bytes32::zeroed(),
+                messages_root: Bytes32::zeroed(),
+                transactions_root: Bytes32::zeroed(),
+            };
+        }
+        block.header.prev_root = roots.root();
+        corrupt(&mut block);
+        seal(&mut block);
+        roots.push(height, &block.id).unwrap();
+        blocks.push(block);
+    }
+    blocks
+}
+
+fn validator(blocks: Vec<BlockDump>) -> BlockValidator {
+    BlockValidator::from_source(MemorySource::new(blocks.into_iter().map(FullBlock::from)))
+        .with_headers_only(true)
+        .with_prev_root_check(true)
+        .with_page_size(3)
+}
+
+/// Heights of the blocks of `0..=9` failing `check`.
+async fn failing(validator: &BlockValidator, check: Check) -> Vec<u32> {
+    let range = validator.validate_range(0, 9).await.unwrap();
+    range
+        .outcomes
+        .iter()
+        .filter(|outcome| {
+            let report = outcome.result.as_ref().unwrap();
+            report.failures().any(|failure| failure.check == check)
+        })
+        .map(|outcome| outcome.height)
+        .collect()
+}
+
+#[tokio::test]
+async fn linked_headers_pass() {
+    let validator = validator(chain(|_| {}));
+
+    let range = validator.validate_range(0, 9).await.unwrap();
+
+    assert_eq!(range.passed(), 10);
+    let report = range.outcomes[5].result.as_ref().unwrap();
+    let checks: Vec<_> = report.checks.iter().map(|result| result.check).collect();
+    assert_eq!(
+        checks,
+        [Check::ApplicationHash, Check::BlockId, Check::PrevRoot]
+    );
+}
+
+#[tokio::test]
+async fn transactions_arent_checked() {
+    // The receipts root of the transaction no longer matches its receipts.
+    let blocks = chain(|block| {
+        receipts(block, 0).pop();
+    });
+
+    assert!(validator(blocks)
+        .validate_range(0, 9)
+        .await
+        .unwrap()
+        .outcomes
+        .iter()
+        .all(|o| o.passed()));
+}
+
+#[tokio::test]
+async fn broken_link_fails_the_blocks_after_it() {
+    let mut blocks = chain(|_| {});
+    // Block 4 is sealed again on another parent, which changes its id.
+    flip(&mut blocks[4].header.prev_root);
+    seal(&mut blocks[4]);
+
+    let validator = validator(blocks);
+
+    assert_eq!(
+        failing(&validator, Check::PrevRoot).await,
+        [4, 5, 6, 7, 8, 9]
+    );
+    assert!(failing(&validator, Check::BlockId).await.is_empty());
+}
+
+#[tokio::test]
+async fn altered_header_fails_its_block_id() {
+    let mut blocks = chain(|_| {});
+    blocks[6].header.da_height += 1;
+
+    let validator = validator(blocks);
+
+    assert_eq!(failing(&validator, Check::BlockId).await, [6]);
+    assert!(failing(&validator, Check::PrevRoot).await.is_empty());
+}
+
+#[tokio::test]
+async fn missing_header_is_reported_and_unlinks_the_rest() {
+    let mut blocks = chain(|_| {});
+    blocks.remove(3);
+
+    let range = validator(blocks).validate_range(0, 9).await.unwrap();
+
+    assert!(range.outcomes[3].result.is_err());
+    let report = range.outcomes[4].result.as_ref().unwrap();
+    let prev_root = report
+        .checks
+        .iter()
+        .find(|result| result.check == Check::PrevRoot)
+        .unwrap();
+    assert!(!prev_root.passed);
+}
+
+#[tokio::test]
+async fn single_height_is_validated_from_its_header() {
+    let validator = validator(chain(|_| {})).with_prev_root_check(false);
+
+    let outcome = validator.validate_height(7).await;
+
+    assert!(outcome.passed());
+    let report = outcome.result.unwrap();
+    assert!(report
+        .checks
+        .iter()
+        .all(|result| result.check != Check::TxRoot));
+}
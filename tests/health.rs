@@ -0,0 +1,107 @@
+// tests/health.rs
+//! Readiness of the validator as it follows the chain, and the probes of
+//! the HTTP API.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use fuel_root_validation::{
+    server, BlockValidator, FullBlock, Health, MemorySource, ValidationError,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use common::valid_block;
+
+fn validator(heights: impl IntoIterator<Item = u32>) -> BlockValidator {
+    BlockValidator::from_source(MemorySource::new(
+        heights
+            .into_iter()
+            .map(|height| FullBlock::from(valid_block(height))),
+    ))
+}
+
+#[tokio::test]
+async fn ready_while_validation_keeps_up_with_the_head() {
+    let validator = validator(10..=11);
+    let health = Health::new().with_max_lag(2);
+    let readiness = health.readiness();
+    assert!(!readiness.ready);
+    assert_eq!(
+        readiness.reason.as_deref(),
+        Some("the node hasn't been reached yet")
+    );
+
+    health.probe(&validator).await;
+    assert_eq!(
+        health.readiness().reason.as_deref(),
+        Some("no block has been validated yet")
+    );
+
+    for outcome in validator.validate_range(10, 11).await.unwrap().outcomes {
+        health.observe(&outcome);
+    }
+    let readiness = health.readiness();
+    assert!(readiness.ready, "{readiness:?}");
+    assert_eq!(
+        (
+            readiness.validated_height,
+            readiness.head_height,
+            readiness.lag
+        ),
+        (Some(11), Some(11), Some(0))
+    );
+
+    health.observe_head(&Ok(14));
+    let readiness = health.readiness();
+    assert!(!readiness.ready);
+    assert_eq!(readiness.lag, Some(3));
+
+    health.observe_head(&Ok(13));
+    assert!(health.readiness().ready);
+    health.observe_head(&Err(ValidationError::Cancelled));
+    let readiness = health.readiness();
+    assert!(!readiness.ready);
+    assert_eq!(readiness.head_height, Some(13));
+    assert!(readiness
+        .reason
+        .unwrap()
+        .starts_with("the node can't be reached"));
+}
+
+async fn get(validator: BlockValidator, path: &str) -> (StatusCode, Vec<u8>) {
+    let response = server::router(Arc::new(validator))
+        .oneshot(Request::get(path).body(Body::empty()).unwrap())
+        .await
+        .expect("the router doesn't fail");
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    (status, body.to_vec())
+}
+
+#[tokio::test]
+async fn api_is_ready_while_its_source_answers() {
+    let (status, body) = get(validator([]), "/healthz").await;
+    assert_eq!(
+        (status, body.as_slice()),
+        (StatusCode::OK, b"ok".as_slice())
+    );
+
+    let (status, body) = get(validator([10]), "/readyz").await;
+    let readiness: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(readiness["ready"], true);
+    assert_eq!(readiness["head_height"], 10);
+    assert_eq!(readiness["validated_height"], Value::Null);
+
+    let (status, body) = get(validator([]), "/readyz").await;
+    let readiness: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(readiness["ready"], false);
+    assert!(readiness["reason"].as_str().is_some(), "{readiness:#}");
+}
@@ -0,0 +1,54 @@
+// tests/inspect.rs
+//! The transactions of a block decoded for inspection.
+
+mod common;
+
+use fuel_root_validation::{inspect::inspect, FullBlock};
+use fuel_tx::{field::Script as _, Receipt, Transaction};
+
+use common::{receipts, valid_block};
+
+#[test]
+fn transactions_decode_with_their_receipts() {
+    let block = valid_block(10);
+    let ids: Vec<_> = block.transactions.iter().map(|tx| tx.id).collect();
+
+    let transactions = inspect(&FullBlock::from(block));
+
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(transactions[0].id, ids[0]);
+    assert_eq!(transactions[0].status, Some("success"));
+    assert!(matches!(
+        transactions[0].transaction,
+        Some(Transaction::Script(ref script)) if script.script().is_empty()
+    ));
+    assert!(matches!(
+        transactions[0].receipts.as_slice(),
+        [Receipt::MessageOut { .. }, Receipt::ScriptResult { .. }]
+    ));
+    assert!(matches!(
+        transactions[1].transaction,
+        Some(Transaction::Mint(_))
+    ));
+    assert!(transactions.iter().all(|tx| tx.errors.is_empty()));
+
+    let text = transactions[0].to_string();
+    assert!(text.contains("script, success"), "{text}");
+    assert!(text.contains("receipts: 2"), "{text}");
+}
+
+#[test]
+fn undecodable_parts_are_reported_with_the_rest() {
+    let mut block = valid_block(10);
+    receipts(&mut block, 0)[1].gas_used = None;
+    let payload = &mut block.transactions[1].raw_payload.0;
+    payload.truncate(payload.len() / 2);
+
+    let transactions = inspect(&FullBlock::from(block));
+
+    assert_eq!(transactions[0].receipts.len(), 1);
+    assert_eq!(transactions[0].errors.len(), 1);
+    assert!(transactions[0].errors[0].starts_with("receipt #1"));
+    assert!(transactions[1].transaction.is_none());
+    assert_eq!(transactions[1].errors.len(), 1);
+}
@@ -0,0 +1,87 @@
+// tests/keep_going.rs
+//! Range scans that keep going past blocks that can't be fetched.
+
+mod common;
+
+use std::io;
+
+use fuel_root_validation::{
+    checkpoint::RecordedFailure, error::Result, BlockSource, BlockValidator, ErrorKind, FullBlock,
+    MemorySource, ValidationError,
+};
+
+use common::valid_block;
+
+/// A node that times out on every page query, and on the block at `bad`.
+#[derive(Debug)]
+struct TimingOut {
+    blocks: MemorySource,
+    bad: u32,
+}
+
+fn timeout(query: &'static str) -> ValidationError {
+    ValidationError::Query {
+        query,
+        attempts: 3,
+        source: io::Error::new(io::ErrorKind::TimedOut, "timed out"),
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockSource for TimingOut {
+    async fn fetch_block(&self, height: u32) -> Result<FullBlock> {
+        if height == self.bad {
+            return Err(timeout("block"));
+        }
+        self.blocks.fetch_block(height).await
+    }
+
+    async fn fetch_range(&self, _: u32, _: u32) -> Result<Vec<FullBlock>> {
+        Err(timeout("blocks"))
+    }
+
+    async fn latest_height(&self) -> Result<u32> {
+        self.blocks.latest_height().await
+    }
+}
+
+fn validator() -> BlockValidator {
+    let blocks = MemorySource::new((1..=6).map(|height| FullBlock::from(valid_block(height))));
+    BlockValidator::from_source(TimingOut { blocks, bad: 4 }).with_page_size(3)
+}
+
+#[tokio::test]
+async fn query_errors_abort_the_range_by_default() {
+    let error = validator().validate_range(1, 6).await.unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::Query);
+}
+
+#[tokio::test]
+async fn keep_going_reports_the_blocks_that_cant_be_fetched() {
+    let validator = validator().with_keep_going(true);
+
+    let range = validator.validate_range(1, 6).await.unwrap();
+
+    let heights: Vec<_> = range
+        .outcomes
+        .iter()
+        .map(|outcome| outcome.height)
+        .collect();
+    assert_eq!(heights, [1, 2, 3, 4, 5, 6]);
+    assert_eq!((range.passed(), range.failed()), (5, 1));
+
+    let failures: Vec<_> = range
+        .outcomes
+        .iter()
+        .filter_map(RecordedFailure::of)
+        .collect();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].height, 4);
+    assert_eq!(failures[0].kinds, [ErrorKind::Query]);
+    assert!(
+        failures[0].reason.contains("timed out"),
+        "{}",
+        failures[0].reason
+    );
+}
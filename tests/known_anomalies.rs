@@ -0,0 +1,136 @@
+// tests/known_anomalies.rs
+//! Failures that are known anomalies are acknowledged rather than failing
+//! their block.
+
+mod common;
+
+use std::fs;
+
+use fuel_root_validation::{
+    BlockValidator, Check, FullBlock, KnownAnomalies, KnownAnomaly, MemorySource, ValidationError,
+};
+use fuel_types::Bytes32;
+
+use common::{bad_block, temp_dir, valid_block};
+
+fn tx_id(block: &FullBlock) -> Bytes32 {
+    block.transactions[0].id.clone().into()
+}
+
+fn anomaly(height: Option<u32>, tx_id: Option<Bytes32>, check: Option<&str>) -> KnownAnomaly {
+    KnownAnomaly {
+        height,
+        tx_id,
+        check: check.map(ToString::to_string),
+        note: "receipts of a node bug".to_string(),
+    }
+}
+
+fn validator(anomalies: Vec<KnownAnomaly>) -> BlockValidator {
+    BlockValidator::from_source(MemorySource::new([]))
+        .with_known_anomalies(KnownAnomalies::new(anomalies).expect("the anomalies are valid"))
+}
+
+#[test]
+fn known_failures_are_acknowledged() {
+    let block = bad_block(10);
+
+    for known in [
+        anomaly(Some(10), None, None),
+        anomaly(Some(10), None, Some("receipt-root")),
+        anomaly(None, Some(tx_id(&block)), None),
+        anomaly(Some(10), Some(tx_id(&block)), Some("receipt-root")),
+    ] {
+        let report = validator(vec![known.clone()]).validate_full_block(&block);
+        assert!(report.passed(), "{known:?}");
+        assert_eq!(report.failures().count(), 0);
+        let acknowledged: Vec<_> = report.acknowledged().collect();
+        assert_eq!(acknowledged.len(), 1, "{known:?}");
+        assert_eq!(acknowledged[0].check, Check::ReceiptRoot);
+        assert_eq!(
+            acknowledged[0].acknowledged.as_deref(),
+            Some("receipts of a node bug")
+        );
+    }
+}
+
+#[test]
+fn other_failures_still_fail() {
+    let block = bad_block(10);
+
+    for other in [
+        anomaly(Some(11), None, None),
+        anomaly(Some(10), None, Some("tx-root")),
+        anomaly(None, Some(Bytes32::new([9; 32])), None),
+        anomaly(Some(11), Some(tx_id(&block)), None),
+    ] {
+        let report = validator(vec![other.clone()]).validate_full_block(&block);
+        assert!(!report.passed(), "{other:?}");
+        assert_eq!(report.acknowledged().count(), 0, "{other:?}");
+    }
+}
+
+#[tokio::test]
+async fn range_counts_acknowledged_blocks_apart() {
+    let validator = BlockValidator::from_source(MemorySource::new([
+        valid_block(10).into(),
+        bad_block(11),
+        bad_block(12),
+    ]))
+    .with_known_anomalies(KnownAnomalies::new(vec![anomaly(Some(11), None, None)]).unwrap());
+
+    let range = validator.validate_range(10, 12).await.unwrap();
+    assert_eq!(
+        (range.passed(), range.acknowledged(), range.failed()),
+        (2, 1, 1)
+    );
+    assert!(range.outcomes[1].acknowledged());
+    assert!(!range.outcomes[0].acknowledged());
+
+    let json = serde_json::to_value(&range).unwrap();
+    assert_eq!(json["acknowledged"], 1);
+    let checks = json["outcomes"][1]["report"]["checks"].as_array().unwrap();
+    assert!(checks
+        .iter()
+        .any(|check| check["acknowledged"] == "receipts of a node bug"));
+}
+
+#[test]
+fn anomalies_are_loaded_from_json() {
+    let dir = temp_dir("known_anomalies");
+    let path = dir.join("anomalies.json");
+    let block = bad_block(10);
+    fs::write(
+        &path,
+        format!(
+            r#"[
+                {{ "height": 3674967, "check": "receipt-root", "note": "testnet quirk" }},
+                {{ "tx_id": "{:#x}", "note": "receipts of a node bug" }}
+            ]"#,
+            tx_id(&block)
+        ),
+    )
+    .unwrap();
+    let anomalies = KnownAnomalies::load(&path).unwrap();
+    assert_eq!(anomalies.len(), 2);
+    let report = BlockValidator::from_source(MemorySource::new([]))
+        .with_known_anomalies(anomalies)
+        .validate_full_block(&block);
+    assert!(report.passed());
+
+    fs::write(
+        &path,
+        r#"[{ "check": "receipt-root", "note": "everything" }]"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        KnownAnomalies::load(&path),
+        Err(ValidationError::InvalidKnownAnomaly { index: 0, .. })
+    ));
+
+    fs::write(&path, r#"[{ "height": 1 }]"#).unwrap();
+    assert!(matches!(
+        KnownAnomalies::load(&path),
+        Err(ValidationError::ParseKnownAnomalies { .. })
+    ));
+}
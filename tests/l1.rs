@@ -0,0 +1,102 @@
+// tests/l1.rs
+//! Cross-checks blocks with a stub of the L1 chain state contract.
+
+mod common;
+
+use fuel_root_validation::{
+    BlockValidator, Check, EthAddress, FullBlock, L1ChainState, MemorySource,
+};
+use fuel_types::{Bytes32, ChainId};
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+use common::{valid_block, CHAIN_ID};
+
+/// Serves a JSON-RPC endpoint whose latest block is `latest_block`, with a
+/// commit interval of 10 blocks committing `committed` for every interval.
+async fn stub_l1(latest_block: u64, committed: Bytes32) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = vec![];
+            let mut buffer = [0; 4096];
+            let body = loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length: ")
+                                .map(str::to_owned)
+                        })
+                        .and_then(|length| length.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break body.to_owned();
+                    }
+                }
+            };
+
+            let request: Value = serde_json::from_str(&body).unwrap();
+            let result = match request["method"].as_str() {
+                Some("eth_blockNumber") => json!(format!("{latest_block:#x}")),
+                // `BLOCKS_PER_COMMIT_INTERVAL()` is the only call without
+                // arguments, `blockHashAtCommit(uint256)` the other one.
+                Some("eth_call") => match request["params"][0]["data"].as_str().unwrap().len() {
+                    10 => json!(format!("0x{:064x}", 10)),
+                    _ => json!(format!("0x{}", hex::encode(committed))),
+                },
+                method => panic!("unexpected method {method:?}"),
+            };
+            let response =
+                json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response}",
+                response.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    url
+}
+
+async fn l1_checks(latest_block: u64, committed: Bytes32) -> Vec<(Check, bool)> {
+    let block = valid_block(10);
+    let l1 = L1ChainState::new(stub_l1(latest_block, committed).await, EthAddress([1; 20]));
+    let report = BlockValidator::from_source(MemorySource::new([FullBlock::from(block)]))
+        .with_chain_id(ChainId::new(CHAIN_ID))
+        .with_l1_chain_state(l1)
+        .validate_block(10)
+        .await
+        .expect("the block is in the source");
+
+    report
+        .checks
+        .iter()
+        .filter(|result| matches!(result.check, Check::L1DaHeight | Check::L1Commit))
+        .map(|result| (result.check, result.passed))
+        .collect()
+}
+
+#[tokio::test]
+async fn committed_block_passes() {
+    let id = valid_block(10).id;
+
+    let checks = l1_checks(100, id).await;
+
+    assert_eq!(checks, [(Check::L1DaHeight, true), (Check::L1Commit, true)]);
+}
+
+#[tokio::test]
+async fn da_height_ahead_of_l1_fails() {
+    let checks = l1_checks(99, Bytes32::zeroed()).await;
+
+    assert_eq!(checks, [(Check::L1DaHeight, false)]);
+}
@@ -0,0 +1,116 @@
+// tests/memory.rs
+//! Range scans bounded by a memory budget.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use fuel_root_validation::{
+    error::Result, memory, BlockSource, BlockValidator, FullBlock, MemoryBudget, MemorySource,
+};
+
+use common::block_with_transactions;
+
+type Pages = Arc<Mutex<Vec<(u32, u32)>>>;
+
+/// Blocks of many transactions, recording the pages queried.
+#[derive(Debug, Default)]
+struct Recording {
+    blocks: MemorySource,
+    pages: Pages,
+}
+
+#[async_trait::async_trait]
+impl BlockSource for Recording {
+    async fn fetch_block(&self, height: u32) -> Result<FullBlock> {
+        self.blocks.fetch_block(height).await
+    }
+
+    async fn fetch_range(&self, from: u32, to: u32) -> Result<Vec<FullBlock>> {
+        self.pages.lock().unwrap().push((from, to));
+        self.blocks.fetch_range(from, to).await
+    }
+
+    async fn latest_height(&self) -> Result<u32> {
+        self.blocks.latest_height().await
+    }
+}
+
+fn large_block(height: u32) -> FullBlock {
+    block_with_transactions(height, 200).into()
+}
+
+fn recording() -> (Recording, Pages) {
+    let source = Recording {
+        blocks: MemorySource::new((1..=40).map(large_block)),
+        ..Recording::default()
+    };
+    let pages = source.pages.clone();
+    (source, pages)
+}
+
+#[tokio::test]
+async fn pages_shrink_to_fit_large_blocks() {
+    let size = memory::estimated_size(&large_block(1));
+    let (source, pages) = recording();
+    let validator = BlockValidator::from_source(source)
+        .with_page_size(10)
+        .with_concurrency(2)
+        .with_max_memory(4 * size as u64);
+
+    let range = validator.validate_range(1, 40).await.unwrap();
+    assert_eq!(range.passed(), 40);
+    assert!(range
+        .outcomes
+        .iter()
+        .map(|outcome| outcome.height)
+        .eq(1..=40));
+
+    let budget = validator.memory_budget().unwrap();
+    assert_eq!(budget.block_size(), size);
+    assert_eq!(budget.available(), budget.bytes());
+    let pages = pages.lock().unwrap().clone();
+    // The first pages are sized before any block is fetched, later ones
+    // hold at most half the budget.
+    assert!(pages.len() > 10, "{pages:?}");
+    for &(from, to) in &pages[2..] {
+        assert!(to - from < 2, "{pages:?}");
+    }
+
+    let (source, pages) = recording();
+    BlockValidator::from_source(source)
+        .with_page_size(10)
+        .validate_range(1, 40)
+        .await
+        .unwrap();
+    assert_eq!(pages.lock().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn reservations_are_given_back_as_blocks_are_validated() {
+    let block = large_block(1);
+    let size = memory::estimated_size(&block);
+    let budget = MemoryBudget::new(8 * size as u64);
+    assert_eq!(budget.block_size(), memory::DEFAULT_BLOCK_ESTIMATE);
+
+    budget.observe(&block);
+    assert_eq!(budget.block_size(), size);
+    assert_eq!(budget.blocks_per_page(100, 2), 4);
+    assert_eq!(budget.blocks_per_page(3, 2), 3);
+    assert_eq!(budget.blocks_per_page(100, 100), 1);
+
+    let mut reservation = budget.reserve(4).await;
+    let held = budget.bytes() - budget.available();
+    assert!(held >= 4 * size as u64 && held < 5 * size as u64, "{held}");
+    reservation.release(size);
+    assert!(budget.bytes() - budget.available() < held);
+    drop(reservation);
+    assert_eq!(budget.available(), budget.bytes());
+
+    // A page larger than the budget takes it whole rather than waiting
+    // forever.
+    let whole = budget.reserve(100).await;
+    assert_eq!(budget.available(), 0);
+    drop(whole);
+    assert_eq!(budget.available(), budget.bytes());
+}
@@ -0,0 +1,55 @@
+// tests/merkle.rs
+//! The Merkle roots computed from separately hashed leaves, in parallel with
+//! the `parallel` feature.
+
+mod common;
+
+use fuel_root_validation::{checks, merkle, FullBlock};
+
+use common::{block_with_transactions, receipts, root};
+
+#[test]
+fn root_of_leaf_hashes_is_the_root_of_the_leaves() {
+    for count in 0..70u8 {
+        let leaves: Vec<_> = (0..count)
+            .map(|leaf| vec![leaf; usize::from(leaf)])
+            .collect();
+
+        let calculated = merkle::root(leaves.iter().map(|leaf| merkle::leaf_hash(leaf)));
+
+        assert_eq!(calculated, root(&leaves), "{count} leaves");
+    }
+}
+
+#[test]
+fn block_with_many_transactions_is_valid() {
+    let block = FullBlock::from(block_with_transactions(10, 300));
+
+    assert!(checks::tx_root(&block).passed);
+    let receipt_roots = checks::receipt_roots(&block);
+    assert_eq!(receipt_roots.len(), 300);
+    assert!(receipt_roots.iter().all(|result| result.passed));
+}
+
+#[test]
+fn results_are_in_transaction_order() {
+    let mut block = block_with_transactions(10, 100);
+    // The trailing mint commits to no receipts.
+    let tx_ids: Vec<_> = block.transactions[..100]
+        .iter()
+        .map(|tx| format!("{:#x}", tx.id))
+        .collect();
+    receipts(&mut block, 57).pop();
+    let block = FullBlock::from(block);
+
+    let results = checks::receipt_roots(&block);
+
+    let checked: Vec<_> = results
+        .iter()
+        .filter_map(|result| result.tx_id.clone())
+        .collect();
+    assert_eq!(checked, tx_ids);
+    let failed: Vec<_> = results.iter().map(|result| !result.passed).collect();
+    assert_eq!(failed.iter().position(|failed| *failed), Some(57));
+    assert_eq!(failed.iter().filter(|failed| **failed).count(), 1);
+}
@@ -0,0 +1,110 @@
+// tests/mint.rs
+//! The mint of the fees of a block to its producer.
+
+mod common;
+
+use fuel_root_validation::{
+    checks, dump::TransactionDump, BlockDump, BlockValidator, ChainParameters, Check, FullBlock,
+    MemorySource,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::{AssetId, ChainId, ContractId};
+
+use common::{block_with_transactions, mint, root, seal, CHAIN_ID, COINBASE};
+
+/// Recommits `block` to its transactions, as a node producing it would.
+fn recommit(block: &mut BlockDump) {
+    block.header.transactions_count = block.transactions.len() as u16;
+    block.header.transactions_root =
+        root(block.transactions.iter().map(|tx| tx.raw_payload.0.clone()));
+    seal(block);
+}
+
+/// `block` with its mint replaced by `mint`.
+fn with_mint(mut block: BlockDump, mint: TransactionDump) -> BlockDump {
+    block.transactions.pop();
+    block.transactions.push(mint);
+    recommit(&mut block);
+    block
+}
+
+fn failed(block: BlockDump) -> Vec<Check> {
+    let chain_id = ChainId::new(CHAIN_ID);
+    checks::validate_block(&FullBlock::from(block), Some(&chain_id), None)
+        .failures()
+        .map(|failure| failure.check)
+        .collect()
+}
+
+#[test]
+fn mint_collects_the_fees_of_the_block() {
+    let block = FullBlock::from(block_with_transactions(10, 3));
+
+    let results = checks::mint(&block);
+
+    let checks: Vec<_> = results.iter().map(|result| result.check).collect();
+    assert_eq!(checks, [Check::MintPosition, Check::MintAmount]);
+    assert!(results.iter().all(|result| result.passed));
+    assert_eq!(results[1].actual.as_deref(), Some("3"));
+}
+
+#[test]
+fn mint_must_be_the_last_transaction() {
+    let mut block = block_with_transactions(10, 2);
+    block.transactions.rotate_right(1);
+    recommit(&mut block);
+    assert_eq!(failed(block), [Check::MintPosition]);
+
+    let mut block = block_with_transactions(10, 2);
+    block.transactions.pop();
+    recommit(&mut block);
+    assert_eq!(failed(block), [Check::MintPosition]);
+
+    let block = block_with_transactions(10, 2);
+    let mut twice = block.clone();
+    twice.transactions.insert(0, block.transactions[2].clone());
+    recommit(&mut twice);
+    assert_eq!(failed(twice), [Check::MintPosition]);
+}
+
+#[test]
+fn mint_amount_must_match_the_fees() {
+    let block = with_mint(
+        block_with_transactions(10, 2),
+        mint(10, 2, COINBASE, 3, AssetId::BASE),
+    );
+
+    assert_eq!(failed(block), [Check::MintAmount]);
+}
+
+#[test]
+fn mint_without_coinbase_recipient_mints_nothing() {
+    let block = block_with_transactions(10, 2);
+    let unpaid = with_mint(
+        block.clone(),
+        mint(10, 2, ContractId::zeroed(), 0, AssetId::BASE),
+    );
+    let paid = with_mint(block, mint(10, 2, ContractId::zeroed(), 2, AssetId::BASE));
+
+    assert_eq!(failed(unpaid), []);
+    assert_eq!(failed(paid), [Check::MintAmount]);
+}
+
+#[tokio::test]
+async fn mint_asset_is_the_base_asset_of_the_consensus_parameters() {
+    let block = with_mint(
+        block_with_transactions(10, 1),
+        mint(10, 1, COINBASE, 1, AssetId::new([5; 32])),
+    );
+    let mut params = ConsensusParameters::standard_with_id(ChainId::new(CHAIN_ID));
+    params.set_base_asset_id(AssetId::BASE);
+    let mut pinned = ChainParameters::new();
+    pinned.insert(block.header.consensus_parameters_version, params);
+    let validator = BlockValidator::from_source(MemorySource::new([FullBlock::from(block)]))
+        .with_consensus_parameters(pinned);
+
+    let report = validator.validate_block(10).await.unwrap();
+
+    let failed: Vec<_> = report.failures().map(|failure| failure.check).collect();
+    assert_eq!(failed, [Check::MintAssetId]);
+}
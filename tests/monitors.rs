@@ -0,0 +1,94 @@
+// tests/monitors.rs
+//! Metrics and readiness of several networks followed by one process.
+
+mod common;
+
+use std::sync::Arc;
+
+use fuel_root_validation::{
+    metrics::Monitored, BlockValidator, FullBlock, Health, MemorySource, Metrics,
+};
+
+use common::valid_block;
+
+async fn monitored(network: &str, heights: std::ops::RangeInclusive<u32>) -> Monitored {
+    let metrics = Arc::new(Metrics::for_network(network));
+    let health = Arc::new(Health::new().with_max_lag(2));
+    let validator = BlockValidator::from_source(MemorySource::new(
+        heights
+            .clone()
+            .map(|height| FullBlock::from(valid_block(height))),
+    ))
+    .with_metrics(metrics.clone());
+    health.probe(&validator).await;
+    for outcome in validator
+        .validate_range(*heights.start(), *heights.end())
+        .await
+        .unwrap()
+        .outcomes
+    {
+        metrics.observe(&outcome);
+        health.observe(&outcome);
+    }
+    Monitored {
+        network: network.to_string(),
+        metrics,
+        health,
+    }
+}
+
+#[tokio::test]
+async fn metrics_of_every_network_are_labelled() {
+    let networks = [
+        monitored("mainnet", 1..=3).await,
+        monitored("testnet", 10..=11).await,
+    ];
+
+    let text = networks[0].metrics.encode();
+    assert!(
+        text.contains(r#"fuel_validator_blocks_validated_total{network="mainnet"} 3"#),
+        "{text}"
+    );
+
+    let text = Monitored::encode_all(&networks);
+    assert!(
+        text.contains(r#"fuel_validator_blocks_validated_total{network="mainnet"} 3"#),
+        "{text}"
+    );
+    assert!(
+        text.contains(r#"fuel_validator_blocks_validated_total{network="testnet"} 2"#),
+        "{text}"
+    );
+    assert!(
+        text.contains(r#"fuel_validator_last_validated_height{network="testnet"} 11"#),
+        "{text}"
+    );
+    // Every metric is one family, as the text format requires.
+    assert_eq!(
+        text.matches("# TYPE fuel_validator_blocks_validated_total counter")
+            .count(),
+        1,
+        "{text}"
+    );
+}
+
+#[tokio::test]
+async fn ready_once_every_network_is() {
+    let networks = vec![
+        monitored("mainnet", 1..=3).await,
+        monitored("testnet", 10..=11).await,
+    ];
+    let readiness = Monitored::readiness_all(&networks);
+    assert!(readiness.ready, "{readiness:?}");
+    assert_eq!(readiness.networks["testnet"].validated_height, Some(11));
+
+    networks[1].health.observe_head(&Ok(20));
+    let readiness = Monitored::readiness_all(&networks);
+    assert!(!readiness.ready);
+    assert!(readiness.networks["mainnet"].ready);
+    assert_eq!(readiness.networks["testnet"].lag, Some(9));
+
+    let json = serde_json::to_value(&readiness).unwrap();
+    assert_eq!(json["ready"], false);
+    assert_eq!(json["networks"]["testnet"]["ready"], false);
+}
@@ -0,0 +1,73 @@
+// tests/network.rs
+//! The built-in network profiles and custom ones built on top of them.
+
+use fuel_root_validation::{Network, NetworkProfile, NetworkUpgrade, ValidationError};
+use fuel_types::Address;
+
+#[test]
+fn builtin_profiles_know_their_endpoint_and_chain_id() {
+    let mainnet = NetworkProfile::builtin("Mainnet").unwrap();
+
+    assert_eq!(mainnet, Network::Mainnet.profile());
+    assert_eq!(mainnet.name, "mainnet");
+    assert_eq!(
+        mainnet.endpoint(),
+        Some("https://mainnet.fuel.network/v1/graphql")
+    );
+    assert_eq!(mainnet.chain_id, Some(9889));
+    assert_eq!(NetworkProfile::default().name, "testnet");
+    assert!(NetworkProfile::builtin("local").is_none());
+    assert!(matches!(
+        "local".parse::<Network>(),
+        Err(ValidationError::UnknownNetwork(name)) if name == "local"
+    ));
+}
+
+#[test]
+fn custom_profiles_keep_their_upgrades_sorted() {
+    let authority = Address::new([7; 32]);
+    let profile = NetworkProfile::new("local")
+        .with_endpoints(vec!["http://127.0.0.1:4000/v1/graphql".to_string()])
+        .with_chain_id(1)
+        .with_poa_authority(authority)
+        .with_upgrade(300, "stf-v2")
+        .with_upgrade(100, "consensus-v1");
+
+    assert_eq!(profile.poa_authority, Some(authority));
+    assert_eq!(
+        profile.upgrades,
+        [
+            NetworkUpgrade {
+                height: 100,
+                name: "consensus-v1".to_string()
+            },
+            NetworkUpgrade {
+                height: 300,
+                name: "stf-v2".to_string()
+            }
+        ]
+    );
+    let crossed: Vec<_> = profile.upgrades_in(100, 299).map(|u| u.height).collect();
+    assert_eq!(crossed, [100]);
+    assert_eq!(profile.upgrade_at(300).unwrap().name, "stf-v2");
+    assert!(profile.upgrade_at(301).is_none());
+}
+
+#[test]
+fn profile_authorities_switch_keys_at_their_overrides() {
+    let genesis = Address::new([1; 32]);
+    let rotated = Address::new([2; 32]);
+    let profile = NetworkProfile::new("local")
+        .with_signing_key_override(1000, rotated)
+        .with_poa_authority(genesis);
+
+    let authority = profile.authority().unwrap();
+    assert_eq!(authority.genesis_signing_key, genesis);
+    assert_eq!(authority.signing_key_at(999), genesis);
+    assert_eq!(authority.signing_key_at(1000), rotated);
+    assert_eq!(authority.signing_key_at(u32::MAX), rotated);
+    assert!(NetworkProfile::new("local")
+        .with_signing_key_override(1000, rotated)
+        .authority()
+        .is_none());
+}
@@ -0,0 +1,139 @@
+// tests/proof_bundle.rs
+//! Proof bundles of transactions and their receipts, verified on their own
+//! and in their binary encoding.
+
+mod common;
+
+use fuel_crypto::{Hasher, Message, SecretKey, Signature};
+use fuel_root_validation::{
+    dump::ConsensusDump, BlockDump, Check, CheckResult, FullBlock, PoaAuthority, ProofBundle,
+    ValidationError,
+};
+use fuel_tx::Input;
+use fuel_types::{Bytes32, Bytes64, ChainId};
+
+use common::{block_with_transactions, receipts, seal, CHAIN_ID};
+
+fn bundle(block: &BlockDump, tx: usize) -> ProofBundle {
+    ProofBundle::build(&FullBlock::from(block.clone()), &block.transactions[tx].id)
+        .expect("the block commits to its transactions")
+}
+
+fn failed_checks(results: &[CheckResult]) -> Vec<Check> {
+    results
+        .iter()
+        .filter(|result| !result.passed)
+        .map(|result| result.check)
+        .collect()
+}
+
+#[test]
+fn bundle_proves_the_transaction_and_every_receipt() {
+    let block = block_with_transactions(10, 3);
+    let proof = bundle(&block, 1);
+
+    assert_eq!(proof.block_id, block.id);
+    assert_eq!(proof.transaction.proof_index, 1);
+    assert_eq!(proof.receipts.len(), 2);
+    let results = proof.verify(Some(&ChainId::new(CHAIN_ID)), None);
+    assert!(failed_checks(&results).is_empty(), "{results:?}");
+    for check in [
+        Check::BlockId,
+        Check::TxInclusion,
+        Check::TxId,
+        Check::ReceiptRoot,
+    ] {
+        assert!(
+            results.iter().any(|result| result.check == check),
+            "{check} wasn't run"
+        );
+    }
+
+    let mint = bundle(&block, block.transactions.len() - 1);
+    assert_eq!(mint.receipts_root, None);
+    let results = mint.verify(None, None);
+    assert!(failed_checks(&results).is_empty(), "{results:?}");
+    assert!(!results
+        .iter()
+        .any(|result| result.check == Check::ReceiptRoot));
+}
+
+#[test]
+fn tampered_bundles_fail_verification() {
+    let block = block_with_transactions(10, 2);
+
+    let mut reordered = bundle(&block, 0);
+    reordered.receipts.swap(0, 1);
+    assert_eq!(
+        failed_checks(&reordered.verify(None, None)),
+        [Check::ReceiptRoot]
+    );
+
+    let mut moved = bundle(&block, 0);
+    moved.header.height += 1;
+    assert_eq!(failed_checks(&moved.verify(None, None)), [Check::BlockId]);
+
+    let mut swapped = bundle(&block, 0);
+    swapped.transaction = bundle(&block, 1).transaction;
+    assert_eq!(
+        failed_checks(&swapped.verify(None, None)),
+        [Check::ReceiptRoot]
+    );
+}
+
+#[test]
+fn signature_is_checked_against_the_authority() {
+    let mut block = block_with_transactions(10, 1);
+    let key = SecretKey::try_from([7; 32].as_slice()).unwrap();
+    let signature = Signature::sign(&key, &Message::from_bytes(*block.id));
+    block.consensus = ConsensusDump::Poa {
+        signature: Bytes64::new(*signature),
+    };
+    let bundle = bundle(&block, 0);
+
+    let producer = PoaAuthority::new(Input::owner(&key.public_key()));
+    assert!(failed_checks(&bundle.verify(None, Some(&producer))).is_empty());
+    let other = PoaAuthority::new(Default::default());
+    assert_eq!(
+        failed_checks(&bundle.verify(None, Some(&other))),
+        [Check::PoaSignature]
+    );
+}
+
+#[test]
+fn binary_encoding_hashes_to_the_block_id() {
+    let block = block_with_transactions(10, 1);
+    let bundle = bundle(&block, 0);
+    let bytes = bundle.to_bytes().unwrap();
+
+    let length = u32::from_be_bytes(bytes[..4].try_into().unwrap()) as usize;
+    let application_header = &bytes[4..4 + length];
+    let consensus_header = &bytes[4 + length..4 + length + 76];
+    assert_eq!(
+        Hasher::hash(application_header),
+        block.header.application_hash
+    );
+    assert_eq!(
+        &consensus_header[44..],
+        block.header.application_hash.as_ref()
+    );
+    assert_eq!(Hasher::hash(consensus_header), block.id);
+}
+
+#[test]
+fn inconsistent_blocks_have_no_bundle() {
+    let mut block = block_with_transactions(10, 1);
+    receipts(&mut block, 0).pop();
+    seal(&mut block);
+    let tx_id = block.transactions[0].id;
+    assert!(matches!(
+        ProofBundle::build(&FullBlock::from(block.clone()), &tx_id),
+        Err(ValidationError::ReceiptRootMismatch { .. })
+    ));
+
+    let unknown = Bytes32::new([9; 32]);
+    assert!(matches!(
+        ProofBundle::build(&FullBlock::from(block), &unknown),
+        Err(ValidationError::TransactionNotFound { .. })
+    ));
+}
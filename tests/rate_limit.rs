@@ -0,0 +1,154 @@
+// tests/rate_limit.rs
+//! Pacing the queries to a node, and backing off when it rate limits them.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use fuel_root_validation::{
+    rate_limit::{retry_after, Limiter, MAX_RETRY_AFTER},
+    BlockSource, GraphqlSource, RateLimit, RetryPolicy,
+};
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+#[tokio::test]
+async fn requests_are_spread_over_the_second() {
+    let limiter = Limiter::new(RateLimit {
+        max_rps: Some(50.0),
+        max_in_flight: None,
+    });
+
+    let started = Instant::now();
+    for _ in 0..5 {
+        drop(limiter.acquire().await);
+    }
+
+    // The first request starts right away, the others 20ms apart.
+    assert!(started.elapsed() >= Duration::from_millis(80));
+}
+
+#[tokio::test]
+async fn requests_in_flight_are_capped() {
+    let limiter = Limiter::new(RateLimit {
+        max_rps: None,
+        max_in_flight: Some(1),
+    });
+
+    let permit = limiter.acquire().await;
+    let blocked = tokio::time::timeout(Duration::from_millis(50), limiter.acquire()).await;
+    assert!(blocked.is_err());
+
+    drop(permit);
+    let next = tokio::time::timeout(Duration::from_millis(50), limiter.acquire()).await;
+    assert!(next.is_ok());
+}
+
+#[test]
+fn retry_after_is_seconds_or_a_date() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(retry_after(&headers), None);
+
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+    assert_eq!(retry_after(&headers), Some(Duration::from_secs(3)));
+
+    // Dates in the past don't hold anything back.
+    headers.insert(
+        RETRY_AFTER,
+        HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+    );
+    assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+}
+
+#[tokio::test]
+async fn oversized_retry_after_is_capped() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        RETRY_AFTER,
+        HeaderValue::from_static("18446744073709551615"),
+    );
+    assert_eq!(retry_after(&headers), Some(MAX_RETRY_AFTER));
+    headers.insert(
+        RETRY_AFTER,
+        HeaderValue::from_static("Fri, 31 Dec 9999 23:59:59 GMT"),
+    );
+    assert_eq!(retry_after(&headers), Some(MAX_RETRY_AFTER));
+
+    // Pausing for longer than the clock can count holds the requests back
+    // rather than overflowing it.
+    let limiter = Limiter::default();
+    limiter.pause(Duration::MAX);
+    let blocked = tokio::time::timeout(Duration::from_millis(50), limiter.acquire()).await;
+    assert!(blocked.is_err());
+}
+
+/// Serves a node rate limiting its first query with `Retry-After: 1`, and
+/// answering the next ones with a latest block at height 7. Counts the
+/// queries in `queries`.
+async fn rate_limiting_node(queries: Arc<AtomicUsize>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = vec![];
+            let mut buffer = [0; 4096];
+            loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length: ")
+                                .map(str::to_owned)
+                        })
+                        .and_then(|length| length.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+            }
+
+            let response = if queries.fetch_add(1, Ordering::SeqCst) == 0 {
+                "HTTP/1.1 429 Too Many Requests\r\nretry-after: 1\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_string()
+            } else {
+                let body = r#"{"data":{"chain":{"latestBlock":{"height":"7"}}}}"#;
+                format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+            };
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn rate_limited_queries_wait_as_long_as_the_node_asks() {
+    let queries = Arc::new(AtomicUsize::new(0));
+    let url = rate_limiting_node(queries.clone()).await;
+    let node = GraphqlSource::new(url)
+        .unwrap()
+        .with_retry_policy(RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        });
+
+    let started = Instant::now();
+    let height = node.latest_height().await.unwrap();
+
+    assert_eq!(height, 7);
+    assert_eq!(queries.load(Ordering::SeqCst), 2);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+}
@@ -0,0 +1,71 @@
+// tests/receipt_diff.rs
+//! The receipt by receipt breakdown of a receipts root mismatch.
+
+mod common;
+
+use fuel_root_validation::{checks, dump::BlockDump, FullBlock, ReceiptDiff};
+use fuel_types::Bytes32;
+
+use common::{receipts, valid_block};
+
+/// The breakdown of the receipts of the first transaction of `block`.
+fn diff(block: BlockDump) -> Option<ReceiptDiff> {
+    let results = checks::receipt_roots(&FullBlock::from(block));
+    results[0].receipt_diff.as_deref().cloned()
+}
+
+#[test]
+fn matching_receipts_have_no_diff() {
+    assert_eq!(diff(valid_block(10)), None);
+}
+
+#[test]
+fn receipts_past_the_committed_ones_diverge() {
+    let mut block = valid_block(10);
+    let served = receipts(&mut block, 0);
+    served.push(served[0].clone());
+
+    let diff = diff(block).expect("the receipts root doesn't match");
+
+    let types: Vec<_> = diff.leaves.iter().map(|leaf| leaf.receipt_type).collect();
+    assert_eq!(types, ["MessageOut", "ScriptResult", "MessageOut"]);
+    assert_eq!(diff.leaves[0].bytes, diff.leaves[2].bytes);
+    assert_eq!(diff.first_divergent, Some(2));
+    assert_ne!(diff.committed_root, diff.calculated_root);
+}
+
+#[test]
+fn receipt_between_the_committed_ones_diverges() {
+    let mut block = valid_block(10);
+    let served = receipts(&mut block, 0);
+    let mut extra = served[1].clone();
+    extra.gas_used = Some(1);
+    served.insert(1, extra);
+
+    let diff = diff(block).expect("the receipts root doesn't match");
+
+    assert_eq!(diff.first_divergent, Some(1));
+}
+
+#[test]
+fn receipt_not_matching_its_data_diverges() {
+    let mut block = valid_block(10);
+    receipts(&mut block, 0)[0].digest = Some(Bytes32::new([7; 32]));
+
+    let diff = diff(block).expect("the receipts root doesn't match");
+
+    assert_eq!(diff.first_divergent, Some(0));
+    assert!(diff.leaves[0].issue.is_some());
+    assert!(diff.leaves[1].issue.is_none());
+}
+
+#[test]
+fn missing_receipt_cant_be_located() {
+    let mut block = valid_block(10);
+    receipts(&mut block, 0).pop();
+
+    let diff = diff(block).expect("the receipts root doesn't match");
+
+    assert_eq!(diff.leaves.len(), 1);
+    assert_eq!(diff.first_divergent, None);
+}
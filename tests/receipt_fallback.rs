@@ -0,0 +1,141 @@
+// tests/receipt_fallback.rs
+//! Blocks served without some of their receipts, and blocks whose query
+//! fails, completed from the status query of each transaction.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use fuel_root_validation::{
+    receipt_fallback::{fill_in, missing_receipts},
+    BlockSource, BlockValidator, FullBlock, GraphqlSource, MemorySource, RetryPolicy,
+    ValidationError,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+use common::{block_with_transactions, receipts};
+
+#[test]
+fn transactions_lacking_receipts_are_found() {
+    let mut block = block_with_transactions(10, 3);
+    assert!(missing_receipts(&block.clone().into()).is_empty());
+
+    receipts(&mut block, 0).clear();
+    block.transactions[2].status = None;
+    // The mint of the fees has no receipts of its own.
+    assert_eq!(missing_receipts(&block.into()), vec![0, 2]);
+}
+
+#[test]
+fn statuses_with_receipts_are_filled_in() {
+    let served = FullBlock::from(block_with_transactions(10, 2));
+    let mut block = block_with_transactions(10, 2);
+    receipts(&mut block, 0).clear();
+    block.transactions[1].status = None;
+    let mut block = FullBlock::from(block);
+
+    let status = |index: usize| served.transactions[index].status.clone().unwrap();
+    assert!(fill_in(&mut block.transactions[0], status(0)));
+    assert!(fill_in(&mut block.transactions[1], status(1)));
+    assert!(missing_receipts(&block).is_empty());
+    assert!(BlockValidator::from_source(MemorySource::new([]))
+        .validate_full_block(&block)
+        .passed());
+
+    // A status without receipts doesn't replace a served one.
+    let mut lacking = block_with_transactions(10, 1);
+    receipts(&mut lacking, 0).clear();
+    let without_receipts = FullBlock::from(lacking.clone()).transactions[0]
+        .status
+        .clone()
+        .unwrap();
+    let mut lacking = FullBlock::from(lacking);
+    lacking.transactions[0].status = Some(status(0));
+    assert!(!fill_in(&mut lacking.transactions[0], without_receipts));
+    assert!(missing_receipts(&lacking).is_empty());
+}
+
+/// Serves a node failing the combined block query and finding no block
+/// without the statuses. Records which of the two every query is.
+async fn node(operations: Arc<Mutex<Vec<&'static str>>>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = vec![];
+            let mut buffer = [0; 4096];
+            let body = loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length: ")
+                                .map(str::to_owned)
+                        })
+                        .and_then(|length| length.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break body.to_string();
+                    }
+                }
+            };
+
+            let query: serde_json::Value = serde_json::from_str(&body).unwrap();
+            let with_statuses = query["query"].as_str().unwrap().contains("status");
+            let operation = if with_statuses {
+                "block"
+            } else {
+                "block without statuses"
+            };
+            let response = if with_statuses {
+                r#"{"errors":[{"message":"response too large"}]}"#
+            } else {
+                r#"{"data":{"block":null}}"#
+            };
+            operations.lock().unwrap().push(operation);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response}",
+                response.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn failed_block_queries_fall_back_to_the_block_without_statuses() {
+    for fallback in [true, false] {
+        let operations = Arc::new(Mutex::new(vec![]));
+        let source = GraphqlSource::new(node(operations.clone()).await)
+            .unwrap()
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            })
+            .with_receipt_fallback(fallback);
+
+        let error = source.fetch_block(10).await.unwrap_err();
+        let operations = operations.lock().unwrap().clone();
+        if fallback {
+            assert!(
+                matches!(error, ValidationError::BlockNotFound { height: 10 }),
+                "{error}"
+            );
+            assert_eq!(operations, ["block", "block without statuses"]);
+        } else {
+            assert!(
+                matches!(error, ValidationError::Query { query: "block", .. }),
+                "{error}"
+            );
+            assert_eq!(operations, ["block"]);
+        }
+    }
+}
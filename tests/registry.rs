@@ -0,0 +1,121 @@
+// tests/registry.rs
+//! Checks selected by name, and checks registered by library users.
+
+mod common;
+
+use fuel_root_validation::{
+    registry, BlockCheck, BlockValidator, Check, CheckContext, CheckRegistry, CheckResult,
+    FullBlock, MemorySource, Severity, ValidationError,
+};
+
+use common::{receipts, valid_block};
+
+fn validator() -> BlockValidator {
+    BlockValidator::from_source(MemorySource::new([FullBlock::from(valid_block(10))]))
+}
+
+/// Fails blocks with more than `max` transactions.
+#[derive(Debug)]
+struct MaxTransactions {
+    max: usize,
+}
+
+impl BlockCheck for MaxTransactions {
+    fn name(&self) -> &'static str {
+        "max-transactions"
+    }
+
+    fn run(&self, context: &CheckContext<'_>) -> Vec<CheckResult> {
+        let count = context.block.transactions.len();
+        vec![CheckResult::compare(
+            Check::Custom(self.name()),
+            format!("at most {}", self.max),
+            if count <= self.max {
+                format!("at most {}", self.max)
+            } else {
+                count.to_string()
+            },
+        )]
+    }
+}
+
+#[test]
+fn default_registry_leaves_out_the_expensive_checks() {
+    let registry = CheckRegistry::default();
+
+    for name in [
+        registry::FEES,
+        registry::CONTRACT_STATE,
+        registry::PREV_ROOT,
+        registry::EVENT_INBOX,
+    ] {
+        assert!(!registry.is_enabled(name), "{name}");
+    }
+    assert!(registry.is_enabled(registry::RECEIPT_ROOT));
+    assert_eq!(registry.names().count(), registry.enabled().len() + 4);
+}
+
+#[tokio::test]
+async fn skipped_checks_are_not_reported() {
+    let validator = validator()
+        .with_check_enabled(registry::RECEIPT_ROOT, false)
+        .unwrap();
+
+    let report = validator.validate_block(10).await.unwrap();
+
+    assert!(report.passed());
+    assert!(report
+        .checks
+        .iter()
+        .all(|check| check.check != Check::ReceiptRoot));
+    assert!(report
+        .checks
+        .iter()
+        .any(|check| check.check == Check::TxRoot));
+}
+
+#[tokio::test]
+async fn custom_checks_report_under_their_name() {
+    let validator = validator().with_check(MaxTransactions { max: 1 });
+
+    let report = validator.validate_block(10).await.unwrap();
+
+    let failed: Vec<_> = report.failures().collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].check, Check::Custom("max-transactions"));
+    assert_eq!(failed[0].check.to_string(), "max-transactions");
+    assert!(validator.checks().is_enabled("max-transactions"));
+}
+
+#[test]
+fn unknown_checks_list_the_known_ones() {
+    let error = validator().with_check_enabled("merkle", true).unwrap_err();
+
+    match error {
+        ValidationError::UnknownCheck { name, known } => {
+            assert_eq!(name, "merkle");
+            assert!(known.contains("receipt-root"), "{known}");
+        }
+        error => panic!("unexpected error: {error}"),
+    }
+}
+
+#[tokio::test]
+async fn checks_can_be_downgraded_to_warnings() {
+    let mut block = valid_block(10);
+    receipts(&mut block, 0).pop();
+    let validator = BlockValidator::from_source(MemorySource::new([FullBlock::from(block)]))
+        .with_check_severity(registry::RECEIPT_ROOT, Severity::Warning)
+        .unwrap();
+
+    let report = validator.validate_block(10).await.unwrap();
+
+    assert!(report.passed());
+    let warnings: Vec<_> = report.warnings().collect();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].check, Check::ReceiptRoot);
+    assert_eq!(
+        serde_json::to_value(warnings[0]).unwrap()["severity"],
+        "warning"
+    );
+}
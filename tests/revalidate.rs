@@ -0,0 +1,80 @@
+// tests/revalidate.rs
+//! The failed blocks of a previous run, read back from its report.
+
+mod common;
+
+use fuel_root_validation::{
+    checkpoint::RecordedFailure, registry, BlockValidator, Checkpoint, ErrorKind, FailureReport,
+    FullBlock, MemorySource, RangeValidation,
+};
+
+use common::{receipts, valid_block};
+
+async fn validation() -> RangeValidation {
+    let mut bad = valid_block(11);
+    receipts(&mut bad, 0)[1].gas_used = Some(1);
+    let blocks = [valid_block(10), bad, valid_block(12)].map(FullBlock::from);
+    BlockValidator::from_source(MemorySource::new(blocks))
+        .validate_range(10, 12)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn json_reports_name_the_failed_blocks_and_checks() {
+    let text = serde_json::to_string(&validation().await).unwrap();
+
+    let report = FailureReport::parse(&text).unwrap();
+
+    assert_eq!(report.heights().collect::<Vec<_>>(), [11]);
+    assert!(report.blocks[0].checks.contains(registry::RECEIPT_ROOT));
+    assert!(report.blocks[0].endpoints.is_empty());
+}
+
+#[tokio::test]
+async fn ndjson_reports_are_read_line_by_line() {
+    let validation = validation().await;
+    let mut text: String = validation
+        .outcomes
+        .iter()
+        .map(|outcome| serde_json::to_string(outcome).unwrap() + "\n")
+        .collect();
+    text.push_str("{\"reorg\":{\"depth\":1}}\n");
+
+    let report = FailureReport::parse(&text).unwrap();
+
+    assert_eq!(report.heights().collect::<Vec<_>>(), [11]);
+}
+
+#[test]
+fn checkpoint_failures_are_revalidated_with_every_check() {
+    let mut checkpoint = Checkpoint::new(1, 100);
+    checkpoint.failures = vec![
+        RecordedFailure {
+            height: 40,
+            reason: "query blocks failed".to_string(),
+            kinds: vec![ErrorKind::Query],
+        },
+        RecordedFailure {
+            height: 7,
+            reason: "receipt-root mismatch".to_string(),
+            kinds: vec![ErrorKind::Mismatch],
+        },
+    ];
+    let text = serde_json::to_string(&checkpoint).unwrap();
+
+    let report = FailureReport::parse(&text).unwrap();
+
+    assert_eq!(report.heights().collect::<Vec<_>>(), [7, 40]);
+    assert!(report.blocks.iter().all(|block| block.checks.is_empty()));
+}
+
+#[test]
+fn result_names_map_to_the_checks_reporting_them() {
+    assert_eq!(registry::reported_by("mint-amount"), registry::MINT);
+    assert_eq!(registry::reported_by("tx-fee"), registry::FEES);
+    assert_eq!(
+        registry::reported_by("max-transactions"),
+        "max-transactions"
+    );
+}
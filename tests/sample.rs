@@ -0,0 +1,31 @@
+// tests/sample.rs
+//! Heights picked at random to spot check a range.
+
+use fuel_root_validation::sample::sample_heights;
+
+#[test]
+fn samples_are_distinct_sorted_heights_of_the_range() {
+    let heights = sample_heights(1000, 1999, 50, 7);
+
+    assert_eq!(heights.len(), 50);
+    assert!(heights.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(heights.iter().all(|height| (1000..=1999).contains(height)));
+}
+
+#[test]
+fn same_seed_samples_the_same_heights() {
+    assert_eq!(
+        sample_heights(0, 1_000_000, 20, 42),
+        sample_heights(0, 1_000_000, 20, 42)
+    );
+    assert_ne!(
+        sample_heights(0, 1_000_000, 20, 42),
+        sample_heights(0, 1_000_000, 20, 43)
+    );
+}
+
+#[test]
+fn small_ranges_are_checked_in_full() {
+    assert_eq!(sample_heights(5, 9, 10, 1), [5, 6, 7, 8, 9]);
+    assert_eq!(sample_heights(u32::MAX, u32::MAX, 1, 1), [u32::MAX]);
+}
@@ -0,0 +1,58 @@
+// tests/self_test.rs
+//! The blocks bundled for `self-test`.
+
+mod common;
+
+use fuel_root_validation::{dump::ConsensusDump, self_test, BlockDump, Check};
+use fuel_types::Bytes64;
+
+use common::flip;
+
+#[test]
+fn bundled_blocks_pass() {
+    let fixtures = self_test::fixtures();
+    assert_eq!(fixtures.len(), 2);
+    for fixture in &fixtures {
+        let report = fixture.validate();
+        assert!(report.passed(), "{}: {report:?}", fixture.name);
+        // Transaction ids and the signature of the node are checked too.
+        for check in [Check::TxId, Check::PoaSignature, Check::MessageOutboxRoot] {
+            assert!(
+                report.checks.iter().any(|result| result.check == check),
+                "{}: {check}",
+                fixture.name
+            );
+        }
+    }
+    assert_eq!(fixtures[1].block.transactions.len(), 65);
+    assert_eq!(fixtures[1].block.header.message_receipt_count.0, 64);
+}
+
+#[test]
+fn blocks_not_signed_by_the_node_fail() {
+    let mut fixture = self_test::fixtures().remove(0);
+    let mut dump = BlockDump::from(&fixture.block);
+    dump.consensus = ConsensusDump::Poa {
+        signature: Bytes64::zeroed(),
+    };
+    fixture.block = dump.into();
+
+    let report = fixture.validate();
+    assert!(report
+        .failures()
+        .any(|failure| failure.check == Check::PoaSignature));
+}
+
+#[test]
+fn wrong_roots_fail() {
+    let mut fixture = self_test::fixtures().remove(0);
+    let mut dump = BlockDump::from(&fixture.block);
+    flip(&mut dump.header.transactions_root);
+    fixture.block = dump.into();
+
+    let report = fixture.validate();
+    assert!(!report.passed());
+    assert!(report
+        .failures()
+        .any(|failure| failure.check == Check::TxRoot));
+}
@@ -0,0 +1,101 @@
+// tests/server.rs
+//! Validates blocks through the HTTP API.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+};
+use fuel_root_validation::{server, BlockValidator, FullBlock, MemorySource};
+use fuel_types::ChainId;
+use serde_json::Value;
+use tower::ServiceExt;
+
+use common::{flip, seal, valid_block, CHAIN_ID};
+
+async fn request(request: Request<Body>) -> (StatusCode, Value) {
+    let validator =
+        BlockValidator::from_source(MemorySource::new([FullBlock::from(valid_block(10))]))
+            .with_chain_id(ChainId::new(CHAIN_ID));
+    let response = server::router(Arc::new(validator))
+        .oneshot(request)
+        .await
+        .expect("the router doesn't fail");
+
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    (
+        status,
+        serde_json::from_slice(&body).expect("the body is JSON"),
+    )
+}
+
+async fn get(path: &str) -> (StatusCode, Value) {
+    request(Request::get(path).body(Body::empty()).unwrap()).await
+}
+
+#[tokio::test]
+async fn block_of_the_source_is_validated() {
+    let (status, outcome) = get("/validate/10").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(outcome["height"], 10);
+    assert_eq!(outcome["passed"], true, "{outcome:#}");
+    assert!(outcome["report"]["checks"].as_array().is_some());
+}
+
+#[tokio::test]
+async fn missing_block_is_not_found() {
+    let (status, outcome) = get("/validate/11").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(outcome["passed"], false);
+    assert!(outcome["error"].as_str().is_some(), "{outcome:#}");
+}
+
+#[tokio::test]
+async fn posted_block_is_validated() {
+    let mut block = valid_block(20);
+    flip(&mut block.header.transactions_root);
+    seal(&mut block);
+
+    let (status, outcome) = request(
+        Request::builder()
+            .method(Method::POST)
+            .uri("/validate")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&block).unwrap()))
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(outcome["height"], 20);
+    assert_eq!(outcome["passed"], false);
+    let failed: Vec<_> = outcome["report"]["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|check| check["passed"] == false)
+        .map(|check| check["check"].as_str().unwrap())
+        .collect();
+    assert_eq!(failed, ["tx-root"]);
+}
+
+#[tokio::test]
+async fn posted_garbage_is_a_bad_request() {
+    let (status, body) = request(
+        Request::builder()
+            .method(Method::POST)
+            .uri("/validate")
+            .body(Body::from("{"))
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().starts_with("invalid block"));
+}
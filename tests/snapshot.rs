@@ -0,0 +1,255 @@
+// tests/snapshot.rs
+//! Comparing a fuel-core state snapshot with the chain up to its height.
+
+mod common;
+
+use std::{fs, path::Path};
+
+use fuel_crypto::{Message, SecretKey, Signature};
+use fuel_root_validation::{
+    dump::{BlockDump, ConsensusDump},
+    snapshot::{self, SnapshotComparison},
+    BlockValidator, ChainParameters, FullBlock, MemorySource, PrevRoots, Snapshot, ValidationError,
+};
+use fuel_tx::{ConsensusParameters, Input};
+use fuel_types::{Address, Bytes32, Bytes64, ChainId};
+use serde_json::{json, Value};
+
+use common::{seal, temp_dir, valid_block, CHAIN_ID};
+
+/// Height the snapshots are taken at.
+const HEIGHT: u32 = 4;
+
+fn key() -> SecretKey {
+    SecretKey::try_from([7; 32].as_slice()).unwrap()
+}
+
+fn producer() -> Address {
+    Input::owner(&key().public_key())
+}
+
+/// A chain of blocks `0..=HEIGHT` from a genesis block, each committing to
+/// the ids of the blocks before it, and the root of all their ids. The last
+/// block is signed by [`producer`]. `corrupt` is applied to each block
+/// before it is sealed.
+fn chain(mut corrupt: impl FnMut(&mut BlockDump)) -> (Vec<BlockDump>, Bytes32) {
+    let mut roots = PrevRoots::new();
+    let mut blocks = vec![];
+    for height in 0..=HEIGHT {
+        let mut block = valid_block(height);
+        if height == 0 {
+            block.consensus = ConsensusDump::Genesis {
+                chain_config_hash: Bytes32::zeroed(),
+                coins_root: Bytes32::zeroed(),
+                contracts_root: Bytes32::zeroed(),
+                messages_root: Bytes32::zeroed(),
+                transactions_root: Bytes32::zeroed(),
+            };
+        }
+        block.header.prev_root = roots.root();
+        corrupt(&mut block);
+        seal(&mut block);
+        roots.push(height, &block.id).unwrap();
+        blocks.push(block);
+    }
+    let last = blocks.last_mut().unwrap();
+    let signature = Signature::sign(&key(), &Message::from_bytes(*last.id));
+    last.consensus = ConsensusDump::Poa {
+        signature: Bytes64::new(*signature),
+    };
+    (blocks, roots.root())
+}
+
+fn validator(blocks: Vec<BlockDump>) -> BlockValidator {
+    let mut params = ChainParameters::new();
+    params.insert(1, ConsensusParameters::default());
+    BlockValidator::from_source(MemorySource::new(blocks.into_iter().map(FullBlock::from)))
+        .with_consensus_parameters(params)
+        .with_chain_id(ChainId::new(CHAIN_ID))
+}
+
+/// Writes a JSON snapshot of the chain at [`HEIGHT`], whose blocks have the
+/// ids with root `blocks_root`.
+fn write_snapshot(dir: &Path, blocks_root: Bytes32) -> (Value, Value) {
+    let chain_config = json!({
+        "chain_name": "local_testnet",
+        "consensus_parameters": ConsensusParameters::default(),
+        "consensus": {
+            "PoAV2": {
+                "genesis_signing_key": producer(),
+                "signing_key_overrides": {}
+            }
+        }
+    });
+    let state = json!({
+        "coins": [{
+            "tx_id": Bytes32::new([1; 32]),
+            "output_index": 0,
+            "tx_pointer_block_height": 2,
+            "tx_pointer_tx_idx": 0,
+            "owner": Address::new([2; 32]),
+            "amount": 100,
+            "asset_id": Bytes32::zeroed()
+        }],
+        "messages": [{
+            "sender": Address::new([1; 32]),
+            "recipient": Address::new([2; 32]),
+            "nonce": Bytes32::new([3; 32]),
+            "amount": 100,
+            "data": "",
+            "da_height": 90
+        }],
+        "contracts": [],
+        "last_block": {
+            "block_height": HEIGHT,
+            "da_block_height": 100,
+            "consensus_parameters_version": 1,
+            "state_transition_version": 1,
+            "blocks_root": blocks_root
+        }
+    });
+    save(dir, &chain_config, &state);
+    (chain_config, state)
+}
+
+fn save(dir: &Path, chain_config: &Value, state: &Value) {
+    fs::write(
+        dir.join("metadata.json"),
+        r#"{"chain_config":"chain_config.json","table_encoding":{"Json":{"filepath":"state_config.json"}}}"#,
+    )
+    .unwrap();
+    fs::write(dir.join("chain_config.json"), chain_config.to_string()).unwrap();
+    fs::write(dir.join("state_config.json"), state.to_string()).unwrap();
+}
+
+async fn compare(blocks: Vec<BlockDump>, dir: &Path) -> SnapshotComparison {
+    let snapshot = Snapshot::load(dir).unwrap();
+    assert_eq!(snapshot.height(), Some(HEIGHT));
+    snapshot::check_snapshot(&validator(blocks), &snapshot, HEIGHT)
+        .await
+        .unwrap()
+}
+
+fn paths(comparison: &SnapshotComparison) -> Vec<&str> {
+    comparison
+        .differences
+        .iter()
+        .map(|difference| difference.path.as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn snapshots_of_the_chain_are_consistent() {
+    let dir = temp_dir("snapshot_consistent");
+    let (blocks, blocks_root) = chain(|_| {});
+    write_snapshot(&dir, blocks_root);
+
+    let comparison = compare(blocks, &dir).await;
+    assert!(comparison.is_consistent(), "{comparison:#?}");
+    assert_eq!(comparison.blocks, HEIGHT + 1);
+}
+
+#[tokio::test]
+async fn differences_with_the_chain_are_reported() {
+    let dir = temp_dir("snapshot_differences");
+    let (blocks, blocks_root) = chain(|_| {});
+    let (mut chain_config, mut state) = write_snapshot(&dir, blocks_root);
+    state["last_block"]["blocks_root"] = json!(Bytes32::new([9; 32]));
+    state["last_block"]["da_block_height"] = json!(99);
+    state["coins"][0]["tx_pointer_block_height"] = json!(HEIGHT + 1);
+    state["messages"][0]["da_height"] = json!(101);
+    chain_config["consensus"] = json!({ "PoA": { "signing_key": Address::new([5; 32]) } });
+    chain_config["consensus_parameters"]["V2"]["tx_params"]["V1"]["max_inputs"] = json!(1);
+    save(&dir, &chain_config, &state);
+
+    let comparison = compare(blocks, &dir).await;
+    assert!(comparison.chain.is_empty(), "{comparison:#?}");
+    assert_eq!(
+        paths(&comparison),
+        [
+            "last_block.da_block_height",
+            "last_block.blocks_root",
+            "chain_config.consensus_parameters.V2.tx_params.V1.max_inputs",
+            "chain_config.consensus.signing_key",
+            "coins[0].tx_pointer_block_height",
+            "messages[0].da_height",
+        ]
+    );
+    let root = &comparison.differences[1];
+    assert_eq!(root.expected, Some(blocks_root.to_string()));
+    assert_eq!(root.actual, Some(Bytes32::new([9; 32]).to_string()));
+    let signer = &comparison.differences[3];
+    assert_eq!(signer.expected, Some(producer().to_string()));
+}
+
+#[tokio::test]
+async fn broken_block_id_chains_are_reported() {
+    let dir = temp_dir("snapshot_broken_chain");
+    let (blocks, blocks_root) = chain(|block| {
+        if block.header.height == 2 {
+            block.header.prev_root = Bytes32::new([9; 32]);
+        }
+    });
+    write_snapshot(&dir, blocks_root);
+
+    let comparison = compare(blocks, &dir).await;
+    assert!(comparison.differences.is_empty(), "{comparison:#?}");
+    let heights: Vec<_> = comparison.chain.iter().map(|m| m.height).collect();
+    assert_eq!(heights, [2]);
+    assert!(!comparison.is_consistent());
+}
+
+#[tokio::test]
+async fn blocks_missing_from_the_chain_are_reported() {
+    let dir = temp_dir("snapshot_gap");
+    let (mut blocks, blocks_root) = chain(|_| {});
+    write_snapshot(&dir, blocks_root);
+    blocks.remove(2);
+
+    let comparison = compare(blocks, &dir).await;
+    // The blocks root can't be derived past the gap, so it isn't compared.
+    assert!(comparison.differences.is_empty(), "{comparison:#?}");
+    assert_eq!(comparison.blocks, HEIGHT);
+    let heights: Vec<_> = comparison.chain.iter().map(|m| m.height).collect();
+    assert_eq!(heights, [2]);
+    assert!(comparison.chain[0]
+        .result
+        .message
+        .as_deref()
+        .unwrap()
+        .starts_with("block #2 isn't served"));
+    assert!(!comparison.is_consistent());
+}
+
+#[tokio::test]
+async fn snapshots_must_reach_their_height() {
+    let dir = temp_dir("snapshot_missing_blocks");
+    let (mut blocks, blocks_root) = chain(|_| {});
+    write_snapshot(&dir, blocks_root);
+    blocks.pop();
+
+    let snapshot = Snapshot::load(&dir).unwrap();
+    assert!(matches!(
+        snapshot::check_snapshot(&validator(blocks), &snapshot, HEIGHT).await,
+        Err(ValidationError::BlockNotFound { height: HEIGHT })
+    ));
+}
+
+#[test]
+fn only_json_snapshots_are_read() {
+    let dir = temp_dir("snapshot_parquet");
+    assert!(matches!(
+        Snapshot::load(&dir),
+        Err(ValidationError::ReadSnapshot { .. })
+    ));
+
+    fs::write(
+        dir.join("metadata.json"),
+        r#"{"chain_config":"chain_config.json","table_encoding":{"Parquet":{"tables":{},"latest_block_config_path":"last_block.parquet"}}}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        Snapshot::load(&dir),
+        Err(ValidationError::UnsupportedSnapshot { .. })
+    ));
+}
@@ -0,0 +1,149 @@
+// tests/status_receipts.rs
+//! The details of transaction statuses checked against their receipts and
+//! their block.
+
+mod common;
+
+use fuel_root_validation::{
+    checks,
+    dump::{BlockDump, StatusDump},
+    BlockValidator, Check, CheckResult, FullBlock, MemorySource, Severity,
+};
+use fuel_tx::{PanicInstruction, PanicReason, Receipt, ScriptExecutionResult};
+use fuel_types::ContractId;
+
+use common::valid_block;
+
+const STATUS_CHECKS: [Check; 4] = [
+    Check::StatusHeight,
+    Check::StatusResult,
+    Check::StatusReason,
+    Check::StatusGas,
+];
+
+fn status_results(block: BlockDump) -> Vec<CheckResult> {
+    checks::status_receipts(&FullBlock::from(block))
+}
+
+fn failed(results: &[CheckResult]) -> Vec<Check> {
+    results
+        .iter()
+        .filter(|result| !result.passed)
+        .map(|result| result.check)
+        .collect()
+}
+
+/// Makes the script of `block` fail with `receipt`, reporting `reason`.
+fn fail_with(block: &mut BlockDump, receipt: Receipt, result: ScriptExecutionResult, reason: &str) {
+    block.transactions[0].status = Some(StatusDump::Failure {
+        block_height: 10,
+        time: 0,
+        reason: reason.to_string(),
+        program_state: None,
+        receipts: vec![
+            (&receipt).into(),
+            (&Receipt::script_result(result, 900)).into(),
+        ],
+        total_gas: 1000,
+        total_fee: 1,
+    });
+}
+
+#[test]
+fn consistent_statuses_pass() {
+    let results = status_results(valid_block(10));
+
+    // The height of the script and the mint, and the result and gas of the
+    // script.
+    assert_eq!(
+        results
+            .iter()
+            .map(|result| result.check)
+            .collect::<Vec<_>>(),
+        [
+            Check::StatusHeight,
+            Check::StatusResult,
+            Check::StatusGas,
+            Check::StatusHeight
+        ]
+    );
+    assert!(results.iter().all(|result| result.passed));
+}
+
+#[tokio::test]
+async fn statuses_of_another_block_are_warnings() {
+    let mut block = valid_block(10);
+    let Some(StatusDump::Success { block_height, .. }) = &mut block.transactions[0].status else {
+        panic!("the script succeeded");
+    };
+    *block_height = 9;
+
+    let report = BlockValidator::from_source(MemorySource::new([FullBlock::from(block)]))
+        .validate_block(10)
+        .await
+        .unwrap();
+
+    assert!(report.passed());
+    let warnings: Vec<_> = report.warnings().collect();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].check, Check::StatusHeight);
+    assert_eq!(warnings[0].severity, Severity::Warning);
+    assert_eq!(warnings[0].actual.as_deref(), Some("9"));
+}
+
+#[test]
+fn successes_must_agree_with_the_script_result() {
+    let mut block = valid_block(10);
+    let Some(StatusDump::Success {
+        receipts,
+        total_gas,
+        ..
+    }) = &mut block.transactions[0].status
+    else {
+        panic!("the script succeeded");
+    };
+    receipts[1] = (&Receipt::script_result(ScriptExecutionResult::Panic, 1000)).into();
+    *total_gas = 999;
+
+    let results = status_results(block);
+
+    assert_eq!(failed(&results), [Check::StatusResult, Check::StatusGas]);
+    assert_eq!(results[1].expected.as_deref(), Some("failure"));
+    assert_eq!(results[2].expected.as_deref(), Some("at most 999"));
+}
+
+#[test]
+fn failure_reasons_come_from_the_revert_or_panic_receipt() {
+    let contract = ContractId::zeroed();
+    let mut reverted = valid_block(10);
+    fail_with(
+        &mut reverted,
+        Receipt::revert(contract, 42, 0, 0),
+        ScriptExecutionResult::Revert,
+        "Revert(42)",
+    );
+    let mut panicked = valid_block(10);
+    fail_with(
+        &mut panicked,
+        Receipt::panic(
+            contract,
+            PanicInstruction::error(PanicReason::OutOfGas, 0),
+            0,
+            0,
+        ),
+        ScriptExecutionResult::Panic,
+        "Revert(42)",
+    );
+
+    assert!(failed(&status_results(reverted)).is_empty());
+    let results = status_results(panicked);
+    assert_eq!(failed(&results), [Check::StatusReason]);
+    let reason = results
+        .iter()
+        .find(|result| result.check == Check::StatusReason)
+        .unwrap();
+    assert_eq!(reason.expected.as_deref(), Some("OutOfGas"));
+    assert!(STATUS_CHECKS
+        .iter()
+        .all(|check| check.default_severity() == Severity::Warning));
+}
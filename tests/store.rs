@@ -0,0 +1,104 @@
+// tests/store.rs
+//! Validated blocks recorded in the results store, and skipped by the runs
+//! after them.
+
+mod common;
+
+use std::time::Duration;
+
+use fuel_root_validation::{
+    store, BlockOutcome, BlockValidator, FullBlock, MemorySource, ResultStore, ValidationError,
+};
+
+use common::{receipts, temp_dir, valid_block};
+
+async fn outcomes() -> Vec<BlockOutcome> {
+    let mut bad = valid_block(11);
+    receipts(&mut bad, 0)[1].gas_used = Some(1);
+    let blocks = [valid_block(10), bad].map(FullBlock::from);
+    let mut outcomes = BlockValidator::from_source(MemorySource::new(blocks))
+        .validate_range(10, 11)
+        .await
+        .unwrap()
+        .outcomes;
+    outcomes.push(BlockOutcome {
+        height: 12,
+        result: Err(ValidationError::BlockNotFound { height: 12 }),
+        duration: Duration::ZERO,
+        timing: None,
+    });
+    outcomes
+}
+
+#[tokio::test]
+async fn validated_blocks_are_skipped_by_the_next_run() {
+    let path = temp_dir("store-sqlite").join("results.sqlite");
+    let mut first = ResultStore::open(&path, "http://node.example").unwrap();
+    for outcome in outcomes().await {
+        first.record(&outcome).unwrap();
+    }
+
+    let second = ResultStore::open(&path, "http://node.example").unwrap();
+
+    assert_ne!(second.run_id(), first.run_id());
+    assert!(second.is_validated(11).unwrap());
+    assert!(!second.is_validated(12).unwrap());
+    assert_eq!(second.pending_ranges(8, 14).unwrap(), [(8, 9), (12, 14)]);
+}
+
+#[test]
+fn postgres_urls_are_told_apart_from_paths() {
+    assert!(store::is_postgres_url("postgres://validator@db/results"));
+    assert!(store::is_postgres_url("postgresql://db/results"));
+    assert!(!store::is_postgres_url("results.sqlite"));
+}
+
+#[cfg(not(feature = "postgres"))]
+#[test]
+fn postgres_needs_its_feature() {
+    let error = ResultStore::connect("postgres://db/results", "testnet", "http://node.example")
+        .err()
+        .expect("PostgreSQL isn't built in");
+
+    assert!(error.to_string().contains("`postgres` feature"), "{error}");
+}
+
+/// Runs against the database at `FUEL_VALIDATOR_POSTGRES_URL`, when set.
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn postgres_is_shared_by_the_validators_of_a_network() {
+    let Ok(url) = std::env::var("FUEL_VALIDATOR_POSTGRES_URL") else {
+        eprintln!("FUEL_VALIDATOR_POSTGRES_URL isn't set, skipping");
+        return;
+    };
+    // A network of its own keeps the rows of other runs of the test out.
+    let network = format!(
+        "test-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    );
+    let outcomes = outcomes().await;
+    let mut first = ResultStore::connect(&url, &network, "http://node.example").unwrap();
+    let mut second = ResultStore::connect(&url, &network, "http://other.example").unwrap();
+    for outcome in &outcomes {
+        first.record(outcome).unwrap();
+        second.record(outcome).unwrap();
+    }
+    // A failed query doesn't replace the validation of the block.
+    second
+        .record(&BlockOutcome {
+            height: 11,
+            result: Err(ValidationError::BlockNotFound { height: 11 }),
+            duration: Duration::ZERO,
+            timing: None,
+        })
+        .unwrap();
+
+    assert!(first.is_validated(11).unwrap());
+    assert!(!first.is_validated(12).unwrap());
+    assert_eq!(first.pending_ranges(8, 14).unwrap(), [(8, 9), (12, 14)]);
+    let other = ResultStore::connect(&url, "other", "http://node.example").unwrap();
+    assert!(!other.is_validated(10).unwrap());
+}
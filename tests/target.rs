@@ -0,0 +1,47 @@
+// tests/target.rs
+//! Blocks named relative to the latest one.
+
+mod common;
+
+use fuel_root_validation::{BlockTarget, BlockValidator, FullBlock, MemorySource, ValidationError};
+
+use common::valid_block;
+
+#[test]
+fn targets_parse_and_print_back() {
+    for (text, target) in [
+        ("42", BlockTarget::Height(42)),
+        ("latest", BlockTarget::Latest { behind: 0 }),
+        ("latest-6", BlockTarget::Latest { behind: 6 }),
+    ] {
+        assert_eq!(text.parse::<BlockTarget>().unwrap(), target);
+        assert_eq!(target.to_string(), text);
+    }
+    for text in ["latest+1", "latest-", "latest-x", "-1", "newest"] {
+        let error = text.parse::<BlockTarget>().unwrap_err();
+        assert!(
+            matches!(&error, ValidationError::InvalidBlockTarget { target, .. } if target == text),
+            "{error:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn targets_are_resolved_against_the_latest_block() {
+    let blocks = (10..=12).map(|height| FullBlock::from(valid_block(height)));
+    let validator = BlockValidator::from_source(MemorySource::new(blocks));
+    let targets = ["latest", "latest-2", "5"].map(|target| target.parse().unwrap());
+
+    assert_eq!(
+        validator.resolve_targets(&targets).await.unwrap(),
+        [12, 10, 5]
+    );
+    let error = validator
+        .resolve_targets(&[BlockTarget::Latest { behind: 13 }])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid block `latest-13`: the latest block is #12"
+    );
+}
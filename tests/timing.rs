@@ -0,0 +1,61 @@
+// tests/timing.rs
+//! Records where the time validating a range goes.
+
+mod common;
+
+use std::time::Duration;
+
+use fuel_root_validation::{timing::Percentiles, BlockValidator, FullBlock, MemorySource};
+
+use common::valid_block;
+
+#[tokio::test]
+async fn timed_range_has_the_timing_of_every_block() {
+    let validator = BlockValidator::from_source(MemorySource::new(
+        (10..=14).map(valid_block).map(FullBlock::from),
+    ))
+    .with_timing(true);
+
+    let range = validator.validate_range(10, 15).await.unwrap();
+
+    let timed: Vec<_> = range
+        .outcomes
+        .iter()
+        .filter(|outcome| outcome.timing.is_some())
+        .map(|outcome| outcome.height)
+        .collect();
+    assert_eq!(timed, [10, 11, 12, 13, 14], "the missing block isn't timed");
+    let summary = range.timing().expect("blocks were timed");
+    assert_eq!(summary.blocks, 5);
+    assert!(summary.merkle.total > Duration::ZERO);
+
+    let json = serde_json::to_value(&range).unwrap();
+    assert_eq!(json["timing"]["blocks"], 5);
+    assert!(json["outcomes"][0]["timing"]["merkle_ms"].is_number());
+}
+
+#[tokio::test]
+async fn untimed_range_has_no_timing() {
+    let validator =
+        BlockValidator::from_source(MemorySource::new([FullBlock::from(valid_block(10))]));
+
+    let range = validator.validate_range(10, 10).await.unwrap();
+
+    assert!(range.outcomes[0].timing.is_none());
+    assert!(range.timing().is_none());
+    assert!(serde_json::to_value(&range)
+        .unwrap()
+        .get("timing")
+        .is_none());
+}
+
+#[test]
+fn percentiles_are_nearest_rank() {
+    let percentiles = Percentiles::of((1..=200).rev().map(Duration::from_millis).collect());
+
+    assert_eq!(percentiles.p50, Duration::from_millis(100));
+    assert_eq!(percentiles.p90, Duration::from_millis(180));
+    assert_eq!(percentiles.p99, Duration::from_millis(198));
+    assert_eq!(percentiles.max, Duration::from_millis(200));
+    assert_eq!(percentiles.total, Duration::from_millis(200 * 201 / 2));
+}
@@ -0,0 +1,63 @@
+// tests/tx_status.rs
+//! Transactions of a block the node reports a status other than success or
+//! failure for.
+
+mod common;
+
+use fuel_root_validation::{
+    checks, dump::StatusDump, BlockValidator, Check, FullBlock, MemorySource, Severity,
+};
+
+use common::valid_block;
+
+#[test]
+fn executed_transactions_pass() {
+    let results = checks::tx_statuses(&FullBlock::from(valid_block(10)));
+
+    assert_eq!(results.len(), 1);
+    assert!(results[0].passed);
+    assert_eq!(results[0].expected.as_deref(), Some("2"));
+}
+
+#[tokio::test]
+async fn squeezed_out_transactions_are_warnings_and_stay_in_the_tx_root() {
+    let mut block = valid_block(10);
+    let squeezed = block.transactions[0].id;
+    block.transactions[0].status = Some(StatusDump::SqueezedOut {
+        reason: "out of gas".to_string(),
+    });
+    block.transactions[1].status = None;
+
+    let report = BlockValidator::from_source(MemorySource::new([FullBlock::from(block)]))
+        .validate_block(10)
+        .await
+        .unwrap();
+
+    let statuses: Vec<_> = report
+        .warnings()
+        .filter(|warning| warning.check == Check::TxStatus)
+        .collect();
+    assert_eq!(statuses.len(), 2);
+    assert_eq!(statuses[0].tx_id, Some(format!("{squeezed:#x}")));
+    assert_eq!(statuses[0].actual.as_deref(), Some("squeezed out"));
+    assert_eq!(statuses[0].message.as_deref(), Some("out of gas"));
+    assert_eq!(statuses[1].actual.as_deref(), Some("missing"));
+    assert!(statuses
+        .iter()
+        .all(|status| status.severity == Severity::Warning));
+    assert!(report
+        .checks
+        .iter()
+        .any(|result| result.check == Check::TxRoot && result.passed));
+    // Without its receipts and fee, the message the squeezed out script sent
+    // and the fee it paid are missing.
+    let failed: Vec<_> = report.failures().map(|failure| failure.check).collect();
+    assert_eq!(
+        failed,
+        [
+            Check::MessageReceiptCount,
+            Check::MessageOutboxRoot,
+            Check::MintAmount
+        ]
+    );
+}
@@ -0,0 +1,74 @@
+// tests/validate_tx.rs
+//! A single transaction validated with the block it is part of.
+
+mod common;
+
+use fuel_root_validation::{BlockValidator, Check, FullBlock, MemorySource, ValidationError};
+use fuel_types::{Bytes32, ChainId};
+
+use common::{flip, receipts, seal, valid_block, CHAIN_ID};
+
+fn validator(block: fuel_root_validation::BlockDump) -> BlockValidator {
+    BlockValidator::from_source(MemorySource::new([FullBlock::from(block)]))
+        .with_chain_id(ChainId::new(CHAIN_ID))
+}
+
+#[tokio::test]
+async fn transaction_is_checked_on_its_own() {
+    let block = valid_block(10);
+    let tx_id = block.transactions[0].id;
+
+    let report = validator(block)
+        .validate_transaction_at(10, &tx_id)
+        .await
+        .unwrap();
+
+    let checks: Vec<_> = report.checks.iter().map(|check| check.check).collect();
+    assert_eq!(
+        checks,
+        [Check::TxId, Check::ReceiptRoot, Check::TxInclusion]
+    );
+    assert!(report.passed());
+}
+
+#[tokio::test]
+async fn transaction_outside_the_transactions_root_fails_its_inclusion() {
+    let mut block = valid_block(10);
+    let tx_id = block.transactions[0].id;
+    flip(&mut block.header.transactions_root);
+    receipts(&mut block, 0).pop();
+    seal(&mut block);
+
+    let report = validator(block)
+        .validate_transaction_at(10, &tx_id)
+        .await
+        .unwrap();
+
+    let failed: Vec<_> = report.failures().map(|failure| failure.check).collect();
+    assert_eq!(failed, [Check::ReceiptRoot, Check::TxInclusion]);
+}
+
+#[tokio::test]
+async fn transaction_must_be_in_the_block() {
+    let error = validator(valid_block(10))
+        .validate_transaction_at(10, &Bytes32::zeroed())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            error,
+            ValidationError::TransactionNotFound { height: 10, .. }
+        ),
+        "{error}"
+    );
+
+    // Finding the block of a transaction takes a node.
+    let error = validator(valid_block(10))
+        .validate_transaction(&Bytes32::zeroed())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ValidationError::NodeRequired { .. }),
+        "{error}"
+    );
+}